@@ -10,6 +10,9 @@ use fvm_shared::{MethodNum, METHOD_CONSTRUCTOR};
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
 
+use fil_actors_runtime::builtin::api::account::{
+    AUTHENTICATE_MESSAGE_METHOD, PUBKEY_ADDRESS_METHOD,
+};
 use fil_actors_runtime::builtin::singletons::SYSTEM_ACTOR_ADDR;
 use fil_actors_runtime::runtime::{ActorCode, Runtime};
 use fil_actors_runtime::{actor_dispatch, restrict_internal_api, ActorDowncast};
@@ -31,10 +34,10 @@ fil_actors_runtime::wasm_trampoline!(Actor);
 #[repr(u64)]
 pub enum Method {
     Constructor = METHOD_CONSTRUCTOR,
-    PubkeyAddress = 2,
+    PubkeyAddress = PUBKEY_ADDRESS_METHOD,
     // Deprecated in v10
     // AuthenticateMessage = 3,
-    AuthenticateMessageExported = frc42_dispatch::method_hash!("AuthenticateMessage"),
+    AuthenticateMessageExported = AUTHENTICATE_MESSAGE_METHOD,
     UniversalReceiverHook = frc42_dispatch::method_hash!("Receive"),
 }
 
@@ -43,8 +46,7 @@ pub struct Actor;
 
 impl Actor {
     /// Constructor for Account actor
-    pub fn constructor(rt: &mut impl Runtime, params: Address) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_is(std::iter::once(&SYSTEM_ACTOR_ADDR))?;
+    fn constructor(rt: &mut impl Runtime, params: Address) -> Result<(), ActorError> {
         match params.protocol() {
             Protocol::Secp256k1 | Protocol::BLS => {}
             protocol => {
@@ -57,8 +59,7 @@ impl Actor {
     }
 
     /// Fetches the pubkey-type address from this actor.
-    pub fn pubkey_address(rt: &mut impl Runtime) -> Result<Address, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
+    fn pubkey_address(rt: &mut impl Runtime) -> Result<Address, ActorError> {
         let st: State = rt.state()?;
         Ok(st.address)
     }
@@ -66,11 +67,10 @@ impl Actor {
     /// Authenticates whether the provided signature is valid for the provided message.
     /// Should be called with the raw bytes of a signature, NOT a serialized Signature object that includes a SignatureType.
     /// Errors with USR_ILLEGAL_ARGUMENT if the authentication is invalid.
-    pub fn authenticate_message(
+    fn authenticate_message(
         rt: &mut impl Runtime,
         params: AuthenticateMessageParams,
     ) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let st: State = rt.state()?;
         let address = st.address;
         let sig_type: SignatureType = match address.protocol() {
@@ -93,11 +93,10 @@ impl Actor {
     }
 
     // Always succeeds, accepting any transfers.
-    pub fn universal_receiver_hook(
-        rt: &mut impl Runtime,
+    fn universal_receiver_hook(
+        _rt: &mut impl Runtime,
         _params: UniversalReceiverParams,
     ) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         Ok(())
     }
 }
@@ -105,9 +104,9 @@ impl Actor {
 impl ActorCode for Actor {
     type Methods = Method;
     actor_dispatch! {
-        Constructor => constructor,
-        PubkeyAddress => pubkey_address,
-        AuthenticateMessageExported => authenticate_message,
-        UniversalReceiverHook => universal_receiver_hook,
+        Constructor => constructor(Address) [caller: is(SYSTEM_ACTOR_ADDR)],
+        PubkeyAddress => pubkey_address -> Address [caller: any, read_only],
+        AuthenticateMessageExported => authenticate_message(AuthenticateMessageParams) [caller: any, read_only],
+        UniversalReceiverHook => universal_receiver_hook(UniversalReceiverParams) [caller: any],
     }
 }
@@ -16,13 +16,15 @@ pub fn check_state_invariants(
 
     match id_address.id() {
         Ok(id) if id >= FIRST_NON_SINGLETON_ADDR => {
-            acc.require(
+            acc.invariant("account.key_address_protocol").require(
                 state.address.protocol() == Protocol::BLS
                     || state.address.protocol() == Protocol::Secp256k1,
                 format!("actor address {} must be BLS or SECP256K1 protocol", state.address),
             );
         }
-        Err(e) => acc.add(format!("error extracting actor ID from address: {e}")),
+        Err(e) => acc
+            .invariant("account.id_address")
+            .add(format!("error extracting actor ID from address: {e}")),
         _ => (),
     }
 
@@ -45,7 +45,6 @@ pub struct Actor;
 impl Actor {
     /// Constructor for Cron actor
     fn constructor(rt: &mut impl Runtime, params: ConstructorParams) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_is(std::iter::once(&SYSTEM_ACTOR_ADDR))?;
         rt.create(&State { entries: params.entries })?;
         Ok(())
     }
@@ -53,18 +52,27 @@ impl Actor {
     /// epoch_tick(r) is called after all other messages in the epoch have been applied.
     /// This can be seen as an implicit last message.
     fn epoch_tick(rt: &mut impl Runtime) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_is(std::iter::once(&SYSTEM_ACTOR_ADDR))?;
-
         let st: State = rt.state()?;
         for entry in st.entries {
             // Intentionally ignore any error when calling cron methods
             let res = rt.send(&entry.receiver, entry.method_num, None, TokenAmount::zero());
-            if let Err(e) = res {
-                log::error!(
-                    "cron failed to send entry to {}, send error code {}",
-                    entry.receiver,
-                    e
-                );
+            match res {
+                Ok(ret) if ret.exit_code.is_success() => {}
+                Ok(ret) => {
+                    log::error!(
+                        "cron entry {} method {} exited with code {}",
+                        entry.receiver,
+                        entry.method_num,
+                        ret.exit_code
+                    );
+                }
+                Err(e) => {
+                    log::error!(
+                        "cron failed to send entry to {}, send error code {}",
+                        entry.receiver,
+                        e
+                    );
+                }
             }
         }
         Ok(())
@@ -74,7 +82,7 @@ impl Actor {
 impl ActorCode for Actor {
     type Methods = Method;
     actor_dispatch! {
-        Constructor => constructor,
-        EpochTick => epoch_tick,
+        Constructor => constructor(ConstructorParams) [caller: is(SYSTEM_ACTOR_ADDR)],
+        EpochTick => epoch_tick [caller: is(SYSTEM_ACTOR_ADDR)],
     }
 }
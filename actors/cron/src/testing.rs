@@ -11,11 +11,11 @@ pub fn check_state_invariants(state: &State) -> (StateSummary, MessageAccumulato
     let acc = MessageAccumulator::default();
 
     state.entries.iter().enumerate().for_each(|(i, entry)| {
-        acc.require(
+        acc.invariant("cron.entry.receiver_id").require(
             entry.receiver.protocol() == Protocol::ID,
             format!("entry {i} receiver address {} must be ID protocol", entry.receiver),
         );
-        acc.require(
+        acc.invariant("cron.entry.method_num").require(
             entry.method_num > 0,
             format!("entry {i} has invalid method number {}", entry.method_num),
         );
@@ -0,0 +1,17 @@
+// Copyright 2019-2022 ChainSafe Systems
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use fil_actors_runtime::runtime::Runtime;
+use fil_actors_runtime::{ActorError, EventBuilder};
+use fvm_shared::address::Address;
+use fvm_shared::econ::TokenAmount;
+
+/// Indicates data cap tokens have been minted to an address.
+pub fn mint(rt: &impl Runtime, to: &Address, amount: &TokenAmount) -> Result<(), ActorError> {
+    EventBuilder::new().typ("mint").field_indexed("to", to).field("amount", amount).emit(rt)
+}
+
+/// Indicates data cap tokens have been burnt from an address.
+pub fn burn(rt: &impl Runtime, owner: &Address, amount: &TokenAmount) -> Result<(), ActorError> {
+    EventBuilder::new().typ("burn").field_indexed("owner", owner).field("amount", amount).emit(rt)
+}
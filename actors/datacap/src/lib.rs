@@ -17,10 +17,11 @@ use log::info;
 use num_derive::FromPrimitive;
 use num_traits::{FromPrimitive, Zero};
 
+use fil_actors_runtime::builtin::api::datacap::TRANSFER_FROM_METHOD;
 use fil_actors_runtime::runtime::{ActorCode, Runtime};
 use fil_actors_runtime::{
-    actor_dispatch, actor_error, restrict_internal_api, ActorContext, ActorError, AsActorError,
-    SYSTEM_ACTOR_ADDR,
+    actor_dispatch, actor_error, extract_send_result, restrict_internal_api, ActorContext,
+    ActorError, AsActorError, SYSTEM_ACTOR_ADDR,
 };
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 
@@ -30,6 +31,7 @@ pub use self::types::*;
 #[cfg(feature = "fil-actor")]
 fil_actors_runtime::wasm_trampoline!(Actor);
 
+mod emit;
 mod state;
 pub mod testing;
 mod types;
@@ -73,7 +75,7 @@ pub enum Method {
     TotalSupplyExported = frc42_dispatch::method_hash!("TotalSupply"),
     BalanceExported = frc42_dispatch::method_hash!("Balance"),
     TransferExported = frc42_dispatch::method_hash!("Transfer"),
-    TransferFromExported = frc42_dispatch::method_hash!("TransferFrom"),
+    TransferFromExported = TRANSFER_FROM_METHOD,
     IncreaseAllowanceExported = frc42_dispatch::method_hash!("IncreaseAllowance"),
     DecreaseAllowanceExported = frc42_dispatch::method_hash!("DecreaseAllowance"),
     RevokeAllowanceExported = frc42_dispatch::method_hash!("RevokeAllowance"),
@@ -86,9 +88,7 @@ pub struct Actor;
 
 impl Actor {
     /// Constructor for DataCap Actor
-    pub fn constructor(rt: &mut impl Runtime, governor: Address) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_is(std::iter::once(&SYSTEM_ACTOR_ADDR))?;
-
+    fn constructor(rt: &mut impl Runtime, governor: Address) -> Result<(), ActorError> {
         // Confirm the governor address is an ID.
         rt.resolve_address(&governor)
             .ok_or_else(|| actor_error!(illegal_argument, "failed to resolve governor address"))?;
@@ -98,43 +98,37 @@ impl Actor {
         Ok(())
     }
 
-    pub fn name(rt: &mut impl Runtime) -> Result<String, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
+    fn name(_rt: &mut impl Runtime) -> Result<String, ActorError> {
         Ok("DataCap".to_string())
     }
 
-    pub fn symbol(rt: &mut impl Runtime) -> Result<String, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
+    fn symbol(_rt: &mut impl Runtime) -> Result<String, ActorError> {
         Ok("DCAP".to_string())
     }
 
-    pub fn granularity(rt: &mut impl Runtime) -> Result<GranularityReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
+    fn granularity(_rt: &mut impl Runtime) -> Result<GranularityReturn, ActorError> {
         Ok(GranularityReturn { granularity: DATACAP_GRANULARITY })
     }
 
-    pub fn total_supply(rt: &mut impl Runtime) -> Result<TokenAmount, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
+    fn total_supply(rt: &mut impl Runtime) -> Result<TokenAmount, ActorError> {
         let mut st: State = rt.state()?;
         let msg = Messenger { rt };
         let token = as_token(&mut st, &msg);
         Ok(token.total_supply())
     }
 
-    pub fn balance(rt: &mut impl Runtime, params: Address) -> Result<TokenAmount, ActorError> {
+    fn balance(rt: &mut impl Runtime, params: Address) -> Result<TokenAmount, ActorError> {
         // NOTE: mutability and method caller here are awkward for a read-only call
-        rt.validate_immediate_caller_accept_any()?;
         let mut st: State = rt.state()?;
         let msg = Messenger { rt };
         let token = as_token(&mut st, &msg);
         token.balance_of(&params).actor_result()
     }
 
-    pub fn allowance(
+    fn allowance(
         rt: &mut impl Runtime,
         params: GetAllowanceParams,
     ) -> Result<TokenAmount, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let mut st: State = rt.state()?;
         let msg = Messenger { rt };
         let token = as_token(&mut st, &msg);
@@ -145,7 +139,7 @@ impl Actor {
     /// Simultaneously sets the allowance for any specified operators to effectively infinite.
     /// Only the governor can call this method.
     /// This method is not part of the fungible token standard.
-    pub fn mint(rt: &mut impl Runtime, params: MintParams) -> Result<MintReturn, ActorError> {
+    fn mint(rt: &mut impl Runtime, params: MintParams) -> Result<MintReturn, ActorError> {
         let mut hook = rt
             .transaction(|st: &mut State, rt| {
                 // Only the governor can mint datacap tokens.
@@ -179,35 +173,39 @@ impl Actor {
         let mut st: State = rt.state()?;
         let msg = Messenger { rt };
         let intermediate = hook.call(&&msg).actor_result()?;
-        as_token(&mut st, &msg).mint_return(intermediate).actor_result()
+        let ret = as_token(&mut st, &msg).mint_return(intermediate).actor_result()?;
+        emit::mint(rt, &params.to, &params.amount)?;
+        Ok(ret)
     }
 
     /// Destroys data cap tokens for an address (a verified client).
     /// Only the governor can call this method.
     /// This method is not part of the fungible token standard, and is named distinctly from
     /// "burn" to reflect that distinction.
-    pub fn destroy(rt: &mut impl Runtime, params: DestroyParams) -> Result<BurnReturn, ActorError> {
-        rt.transaction(|st: &mut State, rt| {
-            // Only the governor can destroy datacap tokens on behalf of a holder.
-            rt.validate_immediate_caller_is(std::iter::once(&st.governor))?;
+    fn destroy(rt: &mut impl Runtime, params: DestroyParams) -> Result<BurnReturn, ActorError> {
+        let ret = rt
+            .transaction(|st: &mut State, rt| {
+                // Only the governor can destroy datacap tokens on behalf of a holder.
+                rt.validate_immediate_caller_is(std::iter::once(&st.governor))?;
 
-            let msg = Messenger { rt };
-            let mut token = as_token(st, &msg);
-            // Burn tokens as if the holder had invoked burn() themselves.
-            // The governor doesn't need an allowance.
-            token.burn(&params.owner, &params.amount).actor_result()
-        })
-        .context("state transaction failed")
+                let msg = Messenger { rt };
+                let mut token = as_token(st, &msg);
+                // Burn tokens as if the holder had invoked burn() themselves.
+                // The governor doesn't need an allowance.
+                token.burn(&params.owner, &params.amount).actor_result()
+            })
+            .context("state transaction failed")?;
+        emit::burn(rt, &params.owner, &params.amount)?;
+        Ok(ret)
     }
 
     /// Transfers data cap tokens to an address.
     /// Data cap tokens are not generally transferable.
     /// Succeeds if the to or from address is the governor, otherwise always fails.
-    pub fn transfer(
+    fn transfer(
         rt: &mut impl Runtime,
         params: TransferParams,
     ) -> Result<TransferReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let operator = &rt.message().caller();
         let from = operator;
         // Resolve to address for comparison with governor address.
@@ -252,11 +250,10 @@ impl Actor {
     /// Transfers data cap tokens between addresses.
     /// Data cap tokens are not generally transferable between addresses.
     /// Succeeds if the to address is the governor, otherwise always fails.
-    pub fn transfer_from(
+    fn transfer_from(
         rt: &mut impl Runtime,
         params: TransferFromParams,
     ) -> Result<TransferFromReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let operator = rt.message().caller();
         let from = params.from;
         // Resolve to address for comparison with governor.
@@ -299,11 +296,10 @@ impl Actor {
         as_token(&mut st, &msg).transfer_from_return(intermediate).actor_result()
     }
 
-    pub fn increase_allowance(
+    fn increase_allowance(
         rt: &mut impl Runtime,
         params: IncreaseAllowanceParams,
     ) -> Result<TokenAmount, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let owner = rt.message().caller();
         let operator = params.operator;
 
@@ -315,11 +311,10 @@ impl Actor {
         .context("state transaction failed")
     }
 
-    pub fn decrease_allowance(
+    fn decrease_allowance(
         rt: &mut impl Runtime,
         params: DecreaseAllowanceParams,
     ) -> Result<TokenAmount, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let owner = &rt.message().caller();
         let operator = &params.operator;
 
@@ -331,11 +326,10 @@ impl Actor {
         .context("state transaction failed")
     }
 
-    pub fn revoke_allowance(
+    fn revoke_allowance(
         rt: &mut impl Runtime,
         params: RevokeAllowanceParams,
     ) -> Result<TokenAmount, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let owner = &rt.message().caller();
         let operator = &params.operator;
 
@@ -347,32 +341,36 @@ impl Actor {
         .context("state transaction failed")
     }
 
-    pub fn burn(rt: &mut impl Runtime, params: BurnParams) -> Result<BurnReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
+    fn burn(rt: &mut impl Runtime, params: BurnParams) -> Result<BurnReturn, ActorError> {
         let owner = &rt.message().caller();
 
-        rt.transaction(|st: &mut State, rt| {
-            let msg = Messenger { rt };
-            let mut token = as_token(st, &msg);
-            token.burn(owner, &params.amount).actor_result()
-        })
-        .context("state transaction failed")
+        let ret = rt
+            .transaction(|st: &mut State, rt| {
+                let msg = Messenger { rt };
+                let mut token = as_token(st, &msg);
+                token.burn(owner, &params.amount).actor_result()
+            })
+            .context("state transaction failed")?;
+        emit::burn(rt, owner, &params.amount)?;
+        Ok(ret)
     }
 
-    pub fn burn_from(
+    fn burn_from(
         rt: &mut impl Runtime,
         params: BurnFromParams,
     ) -> Result<BurnFromReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let operator = &rt.message().caller();
         let owner = &params.owner;
 
-        rt.transaction(|st: &mut State, rt| {
-            let msg = Messenger { rt };
-            let mut token = as_token(st, &msg);
-            token.burn_from(operator, owner, &params.amount).actor_result()
-        })
-        .context("state transaction failed")
+        let ret = rt
+            .transaction(|st: &mut State, rt| {
+                let msg = Messenger { rt };
+                let mut token = as_token(st, &msg);
+                token.burn_from(operator, owner, &params.amount).actor_result()
+            })
+            .context("state transaction failed")?;
+        emit::burn(rt, owner, &params.amount)?;
+        Ok(ret)
     }
 }
 
@@ -405,11 +403,11 @@ where
         params: Option<IpldBlock>,
         value: &TokenAmount,
     ) -> fvm_actor_utils::messaging::Result<Response> {
-        // The Runtime discards some of the information from the syscall :-(
+        // The Runtime discards the syscall error number of a failed send :-(
         let res = self.rt.send(to, method, params, value.clone());
 
         let rec = match res {
-            Ok(ret) => Response { exit_code: ExitCode::OK, return_data: ret },
+            Ok(ret) => Response { exit_code: ret.exit_code, return_data: ret.return_data },
             Err(ae) => {
                 info!("datacap messenger failed: {}", ae.msg());
                 Response { exit_code: ae.exit_code(), return_data: None }
@@ -424,7 +422,13 @@ where
 
     fn initialize_account(&self, address: &Address) -> fvm_actor_utils::messaging::Result<ActorID> {
         let fake_syscall_error_number = ErrorNumber::NotFound;
-        if self.rt.send(address, METHOD_SEND, Default::default(), TokenAmount::zero()).is_err() {
+        if extract_send_result(
+            address,
+            METHOD_SEND,
+            self.rt.send(address, METHOD_SEND, Default::default(), TokenAmount::zero()),
+        )
+        .is_err()
+        {
             return Err(MessagingError::Syscall(fake_syscall_error_number));
         }
         self.resolve_id(address)
@@ -461,21 +465,21 @@ impl<T> AsActorResult<T> for Result<T, ReceiverHookError> {
 impl ActorCode for Actor {
     type Methods = Method;
     actor_dispatch! {
-        Constructor => constructor,
-        MintExported => mint,
-        DestroyExported => destroy,
-        NameExported => name,
-        SymbolExported => symbol,
-        GranularityExported => granularity,
-        TotalSupplyExported => total_supply,
-        BalanceExported => balance,
-        TransferExported => transfer,
-        TransferFromExported => transfer_from,
-        IncreaseAllowanceExported => increase_allowance,
-        DecreaseAllowanceExported => decrease_allowance,
-        RevokeAllowanceExported => revoke_allowance,
-        BurnExported => burn,
-        BurnFromExported => burn_from,
-        AllowanceExported => allowance,
+        Constructor => constructor(Address) [caller: is(SYSTEM_ACTOR_ADDR)],
+        MintExported => mint(MintParams) -> MintReturn,
+        DestroyExported => destroy(DestroyParams) -> BurnReturn,
+        NameExported => name -> String [caller: any, read_only],
+        SymbolExported => symbol -> String [caller: any, read_only],
+        GranularityExported => granularity -> GranularityReturn [caller: any, read_only],
+        TotalSupplyExported => total_supply -> TokenAmount [caller: any, read_only],
+        BalanceExported => balance(Address) -> TokenAmount [caller: any, read_only],
+        TransferExported => transfer(TransferParams) -> TransferReturn [caller: any],
+        TransferFromExported => transfer_from(TransferFromParams) -> TransferFromReturn [caller: any],
+        IncreaseAllowanceExported => increase_allowance(IncreaseAllowanceParams) -> TokenAmount [caller: any],
+        DecreaseAllowanceExported => decrease_allowance(DecreaseAllowanceParams) -> TokenAmount [caller: any],
+        RevokeAllowanceExported => revoke_allowance(RevokeAllowanceParams) -> TokenAmount [caller: any],
+        BurnExported => burn(BurnParams) -> BurnReturn [caller: any],
+        BurnFromExported => burn_from(BurnFromParams) -> BurnFromReturn [caller: any],
+        AllowanceExported => allowance(GetAllowanceParams) -> TokenAmount [caller: any, read_only],
     }
 }
@@ -16,7 +16,8 @@ use fil_actors_runtime::cbor::serialize;
 use fil_actors_runtime::runtime::Runtime;
 use fil_actors_runtime::test_utils::*;
 use fil_actors_runtime::{
-    ActorError, DATACAP_TOKEN_ACTOR_ADDR, SYSTEM_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR,
+    ActorError, EventBuilder, DATACAP_TOKEN_ACTOR_ADDR, SYSTEM_ACTOR_ADDR,
+    VERIFIED_REGISTRY_ACTOR_ADDR,
 };
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 
@@ -93,6 +94,15 @@ impl Harness {
             ExitCode::OK,
         );
 
+        rt.expect_emitted_event(
+            EventBuilder::new()
+                .typ("mint")
+                .field_indexed("to", to)
+                .field("amount", amount)
+                .build()
+                .unwrap(),
+        );
+
         let params = MintParams { to: *to, amount: amount.clone(), operators };
         rt.set_caller(*VERIFREG_ACTOR_CODE_ID, VERIFIED_REGISTRY_ACTOR_ADDR);
         let ret = rt.call::<DataCapActor>(
@@ -112,6 +122,15 @@ impl Harness {
     ) -> Result<BurnReturn, ActorError> {
         rt.expect_validate_caller_addr(vec![VERIFIED_REGISTRY_ACTOR_ADDR]);
 
+        rt.expect_emitted_event(
+            EventBuilder::new()
+                .typ("burn")
+                .field_indexed("owner", owner)
+                .field("amount", amount)
+                .build()
+                .unwrap(),
+        );
+
         let params = DestroyParams { owner: *owner, amount: amount.clone() };
 
         rt.set_caller(*VERIFREG_ACTOR_CODE_ID, VERIFIED_REGISTRY_ACTOR_ADDR);
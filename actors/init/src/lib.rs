@@ -6,7 +6,8 @@ use fil_actors_runtime::runtime::builtins::Type;
 use fil_actors_runtime::runtime::{ActorCode, Runtime};
 
 use fil_actors_runtime::{
-    actor_dispatch, actor_error, restrict_internal_api, ActorContext, ActorError, SYSTEM_ACTOR_ADDR,
+    actor_dispatch, actor_error, extract_send_result, restrict_internal_api, ActorContext,
+    ActorError, SYSTEM_ACTOR_ADDR,
 };
 use fvm_shared::address::Address;
 use fvm_shared::{ActorID, MethodNum, METHOD_CONSTRUCTOR};
@@ -38,9 +39,7 @@ pub struct Actor;
 
 impl Actor {
     /// Init actor constructor
-    pub fn constructor(rt: &mut impl Runtime, params: ConstructorParams) -> Result<(), ActorError> {
-        let sys_ref: &Address = &SYSTEM_ACTOR_ADDR;
-        rt.validate_immediate_caller_is(std::iter::once(sys_ref))?;
+    fn constructor(rt: &mut impl Runtime, params: ConstructorParams) -> Result<(), ActorError> {
         let state = State::new(rt.store(), params.network_name)?;
         rt.create(&state)?;
 
@@ -48,9 +47,7 @@ impl Actor {
     }
 
     /// Exec init actor
-    pub fn exec(rt: &mut impl Runtime, params: ExecParams) -> Result<ExecReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
-
+    fn exec(rt: &mut impl Runtime, params: ExecParams) -> Result<ExecReturn, ActorError> {
         log::trace!("called exec; params.code_cid: {:?}", &params.code_cid);
 
         let caller_code =
@@ -86,11 +83,15 @@ impl Actor {
         rt.create_actor(params.code_cid, id_address)?;
 
         // Invoke constructor
-        rt.send(
+        extract_send_result(
             &Address::new_id(id_address),
             METHOD_CONSTRUCTOR,
-            params.constructor_params.into(),
-            rt.message().value_received(),
+            rt.send(
+                &Address::new_id(id_address),
+                METHOD_CONSTRUCTOR,
+                params.constructor_params.into(),
+                rt.message().value_received(),
+            ),
         )
         .context("constructor failed")?;
 
@@ -101,9 +102,9 @@ impl Actor {
 impl ActorCode for Actor {
     type Methods = Method;
     actor_dispatch! {
-        Constructor => constructor,
-        Exec => exec,
-        ExecExported => exec,
+        Constructor => constructor(ConstructorParams) [caller: is(SYSTEM_ACTOR_ADDR)],
+        Exec => exec(ExecParams) -> ExecReturn [caller: any],
+        ExecExported => exec(ExecParams) -> ExecReturn [caller: any],
     }
 }
 
@@ -21,8 +21,9 @@ pub fn check_state_invariants<BS: Blockstore>(
 ) -> (StateSummary, MessageAccumulator) {
     let acc = MessageAccumulator::default();
 
-    acc.require(!state.network_name.is_empty(), "network name is empty");
-    acc.require(
+    acc.invariant("init.network_name")
+        .require(!state.network_name.is_empty(), "network name is empty");
+    acc.invariant("init.next_id").require(
         state.next_id >= FIRST_NON_SINGLETON_ADDR,
         format!("next id {} is too low", state.next_id),
     );
@@ -35,17 +36,17 @@ pub fn check_state_invariants<BS: Blockstore>(
             let ret = address_map.for_each(|key, actor_id| {
                 let key_address = Address::from_bytes(key)?;
 
-                acc.require(
+                acc.invariant("init.address_map.key_not_id").require(
                     key_address.protocol() != Protocol::ID,
                     format!("key {key_address} is an ID address"),
                 );
-                acc.require(
+                acc.invariant("init.address_map.non_singleton_id").require(
                     actor_id >= &FIRST_NON_SINGLETON_ADDR,
                     format!("unexpected singleton ID value {actor_id}"),
                 );
 
                 if let Some(duplicate) = address_by_id.insert(*actor_id, key_address) {
-                    acc.add(format!(
+                    acc.invariant("init.address_map.unique_id").add(format!(
                         "duplicate mapping to ID {actor_id}: {key_address} {duplicate}"
                     ));
                 }
@@ -54,9 +55,12 @@ pub fn check_state_invariants<BS: Blockstore>(
                 Ok(())
             });
 
-            acc.require_no_error(ret, "error iterating address map");
+            acc.invariant("init.address_map.load")
+                .require_no_error(ret, "error iterating address map");
+        }
+        Err(e) => {
+            acc.invariant("init.address_map.load").add(format!("error loading address map: {e}"))
         }
-        Err(e) => acc.add(format!("error loading address map: {e}")),
     }
 
     (init_summary, acc)
@@ -372,7 +372,9 @@ fn construct_and_verify(rt: &mut MockRuntime) {
 
     // Gets the Result(CID)
     let empty_map =
-        Multimap::from_root(&rt.store, &state_data.address_map, HAMT_BIT_WIDTH, 3).unwrap().root();
+        Multimap::<_, ()>::from_root(&rt.store, &state_data.address_map, HAMT_BIT_WIDTH, 3)
+            .unwrap()
+            .root();
 
     assert_eq!(empty_map.unwrap(), state_data.address_map);
     assert_eq!(FIRST_NON_SINGLETON_ADDR, state_data.next_id);
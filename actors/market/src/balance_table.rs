@@ -3,40 +3,48 @@
 
 use cid::Cid;
 use fvm_ipld_blockstore::Blockstore;
-use fvm_ipld_hamt::Error as HamtError;
 use fvm_shared::address::Address;
 use fvm_shared::econ::TokenAmount;
 use num_traits::Zero;
 
-use fil_actors_runtime::{make_empty_map, make_map_with_root_and_bitwidth, Map};
+use fil_actors_runtime::{actor_error, ActorError, Map2, MapConfig};
 
 pub const BALANCE_TABLE_BITWIDTH: u32 = 6;
 
+/// Map configuration for balance tables.
+pub struct BalanceTableConfig;
+
+impl MapConfig for BalanceTableConfig {
+    const BIT_WIDTH: u32 = BALANCE_TABLE_BITWIDTH;
+}
+
 /// Balance table which handles getting and updating token balances specifically
-pub struct BalanceTable<'a, BS>(pub Map<'a, BS, TokenAmount>);
+pub struct BalanceTable<'a, BS: Blockstore>(
+    pub Map2<'a, BS, Address, TokenAmount, BalanceTableConfig>,
+);
 
 impl<'a, BS> BalanceTable<'a, BS>
 where
     BS: Blockstore,
 {
     /// Initializes a new empty balance table
-    pub fn new(bs: &'a BS) -> Self {
-        Self(make_empty_map(bs, BALANCE_TABLE_BITWIDTH))
+    pub fn new(bs: &'a BS, name: &'static str) -> Self {
+        Self(Map2::empty(bs, name))
     }
 
     /// Initializes a balance table from a root Cid
-    pub fn from_root(bs: &'a BS, cid: &Cid) -> Result<Self, HamtError> {
-        Ok(Self(make_map_with_root_and_bitwidth(cid, bs, BALANCE_TABLE_BITWIDTH)?))
+    pub fn from_root(bs: &'a BS, cid: &Cid, name: &'static str) -> Result<Self, ActorError> {
+        Ok(Self(Map2::load(bs, cid, name)?))
     }
 
     /// Retrieve root from balance table
-    pub fn root(&mut self) -> Result<Cid, HamtError> {
+    pub fn root(&mut self) -> Result<Cid, ActorError> {
         self.0.flush()
     }
 
     /// Gets token amount for given address in balance table
-    pub fn get(&self, key: &Address) -> Result<TokenAmount, HamtError> {
-        if let Some(v) = self.0.get(&key.to_bytes())? {
+    pub fn get(&self, key: &Address) -> Result<TokenAmount, ActorError> {
+        if let Some(v) = self.0.get(key)? {
             Ok(v.clone())
         } else {
             Ok(TokenAmount::zero())
@@ -44,16 +52,16 @@ where
     }
 
     /// Adds token amount to previously initialized account.
-    pub fn add(&mut self, key: &Address, value: &TokenAmount) -> Result<(), HamtError> {
+    pub fn add(&mut self, key: &Address, value: &TokenAmount) -> Result<(), ActorError> {
         let prev = self.get(key)?;
         let sum = &prev + value;
         if sum.is_negative() {
-            Err(format!("New balance in table cannot be negative: {}", sum).into())
+            Err(actor_error!(illegal_state, "new balance in table cannot be negative: {}", sum))
         } else if sum.is_zero() && !prev.is_zero() {
-            self.0.delete(&key.to_bytes())?;
+            self.0.delete(key)?;
             Ok(())
         } else {
-            self.0.set(key.to_bytes().into(), sum)?;
+            self.0.set(key, sum)?;
             Ok(())
         }
     }
@@ -66,7 +74,7 @@ where
         key: &Address,
         req: &TokenAmount,
         floor: &TokenAmount,
-    ) -> Result<TokenAmount, HamtError> {
+    ) -> Result<TokenAmount, ActorError> {
         let prev = self.get(key)?;
         let available = std::cmp::max(TokenAmount::zero(), prev - floor);
         let sub: TokenAmount = std::cmp::min(&available, req).clone();
@@ -79,11 +87,11 @@ where
     }
 
     /// Subtracts value from a balance, and errors if full amount was not substracted.
-    pub fn must_subtract(&mut self, key: &Address, req: &TokenAmount) -> Result<(), HamtError> {
+    pub fn must_subtract(&mut self, key: &Address, req: &TokenAmount) -> Result<(), ActorError> {
         let prev = self.get(key)?;
 
         if req > &prev {
-            Err("couldn't subtract the requested amount".into())
+            Err(actor_error!(illegal_state, "couldn't subtract the requested amount"))
         } else {
             self.add(key, &-req)
         }
@@ -91,7 +99,7 @@ where
 
     /// Returns total balance held by this balance table
     #[allow(dead_code)]
-    pub fn total(&self) -> Result<TokenAmount, HamtError> {
+    pub fn total(&self) -> Result<TokenAmount, ActorError> {
         let mut total = TokenAmount::zero();
 
         self.0.for_each(|_, v: &TokenAmount| {
@@ -116,7 +124,7 @@ mod tests {
         let addr1 = Address::new_id(100);
         let addr2 = Address::new_id(101);
         let store = MemoryBlockstore::default();
-        let mut bt = BalanceTable::new(&store);
+        let mut bt = BalanceTable::new(&store, "test");
 
         assert!(bt.total().unwrap().is_zero());
 
@@ -143,7 +151,7 @@ mod tests {
     fn balance_subtracts() {
         let addr = Address::new_id(100);
         let store = MemoryBlockstore::default();
-        let mut bt = BalanceTable::new(&store);
+        let mut bt = BalanceTable::new(&store, "test");
 
         bt.add(&addr, &TokenAmount::from_atto(80u8)).unwrap();
         assert_eq!(bt.get(&addr).unwrap(), TokenAmount::from_atto(80u8));
@@ -0,0 +1,59 @@
+// Copyright 2019-2022 ChainSafe Systems
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use fil_actors_runtime::runtime::Runtime;
+use fil_actors_runtime::{actor_error, ActorError, EventBuilder};
+use fvm_shared::address::Address;
+use fvm_shared::deal::DealID;
+
+/// Indicates a deal has been published.
+pub fn deal_published(
+    rt: &impl Runtime,
+    client: &Address,
+    provider: &Address,
+    deal_id: DealID,
+) -> Result<(), ActorError> {
+    emit_deal_event(rt, "deal-published", client, provider, deal_id)
+}
+
+/// Indicates a deal has been activated.
+pub fn deal_activated(
+    rt: &impl Runtime,
+    client: &Address,
+    provider: &Address,
+    deal_id: DealID,
+) -> Result<(), ActorError> {
+    emit_deal_event(rt, "deal-activated", client, provider, deal_id)
+}
+
+/// Indicates a deal has been terminated.
+pub fn deal_terminated(
+    rt: &impl Runtime,
+    client: &Address,
+    provider: &Address,
+    deal_id: DealID,
+) -> Result<(), ActorError> {
+    emit_deal_event(rt, "deal-terminated", client, provider, deal_id)
+}
+
+fn emit_deal_event(
+    rt: &impl Runtime,
+    typ: &str,
+    client: &Address,
+    provider: &Address,
+    deal_id: DealID,
+) -> Result<(), ActorError> {
+    let resolve = |addr: &Address| {
+        rt.resolve_address(addr).ok_or_else(|| {
+            actor_error!(illegal_state, "failed to resolve address {} for {} event", addr, typ)
+        })
+    };
+    let client = resolve(client)?;
+    let provider = resolve(provider)?;
+    EventBuilder::new()
+        .typ(typ)
+        .field_indexed("id", &deal_id)
+        .field_indexed("client", &client)
+        .field_indexed("provider", &provider)
+        .emit(rt)
+}
@@ -1,38 +1,11 @@
-use fvm_ipld_encoding::serde_bytes;
 use fvm_ipld_encoding::tuple::*;
-use fvm_shared::address::Address;
-use fvm_shared::bigint::bigint_ser;
-use fvm_shared::econ::TokenAmount;
-
-use fvm_shared::sector::StoragePower;
-use fvm_shared::smooth::FilterEstimate;
 
 pub mod account {
-    use super::*;
-
-    pub const AUTHENTICATE_MESSAGE_METHOD: u64 =
-        frc42_dispatch::method_hash!("AuthenticateMessage");
-
-    #[derive(Serialize_tuple, Deserialize_tuple)]
-    pub struct AuthenticateMessageParams {
-        #[serde(with = "serde_bytes")]
-        pub signature: Vec<u8>,
-        #[serde(with = "serde_bytes")]
-        pub message: Vec<u8>,
-    }
+    pub use fil_actors_runtime::builtin::api::account::*;
 }
 
 pub mod miner {
-    use super::*;
-
-    pub const CONTROL_ADDRESSES_METHOD: u64 = 2;
-
-    #[derive(Serialize_tuple, Deserialize_tuple)]
-    pub struct GetControlAddressesReturnParams {
-        pub owner: Address,
-        pub worker: Address,
-        pub control_addresses: Vec<Address>,
-    }
+    pub use fil_actors_runtime::builtin::api::miner::*;
 }
 
 pub mod verifreg {
@@ -43,8 +16,7 @@ pub mod verifreg {
     use fvm_shared::piece::PaddedPieceSize;
     use fvm_shared::ActorID;
 
-    pub type AllocationID = u64;
-    pub type ClaimID = u64;
+    pub use fil_actors_runtime::builtin::api::verifreg::{AllocationID, ClaimID};
 
     #[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
     pub struct AllocationRequest {
@@ -81,25 +53,13 @@ pub mod verifreg {
 }
 
 pub mod datacap {
-    pub const TRANSFER_FROM_METHOD: u64 = frc42_dispatch::method_hash!("TransferFrom");
+    pub use fil_actors_runtime::builtin::api::datacap::*;
 }
 
 pub mod reward {
-    pub const THIS_EPOCH_REWARD_METHOD: u64 = 3;
+    pub use fil_actors_runtime::builtin::api::reward::*;
 }
 
 pub mod power {
-    use super::*;
-
-    pub const CURRENT_TOTAL_POWER_METHOD: u64 = 9;
-
-    #[derive(Serialize_tuple, Deserialize_tuple)]
-    pub struct CurrentTotalPowerReturnParams {
-        #[serde(with = "bigint_ser")]
-        pub raw_byte_power: StoragePower,
-        #[serde(with = "bigint_ser")]
-        pub quality_adj_power: StoragePower,
-        pub pledge_collateral: TokenAmount,
-        pub quality_adj_power_smoothed: FilterEstimate,
-    }
+    pub use fil_actors_runtime::builtin::api::power::*;
 }
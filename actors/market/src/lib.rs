@@ -18,7 +18,6 @@ use fvm_shared::deal::DealID;
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
 use fvm_shared::piece::PieceInfo;
-use fvm_shared::reward::ThisEpochRewardReturn;
 use fvm_shared::sector::{RegisteredSealProof, SectorSize, StoragePower};
 use fvm_shared::{ActorID, MethodNum, METHOD_CONSTRUCTOR, METHOD_SEND};
 use integer_encoding::VarInt;
@@ -27,13 +26,16 @@ use num_derive::FromPrimitive;
 use num_traits::{FromPrimitive, Zero};
 
 use crate::balance_table::BalanceTable;
+use fil_actors_runtime::builtin::api::market::{
+    ACTIVATE_DEALS_METHOD, ON_MINER_SECTORS_TERMINATE_METHOD, VERIFY_DEALS_FOR_ACTIVATION_METHOD,
+};
 use fil_actors_runtime::cbor::{deserialize, serialize};
 use fil_actors_runtime::runtime::builtins::Type;
 use fil_actors_runtime::runtime::{ActorCode, Policy, Runtime};
 use fil_actors_runtime::{
-    actor_dispatch, actor_error, deserialize_block, ActorContext, ActorDowncast, ActorError,
-    AsActorError, BURNT_FUNDS_ACTOR_ADDR, CRON_ACTOR_ADDR, DATACAP_TOKEN_ACTOR_ADDR,
-    REWARD_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR, SYSTEM_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR,
+    actor_dispatch, actor_error, callee_exit_error, ActorContext, ActorDowncast, ActorError,
+    AsActorError, ErrorContext, BURNT_FUNDS_ACTOR_ADDR, CRON_ACTOR_ADDR, SYSTEM_ACTOR_ADDR,
+    VERIFIED_REGISTRY_ACTOR_ADDR,
 };
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_ipld_encoding::RawBytes;
@@ -53,6 +55,7 @@ pub mod policy;
 pub mod testing;
 
 mod deal;
+mod emit;
 mod state;
 mod types;
 
@@ -72,9 +75,9 @@ pub enum Method {
     AddBalance = 2,
     WithdrawBalance = 3,
     PublishStorageDeals = 4,
-    VerifyDealsForActivation = 5,
-    ActivateDeals = 6,
-    OnMinerSectorsTerminate = 7,
+    VerifyDealsForActivation = VERIFY_DEALS_FOR_ACTIVATION_METHOD,
+    ActivateDeals = ACTIVATE_DEALS_METHOD,
+    OnMinerSectorsTerminate = ON_MINER_SECTORS_TERMINATE_METHOD,
     ComputeDataCommitment = 8,
     CronTick = 9,
     // Method numbers derived from FRC-0042 standards
@@ -98,9 +101,7 @@ pub enum Method {
 pub struct Actor;
 
 impl Actor {
-    pub fn constructor(rt: &mut impl Runtime) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_is(std::iter::once(&SYSTEM_ACTOR_ADDR))?;
-
+    fn constructor(rt: &mut impl Runtime) -> Result<(), ActorError> {
         let st = State::new(rt.store())?;
         rt.create(&st)?;
         Ok(())
@@ -151,7 +152,18 @@ impl Actor {
             Ok(ex)
         })?;
 
-        rt.send(&recipient, METHOD_SEND, None, amount_extracted.clone())?;
+        let ret = rt
+            .send(&recipient, METHOD_SEND, None, amount_extracted.clone())
+            .with_context(|| format!("failed to send withdrawal to {}", recipient))?;
+        if !ret.exit_code.is_success() {
+            return Err(callee_exit_error(
+                ret.exit_code,
+                format!(
+                    "recipient {} rejected withdrawal of {} with code {}",
+                    recipient, amount_extracted, ret.exit_code
+                ),
+            ));
+        }
 
         Ok(WithdrawBalanceReturn { amount_withdrawn: amount_extracted })
     }
@@ -161,7 +173,6 @@ impl Actor {
         rt: &mut impl Runtime,
         account: Address,
     ) -> Result<GetBalanceReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let nominal = rt.resolve_address(&account).ok_or_else(|| {
             actor_error!(illegal_argument, "failed to resolve address {}", account)
         })?;
@@ -169,16 +180,10 @@ impl Actor {
 
         let store = rt.store();
         let st: State = rt.state()?;
-        let balances = BalanceTable::from_root(store, &st.escrow_table)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load escrow table")?;
-        let locks = BalanceTable::from_root(store, &st.locked_table)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load locked table")?;
-        let balance = balances
-            .get(&account)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to get escrow balance")?;
-        let locked = locks
-            .get(&account)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to get locked balance")?;
+        let balances = BalanceTable::from_root(store, &st.escrow_table, "escrow table")?;
+        let locks = BalanceTable::from_root(store, &st.locked_table, "locked table")?;
+        let balance = balances.get(&account).context("failed to get escrow balance")?;
+        let locked = locks.get(&account).context("failed to get locked balance")?;
 
         Ok(GetBalanceReturn { balance, locked })
     }
@@ -188,7 +193,6 @@ impl Actor {
         rt: &mut impl Runtime,
         params: PublishStorageDealsParams,
     ) -> Result<PublishStorageDealsReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         if params.deals.is_empty() {
             return Err(actor_error!(illegal_argument, "Empty deals parameter"));
         }
@@ -336,20 +340,8 @@ impl Actor {
                     &Address::new_id(client_id),
                     vec![alloc_request_for_deal(&deal, rt.policy(), curr_epoch)],
                 )?;
-                let alloc_ids = rt
-                    .send(
-                        &DATACAP_TOKEN_ACTOR_ADDR,
-                        ext::datacap::TRANSFER_FROM_METHOD as u64,
-                        IpldBlock::serialize_cbor(&params)?,
-                        TokenAmount::zero(),
-                    )
-                    .and_then(|ret| {
-                        datacap_transfer_response(
-                            ret.with_context_code(ExitCode::USR_ASSERTION_FAILED, || {
-                                "return expected".to_string()
-                            })?,
-                        )
-                    });
+                let alloc_ids = ext::datacap::DatacapClient::transfer_from(rt, &params)
+                    .and_then(datacap_transfer_response);
                 match alloc_ids {
                     Ok(ids) => {
                         // Note: when changing this to do anything other than expect complete success,
@@ -405,7 +397,7 @@ impl Actor {
             let mut pending_deals: Vec<Cid> = vec![];
             let mut deal_proposals: Vec<(DealID, DealProposal)> = vec![];
             let mut deals_by_epoch: Vec<(ChainEpoch, DealID)> = vec![];
-            let mut pending_deal_allocation_ids: Vec<(BytesKey, AllocationID)> = vec![];
+            let mut pending_deal_allocation_ids: Vec<(DealID, AllocationID)> = vec![];
 
             // All storage dealProposals will be added in an atomic transaction; this operation will be unrolled if any of them fails.
             // This should only fail on programmer error because all expected invalid conditions should be filtered in the first set of checks.
@@ -422,7 +414,7 @@ impl Actor {
                 // Store verified allocation (if any) in the pending allocation IDs map.
                 // It will be removed when the deal is activated or expires.
                 if valid_deal.allocation != NO_ALLOCATION_ID {
-                    pending_deal_allocation_ids.push((deal_id_key(deal_id), valid_deal.allocation));
+                    pending_deal_allocation_ids.push((deal_id, valid_deal.allocation));
                 }
 
                 // Randomize the first epoch for when the deal will be processed so an attacker isn't able to
@@ -446,6 +438,15 @@ impl Actor {
             Ok(())
         })?;
 
+        for (i, valid_deal) in valid_deals.iter().enumerate() {
+            emit::deal_published(
+                rt,
+                &valid_deal.proposal.client,
+                &valid_deal.proposal.provider,
+                new_deal_ids[i],
+            )?;
+        }
+
         // notify clients ignoring any errors
         for (i, valid_deal) in valid_deals.iter().enumerate() {
             _ = rt.send(
@@ -468,7 +469,6 @@ impl Actor {
         rt: &mut impl Runtime,
         params: VerifyDealsForActivationParams,
     ) -> Result<VerifyDealsForActivationReturn, ActorError> {
-        rt.validate_immediate_caller_type(std::iter::once(&Type::Miner))?;
         let miner_addr = rt.message().caller();
         let curr_epoch = rt.curr_epoch();
 
@@ -507,7 +507,6 @@ impl Actor {
         rt: &mut impl Runtime,
         params: ActivateDealsParams,
     ) -> Result<ActivateDealsResult, ActorError> {
-        rt.validate_immediate_caller_type(std::iter::once(&Type::Miner))?;
         let miner_addr = rt.message().caller();
         let curr_epoch = rt.curr_epoch();
 
@@ -528,6 +527,7 @@ impl Actor {
 
         // Update deal states
         let mut verified_infos = Vec::new();
+        let mut activated_deals: Vec<(Address, Address, DealID)> = vec![];
         rt.transaction(|st: &mut State, rt| {
             let mut deal_states: Vec<(DealID, DealState)> = vec![];
 
@@ -564,9 +564,8 @@ impl Actor {
 
                 // Extract and remove any verified allocation ID for the pending deal.
                 let allocation = st
-                    .remove_pending_deal_allocation_id(rt.store(), &deal_id_key(deal_id))?
-                    .unwrap_or((BytesKey(vec![]), NO_ALLOCATION_ID))
-                    .1;
+                    .remove_pending_deal_allocation_id(rt.store(), deal_id)?
+                    .unwrap_or(NO_ALLOCATION_ID);
 
                 if allocation != NO_ALLOCATION_ID {
                     verified_infos.push(VerifiedDealInfo {
@@ -577,6 +576,7 @@ impl Actor {
                     })
                 }
 
+                activated_deals.push((proposal.client, proposal.provider, deal_id));
                 deal_states.push((
                     deal_id,
                     DealState {
@@ -593,6 +593,10 @@ impl Actor {
             Ok(())
         })?;
 
+        for (client, provider, deal_id) in activated_deals {
+            emit::deal_activated(rt, &client, &provider, deal_id)?;
+        }
+
         Ok(ActivateDealsResult { nonverified_deal_space: deal_spaces.deal_space, verified_infos })
     }
 
@@ -603,9 +607,9 @@ impl Actor {
         rt: &mut impl Runtime,
         params: OnMinerSectorsTerminateParams,
     ) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_type(std::iter::once(&Type::Miner))?;
         let miner_addr = rt.message().caller();
 
+        let mut terminated_deals: Vec<(Address, Address, DealID)> = vec![];
         rt.transaction(|st: &mut State, rt| {
             let mut deal_states: Vec<(DealID, DealState)> = vec![];
 
@@ -652,12 +656,17 @@ impl Actor {
                 // and slashing of provider collateral happens in cron_tick.
                 state.slash_epoch = params.epoch;
 
+                terminated_deals.push((deal.client, deal.provider, id));
                 deal_states.push((id, state));
             }
 
             st.put_deal_states(rt.store(), &deal_states)?;
             Ok(())
         })?;
+
+        for (client, provider, deal_id) in terminated_deals {
+            emit::deal_terminated(rt, &client, &provider, deal_id)?;
+        }
         Ok(())
     }
 
@@ -665,8 +674,6 @@ impl Actor {
         rt: &mut impl Runtime,
         params: ComputeDataCommitmentParams,
     ) -> Result<ComputeDataCommitmentReturn, ActorError> {
-        rt.validate_immediate_caller_type(std::iter::once(&Type::Miner))?;
-
         let st: State = rt.state()?;
         let proposals = st.get_proposal_array(rt.store())?;
 
@@ -684,8 +691,6 @@ impl Actor {
     }
 
     fn cron_tick(rt: &mut impl Runtime) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_is(std::iter::once(&CRON_ACTOR_ADDR))?;
-
         let mut amount_slashed = TokenAmount::zero();
         let curr_epoch = rt.curr_epoch();
 
@@ -746,7 +751,7 @@ impl Actor {
                         })?;
 
                         // Delete pending deal allocation id (if present).
-                        st.remove_pending_deal_allocation_id(rt.store(), &deal_id_key(deal_id))?;
+                        st.remove_pending_deal_allocation_id(rt.store(), deal_id)?;
 
                         continue;
                     }
@@ -851,7 +856,20 @@ impl Actor {
         })?;
 
         if !amount_slashed.is_zero() {
-            rt.send(&BURNT_FUNDS_ACTOR_ADDR, METHOD_SEND, None, amount_slashed)?;
+            let ret = rt
+                .send(&BURNT_FUNDS_ACTOR_ADDR, METHOD_SEND, None, amount_slashed.clone())
+                .with_context(|| {
+                format!("failed to burn slashed collateral {}", amount_slashed)
+            })?;
+            if !ret.exit_code.is_success() {
+                return Err(callee_exit_error(
+                    ret.exit_code,
+                    format!(
+                        "burnt funds actor rejected slashed collateral {} with code {}",
+                        amount_slashed, ret.exit_code
+                    ),
+                ));
+            }
         }
         Ok(())
     }
@@ -863,7 +881,6 @@ impl Actor {
         rt: &mut impl Runtime,
         params: GetDealDataCommitmentParams,
     ) -> Result<GetDealDataCommitmentReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let found = rt.state::<State>()?.get_proposal(rt.store(), params.id)?;
         Ok(GetDealDataCommitmentReturn { data: found.piece_cid, size: found.piece_size })
     }
@@ -873,7 +890,6 @@ impl Actor {
         rt: &mut impl Runtime,
         params: GetDealClientParams,
     ) -> Result<GetDealClientReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let found = rt.state::<State>()?.get_proposal(rt.store(), params.id)?;
         Ok(GetDealClientReturn { client: found.client.id().unwrap() })
     }
@@ -883,7 +899,6 @@ impl Actor {
         rt: &mut impl Runtime,
         params: GetDealProviderParams,
     ) -> Result<GetDealProviderReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let found = rt.state::<State>()?.get_proposal(rt.store(), params.id)?;
         Ok(GetDealProviderReturn { provider: found.provider.id().unwrap() })
     }
@@ -893,7 +908,6 @@ impl Actor {
         rt: &mut impl Runtime,
         params: GetDealLabelParams,
     ) -> Result<GetDealLabelReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let found = rt.state::<State>()?.get_proposal(rt.store(), params.id)?;
         Ok(GetDealLabelReturn { label: found.label })
     }
@@ -903,7 +917,6 @@ impl Actor {
         rt: &mut impl Runtime,
         params: GetDealTermParams,
     ) -> Result<GetDealTermReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let found = rt.state::<State>()?.get_proposal(rt.store(), params.id)?;
         Ok(GetDealTermReturn { start: found.start_epoch, duration: found.duration() })
     }
@@ -913,7 +926,6 @@ impl Actor {
         rt: &mut impl Runtime,
         params: GetDealTotalPriceParams,
     ) -> Result<GetDealTotalPriceReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let found = rt.state::<State>()?.get_proposal(rt.store(), params.id)?;
         Ok(GetDealTotalPriceReturn { total_price: found.total_storage_fee() })
     }
@@ -923,7 +935,6 @@ impl Actor {
         rt: &mut impl Runtime,
         params: GetDealClientCollateralParams,
     ) -> Result<GetDealClientCollateralReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let found = rt.state::<State>()?.get_proposal(rt.store(), params.id)?;
         Ok(GetDealClientCollateralReturn { collateral: found.client_collateral })
     }
@@ -933,7 +944,6 @@ impl Actor {
         rt: &mut impl Runtime,
         params: GetDealProviderCollateralParams,
     ) -> Result<GetDealProviderCollateralReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let found = rt.state::<State>()?.get_proposal(rt.store(), params.id)?;
         Ok(GetDealProviderCollateralReturn { collateral: found.provider_collateral })
     }
@@ -945,7 +955,6 @@ impl Actor {
         rt: &mut impl Runtime,
         params: GetDealVerifiedParams,
     ) -> Result<GetDealVerifiedReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let found = rt.state::<State>()?.get_proposal(rt.store(), params.id)?;
         Ok(GetDealVerifiedReturn { verified: found.verified_deal })
     }
@@ -959,7 +968,6 @@ impl Actor {
         rt: &mut impl Runtime,
         params: GetDealActivationParams,
     ) -> Result<GetDealActivationReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let st = rt.state::<State>()?;
         let found = st.find_deal_state(rt.store(), params.id)?;
         match found {
@@ -1051,7 +1059,9 @@ pub fn validate_and_return_deal_space<BS: Blockstore>(
             .ok_or_else(|| actor_error!(not_found, "no such deal {}", deal_id))?;
 
         validate_deal_can_activate(proposal, miner_addr, sector_expiry, sector_activation)
-            .with_context(|| format!("cannot activate deal {}", deal_id))?;
+            .with_context_data(|| {
+                ErrorContext::new(format!("cannot activate deal {}", deal_id)).deal(*deal_id)
+            })?;
 
         if proposal.verified_deal {
             verified_deal_space += proposal.piece_size.0;
@@ -1115,8 +1125,7 @@ fn datacap_transfer_request(
 }
 
 // Parses allocation IDs from a TransferFromReturn
-fn datacap_transfer_response(ret: IpldBlock) -> Result<Vec<AllocationID>, ActorError> {
-    let ret: TransferFromReturn = ret.deserialize()?;
+fn datacap_transfer_response(ret: TransferFromReturn) -> Result<Vec<AllocationID>, ActorError> {
     let allocs: ext::verifreg::AllocationsResponse =
         deserialize(&ret.recipient_data, "allocations response")?;
     Ok(allocs.new_allocations)
@@ -1258,14 +1267,13 @@ fn deal_proposal_is_internally_valid(
     // Generate unsigned bytes
     let proposal_bytes = serialize(&proposal.proposal, "deal proposal")?;
 
-    rt.send(
+    ext::account::AccountClient::authenticate_message(
+        rt,
         &proposal.proposal.client,
-        ext::account::AUTHENTICATE_MESSAGE_METHOD,
-        IpldBlock::serialize_cbor(&ext::account::AuthenticateMessageParams {
+        &ext::account::AuthenticateMessageParams {
             signature: signature_bytes,
             message: proposal_bytes.to_vec(),
-        })?,
-        TokenAmount::zero(),
+        },
     )
     .map_err(|e| e.wrap("proposal authentication failed"))?;
     Ok(())
@@ -1301,12 +1309,7 @@ fn request_miner_control_addrs(
     rt: &mut impl Runtime,
     miner_id: ActorID,
 ) -> Result<(Address, Address, Vec<Address>), ActorError> {
-    let addrs: ext::miner::GetControlAddressesReturnParams = deserialize_block(rt.send(
-        &Address::new_id(miner_id),
-        ext::miner::CONTROL_ADDRESSES_METHOD,
-        None,
-        TokenAmount::zero(),
-    )?)?;
+    let addrs = ext::miner::MinerClient::control_addresses(rt, &Address::new_id(miner_id))?;
 
     Ok((addrs.owner, addrs.worker, addrs.control_addresses))
 }
@@ -1339,12 +1342,7 @@ fn escrow_address(
 
 /// Requests the current epoch target block reward from the reward actor.
 fn request_current_baseline_power(rt: &mut impl Runtime) -> Result<StoragePower, ActorError> {
-    let ret: ThisEpochRewardReturn = deserialize_block(rt.send(
-        &REWARD_ACTOR_ADDR,
-        ext::reward::THIS_EPOCH_REWARD_METHOD,
-        None,
-        TokenAmount::zero(),
-    )?)?;
+    let ret = ext::reward::RewardClient::this_epoch_reward(rt)?;
     Ok(ret.this_epoch_baseline_power)
 }
 
@@ -1353,12 +1351,7 @@ fn request_current_baseline_power(rt: &mut impl Runtime) -> Result<StoragePower,
 fn request_current_network_power(
     rt: &mut impl Runtime,
 ) -> Result<(StoragePower, StoragePower), ActorError> {
-    let ret: ext::power::CurrentTotalPowerReturnParams = deserialize_block(rt.send(
-        &STORAGE_POWER_ACTOR_ADDR,
-        ext::power::CURRENT_TOTAL_POWER_METHOD,
-        None,
-        TokenAmount::zero(),
-    )?)?;
+    let ret = ext::power::PowerClient::current_total_power(rt)?;
     Ok((ret.raw_byte_power, ret.quality_adj_power))
 }
 
@@ -1370,28 +1363,28 @@ pub fn deal_id_key(k: DealID) -> BytesKey {
 impl ActorCode for Actor {
     type Methods = Method;
     actor_dispatch! {
-        Constructor => constructor,
-        AddBalance => add_balance,
-        AddBalanceExported => add_balance,
-        WithdrawBalance => withdraw_balance,
-        WithdrawBalanceExported => withdraw_balance,
-        PublishStorageDeals => publish_storage_deals,
-        PublishStorageDealsExported => publish_storage_deals,
-        VerifyDealsForActivation => verify_deals_for_activation,
-        ActivateDeals => activate_deals,
-        OnMinerSectorsTerminate => on_miner_sectors_terminate,
-        ComputeDataCommitment => compute_data_commitment,
-        CronTick => cron_tick,
-        GetBalanceExported => get_balance,
-        GetDealDataCommitmentExported => get_deal_data_commitment,
-        GetDealClientExported => get_deal_client,
-        GetDealProviderExported => get_deal_provider,
-        GetDealLabelExported => get_deal_label,
-        GetDealTermExported => get_deal_term,
-        GetDealTotalPriceExported => get_deal_total_price,
-        GetDealClientCollateralExported => get_deal_client_collateral,
-        GetDealProviderCollateralExported => get_deal_provider_collateral,
-        GetDealVerifiedExported => get_deal_verified,
-        GetDealActivationExported => get_deal_activation,
+        Constructor => constructor [caller: is(SYSTEM_ACTOR_ADDR)],
+        AddBalance => add_balance(Address),
+        AddBalanceExported => add_balance(Address),
+        WithdrawBalance => withdraw_balance(WithdrawBalanceParams) -> WithdrawBalanceReturn,
+        WithdrawBalanceExported => withdraw_balance(WithdrawBalanceParams) -> WithdrawBalanceReturn,
+        PublishStorageDeals => publish_storage_deals(PublishStorageDealsParams) -> PublishStorageDealsReturn [caller: any],
+        PublishStorageDealsExported => publish_storage_deals(PublishStorageDealsParams) -> PublishStorageDealsReturn [caller: any],
+        VerifyDealsForActivation => verify_deals_for_activation(VerifyDealsForActivationParams) -> VerifyDealsForActivationReturn [caller: types(Type::Miner), read_only],
+        ActivateDeals => activate_deals(ActivateDealsParams) -> ActivateDealsResult [caller: types(Type::Miner)],
+        OnMinerSectorsTerminate => on_miner_sectors_terminate(OnMinerSectorsTerminateParams) [caller: types(Type::Miner)],
+        ComputeDataCommitment => compute_data_commitment(ComputeDataCommitmentParams) -> ComputeDataCommitmentReturn [caller: types(Type::Miner), read_only],
+        CronTick => cron_tick [caller: is(CRON_ACTOR_ADDR)],
+        GetBalanceExported => get_balance(Address) -> GetBalanceReturn [caller: any, read_only],
+        GetDealDataCommitmentExported => get_deal_data_commitment(GetDealDataCommitmentParams) -> GetDealDataCommitmentReturn [caller: any, read_only],
+        GetDealClientExported => get_deal_client(GetDealClientParams) -> GetDealClientReturn [caller: any, read_only],
+        GetDealProviderExported => get_deal_provider(GetDealProviderParams) -> GetDealProviderReturn [caller: any, read_only],
+        GetDealLabelExported => get_deal_label(GetDealLabelParams) -> GetDealLabelReturn [caller: any, read_only],
+        GetDealTermExported => get_deal_term(GetDealTermParams) -> GetDealTermReturn [caller: any, read_only],
+        GetDealTotalPriceExported => get_deal_total_price(GetDealTotalPriceParams) -> GetDealTotalPriceReturn [caller: any, read_only],
+        GetDealClientCollateralExported => get_deal_client_collateral(GetDealClientCollateralParams) -> GetDealClientCollateralReturn [caller: any, read_only],
+        GetDealProviderCollateralExported => get_deal_provider_collateral(GetDealProviderCollateralParams) -> GetDealProviderCollateralReturn [caller: any, read_only],
+        GetDealVerifiedExported => get_deal_verified(GetDealVerifiedParams) -> GetDealVerifiedReturn [caller: any, read_only],
+        GetDealActivationExported => get_deal_activation(GetDealActivationParams) -> GetDealActivationReturn [caller: any, read_only],
     }
 }
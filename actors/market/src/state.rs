@@ -6,12 +6,11 @@ use crate::ext::verifreg::AllocationID;
 use cid::Cid;
 use fil_actors_runtime::runtime::Policy;
 use fil_actors_runtime::{
-    actor_error, make_empty_map, make_map_with_root_and_bitwidth, ActorError, Array, AsActorError,
-    Set, SetMultimap,
+    actor_error, make_empty_map, ActorContext, ActorError, Array, AsActorError, Map2, Set,
+    SetMultimap,
 };
 use fvm_ipld_blockstore::Blockstore;
 use fvm_ipld_encoding::tuple::*;
-use fvm_ipld_hamt::BytesKey;
 use fvm_shared::address::Address;
 use fvm_shared::clock::{ChainEpoch, EPOCH_UNDEFINED};
 use fvm_shared::deal::DealID;
@@ -25,6 +24,9 @@ use super::policy::*;
 use super::types::*;
 use super::{DealProposal, DealState};
 
+/// Verified registry allocation IDs for deals that are not yet activated, keyed by deal ID.
+pub type PendingDealAllocationsMap<'bs, BS> = Map2<'bs, BS, DealID, AllocationID>;
+
 pub enum Reason {
     ClientCollateral,
     ClientStorageFee,
@@ -95,20 +97,16 @@ impl State {
                 "Failed to create empty pending proposals map state",
             )?;
 
-        let empty_balance_table = BalanceTable::new(store).root().context_code(
-            ExitCode::USR_ILLEGAL_STATE,
-            "Failed to create empty balance table map",
-        )?;
+        let empty_balance_table = BalanceTable::new(store, "balance table")
+            .root()
+            .context("Failed to create empty balance table map")?;
 
         let empty_deal_ops_hamt = SetMultimap::new(store)
             .root()
             .context_code(ExitCode::USR_ILLEGAL_STATE, "Failed to create empty multiset")?;
 
-        let empty_pending_deal_allocation_map =
-            make_empty_map::<_, AllocationID>(store, HAMT_BIT_WIDTH).flush().context_code(
-                ExitCode::USR_ILLEGAL_STATE,
-                "Failed to create empty pending deal allocation map",
-            )?;
+        let empty_pending_deal_allocation_map = PendingDealAllocationsMap::flush_empty(store)
+            .context("Failed to create empty pending deal allocation map")?;
 
         Ok(Self {
             proposals: empty_proposals_array,
@@ -286,35 +284,40 @@ impl State {
         Ok(())
     }
 
+    pub fn load_pending_deal_allocation_ids<'bs, BS>(
+        &self,
+        store: &'bs BS,
+    ) -> Result<PendingDealAllocationsMap<'bs, BS>, ActorError>
+    where
+        BS: Blockstore,
+    {
+        PendingDealAllocationsMap::load(
+            store,
+            &self.pending_deal_allocation_ids,
+            "pending deal allocation ids",
+        )
+    }
+
     pub fn put_pending_deal_allocation_ids<BS>(
         &mut self,
         store: &BS,
-        new_pending_deal_allocation_ids: &[(BytesKey, AllocationID)],
+        new_pending_deal_allocation_ids: &[(DealID, AllocationID)],
     ) -> Result<(), ActorError>
     where
         BS: Blockstore,
     {
-        let mut pending_deal_allocation_ids = make_map_with_root_and_bitwidth(
-            &self.pending_deal_allocation_ids,
-            store,
-            HAMT_BIT_WIDTH,
-        )
-        .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load pending deal allocation id's")?;
+        let mut pending_deal_allocation_ids = self.load_pending_deal_allocation_ids(store)?;
 
         new_pending_deal_allocation_ids.iter().try_for_each(
             |(deal_id, allocation_id)| -> Result<(), ActorError> {
-                pending_deal_allocation_ids.set(deal_id.clone(), *allocation_id).context_code(
-                    ExitCode::USR_ILLEGAL_STATE,
-                    "failed to set pending deal allocation id",
-                )?;
+                pending_deal_allocation_ids
+                    .set(deal_id, *allocation_id)
+                    .context("failed to set pending deal allocation id")?;
                 Ok(())
             },
         )?;
 
-        self.pending_deal_allocation_ids = pending_deal_allocation_ids.flush().context_code(
-            ExitCode::USR_ILLEGAL_STATE,
-            "failed to flush pending deal allocation id",
-        )?;
+        self.pending_deal_allocation_ids = pending_deal_allocation_ids.flush()?;
 
         Ok(())
     }
@@ -322,27 +325,18 @@ impl State {
     pub fn remove_pending_deal_allocation_id<BS>(
         &mut self,
         store: &BS,
-        deal_id_key: &BytesKey,
-    ) -> Result<Option<(BytesKey, AllocationID)>, ActorError>
+        deal_id: DealID,
+    ) -> Result<Option<AllocationID>, ActorError>
     where
         BS: Blockstore,
     {
-        let mut pending_deal_allocation_ids = make_map_with_root_and_bitwidth(
-            &self.pending_deal_allocation_ids,
-            store,
-            HAMT_BIT_WIDTH,
-        )
-        .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load pending deal allocation id's")?;
+        let mut pending_deal_allocation_ids = self.load_pending_deal_allocation_ids(store)?;
 
         let rval_allocation_id = pending_deal_allocation_ids
-            .delete(deal_id_key)
-            .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
-                format!("no such deal proposal {:#?}", deal_id_key)
-            })?;
+            .delete(&deal_id)
+            .with_context(|| format!("no such deal proposal {}", deal_id))?;
 
-        self.pending_deal_allocation_ids = pending_deal_allocation_ids
-            .flush()
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to flush deal proposals")?;
+        self.pending_deal_allocation_ids = pending_deal_allocation_ids.flush()?;
 
         Ok(rval_allocation_id)
     }
@@ -458,16 +452,11 @@ impl State {
     where
         BS: Blockstore,
     {
-        let mut escrow_table = BalanceTable::from_root(store, &self.escrow_table)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load escrow table")?;
+        let mut escrow_table = BalanceTable::from_root(store, &self.escrow_table, "escrow table")?;
 
-        escrow_table
-            .add(addr, amount)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to add escrow table")?;
+        escrow_table.add(addr, amount).context("failed to add escrow table")?;
 
-        self.escrow_table = escrow_table
-            .root()
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to flush escrow table")?;
+        self.escrow_table = escrow_table.root().context("failed to flush escrow table")?;
 
         Ok(())
     }
@@ -481,23 +470,17 @@ impl State {
     where
         BS: Blockstore,
     {
-        let mut escrow_table = BalanceTable::from_root(store, &self.escrow_table)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load escrow table")?;
+        let mut escrow_table = BalanceTable::from_root(store, &self.escrow_table, "escrow table")?;
 
-        let locked_table = BalanceTable::from_root(store, &self.locked_table)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load locked table")?;
+        let locked_table = BalanceTable::from_root(store, &self.locked_table, "locked table")?;
 
-        let min_balance = locked_table
-            .get(addr)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to get locked balance")?;
+        let min_balance = locked_table.get(addr).context("failed to get locked balance")?;
 
         let ex = escrow_table
             .subtract_with_minimum(addr, amount, &min_balance)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to subtract from escrow table")?;
+            .context("failed to subtract from escrow table")?;
 
-        self.escrow_table = escrow_table
-            .root()
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to flush escrow table")?;
+        self.escrow_table = escrow_table.root().context("failed to flush escrow table")?;
 
         Ok(ex)
     }
@@ -752,19 +735,13 @@ impl State {
     where
         BS: Blockstore,
     {
-        let escrow_table = BalanceTable::from_root(store, &self.escrow_table)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load escrow table")?;
+        let escrow_table = BalanceTable::from_root(store, &self.escrow_table, "escrow table")?;
 
-        let locked_table = BalanceTable::from_root(store, &self.locked_table)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load locked table")?;
+        let locked_table = BalanceTable::from_root(store, &self.locked_table, "locked table")?;
 
-        let escrow_balance = escrow_table
-            .get(&addr)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to get escrow balance")?;
+        let escrow_balance = escrow_table.get(&addr).context("failed to get escrow balance")?;
 
-        let prev_locked = locked_table
-            .get(&addr)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to get locked balance")?;
+        let prev_locked = locked_table.get(&addr).context("failed to get locked balance")?;
 
         Ok((prev_locked + amount_to_lock) <= escrow_balance)
     }
@@ -782,19 +759,13 @@ impl State {
             return Err(actor_error!(illegal_state, "cannot lock negative amount {}", amount));
         }
 
-        let escrow_table = BalanceTable::from_root(store, &self.escrow_table)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load escrow table")?;
+        let escrow_table = BalanceTable::from_root(store, &self.escrow_table, "escrow table")?;
 
-        let mut locked_table = BalanceTable::from_root(store, &self.locked_table)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load locked table")?;
+        let mut locked_table = BalanceTable::from_root(store, &self.locked_table, "locked table")?;
 
-        let prev_locked = locked_table
-            .get(addr)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to get locked balance")?;
+        let prev_locked = locked_table.get(addr).context("failed to get locked balance")?;
 
-        let escrow_balance = escrow_table
-            .get(addr)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to get escrow balance")?;
+        let escrow_balance = escrow_table.get(addr).context("failed to get escrow balance")?;
 
         if &prev_locked + amount > escrow_balance {
             return Err(actor_error!(insufficient_funds;
@@ -803,13 +774,9 @@ impl State {
                     addr, escrow_balance, prev_locked, amount));
         }
 
-        locked_table
-            .add(addr, amount)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to add locked balance")?;
+        locked_table.add(addr, amount).context("failed to add locked balance")?;
 
-        self.locked_table = locked_table
-            .root()
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to flush locked table")?;
+        self.locked_table = locked_table.root().context("failed to flush locked table")?;
 
         Ok(())
     }
@@ -851,12 +818,9 @@ impl State {
             return Err(actor_error!(illegal_state, "unlock negative amount: {}", amount));
         }
 
-        let mut locked_table = BalanceTable::from_root(store, &self.locked_table)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load locked table")?;
+        let mut locked_table = BalanceTable::from_root(store, &self.locked_table, "locked table")?;
 
-        locked_table
-            .must_subtract(addr, amount)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "subtract from locked table failed")?;
+        locked_table.must_subtract(addr, amount).context("subtract from locked table failed")?;
 
         match lock_reason {
             Reason::ClientCollateral => {
@@ -870,9 +834,7 @@ impl State {
             }
         };
 
-        self.locked_table = locked_table
-            .root()
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to flush locked table")?;
+        self.locked_table = locked_table.root().context("failed to flush locked table")?;
 
         Ok(())
     }
@@ -892,25 +854,18 @@ impl State {
             return Err(actor_error!(illegal_state, "transfer negative amount: {}", amount));
         }
 
-        let mut escrow_table = BalanceTable::from_root(store, &self.escrow_table)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load escrow table")?;
+        let mut escrow_table = BalanceTable::from_root(store, &self.escrow_table, "escrow table")?;
 
         // Subtract from locked and escrow tables
-        escrow_table
-            .must_subtract(from_addr, amount)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "subtract from escrow")?;
+        escrow_table.must_subtract(from_addr, amount).context("subtract from escrow")?;
 
         self.unlock_balance(store, from_addr, amount, Reason::ClientStorageFee)
             .context_code(ExitCode::USR_ILLEGAL_STATE, "subtract from locked")?;
 
         // Add subtracted amount to the recipient
-        escrow_table
-            .add(to_addr, amount)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "add to escrow")?;
+        escrow_table.add(to_addr, amount).context("add to escrow")?;
 
-        self.escrow_table = escrow_table
-            .root()
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to flush escrow table")?;
+        self.escrow_table = escrow_table.root().context("failed to flush escrow table")?;
 
         Ok(())
     }
@@ -929,17 +884,12 @@ impl State {
             return Err(actor_error!(illegal_state, "negative amount to slash: {}", amount));
         }
 
-        let mut escrow_table = BalanceTable::from_root(store, &self.escrow_table)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load escrow table")?;
+        let mut escrow_table = BalanceTable::from_root(store, &self.escrow_table, "escrow table")?;
 
         // Subtract from locked and escrow tables
-        escrow_table
-            .must_subtract(addr, amount)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "subtract from escrow failed")?;
+        escrow_table.must_subtract(addr, amount).context("subtract from escrow failed")?;
 
-        self.escrow_table = escrow_table
-            .root()
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to flush escrow table")?;
+        self.escrow_table = escrow_table.root().context("failed to flush escrow table")?;
 
         self.unlock_balance(store, addr, amount, lock_reason)
     }
@@ -5,10 +5,7 @@ use std::{
 };
 
 use cid::Cid;
-use fil_actors_runtime::builtin::HAMT_BIT_WIDTH;
-use fil_actors_runtime::{
-    make_map_with_root_and_bitwidth, parse_uint_key, MessageAccumulator, SetMultimap,
-};
+use fil_actors_runtime::{make_map_with_root_and_bitwidth, MessageAccumulator, SetMultimap};
 use fvm_ipld_blockstore::Blockstore;
 use fvm_shared::{
     address::{Address, Protocol},
@@ -16,7 +13,6 @@ use fvm_shared::{
     deal::DealID,
     econ::TokenAmount,
 };
-use integer_encoding::VarInt;
 use num_traits::Zero;
 
 use crate::{
@@ -69,21 +65,21 @@ pub fn check_state_invariants<BS: Blockstore + Debug>(
 ) -> (StateSummary, MessageAccumulator) {
     let acc = MessageAccumulator::default();
 
-    acc.require(
+    acc.invariant("market.total_client_locked_collateral.non_negative").require(
         !state.total_client_locked_collateral.is_negative(),
         format!(
             "negative total client locked collateral: {}",
             state.total_client_locked_collateral
         ),
     );
-    acc.require(
+    acc.invariant("market.total_provider_locked_collateral.non_negative").require(
         !state.total_provider_locked_collateral.is_negative(),
         format!(
             "negative total provider locked collateral: {}",
             state.total_provider_locked_collateral
         ),
     );
-    acc.require(
+    acc.invariant("market.total_client_storage_fee.non_negative").require(
         !state.total_client_storage_fee.is_negative(),
         format!("negative total client storage fee: {}", state.total_client_storage_fee),
     );
@@ -98,6 +94,7 @@ pub fn check_state_invariants<BS: Blockstore + Debug>(
     match DealArray::load(&state.proposals, store) {
         Ok(proposals) => {
             let ret = proposals.for_each(|deal_id, proposal| {
+                let acc = acc.related("deal", deal_id);
                 let proposal_cid = deal_cid(proposal)?;
 
                 if proposal.start_epoch >= current_epoch {
@@ -122,23 +119,26 @@ pub fn check_state_invariants<BS: Blockstore + Debug>(
                 total_proposal_collateral +=
                     &proposal.client_collateral + &proposal.provider_collateral;
 
-                acc.require(
+                acc.invariant("market.proposal.client_id_address").require(
                     proposal.client.protocol() == Protocol::ID,
                     "client address for deal {deal_id} is not an ID address",
                 );
-                acc.require(
+                acc.invariant("market.proposal.provider_id_address").require(
                     proposal.provider.protocol() == Protocol::ID,
                     "provider address for deal {deal_id} is not an ID address",
                 );
                 Ok(())
             });
-            acc.require_no_error(ret, "error iterating proposals");
+            acc.invariant("market.proposals.load")
+                .require_no_error(ret, "error iterating proposals");
+        }
+        Err(e) => {
+            acc.invariant("market.proposals.load").add(format!("error loading proposals: {e}"))
         }
-        Err(e) => acc.add(format!("error loading proposals: {e}")),
     };
 
     // next id should be higher than any existing deal
-    acc.require(
+    acc.invariant("market.next_id").require(
         state.next_id as i64 > max_deal_id,
         format!(
             "next id, {}, is not greater than highest id in proposals, {max_deal_id}",
@@ -148,13 +148,13 @@ pub fn check_state_invariants<BS: Blockstore + Debug>(
 
     let mut pending_allocations = BTreeMap::<DealID, AllocationID>::new();
     let mut alloc_id_to_deal_id = BTreeMap::<AllocationID, DealID>::new();
-    match make_map_with_root_and_bitwidth(&state.pending_deal_allocation_ids, store, HAMT_BIT_WIDTH)
-    {
+    match state.load_pending_deal_allocation_ids(store) {
         Ok(pending_allocations_hamt) => {
-            let ret = pending_allocations_hamt.for_each(|key, allocation_id| {
-                let deal_id: u64 = u64::decode_var(key.0.as_slice()).unwrap().0;
+            let ret = pending_allocations_hamt.for_each(|deal_id, allocation_id| {
+                let deal_id = *deal_id;
+                let acc = acc.related("deal", deal_id);
 
-                acc.require(
+                acc.invariant("market.pending_allocation.has_proposal").require(
                     proposal_stats.get(&deal_id).is_some(),
                     format!("pending deal allocation {} not found in proposals", deal_id),
                 );
@@ -163,9 +163,12 @@ pub fn check_state_invariants<BS: Blockstore + Debug>(
                 alloc_id_to_deal_id.insert(*allocation_id, deal_id);
                 Ok(())
             });
-            acc.require_no_error(ret, "error iterating pending allocations");
+            acc.invariant("market.pending_allocations.load")
+                .require_no_error(ret, "error iterating pending allocations");
         }
-        Err(e) => acc.add(format!("error loading pending allocations: {e}")),
+        Err(e) => acc
+            .invariant("market.pending_allocations.load")
+            .add(format!("error loading pending allocations: {e}")),
     };
 
     // deal states
@@ -174,18 +177,19 @@ pub fn check_state_invariants<BS: Blockstore + Debug>(
     match DealMetaArray::load(&state.states, store) {
         Ok(deal_states) => {
             let ret = deal_states.for_each(|deal_id, deal_state| {
-                acc.require(
+                let acc = acc.related("deal", deal_id);
+                acc.invariant("market.deal_state.sector_start_defined").require(
                     deal_state.sector_start_epoch >= 0,
                     format!("deal {deal_id} state start epoch undefined: {:?}", deal_state),
                 );
-                acc.require(
+                acc.invariant("market.deal_state.updated_after_sector_start").require(
                     deal_state.last_updated_epoch == EPOCH_UNDEFINED
                         || deal_state.last_updated_epoch >= deal_state.sector_start_epoch,
                     format!(
                         "deal {deal_id} state last updated before sector start: {deal_state:?}"
                     ),
                 );
-                acc.require(
+                acc.invariant("market.deal_state.updated_before_current_epoch").require(
                     deal_state.last_updated_epoch == EPOCH_UNDEFINED
                         || deal_state.last_updated_epoch <= current_epoch,
                     format!(
@@ -193,17 +197,17 @@ pub fn check_state_invariants<BS: Blockstore + Debug>(
                         deal_state.last_updated_epoch
                     ),
                 );
-                acc.require(deal_state.slash_epoch == EPOCH_UNDEFINED || deal_state.slash_epoch >= deal_state.sector_start_epoch, format!("deal {deal_id} state slashed before sector start: {deal_state:?}"));
-                acc.require(deal_state.slash_epoch == EPOCH_UNDEFINED || deal_state.slash_epoch <= current_epoch, format!("deal {deal_id} state slashed after current epoch {current_epoch}: {deal_state:?}"));
+                acc.invariant("market.deal_state.slashed_after_sector_start").require(deal_state.slash_epoch == EPOCH_UNDEFINED || deal_state.slash_epoch >= deal_state.sector_start_epoch, format!("deal {deal_id} state slashed before sector start: {deal_state:?}"));
+                acc.invariant("market.deal_state.slashed_before_current_epoch").require(deal_state.slash_epoch == EPOCH_UNDEFINED || deal_state.slash_epoch <= current_epoch, format!("deal {deal_id} state slashed after current epoch {current_epoch}: {deal_state:?}"));
 
                 if let Some(stats) = proposal_stats.get_mut(&deal_id) {
                     stats.sector_start_epoch = deal_state.sector_start_epoch;
                     stats.last_update_epoch = deal_state.last_updated_epoch;
                     stats.slash_epoch = deal_state.slash_epoch;
                 } else {
-                    acc.add(format!("no deal proposal for deal state {deal_id}"));
+                    acc.invariant("market.deal_state.has_proposal").add(format!("no deal proposal for deal state {deal_id}"));
                 }
-                acc.require(!pending_allocations.contains_key(&deal_id), format!("deal {deal_id} has pending allocation"));
+                acc.invariant("market.deal_state.no_pending_allocation").require(!pending_allocations.contains_key(&deal_id), format!("deal {deal_id} has pending allocation"));
 
                 deal_state_count += 1;
 
@@ -213,9 +217,12 @@ pub fn check_state_invariants<BS: Blockstore + Debug>(
 
                 Ok(())
             });
-            acc.require_no_error(ret, "error iterating deal states");
+            acc.invariant("market.deal_states.load")
+                .require_no_error(ret, "error iterating deal states");
+        }
+        Err(e) => {
+            acc.invariant("market.deal_states.load").add(format!("error loading deal states: {e}"))
         }
-        Err(e) => acc.add(format!("error loading deal states: {e}")),
     };
 
     // pending proposals
@@ -229,63 +236,71 @@ pub fn check_state_invariants<BS: Blockstore + Debug>(
             let ret = pending_proposals.for_each(|key, _| {
                 let proposal_cid = Cid::try_from(key.0.to_owned())?;
 
-                acc.require(proposal_cids.contains(&proposal_cid), format!("pending proposal with cid {proposal_cid} not found within proposals {pending_proposals:?}"));
+                acc.invariant("market.pending_proposal.has_proposal").require(proposal_cids.contains(&proposal_cid), format!("pending proposal with cid {proposal_cid} not found within proposals {pending_proposals:?}"));
 
                 pending_proposal_count += 1;
                 Ok(())
             });
-            acc.require_no_error(ret, "error iterating pending proposals");
+            acc.invariant("market.pending_proposals.load")
+                .require_no_error(ret, "error iterating pending proposals");
         }
-        Err(e) => acc.add(format!("error loading pending proposals: {e}")),
+        Err(e) => acc
+            .invariant("market.pending_proposals.load")
+            .add(format!("error loading pending proposals: {e}")),
     };
 
     // escrow table and locked table
     let mut lock_table_count = 0;
-    let escrow_table = BalanceTable::from_root(store, &state.escrow_table);
-    let lock_table = BalanceTable::from_root(store, &state.locked_table);
+    let escrow_table = BalanceTable::from_root(store, &state.escrow_table, "escrow table");
+    let lock_table = BalanceTable::from_root(store, &state.locked_table, "locked table");
 
     match (escrow_table, lock_table) {
         (Ok(escrow_table), Ok(lock_table)) => {
             let mut locked_total = TokenAmount::zero();
-            let ret = lock_table.0.for_each(|key, locked_amount| {
-                let address = Address::from_bytes(key)?;
+            let ret = lock_table.0.for_each(|address, locked_amount| {
+                let address = *address;
 
                 locked_total += locked_amount;
 
                 // every entry in locked table should have a corresponding entry in escrow table that is at least as high
                 let escrow_amount = &escrow_table.get(&address)?;
-                acc.require(escrow_amount >= locked_amount, format!("locked funds for {address}, {locked_amount}, greater than escrow amount, {escrow_amount}"));
+                acc.invariant("market.locked.below_escrow").require(escrow_amount >= locked_amount, format!("locked funds for {address}, {locked_amount}, greater than escrow amount, {escrow_amount}"));
 
                 lock_table_count += 1;
 
                 Ok(())
             });
-            acc.require_no_error(ret, "error iterating locked table");
+            acc.invariant("market.locked_table.load")
+                .require_no_error(ret, "error iterating locked table");
 
             // lockTable total should be sum of client and provider locked plus client storage fee
             let expected_lock_total = &state.total_provider_locked_collateral
                 + &state.total_client_locked_collateral
                 + &state.total_client_storage_fee;
-            acc.require(locked_total == expected_lock_total, format!("locked total, {locked_total}, does not sum to provider locked, {}, client locked, {}, and client storage fee, {}", state.total_provider_locked_collateral, state.total_client_locked_collateral, state.total_client_storage_fee));
+            acc.invariant("market.locked.sum_total").require(locked_total == expected_lock_total, format!("locked total, {locked_total}, does not sum to provider locked, {}, client locked, {}, and client storage fee, {}", state.total_provider_locked_collateral, state.total_client_locked_collateral, state.total_client_storage_fee));
 
             // assert escrow <= actor balance
             // lock_table item <= escrow item and escrow_total <= balance implies lock_table total <= balance
             match escrow_table.total() {
                 Ok(escrow_total) => {
-                    acc.require(
+                    acc.invariant("market.escrow.below_balance").require(
                         &escrow_total <= balance,
                         format!(
                             "escrow total, {escrow_total}, greater than actor balance, {balance}"
                         ),
                     );
-                    acc.require(escrow_total >= total_proposal_collateral, format!("escrow total, {escrow_total}, less than sum of proposal collateral, {total_proposal_collateral}"));
+                    acc.invariant("market.escrow.covers_proposal_collateral").require(escrow_total >= total_proposal_collateral, format!("escrow total, {escrow_total}, less than sum of proposal collateral, {total_proposal_collateral}"));
                 }
-                Err(e) => acc.add(format!("error calculating escrow total: {e}")),
+                Err(e) => acc
+                    .invariant("market.escrow_table.load")
+                    .add(format!("error calculating escrow total: {e}")),
             }
         }
         (escrow_table, lock_table) => {
-            acc.require_no_error(escrow_table, "error loading escrow table");
-            acc.require_no_error(lock_table, "error loading locked table");
+            acc.invariant("market.escrow_table.load")
+                .require_no_error(escrow_table, "error loading escrow table");
+            acc.invariant("market.locked_table.load")
+                .require_no_error(lock_table, "error loading locked table");
         }
     };
 
@@ -293,25 +308,24 @@ pub fn check_state_invariants<BS: Blockstore + Debug>(
     let (mut deal_op_epoch_count, mut deal_op_count) = (0, 0);
     match SetMultimap::from_root(store, &state.deal_ops_by_epoch) {
         Ok(deal_ops) => {
-            // get into internals just to iterate through full data structure
-            let ret = deal_ops.0.for_each(|key, _| {
-                let epoch = parse_uint_key(key)? as i64;
-
+            let ret = deal_ops.for_each_key(|epoch| {
                 deal_op_epoch_count += 1;
 
                 deal_ops.for_each(epoch, |deal_id| {
-                    acc.require(proposal_stats.contains_key(&deal_id), format!("deal op found for deal id {deal_id} with missing proposal at epoch {epoch}"));
+                    let acc = acc.related("deal", deal_id);
+                    acc.invariant("market.deal_op.has_proposal").require(proposal_stats.contains_key(&deal_id), format!("deal op found for deal id {deal_id} with missing proposal at epoch {epoch}"));
                     expected_deal_ops.remove(&deal_id);
                     deal_op_count += 1;
                     Ok(())
                 }).map_err(|e| anyhow::anyhow!("error iterating deal ops for epoch {}: {}", epoch, e))
             });
-            acc.require_no_error(ret, "error iterating all deal ops");
+            acc.invariant("market.deal_ops.load")
+                .require_no_error(ret, "error iterating all deal ops");
         }
-        Err(e) => acc.add(format!("error loading deal ops: {e}")),
+        Err(e) => acc.invariant("market.deal_ops.load").add(format!("error loading deal ops: {e}")),
     };
 
-    acc.require(
+    acc.invariant("market.deal_ops.cover_future_deals").require(
         expected_deal_ops.is_empty(),
         format!("missing deal ops for proposals: {expected_deal_ops:?}"),
     );
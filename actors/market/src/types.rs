@@ -1,7 +1,6 @@
 // Copyright 2019-2022 ChainSafe Systems
 // SPDX-License-Identifier: Apache-2.0, MIT
 
-use super::ext::verifreg::AllocationID;
 use cid::Cid;
 use fil_actors_runtime::Array;
 use fvm_ipld_bitfield::BitField;
@@ -20,6 +19,11 @@ use fvm_shared::sector::RegisteredSealProof;
 
 use super::deal::{ClientDealProposal, DealProposal, DealState};
 
+pub use fil_actors_runtime::builtin::api::market::{
+    ActivateDealsParams, ActivateDealsResult, OnMinerSectorsTerminateParams, SectorDealData,
+    SectorDeals, VerifiedDealInfo, VerifyDealsForActivationParams, VerifyDealsForActivationReturn,
+};
+
 pub const PROPOSALS_AMT_BITWIDTH: u32 = 5;
 pub const STATES_AMT_BITWIDTH: u32 = 6;
 
@@ -41,12 +45,6 @@ pub struct GetBalanceReturn {
     pub locked: TokenAmount,
 }
 
-#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
-pub struct OnMinerSectorsTerminateParams {
-    pub epoch: ChainEpoch,
-    pub deal_ids: Vec<DealID>,
-}
-
 #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
 pub struct PublishStorageDealsParams {
     pub deals: Vec<ClientDealProposal>,
@@ -58,52 +56,6 @@ pub struct PublishStorageDealsReturn {
     pub valid_deals: BitField,
 }
 
-// Changed since V2:
-// - Array of Sectors rather than just one
-// - Removed SectorStart
-#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
-pub struct VerifyDealsForActivationParams {
-    pub sectors: Vec<SectorDeals>,
-}
-
-#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
-pub struct SectorDeals {
-    pub sector_type: RegisteredSealProof,
-    pub sector_expiry: ChainEpoch,
-    pub deal_ids: Vec<DealID>,
-}
-
-#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
-pub struct VerifyDealsForActivationReturn {
-    pub sectors: Vec<SectorDealData>,
-}
-
-#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq, Default)]
-pub struct SectorDealData {
-    /// Option::None signifies commitment to empty sector, meaning no deals.
-    pub commd: Option<Cid>,
-}
-
-#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
-pub struct ActivateDealsParams {
-    pub deal_ids: Vec<DealID>,
-    pub sector_expiry: ChainEpoch,
-}
-
-#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
-pub struct VerifiedDealInfo {
-    pub client: ActorID,
-    pub allocation_id: AllocationID,
-    pub data: Cid,
-    pub size: PaddedPieceSize,
-}
-
-#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
-pub struct ActivateDealsResult {
-    #[serde(with = "bigint_ser")]
-    pub nonverified_deal_space: BigInt,
-    pub verified_infos: Vec<VerifiedDealInfo>,
-}
 #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
 pub struct DealSpaces {
     #[serde(with = "bigint_ser")]
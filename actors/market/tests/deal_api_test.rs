@@ -12,11 +12,12 @@ use fil_actor_market::{
 };
 use fil_actors_runtime::network::EPOCHS_IN_DAY;
 use fil_actors_runtime::runtime::policy_constants::DEAL_UPDATES_INTERVAL;
+use fil_actors_runtime::runtime::{ActorCode, MethodMeta};
 use fil_actors_runtime::test_utils::{
     expect_abort_contains_message, MockRuntime, ACCOUNT_ACTOR_CODE_ID,
 };
-use fil_actors_runtime::ActorError;
 use fil_actors_runtime::BURNT_FUNDS_ACTOR_ADDR;
+use fil_actors_runtime::{ActorError, EXIT_CODE_READ_ONLY};
 use harness::*;
 
 mod harness;
@@ -149,6 +150,42 @@ fn activation() {
     );
 }
 
+#[test]
+fn state_changing_method_rejected_in_read_only_mode() {
+    let mut rt = setup();
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, CLIENT_ADDR);
+
+    // Rejected by the dispatch table before the caller is validated.
+    expect_abort_contains_message(
+        EXIT_CODE_READ_ONLY,
+        "read-only",
+        rt.call_read_only::<MarketActor>(
+            Method::AddBalanceExported as u64,
+            IpldBlock::serialize_cbor(&CLIENT_ADDR).unwrap(),
+        ),
+    );
+    rt.verify();
+}
+
+#[test]
+fn method_table_describes_getters() {
+    let meta = MarketActor::METHODS
+        .iter()
+        .find(|m| m.number == Method::GetDealTermExported as u64)
+        .unwrap();
+    assert_eq!(
+        &MethodMeta {
+            name: "GetDealTermExported",
+            number: Method::GetDealTermExported as u64,
+            exported: true,
+            read_only: true,
+            params: Some("GetDealTermParams"),
+            returns: Some("GetDealTermReturn"),
+        },
+        meta
+    );
+}
+
 fn query_deal<T: DeserializeOwned>(rt: &mut MockRuntime, method: Method, id: u64) -> T {
     query_deal_raw(rt, method, id).unwrap().unwrap().deserialize().unwrap()
 }
@@ -160,5 +197,5 @@ fn query_deal_raw(
 ) -> Result<Option<IpldBlock>, ActorError> {
     let params = DealQueryParams { id };
     rt.expect_validate_caller_any();
-    rt.call::<MarketActor>(method as u64, IpldBlock::serialize_cbor(&params).unwrap())
+    rt.call_read_only::<MarketActor>(method as u64, IpldBlock::serialize_cbor(&params).unwrap())
 }
@@ -0,0 +1,34 @@
+// The market's clients for other actors are declared with those actors' own types, in the
+// runtime's builtin API. The types it exchanges with the verified registry through the data cap
+// token are declared independently, so these tests fail if the two drift apart.
+
+use fil_actors_runtime::test_utils::{assert_same_encoding, make_piece_cid};
+use fil_actors_runtime::{BatchReturn, FailCode};
+use fvm_shared::error::ExitCode;
+use fvm_shared::piece::PaddedPieceSize;
+
+#[test]
+fn verifreg_types_match() {
+    use fil_actor_market::ext::verifreg::*;
+    use fil_actor_verifreg as verifreg;
+
+    assert_same_encoding::<verifreg::AllocationRequests>(&AllocationRequests {
+        allocations: vec![AllocationRequest {
+            provider: 100,
+            data: make_piece_cid(b"data"),
+            size: PaddedPieceSize(1 << 20),
+            term_min: 1000,
+            term_max: 2000,
+            expiration: 300,
+        }],
+        extensions: vec![ClaimExtensionRequest { provider: 100, claim: 5, term_max: 4000 }],
+    });
+    assert_same_encoding::<verifreg::AllocationsResponse>(&AllocationsResponse {
+        allocation_results: BatchReturn::ok(1),
+        extension_results: BatchReturn {
+            success_count: 0,
+            fail_codes: vec![FailCode { idx: 0, code: ExitCode::USR_FORBIDDEN }],
+        },
+        new_allocations: vec![7],
+    });
+}
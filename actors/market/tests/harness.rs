@@ -10,7 +10,7 @@ use std::{cell::RefCell, collections::HashMap};
 use fil_actor_market::ext::account::{AuthenticateMessageParams, AUTHENTICATE_MESSAGE_METHOD};
 use fil_actor_market::ext::verifreg::{AllocationID, AllocationRequest, AllocationsResponse};
 use fil_actor_market::{
-    deal_id_key, ext, ext::miner::GetControlAddressesReturnParams, gen_rand_next_epoch,
+    ext, ext::miner::GetControlAddressesReturn, gen_rand_next_epoch,
     testing::check_state_invariants, ActivateDealsParams, ActivateDealsResult,
     Actor as MarketActor, ClientDealProposal, DealArray, DealMetaArray, DealProposal, DealState,
     GetBalanceReturn, Label, MarketNotifyDealParams, Method, OnMinerSectorsTerminateParams,
@@ -20,14 +20,12 @@ use fil_actor_market::{
 };
 use fil_actor_power::{CurrentTotalPowerReturn, Method as PowerMethod};
 use fil_actor_reward::Method as RewardMethod;
-use fil_actors_runtime::builtin::HAMT_BIT_WIDTH;
 use fil_actors_runtime::cbor::serialize;
 use fil_actors_runtime::{
-    make_map_with_root_and_bitwidth,
     network::EPOCHS_IN_DAY,
     runtime::{builtins::Type, Policy, Runtime},
     test_utils::*,
-    ActorError, BatchReturn, SetMultimap, BURNT_FUNDS_ACTOR_ADDR, CRON_ACTOR_ADDR,
+    ActorError, BatchReturn, EventBuilder, SetMultimap, BURNT_FUNDS_ACTOR_ADDR, CRON_ACTOR_ADDR,
     DATACAP_TOKEN_ACTOR_ADDR, REWARD_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR,
     STORAGE_POWER_ACTOR_ADDR, SYSTEM_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR,
 };
@@ -144,7 +142,7 @@ pub fn expect_get_control_addresses(
     worker: Address,
     controls: Vec<Address>,
 ) {
-    let result = GetControlAddressesReturnParams { owner, worker, control_addresses: controls };
+    let result = GetControlAddressesReturn { owner, worker, control_addresses: controls };
 
     rt.expect_send(
         provider,
@@ -286,10 +284,21 @@ pub fn activate_deals_raw(
 
     let params = ActivateDealsParams { deal_ids: deal_ids.to_vec(), sector_expiry };
 
-    let ret = rt.call::<MarketActor>(
-        Method::ActivateDeals as u64,
-        IpldBlock::serialize_cbor(&params).unwrap(),
-    )?;
+    for d in deal_ids {
+        if let Some(proposal) = find_deal_proposal(rt, *d) {
+            expect_deal_event(rt, "deal-activated", *d, &proposal);
+        }
+    }
+
+    let ret = rt
+        .call::<MarketActor>(
+            Method::ActivateDeals as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        )
+        .map_err(|e| {
+            rt.clear_expected_events();
+            e
+        })?;
     rt.verify();
 
     for d in deal_ids {
@@ -300,19 +309,47 @@ pub fn activate_deals_raw(
 }
 
 pub fn get_deal_proposal(rt: &mut MockRuntime, deal_id: DealID) -> DealProposal {
+    find_deal_proposal(rt, deal_id).unwrap()
+}
+
+pub fn find_deal_proposal(rt: &MockRuntime, deal_id: DealID) -> Option<DealProposal> {
     let st: State = rt.get_state();
     let deals = DealArray::load(&st.proposals, &rt.store).unwrap();
-    let d = deals.get(deal_id).unwrap();
-    d.unwrap().clone()
+    deals.get(deal_id).unwrap().cloned()
+}
+
+pub fn find_deal_state(rt: &MockRuntime, deal_id: DealID) -> Option<DealState> {
+    let st: State = rt.get_state();
+    let states = DealMetaArray::load(&st.states, &rt.store).unwrap();
+    states.get(deal_id).unwrap().cloned()
+}
+
+/// Expects the market to emit an event of type `typ` for a deal.
+/// The proposal's client and provider may be non-ID addresses known to the runtime.
+pub fn expect_deal_event(
+    rt: &mut MockRuntime,
+    typ: &str,
+    deal_id: DealID,
+    proposal: &DealProposal,
+) {
+    let client = rt.get_id_address(&proposal.client).unwrap().id().unwrap();
+    let provider = rt.get_id_address(&proposal.provider).unwrap().id().unwrap();
+    rt.expect_emitted_event(
+        EventBuilder::new()
+            .typ(typ)
+            .field_indexed("id", &deal_id)
+            .field_indexed("client", &client)
+            .field_indexed("provider", &provider)
+            .build()
+            .unwrap(),
+    );
 }
 
 pub fn get_pending_deal_allocation(rt: &mut MockRuntime, deal_id: DealID) -> AllocationID {
     let st: State = rt.get_state();
-    let pending_allocations =
-        make_map_with_root_and_bitwidth(&st.pending_deal_allocation_ids, &rt.store, HAMT_BIT_WIDTH)
-            .unwrap();
+    let pending_allocations = st.load_pending_deal_allocation_ids(&rt.store).unwrap();
 
-    *pending_allocations.get(&deal_id_key(deal_id)).unwrap().unwrap_or(&NO_ALLOCATION_ID)
+    *pending_allocations.get(&deal_id).unwrap().unwrap_or(&NO_ALLOCATION_ID)
 }
 
 pub fn get_deal_state(rt: &mut MockRuntime, deal_id: DealID) -> DealState {
@@ -439,7 +476,7 @@ pub fn publish_deals(
     let st: State = rt.get_state();
     let next_deal_id = st.next_id;
     rt.expect_validate_caller_any();
-    let return_value = GetControlAddressesReturnParams {
+    let return_value = GetControlAddressesReturn {
         owner: addrs.owner,
         worker: addrs.worker,
         control_addresses: addrs.control.clone(),
@@ -539,6 +576,7 @@ pub fn publish_deals(
             None,
             ExitCode::USR_UNHANDLED_MESSAGE,
         );
+        expect_deal_event(rt, "deal-published", deal_id, deal);
         deal_id += 1;
     }
 
@@ -983,10 +1021,26 @@ pub fn terminate_deals_raw(
 
     let params = OnMinerSectorsTerminateParams { epoch: rt.epoch, deal_ids: deal_ids.to_vec() };
 
+    // Deals which are missing, expired or already slashed are skipped without an event.
+    for d in deal_ids {
+        let proposal = match find_deal_proposal(rt, *d) {
+            Some(proposal) if proposal.provider == miner_addr => proposal,
+            _ => continue,
+        };
+        let slashed = find_deal_state(rt, *d).map_or(true, |s| s.slash_epoch != EPOCH_UNDEFINED);
+        if proposal.end_epoch > rt.epoch && !slashed {
+            expect_deal_event(rt, "deal-terminated", *d, &proposal);
+        }
+    }
+
     rt.call::<MarketActor>(
         Method::OnMinerSectorsTerminate as u64,
         IpldBlock::serialize_cbor(&params).unwrap(),
     )
+    .map_err(|e| {
+        rt.clear_expected_events();
+        e
+    })
 }
 
 pub fn assert_account_zero(rt: &mut MockRuntime, addr: Address) {
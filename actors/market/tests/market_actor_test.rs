@@ -4,19 +4,18 @@
 use fil_actor_market::balance_table::BALANCE_TABLE_BITWIDTH;
 use fil_actor_market::policy::detail::DEAL_MAX_LABEL_SIZE;
 use fil_actor_market::{
-    deal_id_key, ext, ActivateDealsParams, Actor as MarketActor, ClientDealProposal, DealArray,
-    DealMetaArray, Label, MarketNotifyDealParams, Method, PublishStorageDealsParams,
-    PublishStorageDealsReturn, State, WithdrawBalanceParams, MARKET_NOTIFY_DEAL_METHOD,
-    NO_ALLOCATION_ID, PROPOSALS_AMT_BITWIDTH, STATES_AMT_BITWIDTH,
+    ext, ActivateDealsParams, Actor as MarketActor, ClientDealProposal, DealArray, DealMetaArray,
+    Label, MarketNotifyDealParams, Method, PublishStorageDealsParams, PublishStorageDealsReturn,
+    State, WithdrawBalanceParams, MARKET_NOTIFY_DEAL_METHOD, NO_ALLOCATION_ID,
+    PROPOSALS_AMT_BITWIDTH, STATES_AMT_BITWIDTH,
 };
 use fil_actors_runtime::cbor::{deserialize, serialize};
 use fil_actors_runtime::network::EPOCHS_IN_DAY;
 use fil_actors_runtime::runtime::{builtins::Type, Policy, Runtime};
 use fil_actors_runtime::test_utils::*;
 use fil_actors_runtime::{
-    make_empty_map, make_map_with_root_and_bitwidth, ActorError, BatchReturn, Map, SetMultimap,
-    BURNT_FUNDS_ACTOR_ADDR, DATACAP_TOKEN_ACTOR_ADDR, SYSTEM_ACTOR_ADDR,
-    VERIFIED_REGISTRY_ACTOR_ADDR,
+    make_empty_map, ActorError, BatchReturn, EventBuilder, SetMultimap, BURNT_FUNDS_ACTOR_ADDR,
+    DATACAP_TOKEN_ACTOR_ADDR, SYSTEM_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR,
 };
 use frc46_token::token::types::{TransferFromParams, TransferFromReturn};
 use fvm_ipld_amt::Amt;
@@ -34,7 +33,7 @@ use regex::Regex;
 use std::ops::Add;
 
 use fil_actor_market::ext::account::{AuthenticateMessageParams, AUTHENTICATE_MESSAGE_METHOD};
-use fil_actor_market::ext::verifreg::{AllocationID, AllocationRequest, AllocationsResponse};
+use fil_actor_market::ext::verifreg::{AllocationRequest, AllocationsResponse};
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use num_traits::{FromPrimitive, Zero};
 
@@ -751,10 +750,8 @@ fn deal_expires() {
     assert!(DealArray::load(&st.proposals, &rt.store).unwrap().get(deal_id).unwrap().is_none());
 
     // Pending allocation ID is gone
-    let pending_allocs: Map<_, AllocationID> =
-        make_map_with_root_and_bitwidth(&st.pending_deal_allocation_ids, &rt.store, HAMT_BIT_WIDTH)
-            .unwrap();
-    assert!(pending_allocs.get(&deal_id_key(deal_id)).unwrap().is_none());
+    let pending_allocs = st.load_pending_deal_allocation_ids(&rt.store).unwrap();
+    assert!(pending_allocs.get(&deal_id).unwrap().is_none());
 
     check_state(&rt);
 }
@@ -914,6 +911,8 @@ fn provider_and_client_addresses_are_resolved_before_persisting_state_and_sent_t
         None,
         ExitCode::USR_UNHANDLED_MESSAGE,
     );
+    // The event identifies the client and provider by their resolved IDs.
+    expect_deal_event(&mut rt, "deal-published", deal_id, &normalized_deal);
 
     let ret: PublishStorageDealsReturn = rt
         .call::<MarketActor>(
@@ -1854,6 +1853,7 @@ fn insufficient_client_balance_in_a_batch() {
         None,
         ExitCode::USR_UNHANDLED_MESSAGE,
     );
+    expect_deal_event(&mut rt, "deal-published", next_deal_id, &deal2);
 
     rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, WORKER_ADDR);
 
@@ -1988,6 +1988,7 @@ fn insufficient_provider_balance_in_a_batch() {
         None,
         ExitCode::USR_UNHANDLED_MESSAGE,
     );
+    expect_deal_event(&mut rt, "deal-published", next_deal_id, &deal2);
 
     rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, WORKER_ADDR);
 
@@ -2128,6 +2129,7 @@ fn psd_restricted_correctly() {
         None,
         ExitCode::USR_UNHANDLED_MESSAGE,
     );
+    expect_deal_event(&mut rt, "deal-published", next_deal_id, &deal);
 
     let ret: PublishStorageDealsReturn = rt
         .call::<MarketActor>(
@@ -2144,3 +2146,39 @@ fn psd_restricted_correctly() {
     rt.verify();
     check_state(&rt);
 }
+
+#[test]
+fn deal_lifecycle_emits_events() {
+    let start_epoch = 10;
+    let end_epoch = start_epoch + 200 * EPOCHS_IN_DAY;
+    let sector_expiry = end_epoch + 100;
+
+    let mut rt = setup();
+    let deal_id = generate_and_publish_deal(
+        &mut rt,
+        CLIENT_ADDR,
+        &MinerAddresses::default(),
+        start_epoch,
+        end_epoch,
+    );
+    activate_deals(&mut rt, sector_expiry, PROVIDER_ADDR, 0, &[deal_id]);
+    rt.set_epoch(start_epoch + 1);
+    terminate_deals(&mut rt, PROVIDER_ADDR, &[deal_id]);
+    // Terminating an already slashed deal emits nothing.
+    terminate_deals(&mut rt, PROVIDER_ADDR, &[deal_id]);
+
+    let expected: Vec<_> = ["deal-published", "deal-activated", "deal-terminated"]
+        .iter()
+        .map(|typ| {
+            EventBuilder::new()
+                .typ(typ)
+                .field_indexed("id", &deal_id)
+                .field_indexed("client", &CLIENT_ADDR.id().unwrap())
+                .field_indexed("provider", &PROVIDER_ADDR.id().unwrap())
+                .build()
+                .unwrap()
+        })
+        .collect();
+    assert_eq!(expected, *rt.emitted_events.borrow());
+    check_state(&rt);
+}
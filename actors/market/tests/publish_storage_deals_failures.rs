@@ -365,6 +365,7 @@ fn fail_when_deals_have_different_providers() {
         None,
         ExitCode::USR_UNHANDLED_MESSAGE,
     );
+    expect_deal_event(&mut rt, "deal-published", next_deal_id, &deal1);
 
     let psd_ret: PublishStorageDealsReturn = rt
         .call::<MarketActor>(
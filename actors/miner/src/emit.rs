@@ -0,0 +1,25 @@
+// Copyright 2019-2022 ChainSafe Systems
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use fil_actors_runtime::runtime::Runtime;
+use fil_actors_runtime::{ActorError, EventBuilder};
+use fvm_shared::sector::SectorNumber;
+
+/// Indicates a sector has been pre-committed.
+pub fn sector_precommitted(rt: &impl Runtime, sector: SectorNumber) -> Result<(), ActorError> {
+    emit_sector_event(rt, "sector-precommitted", sector)
+}
+
+/// Indicates a sector has been activated.
+pub fn sector_activated(rt: &impl Runtime, sector: SectorNumber) -> Result<(), ActorError> {
+    emit_sector_event(rt, "sector-activated", sector)
+}
+
+/// Indicates a sector has been terminated.
+pub fn sector_terminated(rt: &impl Runtime, sector: SectorNumber) -> Result<(), ActorError> {
+    emit_sector_event(rt, "sector-terminated", sector)
+}
+
+fn emit_sector_event(rt: &impl Runtime, typ: &str, sector: SectorNumber) -> Result<(), ActorError> {
+    EventBuilder::new().typ(typ).field_indexed("sector", &sector).emit(rt)
+}
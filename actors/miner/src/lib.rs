@@ -37,13 +37,15 @@ pub use deadline_info::*;
 pub use deadline_state::*;
 pub use deadlines::*;
 pub use expiration_queue::*;
+use fil_actors_runtime::builtin::api::miner::CONTROL_ADDRESSES_METHOD;
 use fil_actors_runtime::cbor::{serialize, serialize_vec};
 use fil_actors_runtime::runtime::builtins::Type;
 use fil_actors_runtime::runtime::{ActorCode, DomainSeparationTag, Policy, Runtime};
 use fil_actors_runtime::{
-    actor_dispatch, actor_error, deserialize_block, restrict_internal_api, ActorContext,
-    ActorDowncast, ActorError, BURNT_FUNDS_ACTOR_ADDR, INIT_ACTOR_ADDR, REWARD_ACTOR_ADDR,
-    STORAGE_MARKET_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR,
+    actor_dispatch, actor_error, callee_exit_error, deserialize_block, restrict_internal_api,
+    ActorContext, ActorDowncast, ActorError, ErrorContext, BURNT_FUNDS_ACTOR_ADDR, INIT_ACTOR_ADDR,
+    REWARD_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR,
+    VERIFIED_REGISTRY_ACTOR_ADDR,
 };
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 pub use monies::*;
@@ -70,6 +72,7 @@ mod deadline_assignment;
 mod deadline_info;
 mod deadline_state;
 mod deadlines;
+mod emit;
 mod expiration_queue;
 #[doc(hidden)]
 pub mod ext;
@@ -94,7 +97,7 @@ mod vesting_state;
 #[repr(u64)]
 pub enum Method {
     Constructor = METHOD_CONSTRUCTOR,
-    ControlAddresses = 2,
+    ControlAddresses = CONTROL_ADDRESSES_METHOD,
     ChangeWorkerAddress = 3,
     ChangePeerID = 4,
     SubmitWindowedPoSt = 5,
@@ -151,12 +154,10 @@ pub const ERR_BALANCE_INVARIANTS_BROKEN: ExitCode = ExitCode::new(1000);
 pub struct Actor;
 
 impl Actor {
-    pub fn constructor(
+    fn constructor(
         rt: &mut impl Runtime,
         params: MinerConstructorParams,
     ) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_is(std::iter::once(&INIT_ACTOR_ADDR))?;
-
         check_control_addresses(rt.policy(), &params.control_addresses)?;
         check_peer_info(rt.policy(), &params.peer_id, &params.multi_addresses)?;
         check_valid_post_proof_type(rt.policy(), params.window_post_proof_type)?;
@@ -230,7 +231,6 @@ impl Actor {
 
     /// Returns the "controlling" addresses: the owner, the worker, and all control addresses
     fn control_addresses(rt: &mut impl Runtime) -> Result<GetControlAddressesReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let state: State = rt.state()?;
         let info = get_miner_info(rt.store(), &state)?;
         Ok(GetControlAddressesReturn {
@@ -242,7 +242,6 @@ impl Actor {
 
     /// Returns the owner address, as well as the proposed new owner (if any).
     fn get_owner(rt: &mut impl Runtime) -> Result<GetOwnerReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let state: State = rt.state()?;
         let info = get_miner_info(rt.store(), &state)?;
         Ok(GetOwnerReturn { owner: info.owner, proposed: info.pending_owner_address })
@@ -254,7 +253,6 @@ impl Actor {
         rt: &mut impl Runtime,
         params: IsControllingAddressParam,
     ) -> Result<IsControllingAddressReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let input = match rt.resolve_address(&params.address) {
             Some(a) => Address::new_id(a),
             None => return Ok(IsControllingAddressReturn { is_controlling: false }),
@@ -273,7 +271,6 @@ impl Actor {
 
     /// Returns the miner's sector size.
     fn get_sector_size(rt: &mut impl Runtime) -> Result<GetSectorSizeReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let state: State = rt.state()?;
         let sector_size = get_miner_info(rt.store(), &state)?.sector_size;
         Ok(GetSectorSizeReturn { sector_size })
@@ -285,7 +282,6 @@ impl Actor {
     fn get_available_balance(
         rt: &mut impl Runtime,
     ) -> Result<GetAvailableBalanceReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let state: State = rt.state()?;
         let available_balance =
             state.get_available_balance(&rt.current_balance()).map_err(|e| {
@@ -296,7 +292,6 @@ impl Actor {
 
     /// Returns the funds vesting in this miner as a list of (vesting_epoch, vesting_amount) tuples.
     fn get_vesting_funds(rt: &mut impl Runtime) -> Result<GetVestingFundsReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let state: State = rt.state()?;
         let vesting_funds = state
             .load_vesting_funds(rt.store())
@@ -426,7 +421,6 @@ impl Actor {
 
     /// Returns the Peer ID for this miner.
     fn get_peer_id(rt: &mut impl Runtime) -> Result<GetPeerIDReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let state: State = rt.state()?;
         let peer_id = get_miner_info(rt.store(), &state)?.peer_id;
         Ok(GetPeerIDReturn { peer_id })
@@ -455,7 +449,6 @@ impl Actor {
 
     /// Returns the multiaddresses set for this miner.
     fn get_multiaddresses(rt: &mut impl Runtime) -> Result<GetMultiaddrsReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let state: State = rt.state()?;
         let multi_addrs = get_miner_info(rt.store(), &state)?.multi_address;
         Ok(GetMultiaddrsReturn { multi_addrs })
@@ -1458,7 +1451,6 @@ impl Actor {
         rt: &mut impl Runtime,
         params: DisputeWindowedPoStParams,
     ) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let reporter = rt.message().caller();
 
         {
@@ -1649,9 +1641,17 @@ impl Actor {
 
         request_update_power(rt, power_delta)?;
         if !to_reward.is_zero() {
-            if let Err(e) = rt.send(&reporter, METHOD_SEND, None, to_reward.clone()) {
-                error!("failed to send reward: {}", e);
-                to_burn += to_reward;
+            // A reward the reporter doesn't receive is burnt instead.
+            match rt.send(&reporter, METHOD_SEND, None, to_reward.clone()) {
+                Ok(ret) if ret.exit_code.is_success() => {}
+                Ok(ret) => {
+                    error!("reporter {} rejected reward with code {}", reporter, ret.exit_code);
+                    to_burn += to_reward;
+                }
+                Err(e) => {
+                    error!("failed to send reward to {}: {}", reporter, e);
+                    to_burn += to_reward;
+                }
             }
         }
 
@@ -1987,6 +1987,9 @@ impl Actor {
             state.deadline_cron_active = true;
             Ok(())
         })?;
+        for sector in sector_numbers.iter() {
+            emit::sector_precommitted(rt, sector)?;
+        }
         burn_funds(rt, fee_to_burn)?;
         let state: State = rt.state()?;
         state.check_balance_invariants(&rt.current_balance()).map_err(balance_invariants_broken)?;
@@ -2008,8 +2011,6 @@ impl Actor {
         rt: &mut impl Runtime,
         params: ProveCommitSectorParams,
     ) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
-
         if params.sector_number > MAX_SECTOR_NUMBER {
             return Err(actor_error!(illegal_argument, "sector number greater than maximum"));
         }
@@ -2078,12 +2079,7 @@ impl Actor {
             precommit.info.unsealed_cid,
         )?;
 
-        rt.send(
-            &STORAGE_POWER_ACTOR_ADDR,
-            ext::power::SUBMIT_POREP_FOR_BULK_VERIFY_METHOD,
-            IpldBlock::serialize_cbor(&svi)?,
-            TokenAmount::zero(),
-        )?;
+        ext::power::PowerClient::submit_porep_for_bulk_verify(rt, &svi)?;
 
         Ok(())
     }
@@ -2092,8 +2088,6 @@ impl Actor {
         rt: &mut impl Runtime,
         params: ConfirmSectorProofsParams,
     ) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_is(iter::once(&STORAGE_POWER_ACTOR_ADDR))?;
-
         // This should be enforced by the power actor. We log here just in case
         // something goes wrong.
         if params.sectors.len() > ext::power::MAX_MINER_PROVE_COMMITS_PER_EPOCH {
@@ -2126,8 +2120,6 @@ impl Actor {
         rt: &mut impl Runtime,
         params: CheckSectorProvenParams,
     ) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
-
         if params.sector_number > MAX_SECTOR_NUMBER {
             return Err(actor_error!(illegal_argument, "sector number out of range"));
         }
@@ -3094,7 +3086,6 @@ impl Actor {
         // Note: only the first report of any fault is processed because it sets the
         // ConsensusFaultElapsed state variable to an epoch after the fault, and reports prior to
         // that epoch are no longer valid
-        rt.validate_immediate_caller_accept_any()?;
         let reporter = rt.message().caller();
 
         let fault = rt
@@ -3178,8 +3169,10 @@ impl Actor {
             Ok((burn_amount, reward_amount))
         })?;
 
-        if let Err(e) = rt.send(&reporter, METHOD_SEND, None, reward_amount) {
-            error!("failed to send reward: {}", e);
+        match rt.send(&reporter, METHOD_SEND, None, reward_amount) {
+            Ok(ret) if ret.exit_code.is_success() => {}
+            Ok(ret) => error!("reporter {} rejected reward with code {}", reporter, ret.exit_code),
+            Err(e) => error!("failed to send reward to {}: {}", reporter, e),
         }
 
         burn_funds(rt, burn_amount)?;
@@ -3277,7 +3270,18 @@ impl Actor {
             })?;
 
         if amount_withdrawn.is_positive() {
-            rt.send(&info.beneficiary, METHOD_SEND, None, amount_withdrawn.clone())?;
+            let ret = rt
+                .send(&info.beneficiary, METHOD_SEND, None, amount_withdrawn.clone())
+                .with_context(|| format!("failed to send withdrawal to {}", info.beneficiary))?;
+            if !ret.exit_code.is_success() {
+                return Err(callee_exit_error(
+                    ret.exit_code,
+                    format!(
+                        "beneficiary {} rejected withdrawal of {} with code {}",
+                        info.beneficiary, amount_withdrawn, ret.exit_code
+                    ),
+                ));
+            }
         }
 
         burn_funds(rt, fee_to_burn)?;
@@ -3295,7 +3299,6 @@ impl Actor {
         rt: &mut impl Runtime,
         params: ChangeBeneficiaryParams,
     ) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let caller = rt.message().caller();
         let new_beneficiary =
             Address::new_id(rt.resolve_address(&params.new_beneficiary).ok_or_else(|| {
@@ -3422,8 +3425,8 @@ impl Actor {
     // This method is for use by other actors (such as those acting as beneficiaries),
     // and to abstract the state representation for clients.
     fn get_beneficiary(rt: &mut impl Runtime) -> Result<GetBeneficiaryReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
-        let info = rt.transaction(|state: &mut State, rt| get_miner_info(rt.store(), state))?;
+        let state: State = rt.state()?;
+        let info = get_miner_info(rt.store(), &state)?;
 
         Ok(GetBeneficiaryReturn {
             active: ActiveBeneficiary {
@@ -3467,8 +3470,6 @@ impl Actor {
         rt: &mut impl Runtime,
         params: DeferredCronEventParams,
     ) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_is(std::iter::once(&STORAGE_POWER_ACTOR_ADDR))?;
-
         let payload: CronEventPayload = from_slice(&params.event_payload).map_err(|e| {
             actor_error!(
                 illegal_state,
@@ -3622,8 +3623,10 @@ fn validate_extension_declarations(
             let mut drop_claims = sc.drop_claims.clone();
             let mut all_claim_ids = sc.maintain_claims.clone();
             all_claim_ids.append(&mut drop_claims);
-            let claims = get_claims(rt, &all_claim_ids)
-                .with_context(|| format!("failed to get claims for sector {}", sc.sector_number))?;
+            let claims = get_claims(rt, &all_claim_ids).with_context_data(|| {
+                ErrorContext::new(format!("failed to get claims for sector {}", sc.sector_number))
+                    .sector(sc.sector_number)
+            })?;
             let first_drop = sc.maintain_claims.len();
 
             for (i, claim) in claims.iter().enumerate() {
@@ -3916,6 +3919,12 @@ fn process_early_terminations(
         return Ok(more);
     }
 
+    for (_, sector_numbers) in result.iter() {
+        for sector in sector_numbers.iter() {
+            emit::sector_terminated(rt, sector)?;
+        }
+    }
+
     // Burn penalty.
     log::debug!(
         "storage provider {} penalized {} for sector termination",
@@ -4135,13 +4144,9 @@ fn enroll_cron_event(
     cb: CronEventPayload,
 ) -> Result<(), ActorError> {
     let payload = serialize(&cb, "cron payload")?;
-    let ser_params =
-        IpldBlock::serialize_cbor(&ext::power::EnrollCronEventParams { event_epoch, payload })?;
-    rt.send(
-        &STORAGE_POWER_ACTOR_ADDR,
-        ext::power::ENROLL_CRON_EVENT_METHOD,
-        ser_params,
-        TokenAmount::zero(),
+    ext::power::PowerClient::enroll_cron_event(
+        rt,
+        &ext::power::EnrollCronEventParams { event_epoch, payload },
     )?;
 
     Ok(())
@@ -4154,14 +4159,12 @@ fn request_update_power(rt: &mut impl Runtime, delta: PowerPair) -> Result<(), A
 
     let delta_clone = delta.clone();
 
-    rt.send(
-        &STORAGE_POWER_ACTOR_ADDR,
-        ext::power::UPDATE_CLAIMED_POWER_METHOD,
-        IpldBlock::serialize_cbor(&ext::power::UpdateClaimedPowerParams {
+    ext::power::PowerClient::update_claimed_power(
+        rt,
+        &ext::power::UpdateClaimedPowerParams {
             raw_byte_delta: delta.raw,
             quality_adjusted_delta: delta.qa,
-        })?,
-        TokenAmount::zero(),
+        },
     )
     .map_err(|e| e.wrap(format!("failed to update power with {:?}", delta_clone)))?;
 
@@ -4176,14 +4179,9 @@ fn request_terminate_deals(
     const MAX_LENGTH: usize = 8192;
 
     for chunk in deal_ids.chunks(MAX_LENGTH) {
-        rt.send(
-            &STORAGE_MARKET_ACTOR_ADDR,
-            ext::market::ON_MINER_SECTORS_TERMINATE_METHOD,
-            IpldBlock::serialize_cbor(&ext::market::OnMinerSectorsTerminateParamsRef {
-                epoch,
-                deal_ids: chunk,
-            })?,
-            TokenAmount::zero(),
+        ext::market::MarketClient::on_miner_sectors_terminate(
+            rt,
+            &ext::market::OnMinerSectorsTerminateParamsRef { epoch, deal_ids: chunk },
         )?;
     }
 
@@ -4310,12 +4308,10 @@ fn request_deal_data(
         });
     }
 
-    deserialize_block(rt.send(
-        &STORAGE_MARKET_ACTOR_ADDR,
-        ext::market::VERIFY_DEALS_FOR_ACTIVATION_METHOD,
-        IpldBlock::serialize_cbor(&ext::market::VerifyDealsForActivationParamsRef { sectors })?,
-        TokenAmount::zero(),
-    )?)
+    ext::market::MarketClient::verify_deals_for_activation(
+        rt,
+        &ext::market::VerifyDealsForActivationParamsRef { sectors },
+    )
 }
 
 /// Requests the current epoch target block reward from the reward actor.
@@ -4323,30 +4319,16 @@ fn request_deal_data(
 fn request_current_epoch_block_reward(
     rt: &mut impl Runtime,
 ) -> Result<ThisEpochRewardReturn, ActorError> {
-    deserialize_block(
-        rt.send(
-            &REWARD_ACTOR_ADDR,
-            ext::reward::THIS_EPOCH_REWARD_METHOD,
-            Default::default(),
-            TokenAmount::zero(),
-        )
-        .map_err(|e| e.wrap("failed to check epoch baseline power"))?,
-    )
+    ext::reward::RewardClient::this_epoch_reward(rt)
+        .map_err(|e| e.wrap("failed to check epoch baseline power"))
 }
 
 /// Requests the current network total power and pledge from the power actor.
 fn request_current_total_power(
     rt: &mut impl Runtime,
 ) -> Result<ext::power::CurrentTotalPowerReturn, ActorError> {
-    deserialize_block(
-        rt.send(
-            &STORAGE_POWER_ACTOR_ADDR,
-            ext::power::CURRENT_TOTAL_POWER_METHOD,
-            Default::default(),
-            TokenAmount::zero(),
-        )
-        .map_err(|e| e.wrap("failed to check current power"))?,
-    )
+    ext::power::PowerClient::current_total_power(rt)
+        .map_err(|e| e.wrap("failed to check current power"))
 }
 
 /// Resolves an address to an ID address and verifies that it is address of an account actor with an associated BLS key.
@@ -4368,12 +4350,7 @@ fn resolve_worker_address(rt: &mut impl Runtime, raw: Address) -> Result<ActorID
     }
 
     if raw.protocol() != Protocol::BLS {
-        let pub_key: Address = deserialize_block(rt.send(
-            &Address::new_id(resolved),
-            ext::account::PUBKEY_ADDRESS_METHOD,
-            None,
-            TokenAmount::zero(),
-        )?)?;
+        let pub_key = ext::account::AccountClient::pubkey_address(rt, &Address::new_id(resolved))?;
         if pub_key.protocol() != Protocol::BLS {
             return Err(actor_error!(
                 illegal_argument,
@@ -4389,7 +4366,15 @@ fn resolve_worker_address(rt: &mut impl Runtime, raw: Address) -> Result<ActorID
 fn burn_funds(rt: &mut impl Runtime, amount: TokenAmount) -> Result<(), ActorError> {
     log::debug!("storage provder {} burning {}", rt.message().receiver(), amount);
     if amount.is_positive() {
-        rt.send(&BURNT_FUNDS_ACTOR_ADDR, METHOD_SEND, None, amount)?;
+        let ret = rt
+            .send(&BURNT_FUNDS_ACTOR_ADDR, METHOD_SEND, None, amount.clone())
+            .with_context(|| format!("failed to burn {}", amount))?;
+        if !ret.exit_code.is_success() {
+            return Err(callee_exit_error(
+                ret.exit_code,
+                format!("burnt funds actor rejected {} with code {}", amount, ret.exit_code),
+            ));
+        }
     }
     Ok(())
 }
@@ -4399,11 +4384,9 @@ fn notify_pledge_changed(
     pledge_delta: &TokenAmount,
 ) -> Result<(), ActorError> {
     if !pledge_delta.is_zero() {
-        rt.send(
-            &STORAGE_POWER_ACTOR_ADDR,
-            ext::power::UPDATE_PLEDGE_TOTAL_METHOD,
-            IpldBlock::serialize_cbor(pledge_delta)?,
-            TokenAmount::zero(),
+        ext::power::PowerClient::update_pledge_total(
+            rt,
+            &ext::power::UpdatePledgeTotalParams { pledge_delta: pledge_delta.clone() },
         )?;
     }
     Ok(())
@@ -4417,12 +4400,7 @@ fn get_claims(
         provider: rt.message().receiver().id().unwrap(),
         claim_ids: ids.clone(),
     };
-    let claims_ret: ext::verifreg::GetClaimsReturn = deserialize_block(rt.send(
-        &VERIFIED_REGISTRY_ACTOR_ADDR,
-        ext::verifreg::GET_CLAIMS_METHOD as u64,
-        IpldBlock::serialize_cbor(&params)?,
-        TokenAmount::zero(),
-    )?)?;
+    let claims_ret = ext::verifreg::VerifregClient::get_claims(rt, &params)?;
     if (claims_ret.batch_info.success_count as usize) < ids.len() {
         return Err(actor_error!(illegal_argument, "invalid claims"));
     }
@@ -4715,7 +4693,7 @@ fn confirm_sector_proofs_valid_internal(
         return Err(actor_error!(illegal_argument, "all prove commits failed to validate"));
     }
 
-    let (total_pledge, newly_vested) = rt.transaction(|state: &mut State, rt| {
+    let (total_pledge, newly_vested, activated) = rt.transaction(|state: &mut State, rt| {
         let policy = rt.policy();
         let store = rt.store();
         let info = get_miner_info(store, state)?;
@@ -4847,9 +4825,13 @@ fn confirm_sector_proofs_valid_internal(
 
         state.check_balance_invariants(&rt.current_balance()).map_err(balance_invariants_broken)?;
 
-        Ok((total_pledge, newly_vested))
+        Ok((total_pledge, newly_vested, new_sector_numbers))
     })?;
 
+    for sector in activated {
+        emit::sector_activated(rt, sector)?;
+    }
+
     // Request pledge update for activated sector.
     notify_pledge_changed(rt, &(total_pledge - newly_vested))?;
 
@@ -4876,7 +4858,14 @@ fn activate_deals_and_claim_allocations(
         TokenAmount::zero(),
     );
     let activate_res: ext::market::ActivateDealsResult = match activate_raw {
-        Ok(res) => deserialize_block(res)?,
+        Ok(res) if res.exit_code.is_success() => deserialize_block(res.return_data)?,
+        Ok(res) => {
+            info!(
+                "error activating deals on sector {}: exit code {}",
+                sector_number, res.exit_code
+            );
+            return Ok(None);
+        }
         Err(e) => {
             info!("error activating deals on sector {}: {}", sector_number, e.msg());
             return Ok(None);
@@ -4913,7 +4902,14 @@ fn activate_deals_and_claim_allocations(
         TokenAmount::zero(),
     );
     let claim_res: ext::verifreg::ClaimAllocationsReturn = match claim_raw {
-        Ok(res) => deserialize_block(res)?,
+        Ok(res) if res.exit_code.is_success() => deserialize_block(res.return_data)?,
+        Ok(res) => {
+            info!(
+                "error claiming allocation on sector {}: exit code {}",
+                sector_number, res.exit_code
+            );
+            return Ok(None);
+        }
         Err(e) => {
             info!("error claiming allocation on sector {}: {}", sector_number, e.msg());
             return Ok(None);
@@ -4936,54 +4932,54 @@ fn balance_invariants_broken(e: Error) -> ActorError {
 impl ActorCode for Actor {
     type Methods = Method;
     actor_dispatch! {
-        Constructor => constructor,
-        ControlAddresses => control_addresses,
-        ChangeWorkerAddress => change_worker_address,
-        ChangeWorkerAddressExported => change_worker_address,
-        ChangePeerID => change_peer_id,
-        ChangePeerIDExported=> change_peer_id,
-        SubmitWindowedPoSt => submit_windowed_post,
-        PreCommitSector => pre_commit_sector,
-        ProveCommitSector => prove_commit_sector,
-        ExtendSectorExpiration => extend_sector_expiration,
-        TerminateSectors => terminate_sectors,
-        DeclareFaults => declare_faults,
-        DeclareFaultsRecovered => declare_faults_recovered,
-        OnDeferredCronEvent => on_deferred_cron_event,
-        CheckSectorProven => check_sector_proven,
-        ApplyRewards => apply_rewards,
-        ReportConsensusFault => report_consensus_fault,
-        WithdrawBalance => withdraw_balance,
-        WithdrawBalanceExported => withdraw_balance,
-        ConfirmSectorProofsValid => confirm_sector_proofs_valid,
-        ChangeMultiaddrs => change_multiaddresses,
-        ChangeMultiaddrsExported => change_multiaddresses,
-        CompactPartitions => compact_partitions,
-        CompactSectorNumbers => compact_sector_numbers,
+        Constructor => constructor(MinerConstructorParams) [caller: is(INIT_ACTOR_ADDR)],
+        ControlAddresses => control_addresses -> GetControlAddressesReturn [caller: any, read_only],
+        ChangeWorkerAddress => change_worker_address(ChangeWorkerAddressParams),
+        ChangeWorkerAddressExported => change_worker_address(ChangeWorkerAddressParams),
+        ChangePeerID => change_peer_id(ChangePeerIDParams),
+        ChangePeerIDExported => change_peer_id(ChangePeerIDParams),
+        SubmitWindowedPoSt => submit_windowed_post(SubmitWindowedPoStParams),
+        PreCommitSector => pre_commit_sector(PreCommitSectorParams),
+        ProveCommitSector => prove_commit_sector(ProveCommitSectorParams) [caller: any],
+        ExtendSectorExpiration => extend_sector_expiration(ExtendSectorExpirationParams),
+        TerminateSectors => terminate_sectors(TerminateSectorsParams) -> TerminateSectorsReturn,
+        DeclareFaults => declare_faults(DeclareFaultsParams),
+        DeclareFaultsRecovered => declare_faults_recovered(DeclareFaultsRecoveredParams),
+        OnDeferredCronEvent => on_deferred_cron_event(DeferredCronEventParams) [caller: is(STORAGE_POWER_ACTOR_ADDR)],
+        CheckSectorProven => check_sector_proven(CheckSectorProvenParams) [caller: any, read_only],
+        ApplyRewards => apply_rewards(ApplyRewardParams),
+        ReportConsensusFault => report_consensus_fault(ReportConsensusFaultParams) [caller: any],
+        WithdrawBalance => withdraw_balance(WithdrawBalanceParams) -> WithdrawBalanceReturn,
+        WithdrawBalanceExported => withdraw_balance(WithdrawBalanceParams) -> WithdrawBalanceReturn,
+        ConfirmSectorProofsValid => confirm_sector_proofs_valid(ConfirmSectorProofsParams) [caller: is(STORAGE_POWER_ACTOR_ADDR)],
+        ChangeMultiaddrs => change_multiaddresses(ChangeMultiaddrsParams),
+        ChangeMultiaddrsExported => change_multiaddresses(ChangeMultiaddrsParams),
+        CompactPartitions => compact_partitions(CompactPartitionsParams),
+        CompactSectorNumbers => compact_sector_numbers(CompactSectorNumbersParams),
         ConfirmChangeWorkerAddress => confirm_change_worker_address,
         ConfirmChangeWorkerAddressExported => confirm_change_worker_address,
         RepayDebt => repay_debt,
         RepayDebtExported => repay_debt,
-        ChangeOwnerAddress => change_owner_address,
-        ChangeOwnerAddressExported => change_owner_address,
-        DisputeWindowedPoSt => dispute_windowed_post,
-        PreCommitSectorBatch => pre_commit_sector_batch,
-        ProveCommitAggregate => prove_commit_aggregate,
-        ProveReplicaUpdates => prove_replica_updates,
-        PreCommitSectorBatch2 => pre_commit_sector_batch2,
-        ProveReplicaUpdates2 => prove_replica_updates2,
-        ChangeBeneficiary => change_beneficiary,
-        ChangeBeneficiaryExported => change_beneficiary,
-        GetBeneficiary => get_beneficiary,
-        GetBeneficiaryExported => get_beneficiary,
-        ExtendSectorExpiration2 => extend_sector_expiration2,
-        GetOwnerExported => get_owner,
-        IsControllingAddressExported => is_controlling_address,
-        GetSectorSizeExported => get_sector_size,
-        GetAvailableBalanceExported => get_available_balance,
-        GetVestingFundsExported => get_vesting_funds,
-        GetPeerIDExported => get_peer_id,
-        GetMultiaddrsExported => get_multiaddresses,
+        ChangeOwnerAddress => change_owner_address(Address),
+        ChangeOwnerAddressExported => change_owner_address(Address),
+        DisputeWindowedPoSt => dispute_windowed_post(DisputeWindowedPoStParams) [caller: any],
+        PreCommitSectorBatch => pre_commit_sector_batch(PreCommitSectorBatchParams),
+        ProveCommitAggregate => prove_commit_aggregate(ProveCommitAggregateParams),
+        ProveReplicaUpdates => prove_replica_updates(ProveReplicaUpdatesParams) -> BitField,
+        PreCommitSectorBatch2 => pre_commit_sector_batch2(PreCommitSectorBatchParams2),
+        ProveReplicaUpdates2 => prove_replica_updates2(ProveReplicaUpdatesParams2) -> BitField,
+        ChangeBeneficiary => change_beneficiary(ChangeBeneficiaryParams) [caller: any],
+        ChangeBeneficiaryExported => change_beneficiary(ChangeBeneficiaryParams) [caller: any],
+        GetBeneficiary => get_beneficiary -> GetBeneficiaryReturn [caller: any, read_only],
+        GetBeneficiaryExported => get_beneficiary -> GetBeneficiaryReturn [caller: any, read_only],
+        ExtendSectorExpiration2 => extend_sector_expiration2(ExtendSectorExpiration2Params),
+        GetOwnerExported => get_owner -> GetOwnerReturn [caller: any, read_only],
+        IsControllingAddressExported => is_controlling_address(IsControllingAddressParam) -> IsControllingAddressReturn [caller: any, read_only],
+        GetSectorSizeExported => get_sector_size -> GetSectorSizeReturn [caller: any, read_only],
+        GetAvailableBalanceExported => get_available_balance -> GetAvailableBalanceReturn [caller: any, read_only],
+        GetVestingFundsExported => get_vesting_funds -> GetVestingFundsReturn [caller: any, read_only],
+        GetPeerIDExported => get_peer_id -> GetPeerIDReturn [caller: any, read_only],
+        GetMultiaddrsExported => get_multiaddresses -> GetMultiaddrsReturn [caller: any, read_only],
     }
 }
 
@@ -36,7 +36,7 @@ pub fn check_state_invariants<BS: Blockstore>(
         }
         Err(e) => {
             // Stop here, it's too hard to make other useful checks.
-            acc.add(format!("error loading miner info: {e}"));
+            acc.invariant("miner.info.load").add(format!("error loading miner info: {e}"));
             return (miner_summary, acc);
         }
     };
@@ -48,16 +48,19 @@ pub fn check_state_invariants<BS: Blockstore>(
             if let Some(sectors) = allocated_sectors.bounded_iter(1 << 30) {
                 sectors.map(|i| i as SectorNumber).collect()
             } else {
-                acc.add("error expanding allocated sector bitfield");
+                acc.invariant("miner.allocated_sectors.load")
+                    .add("error expanding allocated sector bitfield");
                 BTreeSet::new()
             }
         }
         Ok(None) => {
-            acc.add("error loading allocated sector bitfield");
+            acc.invariant("miner.allocated_sectors.load")
+                .add("error loading allocated sector bitfield");
             BTreeSet::new()
         }
         Err(e) => {
-            acc.add(format!("error loading allocated sector bitfield: {e}"));
+            acc.invariant("miner.allocated_sectors.load")
+                .add(format!("error loading allocated sector bitfield: {e}"));
             BTreeSet::new()
         }
     };
@@ -69,7 +72,8 @@ pub fn check_state_invariants<BS: Blockstore>(
         Ok(sectors) => {
             let ret = sectors.amt.for_each(|sector_number, sector| {
                 all_sectors.insert(sector_number, sector.clone());
-                acc.require(
+                let acc = acc.related("sector", sector_number);
+                acc.invariant("miner.sector.allocated").require(
                     allocated_sectors.contains(&sector_number),
                     format!(
                         "on chain sector's sector number has not been allocated {sector_number}"
@@ -90,13 +94,13 @@ pub fn check_state_invariants<BS: Blockstore>(
                 Ok(())
             });
 
-            acc.require_no_error(ret, "error iterating sectors");
+            acc.invariant("miner.sectors.load").require_no_error(ret, "error iterating sectors");
         }
-        Err(e) => acc.add(format!("error loading sectors: {e}")),
+        Err(e) => acc.invariant("miner.sectors.load").add(format!("error loading sectors: {e}")),
     };
 
     // check deadlines
-    acc.require(
+    acc.invariant("miner.current_deadline").require(
         state.current_deadline < policy.wpost_period_deadlines,
         format!(
             "current deadline index is greater than deadlines per period({}): {}",
@@ -107,7 +111,9 @@ pub fn check_state_invariants<BS: Blockstore>(
     match state.load_deadlines(store) {
         Ok(deadlines) => {
             let ret = deadlines.for_each(policy, store, |deadline_index, deadline| {
-                let acc = acc.with_prefix(format!("deadline {deadline_index}: "));
+                let acc = acc
+                    .with_prefix(format!("deadline {deadline_index}: "))
+                    .related("deadline", deadline_index);
                 let quant = state.quant_spec_for_deadline(policy, deadline_index);
                 let deadline_summary = check_deadline_state_invariants(
                     &deadline,
@@ -124,10 +130,11 @@ pub fn check_state_invariants<BS: Blockstore>(
                 Ok(())
             });
 
-            acc.require_no_error(ret, "error iterating deadlines");
+            acc.invariant("miner.deadlines.load")
+                .require_no_error(ret, "error iterating deadlines");
         }
         Err(e) => {
-            acc.add(format!("error loading deadlines: {e}"));
+            acc.invariant("miner.deadlines.load").add(format!("error loading deadlines: {e}"));
         }
     };
 
@@ -164,30 +171,30 @@ impl Default for StateSummary {
 }
 
 fn check_miner_info(info: MinerInfo, acc: &MessageAccumulator) {
-    acc.require(
+    acc.invariant("miner.info.owner_id_address").require(
         info.owner.protocol() == Protocol::ID,
         format!("owner address {} is not an ID address", info.owner),
     );
-    acc.require(
+    acc.invariant("miner.info.worker_id_address").require(
         info.worker.protocol() == Protocol::ID,
         format!("worker address {} is not an ID address", info.worker),
     );
     info.control_addresses.iter().for_each(|address| {
-        acc.require(
+        acc.invariant("miner.info.control_id_address").require(
             address.protocol() == Protocol::ID,
             format!("control address {} is not an ID address", address),
         )
     });
 
     if let Some(pending_worker_key) = info.pending_worker_key {
-        acc.require(
+        acc.invariant("miner.info.pending_worker_id_address").require(
             pending_worker_key.new_worker.protocol() == Protocol::ID,
             format!(
                 "pending worker address {} is not an ID address",
                 pending_worker_key.new_worker
             ),
         );
-        acc.require(
+        acc.invariant("miner.info.pending_worker_changed").require(
             pending_worker_key.new_worker != info.worker,
             format!(
                 "pending worker key {} is same as existing worker {}",
@@ -197,11 +204,11 @@ fn check_miner_info(info: MinerInfo, acc: &MessageAccumulator) {
     }
 
     if let Some(pending_owner_address) = info.pending_owner_address {
-        acc.require(
+        acc.invariant("miner.info.pending_owner_id_address").require(
             pending_owner_address.protocol() == Protocol::ID,
             format!("pending owner address {} is not an ID address", pending_owner_address),
         );
-        acc.require(
+        acc.invariant("miner.info.pending_owner_changed").require(
             pending_owner_address != info.owner,
             format!(
                 "pending owner address {} is same as existing owner {}",
@@ -211,11 +218,12 @@ fn check_miner_info(info: MinerInfo, acc: &MessageAccumulator) {
     }
 
     if let RegisteredPoStProof::Invalid(id) = info.window_post_proof_type {
-        acc.add(format!("invalid Window PoSt proof type {id}"));
+        acc.invariant("miner.info.post_proof_type")
+            .add(format!("invalid Window PoSt proof type {id}"));
     } else {
         // safe to unwrap as we know it's valid at this point
         let sector_size = info.window_post_proof_type.sector_size().unwrap();
-        acc.require(
+        acc.invariant("miner.info.sector_size").require(
             info.sector_size == sector_size,
             format!(
                 "sector size {} is wrong for Window PoSt proof type {:?}: {}",
@@ -225,7 +233,7 @@ fn check_miner_info(info: MinerInfo, acc: &MessageAccumulator) {
 
         let partition_sectors =
             info.window_post_proof_type.window_post_partitions_sector().unwrap();
-        acc.require(info.window_post_partition_sectors == partition_sectors, format!("miner partition sectors {} does not match partition sectors {} for PoSt proof type {:?}", info.window_post_partition_sectors, partition_sectors, info.window_post_proof_type));
+        acc.invariant("miner.info.partition_sectors").require(info.window_post_partition_sectors == partition_sectors, format!("miner partition sectors {} does not match partition sectors {} for PoSt proof type {:?}", info.window_post_partition_sectors, partition_sectors, info.window_post_proof_type));
     }
 }
 
@@ -236,28 +244,28 @@ fn check_miner_balances<BS: Blockstore>(
     balance: &TokenAmount,
     acc: &MessageAccumulator,
 ) {
-    acc.require(
+    acc.invariant("miner.balance.non_negative").require(
         !balance.is_negative(),
         format!("miner actor balance is less than zero: {balance}"),
     );
-    acc.require(
+    acc.invariant("miner.locked_funds.non_negative").require(
         !state.locked_funds.is_negative(),
         format!("miner locked funds is less than zero: {}", state.locked_funds),
     );
-    acc.require(
+    acc.invariant("miner.pre_commit_deposits.non_negative").require(
         !state.pre_commit_deposits.is_negative(),
         format!("miner precommit deposit is less than zero: {}", state.pre_commit_deposits),
     );
-    acc.require(
+    acc.invariant("miner.initial_pledge.non_negative").require(
         !state.initial_pledge.is_negative(),
         format!("miner initial pledge is less than zero: {}", state.initial_pledge),
     );
-    acc.require(
+    acc.invariant("miner.fee_debt.non_negative").require(
         !state.fee_debt.is_negative(),
         format!("miner fee debt is less than zero: {}", state.fee_debt),
     );
 
-    acc.require(!(balance - &state.locked_funds - &state.pre_commit_deposits - &state.initial_pledge).is_negative(), format!("miner balance {balance} is less than sum of locked funds ({}), precommit deposit ({}) and initial pledge ({})", state.locked_funds, state.pre_commit_deposits, state.initial_pledge));
+    acc.invariant("miner.balance.covers_locked_funds").require(!(balance - &state.locked_funds - &state.pre_commit_deposits - &state.initial_pledge).is_negative(), format!("miner balance {balance} is less than sum of locked funds ({}), precommit deposit ({}) and initial pledge ({})", state.locked_funds, state.pre_commit_deposits, state.initial_pledge));
 
     // locked funds must be sum of vesting table and vesting table payments must be quantized
     let mut vesting_sum = TokenAmount::zero();
@@ -265,14 +273,14 @@ fn check_miner_balances<BS: Blockstore>(
         Ok(funds) => {
             let quant = state.quant_spec_every_deadline(policy);
             funds.funds.iter().for_each(|entry| {
-                acc.require(
+                acc.invariant("miner.vesting.amount_positive").require(
                     entry.amount.is_positive(),
                     format!("non-positive amount in miner vesting table entry {entry:?}"),
                 );
                 vesting_sum += &entry.amount;
 
                 let quantized = quant.quantize_up(entry.epoch);
-                acc.require(
+                acc.invariant("miner.vesting.epoch_quantized").require(
                     entry.epoch == quantized,
                     format!(
                         "vesting table entry has non-quantized epoch {} (should be {quantized})",
@@ -282,11 +290,11 @@ fn check_miner_balances<BS: Blockstore>(
             });
         }
         Err(e) => {
-            acc.add(format!("error loading vesting funds: {e}"));
+            acc.invariant("miner.vesting.load").add(format!("error loading vesting funds: {e}"));
         }
     };
 
-    acc.require(
+    acc.invariant("miner.vesting.sum_locked_funds").require(
         state.locked_funds == vesting_sum,
         format!(
             "locked funds {} is not sum of vesting table entries {vesting_sum}",
@@ -296,7 +304,8 @@ fn check_miner_balances<BS: Blockstore>(
 
     // non zero funds implies that DeadlineCronActive is true
     if state.continue_deadline_cron() {
-        acc.require(state.deadline_cron_active, "DeadlineCronActive == false when IP+PCD+LF > 0");
+        acc.invariant("miner.deadline_cron_active")
+            .require(state.deadline_cron_active, "DeadlineCronActive == false when IP+PCD+LF > 0");
     }
 }
 
@@ -316,7 +325,7 @@ fn check_precommits<BS: Blockstore>(
             let ret = queue.amt.for_each(|epoch, expiration_bitfield| {
                 let epoch = epoch as ChainEpoch;
                 let quantized = quant.quantize_up(epoch);
-                acc.require(
+                acc.invariant("miner.pre_commit_cleanup.epoch_quantized").require(
                     quantized == epoch,
                     format!("pre-commit expiration {epoch} is not quantized"),
                 );
@@ -326,10 +335,12 @@ fn check_precommits<BS: Blockstore>(
                 });
                 Ok(())
             });
-            acc.require_no_error(ret, "error iterating pre-commit clean-up queue");
+            acc.invariant("miner.pre_commit_cleanup.load")
+                .require_no_error(ret, "error iterating pre-commit clean-up queue");
         }
         Err(e) => {
-            acc.add(format!("error loading pre-commit clean-up queue: {e}"));
+            acc.invariant("miner.pre_commit_cleanup.load")
+                .add(format!("error loading pre-commit clean-up queue: {e}"));
         }
     };
 
@@ -344,31 +355,35 @@ fn check_precommits<BS: Blockstore>(
                 let sector_number = match parse_uint_key(key) {
                     Ok(sector_number) => sector_number,
                     Err(e) => {
-                        acc.add(format!("error parsing pre-commit key as uint: {e}"));
+                        acc.invariant("miner.pre_commits.load")
+                            .add(format!("error parsing pre-commit key as uint: {e}"));
                         return Ok(());
                     }
                 };
+                let acc = acc.related("sector", sector_number);
 
-                acc.require(
+                acc.invariant("miner.pre_commit.allocated").require(
                     allocated_sectors.contains(&sector_number),
                     format!("pre-commited sector number has not been allocated {sector_number}"),
                 );
 
-                acc.require(
+                acc.invariant("miner.pre_commit.has_cleanup_epoch").require(
                     cleanup_epochs.contains_key(&sector_number),
                     format!("no clean-up epoch for pre-commit at {}", precommit.pre_commit_epoch),
                 );
                 precommit_total += &precommit.pre_commit_deposit;
                 Ok(())
             });
-            acc.require_no_error(ret, "error iterating pre-commited sectors");
+            acc.invariant("miner.pre_commits.load")
+                .require_no_error(ret, "error iterating pre-commited sectors");
         }
         Err(e) => {
-            acc.add(format!("error loading precommited_sectors: {e}"));
+            acc.invariant("miner.pre_commits.load")
+                .add(format!("error loading precommited_sectors: {e}"));
         }
     };
 
-    acc.require(state.pre_commit_deposits == precommit_total, format!("sum of pre-commit deposits {precommit_total} does not equal recorded pre-commit deposit {}", state.pre_commit_deposits));
+    acc.invariant("miner.pre_commit_deposits.sum").require(state.pre_commit_deposits == precommit_total, format!("sum of pre-commit deposits {precommit_total} does not equal recorded pre-commit deposit {}", state.pre_commit_deposits));
 }
 
 #[derive(Default)]
@@ -416,36 +431,66 @@ impl PartitionStateSummary {
         let active = partition.active_sectors();
 
         // live contains all live sectors
-        require_contains_all(&live, &active, acc, "live does not contain active");
+        require_contains_all(
+            &live,
+            &active,
+            &acc.invariant("miner.partition.live_contains_active"),
+            "live does not contain active",
+        );
 
         // Live contains all faults.
-        require_contains_all(&live, &partition.faults, acc, "live does not contain faults");
+        require_contains_all(
+            &live,
+            &partition.faults,
+            &acc.invariant("miner.partition.live_contains_faults"),
+            "live does not contain faults",
+        );
 
         // Live contains all unproven.
-        require_contains_all(&live, &partition.unproven, acc, "live does not contain unproven");
+        require_contains_all(
+            &live,
+            &partition.unproven,
+            &acc.invariant("miner.partition.live_contains_unproven"),
+            "live does not contain unproven",
+        );
 
         // Active contains no faults
-        require_contains_none(&active, &partition.faults, acc, "active includes faults");
+        require_contains_none(
+            &active,
+            &partition.faults,
+            &acc.invariant("miner.partition.active_excludes_faults"),
+            "active includes faults",
+        );
 
         // Active contains no unproven
-        require_contains_none(&active, &partition.unproven, acc, "active includes unproven");
+        require_contains_none(
+            &active,
+            &partition.unproven,
+            &acc.invariant("miner.partition.active_excludes_unproven"),
+            "active includes unproven",
+        );
 
         // Faults contains all recoveries.
         require_contains_all(
             &partition.faults,
             &partition.recoveries,
-            acc,
+            &acc.invariant("miner.partition.faults_contain_recoveries"),
             "faults do not contain recoveries",
         );
 
         // Live contains no terminated sectors
-        require_contains_none(&live, &partition.terminated, acc, "live includes terminations");
+        require_contains_none(
+            &live,
+            &partition.terminated,
+            &acc.invariant("miner.partition.live_excludes_terminated"),
+            "live includes terminations",
+        );
 
         // Unproven contains no faults
         require_contains_none(
             &partition.faults,
             &partition.unproven,
-            acc,
+            &acc.invariant("miner.partition.unproven_excludes_faults"),
             "unproven includes faults",
         );
 
@@ -453,7 +498,7 @@ impl PartitionStateSummary {
         require_contains_all(
             &partition.sectors,
             &partition.terminated,
-            acc,
+            &acc.invariant("miner.partition.sectors_contain_terminated"),
             "sectors do not contain terminations",
         );
 
@@ -466,12 +511,13 @@ impl PartitionStateSummary {
         if missing.is_empty() {
             live_power =
                 power_for_sectors(sector_size, &live_sectors.values().cloned().collect::<Vec<_>>());
-            acc.require(
+            acc.invariant("miner.partition.live_power").require(
                 partition.live_power == live_power,
                 format!("live power was {:?}, expected {:?}", partition.live_power, live_power),
             );
         } else {
-            acc.add(format!("live sectors missing from all sectors: {missing:?}"));
+            acc.invariant("miner.partition.sectors_exist")
+                .add(format!("live sectors missing from all sectors: {missing:?}"));
         }
 
         let (unproven_sectors, missing) = select_sectors_map(sectors_map, &partition.unproven);
@@ -480,7 +526,7 @@ impl PartitionStateSummary {
                 sector_size,
                 &unproven_sectors.values().cloned().collect::<Vec<_>>(),
             );
-            acc.require(
+            acc.invariant("miner.partition.unproven_power").require(
                 partition.unproven_power == unproven_power,
                 format!(
                     "unproven power power was {:?}, expected {:?}",
@@ -488,7 +534,8 @@ impl PartitionStateSummary {
                 ),
             );
         } else {
-            acc.add(format!("unproven sectors missing from all sectors: {missing:?}"));
+            acc.invariant("miner.partition.sectors_exist")
+                .add(format!("unproven sectors missing from all sectors: {missing:?}"));
         }
 
         let (faulty_sectors, missing) = select_sectors_map(sectors_map, &partition.faults);
@@ -497,7 +544,7 @@ impl PartitionStateSummary {
                 sector_size,
                 &faulty_sectors.values().cloned().collect::<Vec<_>>(),
             );
-            acc.require(
+            acc.invariant("miner.partition.faulty_power").require(
                 partition.faulty_power == faulty_power,
                 format!(
                     "faulty power power was {:?}, expected {:?}",
@@ -505,7 +552,8 @@ impl PartitionStateSummary {
                 ),
             );
         } else {
-            acc.add(format!("faulty sectors missing from all sectors: {missing:?}"));
+            acc.invariant("miner.partition.sectors_exist")
+                .add(format!("faulty sectors missing from all sectors: {missing:?}"));
         }
 
         let (recovering_sectors, missing) = select_sectors_map(sectors_map, &partition.recoveries);
@@ -514,7 +562,7 @@ impl PartitionStateSummary {
                 sector_size,
                 &recovering_sectors.values().cloned().collect::<Vec<_>>(),
             );
-            acc.require(
+            acc.invariant("miner.partition.recovering_power").require(
                 partition.recovering_power == recovering_power,
                 format!(
                     "recovering power power was {:?}, expected {:?}",
@@ -522,12 +570,13 @@ impl PartitionStateSummary {
                 ),
             );
         } else {
-            acc.add(format!("recovering sectors missing from all sectors: {missing:?}"));
+            acc.invariant("miner.partition.sectors_exist")
+                .add(format!("recovering sectors missing from all sectors: {missing:?}"));
         }
 
         let active_power = &live_power - &faulty_power - unproven_power;
         let partition_active_power = partition.active_power();
-        acc.require(
+        acc.invariant("miner.partition.active_power").require(
             partition_active_power == active_power,
             format!("active power was {active_power:?}, expected {:?}", partition_active_power),
         );
@@ -549,10 +598,16 @@ impl PartitionStateSummary {
                 // check the queue is compatible with partition fields
                 let queue_sectors =
                     BitField::union([&queue_summary.on_time_sectors, &queue_summary.early_sectors]);
-                require_equal(&live, &queue_sectors, acc, "live does not equal all expirations");
+                require_equal(
+                    &live,
+                    &queue_sectors,
+                    &acc.invariant("miner.partition.expiration_queue.live"),
+                    "live does not equal all expirations",
+                );
             }
             Err(err) => {
-                acc.add(format!("error loading expiration_queue: {err}"));
+                acc.invariant("miner.partition.expiration_queue.load")
+                    .add(format!("error loading expiration_queue: {err}"));
             }
         };
 
@@ -561,7 +616,8 @@ impl PartitionStateSummary {
             match BitFieldQueue::new(store, &partition.early_terminated, NO_QUANTIZATION) {
                 Ok(queue) => check_early_termination_queue(queue, &partition.terminated, acc),
                 Err(err) => {
-                    acc.add(format!("error loading early termination queue: {err}"));
+                    acc.invariant("miner.partition.early_terminations.load")
+                        .add(format!("error loading early termination queue: {err}"));
                     0
                 }
             };
@@ -619,42 +675,44 @@ impl ExpirationQueueStateSummary {
             let epoch = epoch as i64;
             let acc = acc.with_prefix(format!("expiration epoch {epoch}: "));
             let quant_up = quant.quantize_up(epoch);
-            acc.require(quant_up == epoch, format!("expiration queue key {epoch} is not quantized, expected {quant_up}"));
+            acc.invariant("miner.expiration_queue.epoch_quantized").require(quant_up == epoch, format!("expiration queue key {epoch} is not quantized, expected {quant_up}"));
 
             expiration_epochs.push(epoch);
 
             let mut on_time_sectors_pledge = TokenAmount::zero();
             for sector_number in expiration_set.on_time_sectors.iter() {
+                let acc = acc.related("sector", sector_number);
                 // check sectors are present only once
                 if !seen_sectors.insert(sector_number) {
-                    acc.add(format!("sector {sector_number} in expiration queue twice"));
+                    acc.invariant("miner.expiration_queue.unique_sectors").add(format!("sector {sector_number} in expiration queue twice"));
                 }
 
                 // check expiring sectors are still alive
                 if let Some(sector) = live_sectors.get(&sector_number) {
                     let target = quant.quantize_up(sector.expiration);
-                    acc.require(epoch == target, format!("invalid expiration {epoch} for sector {sector_number}, expected {target}"));
+                    acc.invariant("miner.expiration_queue.on_time_epoch").require(epoch == target, format!("invalid expiration {epoch} for sector {sector_number}, expected {target}"));
                     on_time_sectors_pledge += sector.initial_pledge.clone();
                 } else {
-                    acc.add(format!("on time expiration sector {sector_number} isn't live"));
+                    acc.invariant("miner.expiration_queue.sector_live").add(format!("on time expiration sector {sector_number} isn't live"));
                 }
             }
 
             for sector_number in expiration_set.early_sectors.iter() {
+                let acc = acc.related("sector", sector_number);
                 // check sectors are present only once
                 if !seen_sectors.insert(sector_number) {
-                    acc.add(format!("sector {sector_number} in expiration queue twice"));
+                    acc.invariant("miner.expiration_queue.unique_sectors").add(format!("sector {sector_number} in expiration queue twice"));
                 }
 
                 // check early sectors are faulty
-                acc.require(partition_faults.get(sector_number), format!("sector {sector_number} expiring early but not faulty"));
+                acc.invariant("miner.expiration_queue.early_sector_faulty").require(partition_faults.get(sector_number), format!("sector {sector_number} expiring early but not faulty"));
 
                 // check expiring sectors are still alive
                 if let Some(sector) = live_sectors.get(&sector_number) {
                     let target = quant.quantize_up(sector.expiration);
-                    acc.require(epoch < target, format!("invalid early expiration {epoch} for sector {sector_number}, expected < {target}"));
+                    acc.invariant("miner.expiration_queue.early_epoch").require(epoch < target, format!("invalid early expiration {epoch} for sector {sector_number}, expected < {target}"));
                 } else {
-                    acc.add(format!("on time expiration sector {sector_number} isn't live"));
+                    acc.invariant("miner.expiration_queue.sector_live").add(format!("on time expiration sector {sector_number} isn't live"));
                 }
             }
 
@@ -663,19 +721,19 @@ impl ExpirationQueueStateSummary {
             let all = BitField::union([&expiration_set.on_time_sectors, &expiration_set.early_sectors]);
             let all_active = &all - partition_faults;
             let (active_sectors, missing) = select_sectors_map(live_sectors, &all_active);
-            acc.require(missing.is_empty(), format!("active sectors missing from live: {missing:?}"));
+            acc.invariant("miner.expiration_queue.active_sectors_live").require(missing.is_empty(), format!("active sectors missing from live: {missing:?}"));
 
             let all_faulty = &all & partition_faults;
             let (faulty_sectors, missing) = select_sectors_map(live_sectors, &all_faulty);
-            acc.require(missing.is_empty(), format!("faulty sectors missing from live: {missing:?}"));
+            acc.invariant("miner.expiration_queue.faulty_sectors_live").require(missing.is_empty(), format!("faulty sectors missing from live: {missing:?}"));
 
             let active_sectors_power = power_for_sectors(sector_size, &active_sectors.values().cloned().collect::<Vec<_>>());
-            acc.require(expiration_set.active_power == active_sectors_power, format!("active power recorded {:?} doesn't match computed {active_sectors_power:?}", expiration_set.active_power));
+            acc.invariant("miner.expiration_queue.active_power").require(expiration_set.active_power == active_sectors_power, format!("active power recorded {:?} doesn't match computed {active_sectors_power:?}", expiration_set.active_power));
 
             let faulty_sectors_power = power_for_sectors(sector_size, &faulty_sectors.values().cloned().collect::<Vec<_>>());
-            acc.require(expiration_set.faulty_power == faulty_sectors_power, format!("faulty power recorded {:?} doesn't match computed {faulty_sectors_power:?}", expiration_set.faulty_power));
+            acc.invariant("miner.expiration_queue.faulty_power").require(expiration_set.faulty_power == faulty_sectors_power, format!("faulty power recorded {:?} doesn't match computed {faulty_sectors_power:?}", expiration_set.faulty_power));
 
-            acc.require(expiration_set.on_time_pledge == on_time_sectors_pledge, format!("on time pledge recorded {} doesn't match computed: {on_time_sectors_pledge}", expiration_set.on_time_pledge));
+            acc.invariant("miner.expiration_queue.on_time_pledge").require(expiration_set.on_time_pledge == on_time_sectors_pledge, format!("on time pledge recorded {} doesn't match computed: {on_time_sectors_pledge}", expiration_set.on_time_pledge));
 
             all_on_time.push(expiration_set.on_time_sectors.clone());
             all_early.push(expiration_set.early_sectors.clone());
@@ -685,7 +743,8 @@ impl ExpirationQueueStateSummary {
 
             Ok(())
         });
-        acc.require_no_error(ret, "error iterating early termination bitfield");
+        acc.invariant("miner.expiration_queue.load")
+            .require_no_error(ret, "error iterating early termination bitfield");
 
         let union_on_time = BitField::union(&all_on_time);
         let union_early = BitField::union(&all_early);
@@ -714,18 +773,22 @@ fn check_early_termination_queue<BS: Blockstore>(
     let iter_result = early_queue.amt.for_each(|epoch, bitfield| {
         let acc = acc.with_prefix(format!("early termination epoch {epoch}: "));
         for i in bitfield.iter() {
-            acc.require(!seen.contains(&i), format!("sector {i} in early termination queue twice"));
+            acc.related("sector", i).invariant("miner.early_terminations.unique_sectors").require(
+                !seen.contains(&i),
+                format!("sector {i} in early termination queue twice"),
+            );
             seen.insert(i);
             seen_bitfield.set(i);
         }
         Ok(())
     });
 
-    acc.require_no_error(iter_result, "error iterating early termination bitfield");
+    acc.invariant("miner.early_terminations.load")
+        .require_no_error(iter_result, "error iterating early termination bitfield");
     require_contains_all(
         terminated,
         &seen_bitfield,
-        acc,
+        &acc.invariant("miner.early_terminations.terminated"),
         "terminated sectors missing early termination entry",
     );
 
@@ -789,7 +852,8 @@ pub fn check_deadline_state_invariants<BS: Blockstore>(
         Ok(partitions) => partitions,
         Err(e) => {
             // Hard to do any useful checks.
-            acc.add(format!("error loading partitions: {e}"));
+            acc.invariant("miner.deadline.partitions.load")
+                .add(format!("error loading partitions: {e}"));
             return DeadlineStateSummary::default();
         }
     };
@@ -812,7 +876,7 @@ pub fn check_deadline_state_invariants<BS: Blockstore>(
     partitions
         .for_each(|index, partition| {
             // check sequential partitions
-            acc.require(
+            acc.invariant("miner.deadline.partitions_sequential").require(
                 index == partition_count,
                 format!(
                     "Non-sequential partitions, expected index {partition_count}, found {index}"
@@ -820,7 +884,7 @@ pub fn check_deadline_state_invariants<BS: Blockstore>(
             );
             partition_count += 1;
 
-            let acc = acc.with_prefix(format!("partition {index}"));
+            let acc = acc.with_prefix(format!("partition {index}")).related("partition", index);
             let summary = PartitionStateSummary::check_partition_state_invariants(
                 partition,
                 store,
@@ -830,7 +894,7 @@ pub fn check_deadline_state_invariants<BS: Blockstore>(
                 &acc,
             );
 
-            acc.require(
+            acc.invariant("miner.deadline.unique_sectors").require(
                 !all_sectors.contains_any(&summary.all_sectors),
                 format!("duplicate sector in partition {index}"),
             );
@@ -859,11 +923,11 @@ pub fn check_deadline_state_invariants<BS: Blockstore>(
 
     // Check invariants on partitions proven
     if let Some(last_proof) = deadline.partitions_posted.last() {
-        acc.require(
+        acc.invariant("miner.deadline.posted_partitions_exist").require(
             partition_count > last_proof,
             format!("expected at least {} partitions, found {partition_count}", last_proof + 1),
         );
-        acc.require(
+        acc.invariant("miner.deadline.posted_partitions_live").require(
             deadline.live_sectors > 0,
             "expected at least one live sector when partitions have been proven",
         );
@@ -874,29 +938,33 @@ pub fn check_deadline_state_invariants<BS: Blockstore>(
     match deadline.partitions_snapshot_amt(store) {
         Ok(partition_snapshot) => {
             let ret = partition_snapshot.for_each(|i, partition| {
-                let acc = acc.with_prefix(format!("partition snapshot {i}"));
-                acc.require(
+                let acc =
+                    acc.with_prefix(format!("partition snapshot {i}")).related("partition", i);
+                acc.invariant("miner.deadline.snapshot.no_recovering_power").require(
                     partition.recovering_power.is_zero(),
                     "snapshot partition has recovering power",
                 );
-                acc.require(
+                acc.invariant("miner.deadline.snapshot.no_recoveries").require(
                     partition.recoveries.is_empty(),
                     "snapshot partition has pending recoveries",
                 );
-                acc.require(
+                acc.invariant("miner.deadline.snapshot.no_unproven_power").require(
                     partition.unproven_power.is_zero(),
                     "snapshot partition has unproven power",
                 );
-                acc.require(
+                acc.invariant("miner.deadline.snapshot.no_unproven").require(
                     partition.unproven.is_empty(),
                     "snapshot partition has unproven sectors",
                 );
 
                 Ok(())
             });
-            acc.require_no_error(ret, "error iterating partitions snapshot");
+            acc.invariant("miner.deadline.snapshot.load")
+                .require_no_error(ret, "error iterating partitions snapshot");
         }
-        Err(e) => acc.add(format!("error loading partitions snapshot: {e}")),
+        Err(e) => acc
+            .invariant("miner.deadline.snapshot.load")
+            .add(format!("error loading partitions snapshot: {e}")),
     };
 
     // Check that we don't have any proofs proving partitions that are not in the snapshot.
@@ -906,24 +974,27 @@ pub fn check_deadline_state_invariants<BS: Blockstore>(
                 let ret = proofs_snapshot.for_each(|_, proof| {
                     for partition in proof.partitions.iter() {
                         match partitions_snapshot.get(partition) {
-                            Ok(snapshot) => acc.require(
+                            Ok(snapshot) => acc.invariant("miner.deadline.proofs_snapshot.partitions_in_snapshot").require(
                                 snapshot.is_some(),
                                 format!("failed to find partition {partition} for recorded proof in the snapshot"),
                             ),
-                            Err(e) => acc.add(format!("error loading partition snapshot: {e}")),
+                            Err(e) => acc.invariant("miner.deadline.snapshot.load").add(format!("error loading partition snapshot: {e}")),
                         }
                     }
                     Ok(())
                 });
-                acc.require_no_error(ret, "error iterating proofs snapshot");
+                acc.invariant("miner.deadline.proofs_snapshot.load")
+                    .require_no_error(ret, "error iterating proofs snapshot");
             }
         }
-        Err(e) => acc.add(format!("error loading proofs snapshot: {e}")),
+        Err(e) => acc
+            .invariant("miner.deadline.proofs_snapshot.load")
+            .add(format!("error loading proofs snapshot: {e}")),
     };
 
     // check memoized sector and power values
     let live_sectors = BitField::union(&all_live_sectors);
-    acc.require(
+    acc.invariant("miner.deadline.live_sectors").require(
         deadline.live_sectors == live_sectors.len(),
         format!(
             "deadline live sectors {} != partitions count {}",
@@ -932,7 +1003,7 @@ pub fn check_deadline_state_invariants<BS: Blockstore>(
         ),
     );
 
-    acc.require(
+    acc.invariant("miner.deadline.total_sectors").require(
         deadline.total_sectors == all_sectors.len(),
         format!(
             "deadline total sectors {} != partitions count {}",
@@ -946,7 +1017,7 @@ pub fn check_deadline_state_invariants<BS: Blockstore>(
     let unproven_sectors = BitField::union(&all_unproven_sectors);
     let terminated_sectors = BitField::union(&all_terminated_sectors);
 
-    acc.require(
+    acc.invariant("miner.deadline.faulty_power").require(
         deadline.faulty_power == all_faulty_power,
         format!(
             "deadline faulty power {:?} != partitions total {all_faulty_power:?}",
@@ -962,24 +1033,28 @@ pub fn check_deadline_state_invariants<BS: Blockstore>(
                 match expiration_queue.amt.get(epoch as u64) {
                     Ok(expiration_bitfield) if expiration_bitfield.is_some() => {
                         for partition in expiring_idx {
-                            acc.require(expiration_bitfield.unwrap().get(partition), format!("expected partition {partition} to be present in deadline expiration queue at epoch {epoch}"));
+                            acc.related("partition", partition).invariant("miner.deadline.expiration_queue.partitions").require(expiration_bitfield.unwrap().get(partition), format!("expected partition {partition} to be present in deadline expiration queue at epoch {epoch}"));
                         }
                     }
-                    Ok(_) => acc.add(format!(
-                        "expected to find partition expiration entry at epoch {epoch}"
-                    )),
-                    Err(e) => acc.add(format!("error fetching expiration bitfield: {e}")),
+                    Ok(_) => acc.invariant("miner.deadline.expiration_queue.partitions").add(
+                        format!("expected to find partition expiration entry at epoch {epoch}"),
+                    ),
+                    Err(e) => acc
+                        .invariant("miner.deadline.expiration_queue.load")
+                        .add(format!("error fetching expiration bitfield: {e}")),
                 }
             }
         }
-        Err(e) => acc.add(format!("error loading expiration queue: {e}")),
+        Err(e) => acc
+            .invariant("miner.deadline.expiration_queue.load")
+            .add(format!("error loading expiration queue: {e}")),
     }
 
     // Validate the early termination queue contains exactly the partitions with early terminations.
     require_equal(
         &partitions_with_early_terminations,
         &deadline.early_terminations,
-        acc,
+        &acc.invariant("miner.deadline.early_terminations"),
         "deadline early terminations doesn't match expected partitions",
     );
 
@@ -24,6 +24,8 @@ use crate::ext::verifreg::ClaimID;
 
 use super::beneficiary::*;
 
+pub use fil_actors_runtime::builtin::api::miner::GetControlAddressesReturn;
+
 pub type CronEvent = i64;
 
 pub const CRON_EVENT_WORKER_KEY_CHANGE: CronEvent = 0;
@@ -54,13 +56,6 @@ pub struct PartitionKey {
     pub partition: u64,
 }
 
-#[derive(Serialize_tuple, Deserialize_tuple)]
-pub struct GetControlAddressesReturn {
-    pub owner: Address,
-    pub worker: Address,
-    pub control_addresses: Vec<Address>,
-}
-
 #[derive(Serialize_tuple, Deserialize_tuple)]
 pub struct ChangeWorkerAddressParams {
     pub new_worker: Address,
@@ -0,0 +1,59 @@
+// The miner's clients for other actors are declared with those actors' own types, in the
+// runtime's builtin API. The types below are declared independently of the actors which
+// consume them, so these tests fail if the two drift apart.
+
+use fil_actors_runtime::test_utils::{assert_same_encoding, make_piece_cid};
+use fvm_ipld_encoding::BytesDe;
+use fvm_shared::address::Address;
+use fvm_shared::bigint::BigInt;
+use fvm_shared::sector::{RegisteredPoStProof, RegisteredSealProof};
+use fvm_shared::smooth::FilterEstimate;
+
+#[test]
+fn market_types_match() {
+    use fil_actor_market as market;
+    use fil_actor_miner::ext::market::*;
+
+    assert_same_encoding::<market::DealSpaces>(&DealSpaces {
+        deal_space: BigInt::from(1 << 30),
+        verified_deal_space: BigInt::from(1 << 20),
+    });
+    let spec = SectorDataSpec {
+        deal_ids: vec![1, 2],
+        sector_type: RegisteredSealProof::StackedDRG32GiBV1P1,
+    };
+    assert_same_encoding::<market::ComputeDataCommitmentParams>(&ComputeDataCommitmentParamsRef {
+        inputs: &[spec],
+    });
+    assert_same_encoding::<market::ComputeDataCommitmentReturn>(&ComputeDataCommitmentReturn {
+        commds: vec![make_piece_cid(b"commd")],
+    });
+}
+
+// The power actor's types for the miner are checked here, as it doesn't depend on the miner
+// crate.
+#[test]
+fn miner_types_match() {
+    use fil_actor_miner as miner;
+    use fil_actor_power::ext::miner::*;
+
+    assert_same_encoding::<miner::MinerConstructorParams>(&MinerConstructorParams {
+        owner: Address::new_id(100),
+        worker: Address::new_id(101),
+        control_addresses: vec![Address::new_id(102)],
+        window_post_proof_type: RegisteredPoStProof::StackedDRGWindow32GiBV1,
+        peer_id: vec![1, 2, 3],
+        multi_addresses: vec![BytesDe(vec![4, 5])],
+    });
+    assert_same_encoding::<miner::ConfirmSectorProofsParams>(&ConfirmSectorProofsParams {
+        sectors: vec![1, 2],
+        reward_smoothed: FilterEstimate::new(BigInt::from(11), BigInt::from(13)),
+        reward_baseline_power: BigInt::from(1i64 << 40),
+        quality_adj_power_smoothed: FilterEstimate::new(BigInt::from(17), BigInt::from(19)),
+    });
+    assert_same_encoding::<miner::DeferredCronEventParams>(&DeferredCronEventParams {
+        event_payload: vec![1, 2, 3],
+        reward_smoothed: FilterEstimate::new(BigInt::from(11), BigInt::from(13)),
+        quality_adj_power_smoothed: FilterEstimate::new(BigInt::from(17), BigInt::from(19)),
+    });
+}
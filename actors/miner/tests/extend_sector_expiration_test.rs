@@ -9,7 +9,9 @@ use fil_actors_runtime::DealWeight;
 use fil_actors_runtime::{
     actor_error,
     runtime::{Runtime, RuntimePolicy},
-    test_utils::{expect_abort_contains_message, make_piece_cid, MockRuntime},
+    test_utils::{
+        expect_abort_contains_message, expect_abort_context, make_piece_cid, MockRuntime,
+    },
     EPOCHS_IN_DAY,
 };
 use fvm_ipld_bitfield::BitField;
@@ -543,11 +545,13 @@ fn update_expiration2_failure_cases() {
         };
 
         let res = h.extend_sectors2(&mut rt, params, claims);
-        expect_abort_contains_message(
+        let err = expect_abort_context(
             ExitCode::USR_ILLEGAL_ARGUMENT,
-            "failed to get claims for sector",
+            &[&format!("failed to get claims for sector {}", old_sector.sector_number)],
             res,
         );
+        let sector = old_sector.sector_number.to_string();
+        assert_eq!(Some(sector.as_str()), err.context()[0].get("sector"));
         // assert sector expiration is set to the new value
         check_for_expiration(
             &mut h,
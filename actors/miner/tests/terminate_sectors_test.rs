@@ -5,7 +5,7 @@ use fil_actor_miner::{
 use fil_actors_runtime::{
     runtime::{Policy, Runtime},
     test_utils::{expect_abort_contains_message, MockRuntime, ACCOUNT_ACTOR_CODE_ID},
-    EPOCHS_IN_DAY,
+    EventBuilder, EPOCHS_IN_DAY,
 };
 use fvm_shared::{econ::TokenAmount, error::ExitCode};
 
@@ -90,6 +90,19 @@ fn removes_sector_with_correct_accounting() {
     //expect pledge requirement to have been decremented
     assert!(state.initial_pledge.is_zero());
 
+    // expect the sector's lifecycle to have been recorded in events
+    let expected: Vec<_> = ["sector-precommitted", "sector-activated", "sector-terminated"]
+        .iter()
+        .map(|typ| {
+            EventBuilder::new()
+                .typ(typ)
+                .field_indexed("sector", &sector.sector_number)
+                .build()
+                .unwrap()
+        })
+        .collect();
+    assert_eq!(expected, *rt.emitted_events.borrow());
+
     h.check_state(&rt);
 }
 
@@ -45,9 +45,9 @@ use fil_actor_miner::ext::verifreg::{
 use fil_actors_runtime::runtime::{DomainSeparationTag, Policy, Runtime, RuntimePolicy};
 use fil_actors_runtime::{test_utils::*, BatchReturn, BatchReturnGen};
 use fil_actors_runtime::{
-    ActorDowncast, ActorError, Array, DealWeight, MessageAccumulator, BURNT_FUNDS_ACTOR_ADDR,
-    INIT_ACTOR_ADDR, REWARD_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR,
-    VERIFIED_REGISTRY_ACTOR_ADDR,
+    ActorDowncast, ActorError, Array, DealWeight, EventBuilder, MessageAccumulator,
+    BURNT_FUNDS_ACTOR_ADDR, INIT_ACTOR_ADDR, REWARD_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR,
+    STORAGE_POWER_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR,
 };
 use fvm_ipld_amt::Amt;
 use fvm_shared::bigint::Zero;
@@ -718,7 +718,16 @@ impl ActorHarness {
             );
         }
 
+        let mut sector_numbers: Vec<_> = sectors.iter().map(|s| s.sector_number).collect();
+        sector_numbers.sort_unstable();
+        for sector in sector_numbers {
+            expect_sector_event(rt, "sector-precommitted", sector);
+        }
+
         let result = rt.call::<Actor>(method as u64, IpldBlock::serialize_cbor(&param).unwrap());
+        if result.is_err() {
+            rt.clear_expected_events();
+        }
         result
     }
 
@@ -798,10 +807,15 @@ impl ActorHarness {
             );
         }
 
+        expect_sector_event(rt, "sector-precommitted", params.sector_number);
+
         let result = rt.call::<Actor>(
             Method::PreCommitSector as u64,
             IpldBlock::serialize_cbor(&params.clone()).unwrap(),
         );
+        if result.is_err() {
+            rt.clear_expected_events();
+        }
         result
     }
 
@@ -1002,7 +1016,11 @@ impl ActorHarness {
         rt.call::<Actor>(
             MinerMethod::ProveCommitAggregate as u64,
             IpldBlock::serialize_cbor(&params).unwrap(),
-        )?;
+        )
+        .map_err(|e| {
+            rt.clear_expected_events();
+            e
+        })?;
         rt.verify();
 
         Ok(())
@@ -1033,7 +1051,11 @@ impl ActorHarness {
         rt.call::<Actor>(
             Method::ConfirmSectorProofsValid as u64,
             IpldBlock::serialize_cbor(&params).unwrap(),
-        )?;
+        )
+        .map_err(|e| {
+            rt.clear_expected_events();
+            e
+        })?;
         rt.verify();
         Ok(())
     }
@@ -1150,6 +1172,7 @@ impl ActorHarness {
                     );
 
                     expected_pledge += pledge;
+                    expect_sector_event(rt, "sector-activated", pc.info.sector_number);
                 }
             }
 
@@ -2068,6 +2091,10 @@ impl ActorHarness {
 
         let params = TerminateSectorsParams { terminations };
 
+        for sector in sectors.iter() {
+            expect_sector_event(rt, "sector-terminated", sector);
+        }
+
         rt.call::<Actor>(
             Method::TerminateSectors as u64,
             IpldBlock::serialize_cbor(&params).unwrap(),
@@ -2835,6 +2862,13 @@ fn make_piece_cid(input: &[u8]) -> Cid {
     Cid::new_v1(FIL_COMMITMENT_UNSEALED, h)
 }
 
+/// Expects the miner to emit an event of type `typ` for a sector.
+pub fn expect_sector_event(rt: &mut MockRuntime, typ: &str, sector: SectorNumber) {
+    rt.expect_emitted_event(
+        EventBuilder::new().typ(typ).field_indexed("sector", &sector).build().unwrap(),
+    );
+}
+
 pub fn make_deadline_cron_event_params(epoch: ChainEpoch) -> EnrollCronEventParams {
     let payload = CronEventPayload { event_type: CRON_EVENT_PROVING_DEADLINE };
     EnrollCronEventParams { event_epoch: epoch, payload: RawBytes::serialize(payload).unwrap() }
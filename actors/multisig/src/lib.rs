@@ -14,10 +14,11 @@ use num_derive::FromPrimitive;
 use num_traits::{FromPrimitive, Zero};
 
 use fil_actors_runtime::cbor::serialize_vec;
-use fil_actors_runtime::runtime::{ActorCode, Primitives, Runtime};
+use fil_actors_runtime::runtime::{ActorCode, Primitives, Runtime, SendFlags};
 use fil_actors_runtime::{
     actor_dispatch, actor_error, make_empty_map, make_map_with_root, resolve_to_actor_id,
     restrict_internal_api, ActorContext, ActorError, AsActorError, Map, INIT_ACTOR_ADDR,
+    USER_RECEIVER_GAS_LIMIT,
 };
 
 pub use self::state::*;
@@ -52,9 +53,7 @@ pub struct Actor;
 
 impl Actor {
     /// Constructor for Multisig actor
-    pub fn constructor(rt: &mut impl Runtime, params: ConstructorParams) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_is(std::iter::once(&INIT_ACTOR_ADDR))?;
-
+    fn constructor(rt: &mut impl Runtime, params: ConstructorParams) -> Result<(), ActorError> {
         if params.signers.is_empty() {
             return Err(actor_error!(illegal_argument; "Must have at least one signer"));
         }
@@ -121,11 +120,10 @@ impl Actor {
     }
 
     /// Multisig actor propose function
-    pub fn propose(
+    fn propose(
         rt: &mut impl Runtime,
         params: ProposeParams,
     ) -> Result<ProposeReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let proposer: Address = rt.message().caller();
 
         if params.value.is_negative() {
@@ -174,11 +172,10 @@ impl Actor {
     }
 
     /// Multisig actor approve function
-    pub fn approve(
+    fn approve(
         rt: &mut impl Runtime,
         params: TxnIDParams,
     ) -> Result<ApproveReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let approver: Address = rt.message().caller();
 
         let id = params.id;
@@ -209,8 +206,7 @@ impl Actor {
     }
 
     /// Multisig actor cancel function
-    pub fn cancel(rt: &mut impl Runtime, params: TxnIDParams) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
+    fn cancel(rt: &mut impl Runtime, params: TxnIDParams) -> Result<(), ActorError> {
         let caller_addr: Address = rt.message().caller();
 
         rt.transaction(|st: &mut State, rt| {
@@ -254,9 +250,7 @@ impl Actor {
     }
 
     /// Multisig actor function to add signers to multisig
-    pub fn add_signer(rt: &mut impl Runtime, params: AddSignerParams) -> Result<(), ActorError> {
-        let receiver = rt.message().receiver();
-        rt.validate_immediate_caller_is(std::iter::once(&receiver))?;
+    fn add_signer(rt: &mut impl Runtime, params: AddSignerParams) -> Result<(), ActorError> {
         let resolved_new_signer = resolve_to_actor_id(rt, &params.signer)?;
 
         rt.transaction(|st: &mut State, _| {
@@ -282,12 +276,10 @@ impl Actor {
     }
 
     /// Multisig actor function to remove signers to multisig
-    pub fn remove_signer(
+    fn remove_signer(
         rt: &mut impl Runtime,
         params: RemoveSignerParams,
     ) -> Result<(), ActorError> {
-        let receiver = rt.message().receiver();
-        rt.validate_immediate_caller_is(std::iter::once(&receiver))?;
         let resolved_old_signer = resolve_to_actor_id(rt, &params.signer)?;
 
         rt.transaction(|st: &mut State, rt| {
@@ -332,9 +324,7 @@ impl Actor {
     }
 
     /// Multisig actor function to swap signers to multisig
-    pub fn swap_signer(rt: &mut impl Runtime, params: SwapSignerParams) -> Result<(), ActorError> {
-        let receiver = rt.message().receiver();
-        rt.validate_immediate_caller_is(std::iter::once(&receiver))?;
+    fn swap_signer(rt: &mut impl Runtime, params: SwapSignerParams) -> Result<(), ActorError> {
         let from_resolved = resolve_to_actor_id(rt, &params.from)?;
         let to_resolved = resolve_to_actor_id(rt, &params.to)?;
 
@@ -361,13 +351,10 @@ impl Actor {
     }
 
     /// Multisig actor function to change number of approvals needed
-    pub fn change_num_approvals_threshold(
+    fn change_num_approvals_threshold(
         rt: &mut impl Runtime,
         params: ChangeNumApprovalsThresholdParams,
     ) -> Result<(), ActorError> {
-        let receiver = rt.message().receiver();
-        rt.validate_immediate_caller_is(std::iter::once(&receiver))?;
-
         rt.transaction(|st: &mut State, _| {
             // Check if valid threshold value
             if params.new_threshold == 0 || params.new_threshold > st.signers.len() as u64 {
@@ -383,13 +370,10 @@ impl Actor {
     }
 
     /// Multisig actor function to change number of approvals needed
-    pub fn lock_balance(
+    fn lock_balance(
         rt: &mut impl Runtime,
         params: LockBalanceParams,
     ) -> Result<(), ActorError> {
-        let receiver = rt.message().receiver();
-        rt.validate_immediate_caller_is(std::iter::once(&receiver))?;
-
         if params.unlock_duration <= 0 {
             return Err(actor_error!(illegal_argument, "unlock duration must be positive"));
         }
@@ -450,11 +434,10 @@ impl Actor {
     }
 
     // Always succeeds, accepting any transfers, so long as the params are valid `UniversalReceiverParams`.
-    pub fn universal_receiver_hook(
-        rt: &mut impl Runtime,
+    fn universal_receiver_hook(
+        _rt: &mut impl Runtime,
         _params: UniversalReceiverParams,
     ) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         Ok(())
     }
 }
@@ -472,14 +455,25 @@ fn execute_transaction_if_approved(
     if threshold_met {
         st.check_available(rt.current_balance(), &txn.value, rt.curr_epoch())?;
 
-        match rt.send(&txn.to, txn.method, txn.params.clone().into(), txn.value.clone()) {
-            Ok(Some(r)) => {
-                out = RawBytes::new(r.data);
+        // The receiver is chosen by the proposer, so the gas it may consume is capped.
+        // A receiver exhausting the cap fails the transaction, not the approval.
+        match rt.send_generalized(
+            &txn.to,
+            txn.method,
+            txn.params.clone().into(),
+            txn.value.clone(),
+            Some(USER_RECEIVER_GAS_LIMIT),
+            SendFlags::empty(),
+        ) {
+            Ok(ret) => {
+                code = ret.exit_code;
+                if let Some(r) = ret.return_data {
+                    out = RawBytes::new(r.data);
+                }
             }
             Err(e) => {
                 code = e.exit_code();
             }
-            _ => {}
         }
         applied = true;
 
@@ -554,15 +548,15 @@ pub fn compute_proposal_hash(txn: &Transaction, sys: &dyn Primitives) -> anyhow:
 impl ActorCode for Actor {
     type Methods = Method;
     actor_dispatch! {
-      Constructor => constructor,
-      Propose => propose,
-      Approve => approve,
-      Cancel => cancel,
-      AddSigner => add_signer,
-      RemoveSigner => remove_signer,
-      SwapSigner => swap_signer,
-      ChangeNumApprovalsThreshold => change_num_approvals_threshold,
-      LockBalance => lock_balance,
-      UniversalReceiverHook => universal_receiver_hook,
+      Constructor => constructor(ConstructorParams) [caller: is(INIT_ACTOR_ADDR)],
+      Propose => propose(ProposeParams) -> ProposeReturn [caller: any],
+      Approve => approve(TxnIDParams) -> ApproveReturn [caller: any],
+      Cancel => cancel(TxnIDParams) [caller: any],
+      AddSigner => add_signer(AddSignerParams) [caller: receiver],
+      RemoveSigner => remove_signer(RemoveSignerParams) [caller: receiver],
+      SwapSigner => swap_signer(SwapSignerParams) [caller: receiver],
+      ChangeNumApprovalsThreshold => change_num_approvals_threshold(ChangeNumApprovalsThresholdParams) [caller: receiver],
+      LockBalance => lock_balance(LockBalanceParams) [caller: receiver],
+      UniversalReceiverHook => universal_receiver_hook(UniversalReceiverParams) [caller: any],
     }
 }
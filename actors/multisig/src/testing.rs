@@ -22,11 +22,11 @@ pub fn check_state_invariants<BS: Blockstore>(
     let acc = MessageAccumulator::default();
 
     // assert invariants involving signers
-    acc.require(
+    acc.invariant("multisig.signers.max").require(
         state.signers.len() <= SIGNERS_MAX,
         format!("multisig has too many signers: {}", state.signers.len()),
     );
-    acc.require(
+    acc.invariant("multisig.signers.threshold").require(
         state.signers.len() as u64 >= state.num_approvals_threshold,
         format!(
             "multisig has insufficient signers to meet threshold ({} < {})",
@@ -37,11 +37,11 @@ pub fn check_state_invariants<BS: Blockstore>(
 
     // See https://github.com/filecoin-project/specs-actors/issues/1185
     if state.unlock_duration == 0 {
-        acc.require(
+        acc.invariant("multisig.unlock.start_epoch").require(
             state.start_epoch == 0,
             format!("non-zero start epoch {} with zero unlock duration", state.start_epoch),
         );
-        acc.require(
+        acc.invariant("multisig.unlock.initial_balance").require(
             state.initial_balance.is_zero(),
             format!("non-zero locked balance {} with zero unlock duration", state.initial_balance),
         );
@@ -67,22 +67,23 @@ pub fn check_state_invariants<BS: Blockstore>(
                     max_tx_id = tx_id;
                 }
 
+                let acc = acc.related("transaction", tx_id.0 as u64);
                 let mut seen_approvals = HashSet::<&Address>::new();
                 transaction.approved.iter().for_each(|approval| {
-                    acc.require(
+                    acc.invariant("multisig.transaction.approver_is_signer").require(
                         signers.contains(approval),
                         format!(
                             "approval {approval} for transaction {tx_id} is not in signers list"
                         ),
                     );
 
-                    acc.require(
+                    acc.invariant("multisig.transaction.unique_approvals").require(
                         !seen_approvals.contains(approval),
                         format!("duplicate approval {approval} for transaction {tx_id}"),
                     );
                     seen_approvals.insert(approval);
                 });
-                acc.require((seen_approvals.len() as u64) < state.num_approvals_threshold,
+                acc.invariant("multisig.transaction.below_threshold").require((seen_approvals.len() as u64) < state.num_approvals_threshold,
                     format!("number of approvals ({}) meets the approvals threshold ({}), transaction should not be pending",
                     seen_approvals.len(), state.num_approvals_threshold));
 
@@ -91,12 +92,15 @@ pub fn check_state_invariants<BS: Blockstore>(
                 Ok(())
             });
 
-            acc.require_no_error(ret, "error iterating transactions");
+            acc.invariant("multisig.transactions.load")
+                .require_no_error(ret, "error iterating transactions");
         }
-        Err(e) => acc.add(format!("error loading transactions: {e}")),
+        Err(e) => acc
+            .invariant("multisig.transactions.load")
+            .add(format!("error loading transactions: {e}")),
     };
 
-    acc.require(
+    acc.invariant("multisig.next_transaction_id").require(
         state.next_tx_id > max_tx_id,
         format!("next transaction id {} is not greater than pending ids", state.next_tx_id),
     );
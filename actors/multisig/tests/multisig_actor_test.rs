@@ -4,9 +4,9 @@ use fil_actor_multisig::{
     Transaction, TxnID, TxnIDParams, SIGNERS_MAX,
 };
 use fil_actors_runtime::cbor::serialize;
-use fil_actors_runtime::runtime::Runtime;
+use fil_actors_runtime::runtime::{Runtime, SendFlags};
 use fil_actors_runtime::test_utils::*;
-use fil_actors_runtime::{INIT_ACTOR_ADDR, SYSTEM_ACTOR_ADDR};
+use fil_actors_runtime::{INIT_ACTOR_ADDR, SYSTEM_ACTOR_ADDR, USER_RECEIVER_GAS_LIMIT};
 use fvm_actor_utils::receiver::UniversalReceiverParams;
 use fvm_ipld_encoding::tuple::*;
 use fvm_ipld_encoding::{RawBytes, DAG_CBOR};
@@ -321,11 +321,13 @@ mod vesting_tests {
 
         // advance the epoch s.t. all funds are unlocked
         rt.set_epoch(START_EPOCH + UNLOCK_DURATION);
-        rt.expect_send(
+        rt.expect_send_generalized(
             DARLENE,
             METHOD_SEND,
             None,
             MSIG_INITIAL_BALANCE.clone(),
+            Some(USER_RECEIVER_GAS_LIMIT),
+            SendFlags::empty(),
             None,
             ExitCode::OK,
         );
@@ -355,11 +357,13 @@ mod vesting_tests {
         );
         rt.set_epoch(START_EPOCH + UNLOCK_DURATION / 2);
         rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, BOB);
-        rt.expect_send(
+        rt.expect_send_generalized(
             DARLENE,
             METHOD_SEND,
             None,
             MSIG_INITIAL_BALANCE.div_floor(2),
+            Some(USER_RECEIVER_GAS_LIMIT),
+            SendFlags::empty(),
             None,
             ExitCode::OK,
         );
@@ -392,7 +396,16 @@ mod vesting_tests {
         rt.reset();
         rt.set_epoch(START_EPOCH + UNLOCK_DURATION / 10);
         let amount_out = MSIG_INITIAL_BALANCE.div_floor(10);
-        rt.expect_send(DARLENE, METHOD_SEND, None, amount_out.clone(), None, ExitCode::OK);
+        rt.expect_send_generalized(
+            DARLENE,
+            METHOD_SEND,
+            None,
+            amount_out.clone(),
+            Some(USER_RECEIVER_GAS_LIMIT),
+            SendFlags::empty(),
+            None,
+            ExitCode::OK,
+        );
         h.propose_ok(&mut rt, DARLENE, amount_out, METHOD_SEND, RawBytes::default());
 
         check_state(&rt);
@@ -452,7 +465,16 @@ mod vesting_tests {
 
         // expect 1 unit available after 2 epochs
         rt.set_epoch(START_EPOCH + 2);
-        rt.expect_send(ANNE, METHOD_SEND, None, one.clone(), None, ExitCode::OK);
+        rt.expect_send_generalized(
+            ANNE,
+            METHOD_SEND,
+            None,
+            one.clone(),
+            Some(USER_RECEIVER_GAS_LIMIT),
+            SendFlags::empty(),
+            None,
+            ExitCode::OK,
+        );
         h.propose_ok(&mut rt, ANNE, one.clone(), METHOD_SEND, RawBytes::default());
         rt.set_balance(locked_balance.clone());
 
@@ -465,11 +487,13 @@ mod vesting_tests {
         rt.reset();
 
         // expect all but one unit available after all but one epochs
-        rt.expect_send(
+        rt.expect_send_generalized(
             ANNE,
             METHOD_SEND,
             None,
             locked_balance.clone() - one.clone(),
+            Some(USER_RECEIVER_GAS_LIMIT),
+            SendFlags::empty(),
             None,
             ExitCode::OK,
         );
@@ -478,7 +502,16 @@ mod vesting_tests {
 
         // expect everything after exactly lock duration
         rt.set_epoch(START_EPOCH + UNLOCK_DURATION);
-        rt.expect_send(ANNE, METHOD_SEND, None, locked_balance.clone(), None, ExitCode::OK);
+        rt.expect_send_generalized(
+            ANNE,
+            METHOD_SEND,
+            None,
+            locked_balance.clone(),
+            Some(USER_RECEIVER_GAS_LIMIT),
+            SendFlags::empty(),
+            None,
+            ExitCode::OK,
+        );
         h.propose_ok(&mut rt, ANNE, locked_balance, METHOD_SEND, RawBytes::default());
         check_state(&rt);
     }
@@ -494,7 +527,16 @@ mod vesting_tests {
         rt.set_received(TokenAmount::zero());
 
         rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, ANNE);
-        rt.expect_send(BOB, METHOD_SEND, None, TokenAmount::zero(), None, ExitCode::OK);
+        rt.expect_send_generalized(
+            BOB,
+            METHOD_SEND,
+            None,
+            TokenAmount::zero(),
+            Some(USER_RECEIVER_GAS_LIMIT),
+            SendFlags::empty(),
+            None,
+            ExitCode::OK,
+        );
         h.propose_ok(&mut rt, BOB, TokenAmount::zero(), METHOD_SEND, RawBytes::default());
         check_state(&rt);
     }
@@ -516,7 +558,16 @@ mod vesting_tests {
         // make a tx that transfers no value
         let send_amount = TokenAmount::zero();
         rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, ANNE);
-        rt.expect_send(BOB, METHOD_SEND, None, send_amount.clone(), None, ExitCode::OK);
+        rt.expect_send_generalized(
+            BOB,
+            METHOD_SEND,
+            None,
+            send_amount.clone(),
+            Some(USER_RECEIVER_GAS_LIMIT),
+            SendFlags::empty(),
+            None,
+            ExitCode::OK,
+        );
         h.propose_ok(&mut rt, BOB, send_amount, METHOD_SEND, RawBytes::default());
 
         // verify that sending any value is prevented
@@ -580,11 +631,13 @@ fn test_propose_with_threshold_met() {
     rt.set_received(TokenAmount::zero());
     h.construct_and_verify(&mut rt, num_approvals, no_unlock_duration, start_epoch, signers);
 
-    rt.expect_send(
+    rt.expect_send_generalized(
         chuck,
         METHOD_SEND,
         to_ipld_block(fake_params.clone()),
         send_value.clone(),
+        Some(USER_RECEIVER_GAS_LIMIT),
+        SendFlags::empty(),
         None,
         ExitCode::OK,
     );
@@ -628,11 +681,13 @@ fn test_propose_with_threshold_and_non_empty_return_value() {
     };
     let inner_ret_bytes = serialize(&propose_ret, "fake proposal return value").unwrap();
     let fake_method = 42u64;
-    rt.expect_send(
+    rt.expect_send_generalized(
         chuck,
         fake_method,
         to_ipld_block(fake_params.clone()),
         send_value.clone(),
+        Some(USER_RECEIVER_GAS_LIMIT),
+        SendFlags::empty(),
         to_ipld_block(inner_ret_bytes.clone()),
         ExitCode::OK,
     );
@@ -1290,11 +1345,13 @@ mod approval_tests {
         // approval
         rt.set_balance(send_value.clone());
         rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, bob);
-        rt.expect_send(
+        rt.expect_send_generalized(
             chuck,
             fake_method,
             to_ipld_block(fake_params),
             send_value,
+            Some(USER_RECEIVER_GAS_LIMIT),
+            SendFlags::empty(),
             to_ipld_block(fake_ret),
             ExitCode::OK,
         );
@@ -1325,11 +1382,13 @@ mod approval_tests {
             h.propose_ok(&mut rt, chuck, send_value.clone(), fake_method, fake_params.clone());
 
         rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, bob);
-        rt.expect_send(
+        rt.expect_send_generalized(
             chuck,
             fake_method,
             to_ipld_block(fake_params),
             send_value,
+            Some(USER_RECEIVER_GAS_LIMIT),
+            SendFlags::empty(),
             to_ipld_block(fake_ret.clone()),
             ExitCode::OK,
         );
@@ -1375,11 +1434,13 @@ mod approval_tests {
         );
         rt.set_epoch(start_epoch + unlock_duration);
         rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, bob);
-        rt.expect_send(
+        rt.expect_send_generalized(
             chuck,
             fake_method,
             to_ipld_block(fake_params),
             send_value,
+            Some(USER_RECEIVER_GAS_LIMIT),
+            SendFlags::empty(),
             None,
             ExitCode::OK,
         );
@@ -1520,11 +1581,13 @@ mod approval_tests {
         h.propose_ok(&mut rt, chuck, send_value.clone(), fake_method, fake_params.clone());
 
         rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, bob);
-        rt.expect_send(
+        rt.expect_send_generalized(
             chuck,
             fake_method,
             to_ipld_block(fake_params),
             send_value,
+            Some(USER_RECEIVER_GAS_LIMIT),
+            SendFlags::empty(),
             None,
             ExitCode::OK,
         );
@@ -1670,11 +1733,13 @@ mod approval_tests {
         h.change_num_approvals_threshold(&mut rt, new_threshold).unwrap();
 
         // self approval executes tx because the msig is across the threshold
-        rt.expect_send(
+        rt.expect_send_generalized(
             chuck,
             fake_method,
             to_ipld_block(fake_params),
             send_value,
+            Some(USER_RECEIVER_GAS_LIMIT),
+            SendFlags::empty(),
             None,
             ExitCode::OK,
         );
@@ -1714,11 +1779,13 @@ mod approval_tests {
         h.change_num_approvals_threshold(&mut rt, new_threshold).unwrap();
 
         // duplicate approval executes tx because the msig is across the threshold
-        rt.expect_send(
+        rt.expect_send_generalized(
             chuck,
             fake_method,
             to_ipld_block(fake_params),
             send_value,
+            Some(USER_RECEIVER_GAS_LIMIT),
+            SendFlags::empty(),
             None,
             ExitCode::OK,
         );
@@ -1762,11 +1829,13 @@ mod approval_tests {
 
         // anne can self approve with lower threshold
         rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, anne);
-        rt.expect_send(
+        rt.expect_send_generalized(
             chuck,
             fake_method,
             to_ipld_block(fake_params),
             send_value,
+            Some(USER_RECEIVER_GAS_LIMIT),
+            SendFlags::empty(),
             None,
             ExitCode::OK,
         );
@@ -2118,7 +2187,16 @@ mod change_threshold_tests {
         h.change_num_approvals_threshold(&mut rt, 1).unwrap();
 
         // anne may re-approve causing tx to be exected
-        rt.expect_send(chuck, fake_method, None, send_value.clone(), None, ExitCode::OK);
+        rt.expect_send_generalized(
+            chuck,
+            fake_method,
+            None,
+            send_value.clone(),
+            Some(USER_RECEIVER_GAS_LIMIT),
+            SendFlags::empty(),
+            None,
+            ExitCode::OK,
+        );
         rt.set_balance(send_value);
         rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, anne);
         h.approve_ok(&mut rt, TxnID(0), proposal_hash);
@@ -2179,7 +2257,16 @@ mod lock_balance_tests {
 
         // can fully spend the vested amount
         rt.set_balance(lock_amount.clone());
-        rt.expect_send(bob, METHOD_SEND, None, vested.clone(), None, ExitCode::OK);
+        rt.expect_send_generalized(
+            bob,
+            METHOD_SEND,
+            None,
+            vested.clone(),
+            Some(USER_RECEIVER_GAS_LIMIT),
+            SendFlags::empty(),
+            None,
+            ExitCode::OK,
+        );
         h.propose_ok(&mut rt, bob, vested.clone(), METHOD_SEND, RawBytes::default());
 
         // can't spend more
@@ -2193,7 +2280,16 @@ mod lock_balance_tests {
         // later can spend the rest
         rt.set_epoch(vest_start + vest_duration);
         let rested = TokenAmount::from_atto(70_000u32);
-        rt.expect_send(bob, METHOD_SEND, None, rested.clone(), None, ExitCode::OK);
+        rt.expect_send_generalized(
+            bob,
+            METHOD_SEND,
+            None,
+            rested.clone(),
+            Some(USER_RECEIVER_GAS_LIMIT),
+            SendFlags::empty(),
+            None,
+            ExitCode::OK,
+        );
         h.propose_ok(&mut rt, bob, rested, METHOD_SEND, RawBytes::default());
         check_state(&rt);
     }
@@ -2222,7 +2318,16 @@ mod lock_balance_tests {
         rt.set_epoch(300);
         rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, anne);
         rt.set_balance(lock_amount.clone() + TokenAmount::from_atto(1));
-        rt.expect_send(bob, METHOD_SEND, None, TokenAmount::from_atto(1), None, ExitCode::OK);
+        rt.expect_send_generalized(
+            bob,
+            METHOD_SEND,
+            None,
+            TokenAmount::from_atto(1),
+            Some(USER_RECEIVER_GAS_LIMIT),
+            SendFlags::empty(),
+            None,
+            ExitCode::OK,
+        );
         h.propose_ok(&mut rt, bob, TokenAmount::from_atto(1), METHOD_SEND, RawBytes::default());
 
         // fail to spend locked funds before vesting starts
@@ -2236,7 +2341,16 @@ mod lock_balance_tests {
         // can spend partially vested amount
         rt.set_epoch(vest_start + 200);
         let expect_vested = TokenAmount::from_atto(20_000);
-        rt.expect_send(bob, METHOD_SEND, None, expect_vested.clone(), None, ExitCode::OK);
+        rt.expect_send_generalized(
+            bob,
+            METHOD_SEND,
+            None,
+            expect_vested.clone(),
+            Some(USER_RECEIVER_GAS_LIMIT),
+            SendFlags::empty(),
+            None,
+            ExitCode::OK,
+        );
         h.propose_ok(&mut rt, bob, expect_vested.clone(), METHOD_SEND, RawBytes::default());
 
         // can't spend more
@@ -2249,7 +2363,16 @@ mod lock_balance_tests {
         // later, can spend the rest
         rt.set_epoch(vest_start + vest_duration);
         let rested = TokenAmount::from_atto(80_000);
-        rt.expect_send(bob, METHOD_SEND, None, rested.clone(), None, ExitCode::OK);
+        rt.expect_send_generalized(
+            bob,
+            METHOD_SEND,
+            None,
+            rested.clone(),
+            Some(USER_RECEIVER_GAS_LIMIT),
+            SendFlags::empty(),
+            None,
+            ExitCode::OK,
+        );
         h.propose_ok(&mut rt, bob, rested, METHOD_SEND, RawBytes::default());
         check_state(&rt);
     }
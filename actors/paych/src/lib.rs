@@ -2,10 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0, MIT
 
 use fil_actors_runtime::runtime::builtins::Type;
-use fil_actors_runtime::runtime::{ActorCode, Runtime};
+use fil_actors_runtime::runtime::{ActorCode, Runtime, SendFlags};
 use fil_actors_runtime::{
-    actor_dispatch, actor_error, resolve_to_actor_id, restrict_internal_api, ActorDowncast,
-    ActorError, Array, AsActorError,
+    actor_dispatch, actor_error, extract_send_result, resolve_to_actor_id, restrict_internal_api,
+    ActorDowncast, ActorError, Array, AsActorError, USER_RECEIVER_GAS_LIMIT,
 };
 use fvm_ipld_blockstore::Blockstore;
 use fvm_ipld_encoding::DAG_CBOR;
@@ -48,11 +48,7 @@ pub struct Actor;
 
 impl Actor {
     /// Constructor for Payment channel actor
-    pub fn constructor(rt: &mut impl Runtime, params: ConstructorParams) -> Result<(), ActorError> {
-        // Only InitActor can create a payment channel actor. It creates the actor on
-        // behalf of the payer/payee.
-        rt.validate_immediate_caller_type(std::iter::once(&Type::Init))?;
-
+    fn constructor(rt: &mut impl Runtime, params: ConstructorParams) -> Result<(), ActorError> {
         // Resolve both parties, confirming they exist in the state tree.
         let to = Self::resolve_address(rt, &params.to)
             .with_context_code(ExitCode::USR_ILLEGAL_ARGUMENT, || {
@@ -85,7 +81,7 @@ impl Actor {
             .ok_or_else(|| actor_error!(illegal_argument, "no code for address {}", resolved))
     }
 
-    pub fn update_channel_state(
+    fn update_channel_state(
         rt: &mut impl Runtime,
         params: UpdateChannelStateParams,
     ) -> Result<(), ActorError> {
@@ -120,14 +116,18 @@ impl Actor {
 
         // Validate signature
 
-        rt.send(
+        extract_send_result(
             &signer,
             ext::account::AUTHENTICATE_MESSAGE_METHOD,
-            IpldBlock::serialize_cbor(&ext::account::AuthenticateMessageParams {
-                signature: sig.to_vec(),
-                message: sv_bz,
-            })?,
-            TokenAmount::zero(),
+            rt.send(
+                &signer,
+                ext::account::AUTHENTICATE_MESSAGE_METHOD,
+                IpldBlock::serialize_cbor(&ext::account::AuthenticateMessageParams {
+                    signature: sig.to_vec(),
+                    message: sv_bz,
+                })?,
+                TokenAmount::zero(),
+            ),
         )
         .map_err(|e| e.wrap("voucher sig authentication failed"))?;
 
@@ -166,11 +166,15 @@ impl Actor {
         }
 
         if let Some(extra) = &sv.extra {
-            rt.send(
+            extract_send_result(
                 &extra.actor,
                 extra.method,
-                Some(IpldBlock { codec: DAG_CBOR, data: extra.data.to_vec() }),
-                TokenAmount::zero(),
+                rt.send(
+                    &extra.actor,
+                    extra.method,
+                    Some(IpldBlock { codec: DAG_CBOR, data: extra.data.to_vec() }),
+                    TokenAmount::zero(),
+                ),
             )
             .map_err(|e| e.wrap("spend voucher verification failed"))?;
         }
@@ -274,7 +278,7 @@ impl Actor {
         })
     }
 
-    pub fn settle(rt: &mut impl Runtime) -> Result<(), ActorError> {
+    fn settle(rt: &mut impl Runtime) -> Result<(), ActorError> {
         rt.transaction(|st: &mut State, rt| {
             rt.validate_immediate_caller_is([st.from, st.to].iter())?;
 
@@ -291,7 +295,7 @@ impl Actor {
         })
     }
 
-    pub fn collect(rt: &mut impl Runtime) -> Result<(), ActorError> {
+    fn collect(rt: &mut impl Runtime) -> Result<(), ActorError> {
         let st: State = rt.state()?;
         rt.validate_immediate_caller_is(&[st.from, st.to])?;
 
@@ -300,8 +304,19 @@ impl Actor {
         }
 
         // send ToSend to `to`
-        rt.send(&st.to, METHOD_SEND, None, st.to_send)
-            .map_err(|e| e.wrap("Failed to send funds to `to` address"))?;
+        extract_send_result(
+            &st.to,
+            METHOD_SEND,
+            rt.send_generalized(
+                &st.to,
+                METHOD_SEND,
+                None,
+                st.to_send,
+                Some(USER_RECEIVER_GAS_LIMIT),
+                SendFlags::empty(),
+            ),
+        )
+        .map_err(|e| e.wrap("Failed to send funds to `to` address"))?;
 
         // the remaining balance will be returned to "From" upon deletion.
         rt.delete_actor(&st.from)?;
@@ -330,8 +345,10 @@ where
 impl ActorCode for Actor {
     type Methods = Method;
     actor_dispatch! {
-        Constructor => constructor,
-        UpdateChannelState => update_channel_state,
+        // Only InitActor can create a payment channel actor. It creates the actor on
+        // behalf of the payer/payee.
+        Constructor => constructor(ConstructorParams) [caller: types(Type::Init)],
+        UpdateChannelState => update_channel_state(UpdateChannelStateParams),
         Settle => settle,
         Collect => collect,
     }
@@ -20,15 +20,15 @@ pub fn check_state_invariants<BS: Blockstore>(
     let acc = MessageAccumulator::default();
     let mut redeemed = TokenAmount::zero();
 
-    acc.require(
+    acc.invariant("paych.from_id").require(
         state.from.protocol() == Protocol::ID,
         format!("from address is not ID address {}", state.from),
     );
-    acc.require(
+    acc.invariant("paych.to_id").require(
         state.to.protocol() == Protocol::ID,
         format!("to address is not ID address {}", state.to),
     );
-    acc.require(
+    acc.invariant("paych.settling_after_min_settle_height").require(
         state.settling_at >= state.min_settle_height,
         format!(
             "channel is setting at epoch {} before min settle height {}",
@@ -39,19 +39,19 @@ pub fn check_state_invariants<BS: Blockstore>(
     match Amt::<LaneState, _>::load(&state.lane_states, store) {
         Ok(lanes) => {
             let ret = lanes.for_each(|i, lane| {
-                acc.require(
+                acc.invariant("paych.lane.redeemed_positive").related("lane", i).require(
                     lane.redeemed.is_positive(),
                     format!("lane {i} redeemed is not greater than zero {}", lane.redeemed),
                 );
                 redeemed += &lane.redeemed;
                 Ok(())
             });
-            acc.require_no_error(ret, "error iterating lanes");
+            acc.invariant("paych.lanes.load").require_no_error(ret, "error iterating lanes");
         }
-        Err(e) => acc.add(format!("error loading lanes: {e}")),
+        Err(e) => acc.invariant("paych.lanes.load").add(format!("error loading lanes: {e}")),
     }
 
-    acc.require(
+    acc.invariant("paych.balance_covers_to_send").require(
         balance >= &state.to_send,
         format!("channel has insufficient funds to send ({} < {})", balance, state.to_send),
     );
@@ -14,9 +14,9 @@ use fil_actor_paych::{
 };
 
 use fil_actors_runtime::runtime::builtins::Type;
-use fil_actors_runtime::runtime::Runtime;
+use fil_actors_runtime::runtime::{Runtime, SendFlags};
 use fil_actors_runtime::test_utils::*;
-use fil_actors_runtime::INIT_ACTOR_ADDR;
+use fil_actors_runtime::{INIT_ACTOR_ADDR, USER_RECEIVER_GAS_LIMIT};
 use fvm_ipld_amt::Amt;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_ipld_encoding::RawBytes;
@@ -931,11 +931,13 @@ mod actor_collect {
         // wait for settlingat epoch
         rt.epoch = st.settling_at + 1;
 
-        rt.expect_send(
+        rt.expect_send_generalized(
             st.to,
             METHOD_SEND,
             Default::default(),
             st.to_send.clone(),
+            Some(USER_RECEIVER_GAS_LIMIT),
+            SendFlags::empty(),
             Default::default(),
             ExitCode::OK,
         );
@@ -989,11 +991,13 @@ mod actor_collect {
             rt.epoch = state.settling_at + 1;
 
             if !tc.dont_settle {
-                rt.expect_send(
+                rt.expect_send_generalized(
                     state.to,
                     METHOD_SEND,
                     Default::default(),
                     state.to_send.clone(),
+                    Some(USER_RECEIVER_GAS_LIMIT),
+                    SendFlags::empty(),
                     Default::default(),
                     tc.exp_send_to,
                 );
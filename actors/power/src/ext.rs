@@ -6,8 +6,6 @@ use fvm_shared::address::Address;
 use fvm_shared::bigint::bigint_ser;
 use fvm_shared::sector::{RegisteredPoStProof, SectorNumber, StoragePower};
 use fvm_shared::smooth::FilterEstimate;
-use fvm_shared::METHOD_CONSTRUCTOR;
-use num_derive::FromPrimitive;
 
 pub mod init {
     use super::*;
@@ -68,16 +66,5 @@ pub mod miner {
 }
 
 pub mod reward {
-    use super::*;
-
-    pub const UPDATE_NETWORK_KPI: u64 = 4;
-
-    #[derive(FromPrimitive)]
-    #[repr(u64)]
-    pub enum Method {
-        Constructor = METHOD_CONSTRUCTOR,
-        AwardBlockReward = 2,
-        ThisEpochReward = 3,
-        UpdateNetworkKPI = 4,
-    }
+    pub use fil_actors_runtime::builtin::api::reward::*;
 }
@@ -6,25 +6,28 @@ use std::convert::TryInto;
 
 use anyhow::anyhow;
 use ext::init;
+use fil_actors_runtime::builtin::api::power::{
+    CURRENT_TOTAL_POWER_METHOD, ENROLL_CRON_EVENT_METHOD, SUBMIT_POREP_FOR_BULK_VERIFY_METHOD,
+    UPDATE_CLAIMED_POWER_METHOD, UPDATE_PLEDGE_TOTAL_METHOD,
+};
 use fil_actors_runtime::runtime::builtins::Type;
 use fil_actors_runtime::runtime::{ActorCode, Runtime};
 use fil_actors_runtime::{
-    actor_dispatch, actor_error, deserialize_block, make_map_with_root_and_bitwidth,
-    restrict_internal_api, ActorDowncast, ActorError, Multimap, CRON_ACTOR_ADDR, INIT_ACTOR_ADDR,
-    REWARD_ACTOR_ADDR, SYSTEM_ACTOR_ADDR,
+    actor_dispatch, actor_error, callee_exit_error, deserialize_block, restrict_internal_api,
+    ActorContext, ActorDowncast, ActorError, Multimap, CRON_ACTOR_ADDR, INIT_ACTOR_ADDR,
+    SYSTEM_ACTOR_ADDR,
 };
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_ipld_encoding::RawBytes;
 use fvm_shared::address::Address;
 use fvm_shared::bigint::bigint_ser::BigIntSer;
-use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
 use fvm_shared::reward::ThisEpochRewardReturn;
 use fvm_shared::sector::SealVerifyInfo;
 use fvm_shared::{MethodNum, HAMT_BIT_WIDTH, METHOD_CONSTRUCTOR};
 use log::{debug, error};
 use num_derive::FromPrimitive;
-use num_traits::{FromPrimitive, Zero};
+use num_traits::FromPrimitive;
 
 pub use self::policy::*;
 pub use self::state::*;
@@ -55,14 +58,14 @@ pub enum Method {
     /// Constructor for Storage Power Actor
     Constructor = METHOD_CONSTRUCTOR,
     CreateMiner = 2,
-    UpdateClaimedPower = 3,
-    EnrollCronEvent = 4,
+    UpdateClaimedPower = UPDATE_CLAIMED_POWER_METHOD,
+    EnrollCronEvent = ENROLL_CRON_EVENT_METHOD,
     OnEpochTickEnd = 5,
-    UpdatePledgeTotal = 6,
+    UpdatePledgeTotal = UPDATE_PLEDGE_TOTAL_METHOD,
     // * Deprecated in v2
     // OnConsensusFault = 7,
-    SubmitPoRepForBulkVerify = 8,
-    CurrentTotalPower = 9,
+    SubmitPoRepForBulkVerify = SUBMIT_POREP_FOR_BULK_VERIFY_METHOD,
+    CurrentTotalPower = CURRENT_TOTAL_POWER_METHOD,
     // Method numbers derived from FRC-0042 standards
     CreateMinerExported = frc42_dispatch::method_hash!("CreateMiner"),
     NetworkRawPowerExported = frc42_dispatch::method_hash!("NetworkRawPower"),
@@ -79,8 +82,6 @@ pub struct Actor;
 impl Actor {
     /// Constructor for StoragePower actor
     fn constructor(rt: &mut impl Runtime) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_is(std::iter::once(&SYSTEM_ACTOR_ADDR))?;
-
         let st = State::new(rt.store()).map_err(|e| {
             e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "Failed to create power actor state")
         })?;
@@ -92,7 +93,6 @@ impl Actor {
         rt: &mut impl Runtime,
         params: CreateMinerParams,
     ) -> Result<CreateMinerReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let value = rt.message().value_received();
 
         let constructor_params = RawBytes::serialize(ext::miner::MinerConstructorParams {
@@ -105,22 +105,29 @@ impl Actor {
         })?;
 
         let miner_actor_code_cid = rt.get_code_cid_for_type(Type::Miner);
-        let ext::init::ExecReturn { id_address, robust_address } = deserialize_block(rt.send(
-            &INIT_ACTOR_ADDR,
-            ext::init::EXEC_METHOD,
-            IpldBlock::serialize_cbor(&init::ExecParams {
-                code_cid: miner_actor_code_cid,
-                constructor_params,
-            })?,
-            value,
-        )?)?;
+        let ret = rt
+            .send(
+                &INIT_ACTOR_ADDR,
+                ext::init::EXEC_METHOD,
+                IpldBlock::serialize_cbor(&init::ExecParams {
+                    code_cid: miner_actor_code_cid,
+                    constructor_params,
+                })?,
+                value,
+            )
+            .context("failed to send exec to the init actor")?;
+        if !ret.exit_code.is_success() {
+            return Err(callee_exit_error(
+                ret.exit_code,
+                format!("init actor failed to create miner with code {}", ret.exit_code),
+            ));
+        }
+        let ext::init::ExecReturn { id_address, robust_address } =
+            deserialize_block(ret.return_data)?;
 
         let window_post_proof_type = params.window_post_proof_type;
         rt.transaction(|st: &mut State, rt| {
-            let mut claims =
-                make_map_with_root_and_bitwidth(&st.claims, rt.store(), HAMT_BIT_WIDTH).map_err(
-                    |e| e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to load claims"),
-                )?;
+            let mut claims = st.load_claims(rt.store())?;
             set_claim(
                 &mut claims,
                 &id_address,
@@ -147,9 +154,7 @@ impl Actor {
                 )
             })?;
 
-            st.claims = claims.flush().map_err(|e| {
-                e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to flush claims")
-            })?;
+            st.save_claims(&mut claims)?;
             Ok(())
         })?;
         println!("12345");
@@ -162,14 +167,10 @@ impl Actor {
         rt: &mut impl Runtime,
         params: UpdateClaimedPowerParams,
     ) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_type(std::iter::once(&Type::Miner))?;
         let miner_addr = rt.message().caller();
 
         rt.transaction(|st: &mut State, rt| {
-            let mut claims =
-                make_map_with_root_and_bitwidth(&st.claims, rt.store(), HAMT_BIT_WIDTH).map_err(
-                    |e| e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to load claims"),
-                )?;
+            let mut claims = st.load_claims(rt.store())?;
 
             st.add_to_claim(
                 rt.policy(),
@@ -188,9 +189,7 @@ impl Actor {
                 )
             })?;
 
-            st.claims = claims.flush().map_err(|e| {
-                e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to flush claims")
-            })?;
+            st.save_claims(&mut claims)?;
             Ok(())
         })
     }
@@ -199,7 +198,6 @@ impl Actor {
         rt: &mut impl Runtime,
         params: EnrollCronEventParams,
     ) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_type(std::iter::once(&Type::Miner))?;
         let miner_event = CronEvent {
             miner_addr: rt.message().caller(),
             callback_payload: params.payload.clone(),
@@ -236,17 +234,8 @@ impl Actor {
     }
 
     fn on_epoch_tick_end(rt: &mut impl Runtime) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_is(std::iter::once(&CRON_ACTOR_ADDR))?;
-
-        let rewret: ThisEpochRewardReturn = deserialize_block(
-            rt.send(
-                &REWARD_ACTOR_ADDR,
-                ext::reward::Method::ThisEpochReward as MethodNum,
-                None,
-                TokenAmount::zero(),
-            )
-            .map_err(|e| e.wrap("failed to check epoch baseline power"))?,
-        )?;
+        let rewret = ext::reward::RewardClient::this_epoch_reward(rt)
+            .map_err(|e| e.wrap("failed to check epoch baseline power"))?;
 
         if let Err(e) = Self::process_batch_proof_verifies(rt, &rewret) {
             error!("unexpected error processing batch proof verifies: {}. Skipping all verification for epoch {}", e, rt.curr_epoch());
@@ -261,17 +250,12 @@ impl Actor {
             // Can assume delta is one since cron is invoked every epoch.
             st.update_smoothed_estimate(1);
 
-            Ok(IpldBlock::serialize_cbor(&BigIntSer(&st.this_epoch_raw_byte_power))?)
+            Ok(st.this_epoch_raw_byte_power.clone())
         })?;
 
         // Update network KPA in reward actor
-        rt.send(
-            &REWARD_ACTOR_ADDR,
-            ext::reward::UPDATE_NETWORK_KPI,
-            this_epoch_raw_byte_power,
-            TokenAmount::zero(),
-        )
-        .map_err(|e| e.wrap("failed to update network KPI with reward actor"))?;
+        ext::reward::RewardClient::update_network_kpi(rt, &BigIntSer(&this_epoch_raw_byte_power))
+            .map_err(|e| e.wrap("failed to update network KPI with reward actor"))?;
 
         Ok(())
     }
@@ -280,7 +264,6 @@ impl Actor {
         rt: &mut impl Runtime,
         params: UpdatePledgeTotalParams,
     ) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_type(std::iter::once(&Type::Miner))?;
         rt.transaction(|st: &mut State, rt| {
             st.validate_miner_has_claim(rt.store(), &rt.message().caller())?;
             st.add_pledge_total(params.pledge_delta);
@@ -299,30 +282,29 @@ impl Actor {
         rt: &mut impl Runtime,
         seal_info: SealVerifyInfo,
     ) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_type(std::iter::once(&Type::Miner))?;
-
         rt.transaction(|st: &mut State, rt| {
             st.validate_miner_has_claim(rt.store(), &rt.message().caller())?;
 
-            let mut mmap = if let Some(ref batch) = st.proof_validation_batch {
-                Multimap::from_root(
-                    rt.store(),
-                    batch,
-                    HAMT_BIT_WIDTH,
-                    PROOF_VALIDATION_BATCH_AMT_BITWIDTH,
-                )
-                .map_err(|e| {
-                    e.downcast_default(
-                        ExitCode::USR_ILLEGAL_STATE,
-                        "failed to load proof batching set",
+            let mut mmap: Multimap<_, SealVerifyInfo> =
+                if let Some(ref batch) = st.proof_validation_batch {
+                    Multimap::from_root(
+                        rt.store(),
+                        batch,
+                        HAMT_BIT_WIDTH,
+                        PROOF_VALIDATION_BATCH_AMT_BITWIDTH,
                     )
-                })?
-            } else {
-                debug!("ProofValidationBatch created");
-                Multimap::new(rt.store(), HAMT_BIT_WIDTH, PROOF_VALIDATION_BATCH_AMT_BITWIDTH)
-            };
+                    .map_err(|e| {
+                        e.downcast_default(
+                            ExitCode::USR_ILLEGAL_STATE,
+                            "failed to load proof batching set",
+                        )
+                    })?
+                } else {
+                    debug!("ProofValidationBatch created");
+                    Multimap::new(rt.store(), HAMT_BIT_WIDTH, PROOF_VALIDATION_BATCH_AMT_BITWIDTH)
+                };
             let miner_addr = rt.message().caller();
-            let arr = mmap.get::<SealVerifyInfo>(&miner_addr.to_bytes()).map_err(|e| {
+            let arr = mmap.get(&miner_addr.to_bytes()).map_err(|e| {
                 e.downcast_default(
                     ExitCode::USR_ILLEGAL_STATE,
                     format!("failed to get seal verify infos at addr {}", miner_addr),
@@ -361,7 +343,6 @@ impl Actor {
     /// so that this method returns consistent values while processing all messages
     /// of an epoch.
     fn current_total_power(rt: &mut impl Runtime) -> Result<CurrentTotalPowerReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let st: State = rt.state()?;
 
         Ok(CurrentTotalPowerReturn {
@@ -378,7 +359,6 @@ impl Actor {
     /// This value is static over an epoch, and does NOT get updated as messages are executed.
     /// It is recalculated after all messages at an epoch have been executed.
     fn network_raw_power(rt: &mut impl Runtime) -> Result<NetworkRawPowerReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let st: State = rt.state()?;
 
         Ok(NetworkRawPowerReturn { raw_byte_power: st.this_epoch_raw_byte_power })
@@ -391,7 +371,6 @@ impl Actor {
         rt: &mut impl Runtime,
         params: MinerRawPowerParams,
     ) -> Result<MinerRawPowerReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let st: State = rt.state()?;
 
         let (raw_byte_power, meets_consensus_minimum) =
@@ -403,7 +382,6 @@ impl Actor {
     /// Returns the total number of miners created, regardless of whether or not
     /// they have any pledged storage.
     fn miner_count(rt: &mut impl Runtime) -> Result<MinerCountReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let st: State = rt.state()?;
 
         Ok(MinerCountReturn { miner_count: st.miner_count })
@@ -414,7 +392,6 @@ impl Actor {
     fn miner_consensus_count(
         rt: &mut impl Runtime,
     ) -> Result<MinerConsensusCountReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let st: State = rt.state()?;
 
         Ok(MinerConsensusCountReturn { miner_consensus_count: st.miner_above_min_power_count })
@@ -434,7 +411,7 @@ impl Actor {
                     debug!("ProofValidationBatch was nil, quitting verification");
                     return result;
                 }
-                let mmap = match Multimap::from_root(
+                let mmap: Multimap<_, SealVerifyInfo> = match Multimap::from_root(
                     rt.store(),
                     st.proof_validation_batch.as_ref().unwrap(),
                     HAMT_BIT_WIDTH,
@@ -447,11 +424,7 @@ impl Actor {
                     }
                 };
 
-                let claims = match make_map_with_root_and_bitwidth::<_, Claim>(
-                    &st.claims,
-                    rt.store(),
-                    HAMT_BIT_WIDTH,
-                ) {
+                let claims = match st.load_claims(rt.store()) {
                     Ok(claims) => claims,
                     Err(e) => {
                         st_err = Some(format!("failed to load claims: {}", e));
@@ -459,7 +432,7 @@ impl Actor {
                     }
                 };
 
-                if let Err(e) = mmap.for_all::<_, SealVerifyInfo>(|k, arr| {
+                if let Err(e) = mmap.for_all(|k, arr| {
                     let addr = match Address::from_bytes(&k.0) {
                         Ok(addr) => addr,
                         Err(e) => {
@@ -467,7 +440,7 @@ impl Actor {
                         }
                     };
 
-                    let contains_claim = match claims.contains_key(&addr.to_bytes()) {
+                    let contains_claim = match claims.contains_key(&addr) {
                         Ok(contains_claim) => contains_claim,
                         Err(e) => return Err(anyhow!("failed to look up clain: {}", e)),
                     };
@@ -534,7 +507,7 @@ impl Actor {
             if successful.is_empty() {
                 continue;
             }
-            if let Err(e) = rt.send(
+            let res = rt.send(
                 &m,
                 ext::miner::CONFIRM_SECTOR_PROOFS_VALID_METHOD,
                 IpldBlock::serialize_cbor(&ext::miner::ConfirmSectorProofsParams {
@@ -545,8 +518,18 @@ impl Actor {
                 })
                 .map_err(|e| format!("failed to serialize ConfirmSectorProofsParams: {}", e))?,
                 Default::default(),
-            ) {
-                error!("failed to confirm sector proof validity to {}, error code {}", m, e);
+            );
+            match res {
+                Ok(ret) if ret.exit_code.is_success() => {}
+                Ok(ret) => {
+                    error!(
+                        "failed to confirm sector proof validity to {}, exit code {}",
+                        m, ret.exit_code
+                    );
+                }
+                Err(e) => {
+                    error!("failed to confirm sector proof validity to {}, error code {}", m, e);
+                }
             }
         }
         Ok(())
@@ -570,11 +553,7 @@ impl Actor {
                 e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to load cron events")
             })?;
 
-            let claims =
-                make_map_with_root_and_bitwidth::<_, Claim>(&st.claims, rt.store(), HAMT_BIT_WIDTH)
-                    .map_err(|e| {
-                        e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to load claims")
-                    })?;
+            let claims = st.load_claims(rt.store())?;
             for epoch in st.first_cron_epoch..=rt_epoch {
                 let epoch_events = load_cron_events(&events, epoch).map_err(|e| {
                     e.downcast_default(
@@ -588,13 +567,7 @@ impl Actor {
                 }
 
                 for evt in epoch_events.into_iter() {
-                    let miner_has_claim =
-                        claims.contains_key(&evt.miner_addr.to_bytes()).map_err(|e| {
-                            e.downcast_default(
-                                ExitCode::USR_ILLEGAL_STATE,
-                                "failed to look up claim",
-                            )
-                        })?;
+                    let miner_has_claim = claims.contains_key(&evt.miner_addr)?;
                     if !miner_has_claim {
                         debug!("skipping cron event for unknown miner: {}", evt.miner_addr);
                         continue;
@@ -635,19 +608,25 @@ impl Actor {
             // and persists state removing the failed event from the event queue. It won't be tried again.
             // Failures are unexpected here but will result in removal of miner power
             // A log message would really help here.
-            if let Err(e) = res {
-                error!("OnDeferredCronEvent failed for miner {}: res {}", event.miner_addr, e);
-                failed_miner_crons.push(event.miner_addr)
+            match res {
+                Ok(ret) if ret.exit_code.is_success() => {}
+                Ok(ret) => {
+                    error!(
+                        "OnDeferredCronEvent failed for miner {}: exit code {}",
+                        event.miner_addr, ret.exit_code
+                    );
+                    failed_miner_crons.push(event.miner_addr)
+                }
+                Err(e) => {
+                    error!("OnDeferredCronEvent failed for miner {}: res {}", event.miner_addr, e);
+                    failed_miner_crons.push(event.miner_addr)
+                }
             }
         }
 
         if !failed_miner_crons.is_empty() {
             rt.transaction(|st: &mut State, rt| {
-                let mut claims =
-                    make_map_with_root_and_bitwidth(&st.claims, rt.store(), HAMT_BIT_WIDTH)
-                        .map_err(|e| {
-                            e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to load claims")
-                        })?;
+                let mut claims = st.load_claims(rt.store())?;
 
                 // Remove power and leave miner frozen
                 for miner_addr in failed_miner_crons {
@@ -662,9 +641,7 @@ impl Actor {
                     st.miner_count -= 1
                 }
 
-                st.claims = claims.flush().map_err(|e| {
-                    e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to flush claims")
-                })?;
+                st.save_claims(&mut claims)?;
                 Ok(())
             })?;
         }
@@ -675,18 +652,18 @@ impl Actor {
 impl ActorCode for Actor {
     type Methods = Method;
     actor_dispatch! {
-        Constructor => constructor,
-        CreateMiner => create_miner,
-        CreateMinerExported => create_miner,
-        UpdateClaimedPower => update_claimed_power            ,
-        EnrollCronEvent => enroll_cron_event,
-        OnEpochTickEnd => on_epoch_tick_end,
-        UpdatePledgeTotal => update_pledge_total,
-        SubmitPoRepForBulkVerify => submit_porep_for_bulk_verify,
-        CurrentTotalPower => current_total_power,
-        NetworkRawPowerExported => network_raw_power,
-        MinerRawPowerExported => miner_raw_power,
-        MinerCountExported => miner_count,
-        MinerConsensusCountExported => miner_consensus_count,
+        Constructor => constructor [caller: is(SYSTEM_ACTOR_ADDR)],
+        CreateMiner => create_miner(CreateMinerParams) -> CreateMinerReturn [caller: any],
+        CreateMinerExported => create_miner(CreateMinerParams) -> CreateMinerReturn [caller: any],
+        UpdateClaimedPower => update_claimed_power(UpdateClaimedPowerParams) [caller: types(Type::Miner)],
+        EnrollCronEvent => enroll_cron_event(EnrollCronEventParams) [caller: types(Type::Miner)],
+        OnEpochTickEnd => on_epoch_tick_end [caller: is(CRON_ACTOR_ADDR)],
+        UpdatePledgeTotal => update_pledge_total(UpdatePledgeTotalParams) [caller: types(Type::Miner)],
+        SubmitPoRepForBulkVerify => submit_porep_for_bulk_verify(SealVerifyInfo) [caller: types(Type::Miner)],
+        CurrentTotalPower => current_total_power -> CurrentTotalPowerReturn [caller: any, read_only],
+        NetworkRawPowerExported => network_raw_power -> NetworkRawPowerReturn [caller: any, read_only],
+        MinerRawPowerExported => miner_raw_power(MinerRawPowerParams) -> MinerRawPowerReturn [caller: any, read_only],
+        MinerCountExported => miner_count -> MinerCountReturn [caller: any, read_only],
+        MinerConsensusCountExported => miner_consensus_count -> MinerConsensusCountReturn [caller: any, read_only],
     }
 }
@@ -7,8 +7,7 @@ use anyhow::anyhow;
 use cid::Cid;
 use fil_actors_runtime::runtime::Policy;
 use fil_actors_runtime::{
-    actor_error, make_empty_map, make_map_with_root, make_map_with_root_and_bitwidth,
-    ActorDowncast, ActorError, AsActorError, Map, Multimap,
+    actor_error, ActorContext, ActorDowncast, ActorError, AsActorError, Map2, Multimap,
 };
 use fvm_ipld_blockstore::Blockstore;
 use fvm_ipld_encoding::tuple::*;
@@ -21,7 +20,7 @@ use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
 use fvm_shared::sector::{RegisteredPoStProof, StoragePower};
 use fvm_shared::smooth::{AlphaBetaFilter, FilterEstimate, DEFAULT_ALPHA, DEFAULT_BETA};
-use fvm_shared::{ActorID, HAMT_BIT_WIDTH};
+use fvm_shared::ActorID;
 use integer_encoding::VarInt;
 use lazy_static::lazy_static;
 use num_traits::Signed;
@@ -35,6 +34,9 @@ lazy_static! {
     pub static ref INITIAL_QA_POWER_ESTIMATE_VELOCITY: StoragePower = StoragePower::from(3_840) * (1 << 30);
 }
 
+/// Claimed power for each miner, keyed by miner address.
+pub type ClaimsMap<'bs, BS> = Map2<'bs, BS, Address, Claim>;
+
 /// Storage power actor state
 #[derive(Default, Serialize_tuple, Deserialize_tuple, Clone, Debug)]
 pub struct State {
@@ -74,15 +76,18 @@ pub struct State {
 
 impl State {
     pub fn new<BS: Blockstore>(store: &BS) -> anyhow::Result<State> {
-        let empty_map = make_empty_map::<_, ()>(store, HAMT_BIT_WIDTH)
-            .flush()
+        let empty_map = ClaimsMap::flush_empty(store)
             .map_err(|e| anyhow!("Failed to create empty map: {}", e))?;
 
-        let empty_mmap = Multimap::new(store, CRON_QUEUE_HAMT_BITWIDTH, CRON_QUEUE_AMT_BITWIDTH)
-            .root()
-            .map_err(|e| {
-                e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "Failed to get empty multimap cid")
-            })?;
+        let empty_mmap =
+            Multimap::<_, CronEvent>::new(store, CRON_QUEUE_HAMT_BITWIDTH, CRON_QUEUE_AMT_BITWIDTH)
+                .root()
+                .map_err(|e| {
+                    e.downcast_default(
+                        ExitCode::USR_ILLEGAL_STATE,
+                        "Failed to get empty multimap cid",
+                    )
+                })?;
         Ok(State {
             cron_event_queue: empty_mmap,
             claims: empty_map,
@@ -105,15 +110,9 @@ impl State {
         s: &BS,
         miner: ActorID,
     ) -> Result<(StoragePower, bool), ActorError> {
-        let claims = make_map_with_root_and_bitwidth(&self.claims, s, HAMT_BIT_WIDTH)
-            .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
-                format!("failed to load claims for miner: {}", miner)
-            })?;
+        let claims = self.load_claims(s)?;
 
-        let claim = get_claim(&claims, &Address::new_id(miner))
-            .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
-                format!("failed to get claim for miner: {}", miner)
-            })?
+        let claim = get_claim(&claims, &Address::new_id(miner))?
             .with_context_code(ExitCode::USR_ILLEGAL_ARGUMENT, || {
                 format!("no claim for actor: {}", miner)
             })?;
@@ -142,14 +141,31 @@ impl State {
         s: &BS,
         miner: &Address,
     ) -> anyhow::Result<Option<Claim>> {
-        let claims = make_map_with_root(&self.claims, s)?;
-        get_claim(&claims, miner).map(|s| s.cloned())
+        let claims = self.load_claims(s)?;
+        Ok(get_claim(&claims, miner)?.cloned())
+    }
+
+    /// Loads the claims map.
+    pub fn load_claims<'bs, BS: Blockstore>(
+        &self,
+        store: &'bs BS,
+    ) -> Result<ClaimsMap<'bs, BS>, ActorError> {
+        ClaimsMap::load(store, &self.claims, "claims")
+    }
+
+    /// Flushes the claims map and records its new root.
+    pub fn save_claims<BS: Blockstore>(
+        &mut self,
+        claims: &mut ClaimsMap<BS>,
+    ) -> Result<(), ActorError> {
+        self.claims = claims.flush()?;
+        Ok(())
     }
 
     pub(super) fn add_to_claim<BS: Blockstore>(
         &mut self,
         policy: &Policy,
-        claims: &mut Map<BS, Claim>,
+        claims: &mut ClaimsMap<BS>,
         miner: &Address,
         power: &StoragePower,
         qa_power: &StoragePower,
@@ -224,7 +240,7 @@ impl State {
 
     pub(super) fn append_cron_event<BS: Blockstore>(
         &mut self,
-        events: &mut Multimap<BS>,
+        events: &mut Multimap<BS, CronEvent>,
         epoch: ChainEpoch,
         event: CronEvent,
     ) -> anyhow::Result<()> {
@@ -280,13 +296,9 @@ impl State {
     where
         BS: Blockstore,
     {
-        let claims = make_map_with_root::<_, Claim>(&self.claims, store).map_err(|e| {
-            e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to load claims")
-        })?;
+        let claims = self.load_claims(store)?;
 
-        if !claims.contains_key(&miner_addr.to_bytes()).map_err(|e| {
-            e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to look up claim")
-        })? {
+        if !claims.contains_key(miner_addr)? {
             return Err(actor_error!(
                 forbidden,
                 "unknown miner {} forbidden to interact with power actor",
@@ -301,11 +313,7 @@ impl State {
         store: &BS,
         miner: &Address,
     ) -> anyhow::Result<Option<Claim>> {
-        let claims =
-            make_map_with_root_and_bitwidth::<_, Claim>(&self.claims, store, HAMT_BIT_WIDTH)
-                .map_err(|e| {
-                    e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to load claims")
-                })?;
+        let claims = self.load_claims(store)?;
 
         let claim = get_claim(&claims, miner)?;
         Ok(claim.cloned())
@@ -314,11 +322,11 @@ impl State {
     pub(super) fn delete_claim<BS: Blockstore>(
         &mut self,
         policy: &Policy,
-        claims: &mut Map<BS, Claim>,
+        claims: &mut ClaimsMap<BS>,
         miner: &Address,
     ) -> anyhow::Result<()> {
         let (rbp, qap) =
-            match get_claim(claims, miner).map_err(|e| e.downcast_wrap("failed to get claim"))? {
+            match get_claim(claims, miner).map_err(|e| anyhow!(e.wrap("failed to get claim")))? {
                 None => {
                     return Ok(());
                 }
@@ -330,15 +338,15 @@ impl State {
             .map_err(|e| e.downcast_wrap("failed to subtract miner power before deleting claim"))?;
 
         claims
-            .delete(&miner.to_bytes())
-            .map_err(|e| e.downcast_wrap(format!("failed to delete claim for address {}", miner)))?
+            .delete(miner)
+            .map_err(|e| anyhow!(e.wrap(format!("failed to delete claim for address {}", miner))))?
             .ok_or_else(|| anyhow!("failed to delete claim for address: doesn't exist"))?;
         Ok(())
     }
 }
 
 pub(super) fn load_cron_events<BS: Blockstore>(
-    mmap: &Multimap<BS>,
+    mmap: &Multimap<BS, CronEvent>,
     epoch: ChainEpoch,
 ) -> anyhow::Result<Vec<CronEvent>> {
     let mut events = Vec::new();
@@ -353,16 +361,14 @@ pub(super) fn load_cron_events<BS: Blockstore>(
 
 /// Gets claim from claims map by address
 fn get_claim<'m, BS: Blockstore>(
-    claims: &'m Map<BS, Claim>,
+    claims: &'m ClaimsMap<BS>,
     a: &Address,
-) -> anyhow::Result<Option<&'m Claim>> {
-    claims
-        .get(&a.to_bytes())
-        .map_err(|e| e.downcast_wrap(format!("failed to get claim for address {}", a)))
+) -> Result<Option<&'m Claim>, ActorError> {
+    claims.get(a).with_context(|| format!("failed to get claim for address {}", a))
 }
 
 pub fn set_claim<BS: Blockstore>(
-    claims: &mut Map<BS, Claim>,
+    claims: &mut ClaimsMap<BS>,
     a: &Address,
     claim: Claim,
 ) -> anyhow::Result<()> {
@@ -382,8 +388,8 @@ pub fn set_claim<BS: Blockstore>(
     }
 
     claims
-        .set(a.to_bytes().into(), claim)
-        .map_err(|e| e.downcast_wrap(format!("failed to set claim for address {}", a)))?;
+        .set(a, claim)
+        .map_err(|e| anyhow!(e.wrap(format!("failed to set claim for address {}", a))))?;
     Ok(())
 }
 
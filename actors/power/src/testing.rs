@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use fil_actors_runtime::{parse_uint_key, runtime::Policy, Map, MessageAccumulator, Multimap};
+use fil_actors_runtime::{parse_uint_key, runtime::Policy, MessageAccumulator, Multimap};
 use fvm_ipld_blockstore::Blockstore;
 use fvm_ipld_encoding::RawBytes;
 use fvm_shared::{
@@ -41,45 +41,45 @@ pub fn check_state_invariants<BS: Blockstore>(
     let acc = MessageAccumulator::default();
 
     // basic invariants around recorded power
-    acc.require(
+    acc.invariant("power.total_raw_power.non_negative").require(
         !state.total_raw_byte_power.is_negative(),
         format!("total raw power is negative {}", state.total_raw_byte_power),
     );
-    acc.require(
+    acc.invariant("power.total_qa_power.non_negative").require(
         !state.total_quality_adj_power.is_negative(),
         format!("total qa power is negative {}", state.total_quality_adj_power),
     );
-    acc.require(
+    acc.invariant("power.total_bytes_committed.non_negative").require(
         !state.total_bytes_committed.is_negative(),
         format!("total raw power committed is negative {}", state.total_bytes_committed),
     );
-    acc.require(
+    acc.invariant("power.total_qa_bytes_committed.non_negative").require(
         !state.total_qa_bytes_committed.is_negative(),
         format!("total qa power committed is negative {}", state.total_qa_bytes_committed),
     );
 
-    acc.require(
+    acc.invariant("power.total_raw_power.below_qa_power").require(
         state.total_raw_byte_power <= state.total_quality_adj_power,
         format!(
             "total raw power {} is greater than total quality adjusted power {}",
             state.total_raw_byte_power, state.total_quality_adj_power
         ),
     );
-    acc.require(
+    acc.invariant("power.total_bytes_committed.below_qa_bytes_committed").require(
         state.total_bytes_committed <= state.total_qa_bytes_committed,
         format!(
             "committed raw power {} is greater than committed quality adjusted power {}",
             state.total_bytes_committed, state.total_qa_bytes_committed
         ),
     );
-    acc.require(
+    acc.invariant("power.total_raw_power.below_committed").require(
         state.total_raw_byte_power <= state.total_bytes_committed,
         format!(
             "total raw power {} is greater than raw power committed {}",
             state.total_raw_byte_power, state.total_bytes_committed
         ),
     );
-    acc.require(
+    acc.invariant("power.total_qa_power.below_committed").require(
         state.total_quality_adj_power <= state.total_qa_bytes_committed,
         format!(
             "total qa power {} is greater than qa power committed {}",
@@ -101,23 +101,24 @@ fn check_cron_invariants<BS: Blockstore>(
 ) -> CronEventsByAddress {
     let mut cron_events_by_address = CronEventsByAddress::new();
 
-    match Multimap::from_root(
+    match Multimap::<_, CronEvent>::from_root(
         store,
         &state.cron_event_queue,
         CRON_QUEUE_HAMT_BITWIDTH,
         CRON_QUEUE_AMT_BITWIDTH,
     ) {
         Ok(queue) => {
-            let ret = queue.for_all::<_, CronEvent>(|key, events| {
+            let ret = queue.for_all(|key, events| {
                 let epoch = match parse_uint_key(key) {
                     Ok(key) => key,
                     Err(e) => {
-                        acc.add(format!("non-int key in cron array: {e}"));
+                        acc.invariant("power.cron.epoch_key")
+                            .add(format!("non-int key in cron array: {e}"));
                         return Ok(());
                     }
                 } as i64;
 
-                acc.require(
+                acc.invariant("power.cron.after_first_cron_epoch").require(
                     epoch >= state.first_cron_epoch,
                     format!(
                         "cron event at epoch {epoch} before first_cron_epoch {}",
@@ -135,9 +136,11 @@ fn check_cron_invariants<BS: Blockstore>(
                         anyhow::anyhow!("error iterating cron events for epoch {}: {}", epoch, e)
                     })
             });
-            acc.require_no_error(ret, "error iterating cron tasks");
+            acc.invariant("power.cron.load").require_no_error(ret, "error iterating cron tasks");
+        }
+        Err(e) => {
+            acc.invariant("power.cron.load").add(format!("error loading cron event queue: {e}"))
         }
-        Err(e) => acc.add(format!("error loading cron event queue: {e}")),
     }
 
     cron_events_by_address
@@ -156,10 +159,10 @@ fn check_claims_invariants<BS: Blockstore>(
     let mut qa_power = StoragePower::zero();
     let mut claims_with_sufficient_power_count = 0;
 
-    match Map::<_, Claim>::load(&state.claims, store) {
+    match state.load_claims(store) {
         Ok(claims) => {
-            let ret = claims.for_each(|key, claim| {
-                let address = Address::from_bytes(key)?;
+            let ret = claims.for_each(|address, claim| {
+                let address = *address;
                 claims_by_address.insert(address, claim.clone());
 
                 committed_raw_power += &claim.raw_byte_power;
@@ -169,7 +172,7 @@ fn check_claims_invariants<BS: Blockstore>(
                     match consensus_miner_min_power(policy, claim.window_post_proof_type) {
                         Ok(power) => power,
                         Err(e) => {
-                            acc.add(format!(
+                            acc.invariant("power.claims.consensus_min_power").add(format!(
                                 "could not get consensus miner min power for miner {address}: {e}"
                             ));
                             // noted above
@@ -185,22 +188,25 @@ fn check_claims_invariants<BS: Blockstore>(
 
                 Ok(())
             });
-            acc.require_no_error(ret, "error iterating power claims");
+            acc.invariant("power.claims.load")
+                .require_no_error(ret, "error iterating power claims");
+        }
+        Err(e) => {
+            acc.invariant("power.claims.load").add(format!("error loading power claims: {e}"))
         }
-        Err(e) => acc.add(format!("error loading power claims: {e}")),
     };
 
-    acc.require(committed_raw_power == state.total_bytes_committed, format!("sum of raw power in claims {committed_raw_power} does not match recorded bytes committed {}", state.total_bytes_committed));
-    acc.require(committed_qa_power == state.total_qa_bytes_committed, format!("sum of qa power in claims {committed_qa_power} does not match recorded qa power committed {}", state.total_qa_bytes_committed));
-    acc.require(claims_with_sufficient_power_count == state.miner_above_min_power_count, format!("claims with sufficient power {claims_with_sufficient_power_count} does not match miner_above_min_power_count {}", state.miner_above_min_power_count));
-    acc.require(
+    acc.invariant("power.claims.sum_raw_committed").require(committed_raw_power == state.total_bytes_committed, format!("sum of raw power in claims {committed_raw_power} does not match recorded bytes committed {}", state.total_bytes_committed));
+    acc.invariant("power.claims.sum_qa_committed").require(committed_qa_power == state.total_qa_bytes_committed, format!("sum of qa power in claims {committed_qa_power} does not match recorded qa power committed {}", state.total_qa_bytes_committed));
+    acc.invariant("power.claims.above_min_power_count").require(claims_with_sufficient_power_count == state.miner_above_min_power_count, format!("claims with sufficient power {claims_with_sufficient_power_count} does not match miner_above_min_power_count {}", state.miner_above_min_power_count));
+    acc.invariant("power.claims.sum_raw_power").require(
         state.total_raw_byte_power == raw_power,
         format!(
             "recorded raw power {} does not match raw power in claims {raw_power}",
             state.total_raw_byte_power
         ),
     );
-    acc.require(
+    acc.invariant("power.claims.sum_qa_power").require(
         state.total_quality_adj_power == qa_power,
         format!(
             "recorded qa power {} does not match qa power in claims {qa_power}",
@@ -221,28 +227,28 @@ fn check_proofs_invariants<BS: Blockstore>(
     }
 
     let mut proofs_by_address = ProofsByAddress::new();
-    match Multimap::from_root(
+    match Multimap::<_, SealVerifyInfo>::from_root(
         store,
         &state.proof_validation_batch.unwrap(),
         HAMT_BIT_WIDTH,
         PROOF_VALIDATION_BATCH_AMT_BITWIDTH,
     ) {
         Ok(queue) => {
-            let ret = queue.for_all::<_, SealVerifyInfo>(|key, infos| {
+            let ret = queue.for_all(|key, infos| {
                 let address = Address::from_bytes(key)?;
                 let claim = if let Some(claim) = claims.get(&address) {
                     claim
                 } else {
-                    acc.add(format!("miner {address} has proofs awaiting validation but no claim"));
+                    acc.invariant("power.proofs.miner_has_claim").add(format!("miner {address} has proofs awaiting validation but no claim"));
                     return Ok(())
                 };
 
                 let ret = infos.for_each(|_, info| {
                     match info.registered_proof.registered_window_post_proof() {
                         Ok(sector_window_post_proof_type) => {
-                            acc.require(claim.window_post_proof_type == sector_window_post_proof_type, format!("miner submitted proof with proof type {:?} different from claim {:?}", sector_window_post_proof_type, claim.window_post_proof_type));
+                            acc.invariant("power.proofs.post_proof_type").require(claim.window_post_proof_type == sector_window_post_proof_type, format!("miner submitted proof with proof type {:?} different from claim {:?}", sector_window_post_proof_type, claim.window_post_proof_type));
                         },
-                        Err(e) => acc.add(format!("Invalid PoSt proof: {e}"))
+                        Err(e) => acc.invariant("power.proofs.post_proof_type").add(format!("Invalid PoSt proof: {e}"))
                     }
                     proofs_by_address.insert(address, info.clone());
                     Ok(())
@@ -252,12 +258,15 @@ fn check_proofs_invariants<BS: Blockstore>(
                     return ret.map_err(|e| anyhow::anyhow!("error iterating proof validation batch for address {}: {}", address, e));
                 }
 
-                acc.require(proofs_by_address.len() as u64 <= MAX_MINER_PROVE_COMMITS_PER_EPOCH, format!("miner {address} has submitted too many proofs ({}) for batch verification", proofs_by_address.len()));
+                acc.invariant("power.proofs.max_per_epoch").require(proofs_by_address.len() as u64 <= MAX_MINER_PROVE_COMMITS_PER_EPOCH, format!("miner {address} has submitted too many proofs ({}) for batch verification", proofs_by_address.len()));
                 Ok(())
             });
-            acc.require_no_error(ret, "error iterating proof validation queue");
+            acc.invariant("power.proofs.load")
+                .require_no_error(ret, "error iterating proof validation queue");
         }
-        Err(e) => acc.add(format!("error loading proof validation queue: {e}")),
+        Err(e) => acc
+            .invariant("power.proofs.load")
+            .add(format!("error loading proof validation queue: {e}")),
     }
 
     proofs_by_address
@@ -2,16 +2,16 @@
 // SPDX-License-Identifier: Apache-2.0, MIT
 
 use fvm_ipld_encoding::tuple::*;
-use fvm_ipld_encoding::{serde_bytes, BytesDe, RawBytes};
+use fvm_ipld_encoding::{serde_bytes, BytesDe};
 use fvm_shared::address::Address;
 use fvm_shared::bigint::bigint_ser;
-use fvm_shared::clock::ChainEpoch;
-use fvm_shared::econ::TokenAmount;
 use fvm_shared::sector::{RegisteredPoStProof, StoragePower};
-use fvm_shared::smooth::FilterEstimate;
 use fvm_shared::ActorID;
 
-use serde::{Deserialize, Serialize};
+pub use fil_actors_runtime::builtin::api::power::{
+    CurrentTotalPowerReturn, EnrollCronEventParams, UpdateClaimedPowerParams,
+    UpdatePledgeTotalParams,
+};
 
 pub type SectorTermination = i64;
 
@@ -44,36 +44,6 @@ pub struct CreateMinerReturn {
     pub robust_address: Address,
 }
 
-#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
-pub struct UpdateClaimedPowerParams {
-    #[serde(with = "bigint_ser")]
-    pub raw_byte_delta: StoragePower,
-    #[serde(with = "bigint_ser")]
-    pub quality_adjusted_delta: StoragePower,
-}
-
-#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
-pub struct EnrollCronEventParams {
-    pub event_epoch: ChainEpoch,
-    pub payload: RawBytes,
-}
-
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Default)]
-#[serde(transparent)]
-pub struct UpdatePledgeTotalParams {
-    pub pledge_delta: TokenAmount,
-}
-
-#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, PartialEq)]
-pub struct CurrentTotalPowerReturn {
-    #[serde(with = "bigint_ser")]
-    pub raw_byte_power: StoragePower,
-    #[serde(with = "bigint_ser")]
-    pub quality_adj_power: StoragePower,
-    pub pledge_collateral: TokenAmount,
-    pub quality_adj_power_smoothed: FilterEstimate,
-}
-
 #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
 #[serde(transparent)]
 pub struct NetworkRawPowerReturn {
@@ -0,0 +1,19 @@
+// The power actor's parameters for the init actor are declared independently of that actor.
+// These tests fail if the two drift apart. The miner's types are checked in the miner's tests.
+
+use fil_actor_power::ext;
+use fil_actors_runtime::test_utils::{assert_same_encoding, make_piece_cid};
+use fvm_ipld_encoding::RawBytes;
+use fvm_shared::address::Address;
+
+#[test]
+fn init_types_match() {
+    assert_same_encoding::<fil_actor_init::ExecParams>(&ext::init::ExecParams {
+        code_cid: make_piece_cid(b"code"),
+        constructor_params: RawBytes::new(vec![1, 2, 3]),
+    });
+    assert_same_encoding::<fil_actor_init::ExecReturn>(&ext::init::ExecReturn {
+        id_address: Address::new_id(100),
+        robust_address: Address::new_actor(b"robust"),
+    });
+}
@@ -2,8 +2,7 @@ use cid::Cid;
 use fil_actor_power::detail::GAS_ON_SUBMIT_VERIFY_SEAL;
 use fil_actor_power::ext::miner::ConfirmSectorProofsParams;
 use fil_actor_power::ext::miner::CONFIRM_SECTOR_PROOFS_VALID_METHOD;
-use fil_actor_power::ext::reward::Method::ThisEpochReward;
-use fil_actor_power::ext::reward::UPDATE_NETWORK_KPI;
+use fil_actor_power::ext::reward::{THIS_EPOCH_REWARD_METHOD, UPDATE_NETWORK_KPI_METHOD};
 use fil_actor_power::testing::check_state_invariants;
 use fil_actor_power::EnrollCronEventParams;
 use fil_actor_power::CRON_QUEUE_AMT_BITWIDTH;
@@ -15,10 +14,7 @@ use fil_actors_runtime::test_utils::CRON_ACTOR_CODE_ID;
 use fil_actors_runtime::Multimap;
 use fil_actors_runtime::CRON_ACTOR_ADDR;
 use fil_actors_runtime::REWARD_ACTOR_ADDR;
-use fvm_ipld_blockstore::Blockstore;
 use fvm_ipld_encoding::{BytesDe, RawBytes};
-use fvm_ipld_hamt::BytesKey;
-use fvm_ipld_hamt::Error;
 use fvm_shared::address::Address;
 use fvm_shared::bigint::bigint_ser::BigIntDe;
 use fvm_shared::bigint::bigint_ser::BigIntSer;
@@ -34,8 +30,6 @@ use fvm_shared::smooth::FilterEstimate;
 use fvm_shared::MethodNum;
 use lazy_static::lazy_static;
 use num_traits::Zero;
-use serde::de::DeserializeOwned;
-use serde::Serialize;
 
 use fil_actor_power::ext::init::ExecParams;
 use fil_actor_power::ext::miner::MinerConstructorParams;
@@ -50,7 +44,7 @@ use fil_actors_runtime::test_utils::{
     MockRuntime, ACCOUNT_ACTOR_CODE_ID, MINER_ACTOR_CODE_ID, SYSTEM_ACTOR_CODE_ID,
 };
 use fil_actors_runtime::{
-    make_map_with_root_and_bitwidth, ActorError, Map, INIT_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR,
+    make_map_with_root_and_bitwidth, ActorError, INIT_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR,
     SYSTEM_ACTOR_ADDR,
 };
 use fvm_ipld_encoding::ipld_block::IpldBlock;
@@ -205,10 +199,15 @@ impl Harness {
 
     pub fn list_miners(&self, rt: &MockRuntime) -> Vec<Address> {
         let st: State = rt.get_state();
-        let claims: Map<_, Claim> =
-            make_map_with_root_and_bitwidth(&st.claims, rt.store(), HAMT_BIT_WIDTH).unwrap();
-        let keys = collect_keys(claims).unwrap();
-        keys.iter().map(|k| Address::from_bytes(k).unwrap()).collect::<Vec<_>>()
+        let claims = st.load_claims(rt.store()).unwrap();
+        let mut miners = Vec::new();
+        claims
+            .for_each(|addr, _| {
+                miners.push(*addr);
+                Ok(())
+            })
+            .unwrap();
+        miners
     }
 
     pub fn miner_count(&self, rt: &mut MockRuntime) -> i64 {
@@ -235,11 +234,9 @@ impl Harness {
     pub fn delete_claim(&mut self, rt: &mut MockRuntime, miner: &Address) {
         let mut state: State = rt.get_state();
 
-        let mut claims =
-            make_map_with_root_and_bitwidth::<_, Claim>(&state.claims, rt.store(), HAMT_BIT_WIDTH)
-                .unwrap();
-        claims.delete(&miner.to_bytes()).expect("Failed to delete claim");
-        state.claims = claims.flush().unwrap();
+        let mut claims = state.load_claims(rt.store()).unwrap();
+        claims.delete(miner).expect("Failed to delete claim");
+        state.save_claims(&mut claims).unwrap();
 
         rt.replace_state(&state);
     }
@@ -265,7 +262,7 @@ impl Harness {
 
     pub fn get_enrolled_cron_ticks(&self, rt: &MockRuntime, epoch: ChainEpoch) -> Vec<CronEvent> {
         let state: State = rt.get_state();
-        let events_map = Multimap::from_root(
+        let events_map = Multimap::<_, CronEvent>::from_root(
             &rt.store,
             &state.cron_event_queue,
             CRON_QUEUE_HAMT_BITWIDTH,
@@ -275,7 +272,7 @@ impl Harness {
 
         let mut events: Vec<CronEvent> = Vec::new();
         events_map
-            .for_each::<_, CronEvent>(&epoch_key(epoch), |_, v| {
+            .for_each(&epoch_key(epoch), |_, v| {
                 events.push(v.to_owned());
                 Ok(())
             })
@@ -394,7 +391,7 @@ impl Harness {
 
         rt.expect_send(
             REWARD_ACTOR_ADDR,
-            ThisEpochReward as u64,
+            THIS_EPOCH_REWARD_METHOD,
             None,
             TokenAmount::zero(),
             IpldBlock::serialize_cbor(&current_reward).unwrap(),
@@ -438,7 +435,7 @@ impl Harness {
         // expect power sends to reward actor
         rt.expect_send(
             REWARD_ACTOR_ADDR,
-            UPDATE_NETWORK_KPI,
+            UPDATE_NETWORK_KPI_METHOD,
             IpldBlock::serialize_cbor(&BigIntSer(expected_raw_power)).unwrap(),
             TokenAmount::zero(),
             None,
@@ -486,21 +483,6 @@ pub fn batch_verify_default_output(infos: &[SealVerifyInfo]) -> Vec<bool> {
     vec![true; infos.len()]
 }
 
-/// Collects all keys from a map into a vector.
-fn collect_keys<BS, V>(m: Map<BS, V>) -> Result<Vec<BytesKey>, Error>
-where
-    BS: Blockstore,
-    V: DeserializeOwned + Serialize,
-{
-    let mut ret_keys = Vec::new();
-    m.for_each(|k, _| {
-        ret_keys.push(k.clone());
-        Ok(())
-    })?;
-
-    Ok(ret_keys)
-}
-
 pub fn verify_empty_map(rt: &MockRuntime, key: Cid) {
     let map =
         make_map_with_root_and_bitwidth::<_, BigIntDe>(&key, &rt.store, HAMT_BIT_WIDTH).unwrap();
@@ -122,8 +122,9 @@ fn create_miner_given_send_to_init_actor_fails_should_fail() {
         ExitCode::SYS_INSUFFICIENT_FUNDS,
     );
 
+    // A system exit code from the callee aborts the caller with an assertion failure.
     expect_abort(
-        ExitCode::SYS_INSUFFICIENT_FUNDS,
+        ExitCode::USR_ASSERTION_FAILED,
         rt.call::<PowerActor>(
             Method::CreateMiner as u64,
             IpldBlock::serialize_cbor(&create_miner_params).unwrap(),
@@ -639,10 +640,9 @@ fn given_no_miner_claim_update_pledge_total_should_abort() {
 mod cron_tests {
     use super::*;
 
-    use fil_actor_power::ext::reward::Method as RewardMethod;
     use fil_actor_power::ext::{
         miner::{DeferredCronEventParams, ON_DEFERRED_CRON_EVENT_METHOD},
-        reward::UPDATE_NETWORK_KPI,
+        reward::UPDATE_NETWORK_KPI_METHOD,
     };
     use fil_actors_runtime::{test_utils::CRON_ACTOR_CODE_ID, CRON_ACTOR_ADDR, REWARD_ACTOR_ADDR};
     use fvm_shared::bigint::BigInt;
@@ -661,7 +661,7 @@ mod cron_tests {
         h.expect_query_network_info(&mut rt);
         rt.expect_send(
             REWARD_ACTOR_ADDR,
-            RewardMethod::UpdateNetworkKPI as u64,
+            UPDATE_NETWORK_KPI_METHOD,
             IpldBlock::serialize_cbor(&BigIntSer(&expected_power)).unwrap(),
             TokenAmount::zero(),
             None,
@@ -772,7 +772,7 @@ mod cron_tests {
 
         rt.expect_send(
             REWARD_ACTOR_ADDR,
-            UPDATE_NETWORK_KPI,
+            UPDATE_NETWORK_KPI_METHOD,
             IpldBlock::serialize_cbor(&BigIntSer(&expected_raw_byte_power)).unwrap(),
             TokenAmount::zero(),
             None,
@@ -800,7 +800,7 @@ mod cron_tests {
         h.expect_query_network_info(&mut rt);
         rt.expect_send(
             REWARD_ACTOR_ADDR,
-            UPDATE_NETWORK_KPI,
+            UPDATE_NETWORK_KPI_METHOD,
             IpldBlock::serialize_cbor(&BigIntSer(&expected_raw_byte_power)).unwrap(),
             TokenAmount::zero(),
             None,
@@ -839,7 +839,7 @@ mod cron_tests {
         );
         rt.expect_send(
             REWARD_ACTOR_ADDR,
-            UPDATE_NETWORK_KPI,
+            UPDATE_NETWORK_KPI_METHOD,
             IpldBlock::serialize_cbor(&BigIntSer(&expected_raw_byte_power)).unwrap(),
             TokenAmount::zero(),
             None,
@@ -916,7 +916,7 @@ mod cron_tests {
         // reward actor is still invoked
         rt.expect_send(
             REWARD_ACTOR_ADDR,
-            UPDATE_NETWORK_KPI,
+            UPDATE_NETWORK_KPI_METHOD,
             IpldBlock::serialize_cbor(&BigIntSer(&BigInt::zero())).unwrap(),
             TokenAmount::zero(),
             None,
@@ -993,7 +993,7 @@ mod cron_tests {
         rt.set_caller(*CRON_ACTOR_CODE_ID, CRON_ACTOR_ADDR);
         rt.expect_send(
             REWARD_ACTOR_ADDR,
-            UPDATE_NETWORK_KPI,
+            UPDATE_NETWORK_KPI_METHOD,
             IpldBlock::serialize_cbor(&BigIntSer(&BigInt::zero())).unwrap(),
             TokenAmount::zero(),
             None,
@@ -1020,7 +1020,7 @@ mod cron_tests {
 
         rt.expect_send(
             REWARD_ACTOR_ADDR,
-            UPDATE_NETWORK_KPI,
+            UPDATE_NETWORK_KPI_METHOD,
             IpldBlock::serialize_cbor(&BigIntSer(&BigInt::zero())).unwrap(),
             TokenAmount::zero(),
             None,
@@ -1040,7 +1040,7 @@ mod cron_batch_proof_verifies_tests {
     use super::*;
     use fil_actor_power::ext::{
         miner::{ConfirmSectorProofsParams, CONFIRM_SECTOR_PROOFS_VALID_METHOD},
-        reward::UPDATE_NETWORK_KPI,
+        reward::UPDATE_NETWORK_KPI_METHOD,
     };
     use fil_actors_runtime::{
         test_utils::{make_piece_cid, make_sealed_cid, CRON_ACTOR_CODE_ID},
@@ -1266,7 +1266,7 @@ mod cron_batch_proof_verifies_tests {
         // expect power sends to reward actor
         rt.expect_send(
             REWARD_ACTOR_ADDR,
-            UPDATE_NETWORK_KPI,
+            UPDATE_NETWORK_KPI_METHOD,
             IpldBlock::serialize_cbor(&BigIntSer(&BigInt::zero())).unwrap(),
             TokenAmount::zero(),
             None,
@@ -1302,7 +1302,7 @@ mod cron_batch_proof_verifies_tests {
         // expect power sends to reward actor
         rt.expect_send(
             REWARD_ACTOR_ADDR,
-            UPDATE_NETWORK_KPI,
+            UPDATE_NETWORK_KPI_METHOD,
             IpldBlock::serialize_cbor(&BigIntSer(&BigInt::zero())).unwrap(),
             TokenAmount::zero(),
             None,
@@ -1357,14 +1357,14 @@ mod submit_porep_for_bulk_verify_tests {
         let st: State = rt.get_state();
         let store = &rt.store;
         assert!(st.proof_validation_batch.is_some());
-        let mmap = Multimap::from_root(
+        let mut mmap = Multimap::<_, SealVerifyInfo>::from_root(
             store,
             st.proof_validation_batch.as_ref().unwrap(),
             HAMT_BIT_WIDTH,
             PROOF_VALIDATION_BATCH_AMT_BITWIDTH,
         )
         .unwrap();
-        let arr = mmap.get::<SealVerifyInfo>(&MINER.to_bytes()).unwrap();
+        let arr = mmap.get(&MINER.to_bytes()).unwrap();
         let found = arr.unwrap();
         assert_eq!(1_u64, found.count());
         let sealed_cid = found.get(0).unwrap().unwrap().sealed_cid;
@@ -1,10 +1,14 @@
 // Copyright 2019-2022 ChainSafe Systems
 // SPDX-License-Identifier: Apache-2.0, MIT
 
+use fil_actors_runtime::builtin::api::reward::{
+    THIS_EPOCH_REWARD_METHOD, UPDATE_NETWORK_KPI_METHOD,
+};
 use fil_actors_runtime::runtime::{ActorCode, Runtime};
 use fil_actors_runtime::{
-    actor_dispatch, actor_error, restrict_internal_api, ActorError, BURNT_FUNDS_ACTOR_ADDR,
-    EXPECTED_LEADERS_PER_EPOCH, STORAGE_POWER_ACTOR_ADDR, SYSTEM_ACTOR_ADDR,
+    actor_dispatch, actor_error, extract_send_result, restrict_internal_api, ActorError,
+    BURNT_FUNDS_ACTOR_ADDR, EXPECTED_LEADERS_PER_EPOCH, STORAGE_POWER_ACTOR_ADDR,
+    SYSTEM_ACTOR_ADDR,
 };
 
 use fvm_ipld_encoding::ipld_block::IpldBlock;
@@ -44,8 +48,8 @@ pub const PENALTY_MULTIPLIER: u64 = 3;
 pub enum Method {
     Constructor = METHOD_CONSTRUCTOR,
     AwardBlockReward = 2,
-    ThisEpochReward = 3,
-    UpdateNetworkKPI = 4,
+    ThisEpochReward = THIS_EPOCH_REWARD_METHOD,
+    UpdateNetworkKPI = UPDATE_NETWORK_KPI_METHOD,
 }
 
 /// Reward Actor
@@ -54,8 +58,6 @@ pub struct Actor;
 impl Actor {
     /// Constructor for Reward actor
     fn constructor(rt: &mut impl Runtime, params: Option<BigIntDe>) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_is(std::iter::once(&SYSTEM_ACTOR_ADDR))?;
-
         if let Some(power) = params.map(|v| v.0) {
             rt.create(&State::new(power))?;
             Ok(())
@@ -78,7 +80,6 @@ impl Actor {
         rt: &mut impl Runtime,
         params: AwardBlockRewardParams,
     ) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_is(std::iter::once(&SYSTEM_ACTOR_ADDR))?;
         let prior_balance = rt.current_balance();
         if params.penalty.is_negative() {
             return Err(actor_error!(illegal_argument, "negative penalty {}", params.penalty));
@@ -146,11 +147,15 @@ impl Actor {
 
         // if this fails, we can assume the miner is responsible and avoid failing here.
         let reward_params = ext::miner::ApplyRewardParams { reward: total_reward.clone(), penalty };
-        let res = rt.send(
+        let res = extract_send_result(
             &Address::new_id(miner_id),
             ext::miner::APPLY_REWARDS_METHOD,
-            IpldBlock::serialize_cbor(&reward_params)?,
-            total_reward.clone(),
+            rt.send(
+                &Address::new_id(miner_id),
+                ext::miner::APPLY_REWARDS_METHOD,
+                IpldBlock::serialize_cbor(&reward_params)?,
+                total_reward.clone(),
+            ),
         );
         if let Err(e) = res {
             error!(
@@ -158,7 +163,11 @@ impl Actor {
                 total_reward,
                 e.exit_code()
             );
-            let res = rt.send(&BURNT_FUNDS_ACTOR_ADDR, METHOD_SEND, None, total_reward);
+            let res = extract_send_result(
+                &BURNT_FUNDS_ACTOR_ADDR,
+                METHOD_SEND,
+                rt.send(&BURNT_FUNDS_ACTOR_ADDR, METHOD_SEND, None, total_reward),
+            );
             if let Err(e) = res {
                 error!(
                     "failed to send unsent reward to the burnt funds actor, code: {:?}",
@@ -174,7 +183,6 @@ impl Actor {
     /// through cron tick.  In the case previous epochs were null blocks this
     /// is the reward value as calculated at the last non-null epoch.
     fn this_epoch_reward(rt: &mut impl Runtime) -> Result<ThisEpochRewardReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let st: State = rt.state()?;
         Ok(ThisEpochRewardReturn {
             this_epoch_baseline_power: st.this_epoch_baseline_power,
@@ -189,7 +197,6 @@ impl Actor {
         rt: &mut impl Runtime,
         params: Option<BigIntDe>,
     ) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_is(std::iter::once(&STORAGE_POWER_ACTOR_ADDR))?;
         let curr_realized_power =
             params.ok_or_else(|| actor_error!(illegal_argument, "argument cannot be None"))?.0;
 
@@ -213,9 +220,9 @@ impl Actor {
 impl ActorCode for Actor {
     type Methods = Method;
     actor_dispatch! {
-        Constructor => constructor,
-        AwardBlockReward => award_block_reward,
-        ThisEpochReward => this_epoch_reward,
-        UpdateNetworkKPI => update_network_kpi,
+        Constructor => constructor(Option<BigIntDe>) [caller: is(SYSTEM_ACTOR_ADDR)],
+        AwardBlockReward => award_block_reward(AwardBlockRewardParams) [caller: is(SYSTEM_ACTOR_ADDR)],
+        ThisEpochReward => this_epoch_reward -> ThisEpochRewardReturn [caller: any, read_only],
+        UpdateNetworkKPI => update_network_kpi(Option<BigIntDe>) [caller: is(STORAGE_POWER_ACTOR_ADDR)],
     }
 }
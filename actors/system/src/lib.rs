@@ -57,9 +57,7 @@ pub struct Actor;
 
 impl Actor {
     /// System actor constructor.
-    pub fn constructor(rt: &mut impl Runtime) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_is(std::iter::once(&SYSTEM_ACTOR_ADDR))?;
-
+    fn constructor(rt: &mut impl Runtime) -> Result<(), ActorError> {
         let state = State::new(rt.store()).context("failed to construct state")?;
         rt.create(&state)?;
         Ok(())
@@ -69,7 +67,7 @@ impl Actor {
 impl ActorCode for Actor {
     type Methods = Method;
     actor_dispatch! {
-        Constructor => constructor,
+        Constructor => constructor [caller: is(SYSTEM_ACTOR_ADDR)],
     }
 }
 
@@ -0,0 +1,63 @@
+// Copyright 2019-2022 ChainSafe Systems
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use fil_actors_runtime::runtime::Runtime;
+use fil_actors_runtime::{ActorError, EventBuilder};
+use fvm_shared::ActorID;
+
+use crate::{AllocationID, ClaimID};
+
+/// Indicates a new allocation has been made.
+pub fn allocation(
+    rt: &impl Runtime,
+    id: AllocationID,
+    client: ActorID,
+    provider: ActorID,
+) -> Result<(), ActorError> {
+    emit_verifreg_event(rt, "allocation", id, client, provider)
+}
+
+/// Indicates an expired allocation has been removed, or an allocation has been claimed.
+pub fn allocation_removed(
+    rt: &impl Runtime,
+    id: AllocationID,
+    client: ActorID,
+    provider: ActorID,
+) -> Result<(), ActorError> {
+    emit_verifreg_event(rt, "allocation-removed", id, client, provider)
+}
+
+/// Indicates an allocation has been claimed.
+pub fn claim(
+    rt: &impl Runtime,
+    id: ClaimID,
+    client: ActorID,
+    provider: ActorID,
+) -> Result<(), ActorError> {
+    emit_verifreg_event(rt, "claim", id, client, provider)
+}
+
+/// Indicates an expired claim has been removed.
+pub fn claim_removed(
+    rt: &impl Runtime,
+    id: ClaimID,
+    client: ActorID,
+    provider: ActorID,
+) -> Result<(), ActorError> {
+    emit_verifreg_event(rt, "claim-removed", id, client, provider)
+}
+
+fn emit_verifreg_event(
+    rt: &impl Runtime,
+    typ: &str,
+    id: u64,
+    client: ActorID,
+    provider: ActorID,
+) -> Result<(), ActorError> {
+    EventBuilder::new()
+        .typ(typ)
+        .field_indexed("id", &id)
+        .field_indexed("client", &client)
+        .field_indexed("provider", &provider)
+        .emit(rt)
+}
@@ -7,25 +7,24 @@ use frc46_token::token::TOKEN_PRECISION;
 use fvm_actor_utils::receiver::UniversalReceiverParams;
 use fvm_ipld_blockstore::Blockstore;
 use fvm_ipld_encoding::RawBytes;
-use fvm_ipld_hamt::BytesKey;
 use fvm_shared::address::Address;
-use fvm_shared::bigint::bigint_ser::BigIntDe;
 use fvm_shared::bigint::BigInt;
 use fvm_shared::clock::ChainEpoch;
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
-use fvm_shared::{ActorID, MethodNum, HAMT_BIT_WIDTH, METHOD_CONSTRUCTOR};
+use fvm_shared::{ActorID, MethodNum, METHOD_CONSTRUCTOR};
 use log::info;
 use num_derive::FromPrimitive;
 use num_traits::{FromPrimitive, Signed, Zero};
 
+use fil_actors_runtime::builtin::api::verifreg::{CLAIM_ALLOCATIONS_METHOD, GET_CLAIMS_METHOD};
 use fil_actors_runtime::cbor::deserialize;
 use fil_actors_runtime::runtime::builtins::Type;
-use fil_actors_runtime::runtime::{ActorCode, Policy, Runtime};
+use fil_actors_runtime::runtime::{ActorCode, Policy, Runtime, SendFlags};
 use fil_actors_runtime::{
-    actor_dispatch, actor_error, deserialize_block, make_map_with_root_and_bitwidth,
-    resolve_to_actor_id, restrict_internal_api, ActorDowncast, ActorError, BatchReturn, Map,
-    DATACAP_TOKEN_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR, SYSTEM_ACTOR_ADDR,
+    actor_dispatch, actor_error, deserialize_block, extract_send_result, resolve_to_actor_id,
+    restrict_internal_api, ActorError, BatchReturn, DATACAP_TOKEN_ACTOR_ADDR,
+    STORAGE_MARKET_ACTOR_ADDR, SYSTEM_ACTOR_ADDR, USER_RECEIVER_GAS_LIMIT,
     VERIFIED_REGISTRY_ACTOR_ADDR,
 };
 use fil_actors_runtime::{ActorContext, AsActorError, BatchReturnGen};
@@ -35,12 +34,14 @@ use crate::ext::datacap::{DestroyParams, MintParams};
 
 pub use self::state::Allocation;
 pub use self::state::Claim;
+pub use self::state::RemoveDataCapProposalMap;
 pub use self::state::State;
 pub use self::types::*;
 
 #[cfg(feature = "fil-actor")]
 fil_actors_runtime::wasm_trampoline!(Actor);
 
+mod emit;
 pub mod expiration;
 pub mod ext;
 pub mod state;
@@ -59,8 +60,8 @@ pub enum Method {
     // RestoreBytes = 6, // Deprecated
     RemoveVerifiedClientDataCap = 7,
     RemoveExpiredAllocations = 8,
-    ClaimAllocations = 9,
-    GetClaims = 10,
+    ClaimAllocations = CLAIM_ALLOCATIONS_METHOD,
+    GetClaims = GET_CLAIMS_METHOD,
     ExtendClaimTerms = 11,
     RemoveExpiredClaims = 12,
     // Method numbers derived from FRC-0042 standards
@@ -76,9 +77,7 @@ pub struct Actor;
 
 impl Actor {
     /// Constructor for Registry Actor
-    pub fn constructor(rt: &mut impl Runtime, root_key: Address) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_is(std::iter::once(&SYSTEM_ACTOR_ADDR))?;
-
+    fn constructor(rt: &mut impl Runtime, root_key: Address) -> Result<(), ActorError> {
         // root should be an ID address
         let id_addr = rt
             .resolve_address(&root_key)
@@ -91,10 +90,7 @@ impl Actor {
         Ok(())
     }
 
-    pub fn add_verifier(
-        rt: &mut impl Runtime,
-        params: AddVerifierParams,
-    ) -> Result<(), ActorError> {
+    fn add_verifier(rt: &mut impl Runtime, params: AddVerifierParams) -> Result<(), ActorError> {
         if params.allowance < rt.policy().minimum_verified_allocation_size {
             return Err(actor_error!(
                 illegal_argument,
@@ -133,7 +129,7 @@ impl Actor {
         })
     }
 
-    pub fn remove_verifier(rt: &mut impl Runtime, params: Address) -> Result<(), ActorError> {
+    fn remove_verifier(rt: &mut impl Runtime, params: Address) -> Result<(), ActorError> {
         let verifier = resolve_to_actor_id(rt, &params)?;
         let verifier = Address::new_id(verifier);
 
@@ -145,13 +141,11 @@ impl Actor {
         })
     }
 
-    pub fn add_verified_client(
+    fn add_verified_client(
         rt: &mut impl Runtime,
         params: AddVerifiedClientParams,
     ) -> Result<(), ActorError> {
         // The caller will be verified by checking table below
-        rt.validate_immediate_caller_accept_any()?;
-
         if params.allowance < rt.policy().minimum_verified_allocation_size {
             return Err(actor_error!(
                 illegal_argument,
@@ -211,7 +205,7 @@ impl Actor {
     }
 
     /// Removes DataCap allocated to a verified client.
-    pub fn remove_verified_client_data_cap(
+    fn remove_verified_client_data_cap(
         rt: &mut impl Runtime,
         params: RemoveDataCapParams,
     ) -> Result<RemoveDataCapReturn, ActorError> {
@@ -250,25 +244,13 @@ impl Actor {
             }
 
             // validate signatures
-            let mut proposal_ids = make_map_with_root_and_bitwidth::<_, RemoveDataCapProposalID>(
-                &st.remove_data_cap_proposal_ids,
-                rt.store(),
-                HAMT_BIT_WIDTH,
-            )
-            .map_err(|e| {
-                e.downcast_default(
-                    ExitCode::USR_ILLEGAL_STATE,
-                    "failed to load datacap removal proposal ids",
-                )
-            })?;
+            let mut proposal_ids = st.load_remove_data_cap_proposal_ids(rt.store())?;
 
             let verifier_1_id = use_proposal_id(&mut proposal_ids, verifier_1, client)?;
             let verifier_2_id = use_proposal_id(&mut proposal_ids, verifier_2, client)?;
 
             // Assume proposal ids are valid and increment them
-            st.remove_data_cap_proposal_ids = proposal_ids
-                .flush()
-                .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to flush proposal ids")?;
+            st.remove_data_cap_proposal_ids = proposal_ids.flush()?;
             Ok((verifier_1_id, verifier_2_id))
         })?;
 
@@ -306,16 +288,15 @@ impl Actor {
     // An allocation may be removed after its expiration epoch has passed (by anyone).
     // When removed, the DataCap tokens are transferred back to the client.
     // If no allocations are specified, all eligible allocations are removed.
-    pub fn remove_expired_allocations(
+    fn remove_expired_allocations(
         rt: &mut impl Runtime,
         params: RemoveExpiredAllocationsParams,
     ) -> Result<RemoveExpiredAllocationsReturn, ActorError> {
-        // Since the allocations are expired, this is safe to be called by anyone.
-        rt.validate_immediate_caller_accept_any()?;
         let curr_epoch = rt.curr_epoch();
         let mut batch_ret = BatchReturn::empty();
         let mut considered = Vec::<ClaimID>::new();
         let mut recovered_datacap = DataCap::zero();
+        let mut removed = Vec::<(AllocationID, Allocation)>::new();
         let recovered_datacap = rt
             .transaction(|st: &mut State, rt| {
                 let mut allocs = st.load_allocs(rt.store())?;
@@ -343,7 +324,9 @@ impl Actor {
                         format!("failed to remove allocation {}", id),
                     )?;
                     // Unwrapping here as both paths to here should ensure the allocation exists.
-                    recovered_datacap += existing.unwrap().size.0;
+                    let existing = existing.unwrap();
+                    recovered_datacap += existing.size.0;
+                    removed.push((id, existing));
                 }
 
                 st.save_allocs(&mut allocs)?;
@@ -351,6 +334,10 @@ impl Actor {
             })
             .context("state transaction failed")?;
 
+        for (id, alloc) in removed {
+            emit::allocation_removed(rt, id, alloc.client, alloc.provider)?;
+        }
+
         // Transfer the recovered datacap back to the client.
         transfer(rt, params.client, &recovered_datacap).with_context(|| {
             format!(
@@ -369,11 +356,10 @@ impl Actor {
     // Called by storage provider actor to claim allocations for data provably committed to storage.
     // For each allocation claim, the registry checks that the provided piece CID
     // and size match that of the allocation.
-    pub fn claim_allocations(
+    fn claim_allocations(
         rt: &mut impl Runtime,
         params: ClaimAllocationsParams,
     ) -> Result<ClaimAllocationsReturn, ActorError> {
-        rt.validate_immediate_caller_type(std::iter::once(&Type::Miner))?;
         let provider = rt.message().caller().id().unwrap();
         if params.sectors.is_empty() {
             return Err(actor_error!(illegal_argument, "claim allocations called with no claims"));
@@ -381,6 +367,7 @@ impl Actor {
         let mut datacap_claimed = DataCap::zero();
         let mut ret_gen = BatchReturnGen::new(params.sectors.len());
         let all_or_nothing = params.all_or_nothing;
+        let mut claimed = Vec::<(ClaimID, ActorID)>::new();
         rt.transaction(|st: &mut State, rt| {
             let mut claims = st.load_claims(rt.store())?;
             let mut allocs = st.load_allocs(rt.store())?;
@@ -444,6 +431,7 @@ impl Actor {
                     format!("failed to remove allocation {}", claim_alloc.allocation_id),
                 )?;
 
+                claimed.push((claim_alloc.allocation_id, claim_alloc.client));
                 datacap_claimed += DataCap::from(claim_alloc.size.0);
                 ret_gen.add_success();
             }
@@ -461,6 +449,12 @@ impl Actor {
             ));
         }
 
+        // A claim takes the ID of the allocation it replaces.
+        for (id, client) in claimed {
+            emit::allocation_removed(rt, id, client, provider)?;
+            emit::claim(rt, id, client, provider)?;
+        }
+
         // Burn the datacap tokens from verified registry's own balance.
         burn(rt, &datacap_claimed)?;
 
@@ -468,32 +462,28 @@ impl Actor {
     }
 
     // get claims for a provider
-    pub fn get_claims(
+    fn get_claims(
         rt: &mut impl Runtime,
         params: GetClaimsParams,
     ) -> Result<GetClaimsReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
         let mut batch_gen = BatchReturnGen::new(params.claim_ids.len());
-        let claims = rt
-            .transaction(|st: &mut State, rt| {
-                let mut st_claims = st.load_claims(rt.store())?;
-                let mut ret_claims = Vec::new();
-                for id in params.claim_ids {
-                    let maybe_claim = state::get_claim(&mut st_claims, params.provider, id)?;
-                    match maybe_claim {
-                        None => {
-                            batch_gen.add_fail(ExitCode::USR_NOT_FOUND);
-                            info!("no claim {} for provider {}", id, params.provider,);
-                        }
-                        Some(claim) => {
-                            batch_gen.add_success();
-                            ret_claims.push(claim.clone());
-                        }
-                    };
+        // Claims are only read, so this may be invoked in read-only mode.
+        let st: State = rt.state()?;
+        let mut st_claims = st.load_claims(rt.store())?;
+        let mut claims = Vec::new();
+        for id in params.claim_ids {
+            let maybe_claim = state::get_claim(&mut st_claims, params.provider, id)?;
+            match maybe_claim {
+                None => {
+                    batch_gen.add_fail(ExitCode::USR_NOT_FOUND);
+                    info!("no claim {} for provider {}", id, params.provider,);
                 }
-                Ok(ret_claims)
-            })
-            .context("state transaction failed")?;
+                Some(claim) => {
+                    batch_gen.add_success();
+                    claims.push(claim.clone());
+                }
+            };
+        }
         Ok(GetClaimsReturn { batch_info: batch_gen.gen(), claims })
     }
 
@@ -504,12 +494,11 @@ impl Actor {
     /// Can extend the term even if the claim has already expired.
     /// Note that this method can't extend the term past the original limit,
     /// even if the term has previously been extended past that by spending new datacap.
-    pub fn extend_claim_terms(
+    fn extend_claim_terms(
         rt: &mut impl Runtime,
         params: ExtendClaimTermsParams,
     ) -> Result<ExtendClaimTermsReturn, ActorError> {
         // Permissions are checked per-claim.
-        rt.validate_immediate_caller_accept_any()?;
         let caller_id = rt.message().caller().id().unwrap();
         let term_limit = rt.policy().maximum_verified_allocation_term;
         let mut batch_gen = BatchReturnGen::new(params.terms.len());
@@ -567,15 +556,14 @@ impl Actor {
 
     // A claim may be removed after its maximum term has elapsed (by anyone).
     // If no claims are specified, all eligible claims are removed.
-    pub fn remove_expired_claims(
+    fn remove_expired_claims(
         rt: &mut impl Runtime,
         params: RemoveExpiredClaimsParams,
     ) -> Result<RemoveExpiredClaimsReturn, ActorError> {
-        // Since the claims are expired, this is safe to be called by anyone.
-        rt.validate_immediate_caller_accept_any()?;
         let curr_epoch = rt.curr_epoch();
         let mut batch_ret = BatchReturn::empty();
         let mut considered = Vec::<ClaimID>::new();
+        let mut removed = Vec::<(ClaimID, Claim)>::new();
         rt.transaction(|st: &mut State, rt| {
             let mut claims = st.load_claims(rt.store())?;
             let to_remove: Vec<ClaimID>;
@@ -596,10 +584,12 @@ impl Actor {
             }
 
             for id in to_remove {
-                claims.remove(params.provider, id).context_code(
+                let existing = claims.remove(params.provider, id).context_code(
                     ExitCode::USR_ILLEGAL_STATE,
                     format!("failed to remove claim {}", id),
                 )?;
+                // Unwrapping here as both paths to here should ensure the claim exists.
+                removed.push((id, existing.unwrap()));
             }
 
             st.save_claims(&mut claims)?;
@@ -607,6 +597,10 @@ impl Actor {
         })
         .context("state transaction failed")?;
 
+        for (id, claim) in removed {
+            emit::claim_removed(rt, id, claim.client, claim.provider)?;
+        }
+
         Ok(RemoveExpiredClaimsReturn { considered, results: batch_ret })
     }
 
@@ -616,13 +610,10 @@ impl Actor {
     // This method does not support partial success (yet): all allocations must succeed,
     // or the transfer will be rejected.
     // Returns the ids of the created allocations.
-    pub fn universal_receiver_hook(
+    fn universal_receiver_hook(
         rt: &mut impl Runtime,
         params: UniversalReceiverParams,
     ) -> Result<AllocationsResponse, ActorError> {
-        // Accept only the data cap token.
-        rt.validate_immediate_caller_is(&[DATACAP_TOKEN_ACTOR_ADDR])?;
-
         let my_id = rt.message().receiver().id().unwrap();
         let curr_epoch = rt.curr_epoch();
 
@@ -695,26 +686,27 @@ impl Actor {
         let extension_results = BatchReturn::ok(updated_claims.len() as u32);
 
         // Save new allocations and updated claims.
+        let providers: Vec<ActorID> = new_allocs.iter().map(|alloc| alloc.provider).collect();
         let ids = rt.transaction(|st: &mut State, rt| {
             let ids = st.insert_allocations(rt.store(), client, new_allocs.into_iter())?;
             st.put_claims(rt.store(), updated_claims.into_iter())?;
             Ok(ids)
         })?;
 
+        for (id, provider) in ids.iter().zip(providers) {
+            emit::allocation(rt, *id, client, provider)?;
+        }
+
         Ok(AllocationsResponse { allocation_results, extension_results, new_allocations: ids })
     }
 }
 
 // Checks whether an address has a verifier entry (which could be zero).
 fn is_verifier(rt: &impl Runtime, st: &State, address: Address) -> Result<bool, ActorError> {
-    let verifiers =
-        make_map_with_root_and_bitwidth::<_, BigIntDe>(&st.verifiers, rt.store(), HAMT_BIT_WIDTH)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load verifiers")?;
+    let verifiers = st.load_verifiers(rt.store())?;
 
     // check that the `address` is currently a verified client
-    let found = verifiers
-        .contains_key(&address.to_bytes())
-        .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to get verifier")?;
+    let found = verifiers.contains_key(&address).context("failed to get verifier")?;
 
     Ok(found)
 }
@@ -722,12 +714,12 @@ fn is_verifier(rt: &impl Runtime, st: &State, address: Address) -> Result<bool,
 // Invokes Balance on the data cap token actor, and converts the result to whole units of data cap.
 fn balance(rt: &mut impl Runtime, owner: &Address) -> Result<DataCap, ActorError> {
     let params = IpldBlock::serialize_cbor(owner)?;
+    let method = ext::datacap::Method::Balance as u64;
     let x: TokenAmount = deserialize_block(
-        rt.send(
+        extract_send_result(
             &DATACAP_TOKEN_ACTOR_ADDR,
-            ext::datacap::Method::Balance as u64,
-            params,
-            TokenAmount::zero(),
+            method,
+            rt.send(&DATACAP_TOKEN_ACTOR_ADDR, method, params, TokenAmount::zero()),
         )
         .context(format!("failed to query datacap balance of {}", owner))?,
     )?;
@@ -743,16 +735,36 @@ fn mint(
 ) -> Result<(), ActorError> {
     let token_amt = datacap_to_tokens(amount);
     let params = MintParams { to: *to, amount: token_amt, operators };
-    rt.send(
-        &DATACAP_TOKEN_ACTOR_ADDR,
+    send_with_receiver_hook(
+        rt,
         ext::datacap::Method::Mint as u64,
         IpldBlock::serialize_cbor(&params)?,
-        TokenAmount::zero(),
     )
     .context(format!("failed to send mint {:?} to datacap", params))?;
     Ok(())
 }
 
+// Invokes a method of the data cap token actor which calls the recipient's receiver hook.
+// The gas available to the token actor is capped, so a recipient can't consume all of ours.
+fn send_with_receiver_hook(
+    rt: &mut impl Runtime,
+    method: MethodNum,
+    params: Option<IpldBlock>,
+) -> Result<Option<IpldBlock>, ActorError> {
+    extract_send_result(
+        &DATACAP_TOKEN_ACTOR_ADDR,
+        method,
+        rt.send_generalized(
+            &DATACAP_TOKEN_ACTOR_ADDR,
+            method,
+            params,
+            TokenAmount::zero(),
+            Some(USER_RECEIVER_GAS_LIMIT),
+            SendFlags::empty(),
+        ),
+    )
+}
+
 // Invokes Burn on a data cap token actor for whole units of data cap.
 fn burn(rt: &mut impl Runtime, amount: &DataCap) -> Result<(), ActorError> {
     if amount.is_zero() {
@@ -761,11 +773,16 @@ fn burn(rt: &mut impl Runtime, amount: &DataCap) -> Result<(), ActorError> {
 
     let token_amt = datacap_to_tokens(amount);
     let params = BurnParams { amount: token_amt };
-    rt.send(
+    let method = ext::datacap::Method::Burn as u64;
+    extract_send_result(
         &DATACAP_TOKEN_ACTOR_ADDR,
-        ext::datacap::Method::Burn as u64,
-        IpldBlock::serialize_cbor(&params)?,
-        TokenAmount::zero(),
+        method,
+        rt.send(
+            &DATACAP_TOKEN_ACTOR_ADDR,
+            method,
+            IpldBlock::serialize_cbor(&params)?,
+            TokenAmount::zero(),
+        ),
     )
     .context(format!("failed to send burn {:?} to datacap", params))?;
     // The burn return value gives the new balance, but it's dropped here.
@@ -780,11 +797,16 @@ fn destroy(rt: &mut impl Runtime, owner: &Address, amount: &DataCap) -> Result<(
     }
     let token_amt = datacap_to_tokens(amount);
     let params = DestroyParams { owner: *owner, amount: token_amt };
-    rt.send(
+    let method = ext::datacap::Method::Destroy as u64;
+    extract_send_result(
         &DATACAP_TOKEN_ACTOR_ADDR,
-        ext::datacap::Method::Destroy as u64,
-        IpldBlock::serialize_cbor(&params)?,
-        TokenAmount::zero(),
+        method,
+        rt.send(
+            &DATACAP_TOKEN_ACTOR_ADDR,
+            method,
+            IpldBlock::serialize_cbor(&params)?,
+            TokenAmount::zero(),
+        ),
     )
     .context(format!("failed to send destroy {:?} to datacap", params))?;
     Ok(())
@@ -798,11 +820,10 @@ fn transfer(rt: &mut impl Runtime, to: ActorID, amount: &DataCap) -> Result<(),
         amount: token_amt,
         operator_data: Default::default(),
     };
-    rt.send(
-        &DATACAP_TOKEN_ACTOR_ADDR,
+    send_with_receiver_hook(
+        rt,
         ext::datacap::Method::Transfer as u64,
         IpldBlock::serialize_cbor(&params)?,
-        TokenAmount::zero(),
     )
     .context(format!("failed to send transfer to datacap {:?}", params))?;
     Ok(())
@@ -817,7 +838,7 @@ fn tokens_to_datacap(amount: &TokenAmount) -> BigInt {
 }
 
 fn use_proposal_id<BS>(
-    proposal_ids: &mut Map<BS, RemoveDataCapProposalID>,
+    proposal_ids: &mut RemoveDataCapProposalMap<BS>,
     verifier: Address,
     client: Address,
 ) -> Result<RemoveDataCapProposalID, ActorError>
@@ -826,32 +847,10 @@ where
 {
     let key = AddrPairKey::new(verifier, client);
 
-    let maybe_id = proposal_ids.get(&key.to_bytes()).map_err(|e| {
-        actor_error!(
-            illegal_state,
-            "failed to get proposal id for verifier {} and client {}: {}",
-            verifier,
-            client,
-            e
-        )
-    })?;
-
-    let curr_id = if let Some(RemoveDataCapProposalID { id }) = maybe_id {
-        RemoveDataCapProposalID { id: *id }
-    } else {
-        RemoveDataCapProposalID { id: 0 }
-    };
+    let curr_id = proposal_ids.get(&key)?.cloned().unwrap_or(RemoveDataCapProposalID { id: 0 });
 
     let next_id = RemoveDataCapProposalID { id: curr_id.id + 1 };
-    proposal_ids.set(BytesKey::from(key.to_bytes()), next_id).map_err(|e| {
-        actor_error!(
-            illegal_state,
-            "failed to update proposal id for verifier {} and client {}: {}",
-            verifier,
-            client,
-            e
-        )
-    })?;
+    proposal_ids.set(&key, next_id)?;
 
     Ok(curr_id)
 }
@@ -876,14 +875,18 @@ fn remove_data_cap_request_is_valid(
 
     let payload = [SIGNATURE_DOMAIN_SEPARATION_REMOVE_DATA_CAP, b.bytes()].concat();
 
-    rt.send(
+    extract_send_result(
         &request.verifier,
         ext::account::AUTHENTICATE_MESSAGE_METHOD,
-        IpldBlock::serialize_cbor(&ext::account::AuthenticateMessageParams {
-            signature: request.signature.bytes.clone(),
-            message: payload,
-        })?,
-        TokenAmount::zero(),
+        rt.send(
+            &request.verifier,
+            ext::account::AUTHENTICATE_MESSAGE_METHOD,
+            IpldBlock::serialize_cbor(&ext::account::AuthenticateMessageParams {
+                signature: request.signature.bytes.clone(),
+                message: payload,
+            })?,
+            TokenAmount::zero(),
+        ),
     )
     .map_err(|e| e.wrap("proposal authentication failed"))?;
     Ok(())
@@ -1072,21 +1075,24 @@ fn can_claim_alloc(
 impl ActorCode for Actor {
     type Methods = Method;
     actor_dispatch! {
-        Constructor => constructor,
-        AddVerifier => add_verifier,
-        RemoveVerifier => remove_verifier,
-        AddVerifiedClient => add_verified_client,
-        AddVerifiedClientExported => add_verified_client,
-        RemoveVerifiedClientDataCap => remove_verified_client_data_cap,
-        RemoveExpiredAllocations => remove_expired_allocations,
-        RemoveExpiredAllocationsExported => remove_expired_allocations,
-        ClaimAllocations => claim_allocations,
-        GetClaims => get_claims,
-        GetClaimsExported => get_claims,
-        ExtendClaimTerms => extend_claim_terms,
-        ExtendClaimTermsExported => extend_claim_terms,
-        RemoveExpiredClaims => remove_expired_claims,
-        RemoveExpiredClaimsExported => remove_expired_claims,
-        UniversalReceiverHook => universal_receiver_hook,
+        Constructor => constructor(Address) [caller: is(SYSTEM_ACTOR_ADDR)],
+        AddVerifier => add_verifier(AddVerifierParams),
+        RemoveVerifier => remove_verifier(Address),
+        AddVerifiedClient => add_verified_client(AddVerifiedClientParams) [caller: any],
+        AddVerifiedClientExported => add_verified_client(AddVerifiedClientParams) [caller: any],
+        RemoveVerifiedClientDataCap => remove_verified_client_data_cap(RemoveDataCapParams) -> RemoveDataCapReturn,
+        // Since the allocations are expired, this is safe to be called by anyone.
+        RemoveExpiredAllocations => remove_expired_allocations(RemoveExpiredAllocationsParams) -> RemoveExpiredAllocationsReturn [caller: any],
+        RemoveExpiredAllocationsExported => remove_expired_allocations(RemoveExpiredAllocationsParams) -> RemoveExpiredAllocationsReturn [caller: any],
+        ClaimAllocations => claim_allocations(ClaimAllocationsParams) -> ClaimAllocationsReturn [caller: types(Type::Miner)],
+        GetClaims => get_claims(GetClaimsParams) -> GetClaimsReturn [caller: any, read_only],
+        GetClaimsExported => get_claims(GetClaimsParams) -> GetClaimsReturn [caller: any, read_only],
+        ExtendClaimTerms => extend_claim_terms(ExtendClaimTermsParams) -> ExtendClaimTermsReturn [caller: any],
+        ExtendClaimTermsExported => extend_claim_terms(ExtendClaimTermsParams) -> ExtendClaimTermsReturn [caller: any],
+        // Since the claims are expired, this is safe to be called by anyone.
+        RemoveExpiredClaims => remove_expired_claims(RemoveExpiredClaimsParams) -> RemoveExpiredClaimsReturn [caller: any],
+        RemoveExpiredClaimsExported => remove_expired_claims(RemoveExpiredClaimsParams) -> RemoveExpiredClaimsReturn [caller: any],
+        // Accept only the data cap token.
+        UniversalReceiverHook => universal_receiver_hook(UniversalReceiverParams) -> AllocationsResponse [caller: is(DATACAP_TOKEN_ACTOR_ADDR)],
     }
 }
@@ -9,23 +9,28 @@ use fvm_shared::bigint::bigint_ser::BigIntDe;
 use fvm_shared::clock::ChainEpoch;
 use fvm_shared::error::ExitCode;
 use fvm_shared::piece::PaddedPieceSize;
-use fvm_shared::sector::SectorNumber;
 use fvm_shared::{ActorID, HAMT_BIT_WIDTH};
 
-use fil_actors_runtime::{
-    actor_error, make_empty_map, make_map_with_root_and_bitwidth, ActorError, AsActorError, Map,
-    MapMap,
-};
+use fil_actors_runtime::{actor_error, ActorContext, ActorError, AsActorError, Map2, MapMap};
 
-use crate::DataCap;
+use crate::{AddrPairKey, DataCap, RemoveDataCapProposalID};
 use crate::{AllocationID, ClaimID};
 
+pub use fil_actors_runtime::builtin::api::verifreg::Claim;
+
+/// Data cap minting allowance for each verifier, keyed by verifier address.
+pub type VerifiersMap<'bs, BS> = Map2<'bs, BS, Address, BigIntDe>;
+
+/// Next data cap removal proposal ID, keyed by verifier and client address pair.
+pub type RemoveDataCapProposalMap<'bs, BS> = Map2<'bs, BS, AddrPairKey, RemoveDataCapProposalID>;
+
 #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone)]
 pub struct State {
     pub root_key: Address,
     // Maps verifier addresses to data cap minting allowance (in bytes).
     pub verifiers: Cid, // HAMT[Address]DataCap
-    pub remove_data_cap_proposal_ids: Cid,
+    // Maps verifier and client address pairs to the next data cap removal proposal ID.
+    pub remove_data_cap_proposal_ids: Cid, // HAMT[AddrPairKey]RemoveDataCapProposalID
     // Maps client IDs to allocations made by that client.
     pub allocations: Cid, // HAMT[ActorID]HAMT[AllocationID]Allocation
     // Next allocation identifier to use.
@@ -37,9 +42,7 @@ pub struct State {
 
 impl State {
     pub fn new<BS: Blockstore>(store: &BS, root_key: Address) -> Result<State, ActorError> {
-        let empty_map = make_empty_map::<_, ()>(store, HAMT_BIT_WIDTH)
-            .flush()
-            .map_err(|e| actor_error!(illegal_state, "failed to create empty map: {}", e))?;
+        let empty_map = VerifiersMap::flush_empty(store)?;
 
         let empty_mapmap =
             MapMap::<_, (), ActorID, u64>::new(store, HAMT_BIT_WIDTH, HAMT_BIT_WIDTH)
@@ -65,15 +68,9 @@ impl State {
         verifier: &Address,
         cap: &DataCap,
     ) -> Result<(), ActorError> {
-        let mut verifiers =
-            make_map_with_root_and_bitwidth::<_, BigIntDe>(&self.verifiers, store, HAMT_BIT_WIDTH)
-                .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load verifiers")?;
-        verifiers
-            .set(verifier.to_bytes().into(), BigIntDe(cap.clone()))
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to set verifier")?;
-        self.verifiers = verifiers
-            .flush()
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to flush verifiers")?;
+        let mut verifiers = self.load_verifiers(store)?;
+        verifiers.set(verifier, BigIntDe(cap.clone())).context("failed to set verifier")?;
+        self.verifiers = verifiers.flush()?;
         Ok(())
     }
 
@@ -82,18 +79,14 @@ impl State {
         store: &impl Blockstore,
         verifier: &Address,
     ) -> Result<(), ActorError> {
-        let mut verifiers =
-            make_map_with_root_and_bitwidth::<_, BigIntDe>(&self.verifiers, store, HAMT_BIT_WIDTH)
-                .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load verifiers")?;
+        let mut verifiers = self.load_verifiers(store)?;
 
         verifiers
-            .delete(&verifier.to_bytes())
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to remove verifier")?
+            .delete(verifier)
+            .context("failed to remove verifier")?
             .context_code(ExitCode::USR_ILLEGAL_ARGUMENT, "verifier not found")?;
 
-        self.verifiers = verifiers
-            .flush()
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to flush verifiers")?;
+        self.verifiers = verifiers.flush()?;
         Ok(())
     }
 
@@ -102,21 +95,27 @@ impl State {
         store: &impl Blockstore,
         verifier: &Address,
     ) -> Result<Option<DataCap>, ActorError> {
-        let verifiers =
-            make_map_with_root_and_bitwidth::<_, BigIntDe>(&self.verifiers, store, HAMT_BIT_WIDTH)
-                .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load verifiers")?;
-        let allowance = verifiers
-            .get(&verifier.to_bytes())
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to get verifier")?;
+        let verifiers = self.load_verifiers(store)?;
+        let allowance = verifiers.get(verifier).context("failed to get verifier")?;
         Ok(allowance.map(|a| a.0.clone() as DataCap))
     }
 
     pub fn load_verifiers<'a, BS: Blockstore>(
         &self,
         store: &'a BS,
-    ) -> Result<Map<'a, BS, BigIntDe>, ActorError> {
-        make_map_with_root_and_bitwidth::<_, BigIntDe>(&self.verifiers, store, HAMT_BIT_WIDTH)
-            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load verifiers")
+    ) -> Result<VerifiersMap<'a, BS>, ActorError> {
+        VerifiersMap::load(store, &self.verifiers, "verifiers")
+    }
+
+    pub fn load_remove_data_cap_proposal_ids<'a, BS: Blockstore>(
+        &self,
+        store: &'a BS,
+    ) -> Result<RemoveDataCapProposalMap<'a, BS>, ActorError> {
+        RemoveDataCapProposalMap::load(
+            store,
+            &self.remove_data_cap_proposal_ids,
+            "remove data cap proposal ids",
+        )
     }
 
     pub fn load_allocs<'a, BS: Blockstore>(
@@ -212,26 +211,6 @@ impl State {
         Ok(())
     }
 }
-#[derive(Serialize_tuple, Deserialize_tuple, Clone, Debug, PartialEq, Eq)]
-pub struct Claim {
-    // The provider storing the data (from allocation).
-    pub provider: ActorID,
-    // The client which allocated the DataCap (from allocation).
-    pub client: ActorID,
-    // Identifier of the data committed (from allocation).
-    pub data: Cid,
-    // The (padded) size of data (from allocation).
-    pub size: PaddedPieceSize,
-    // The min period after term_start which the provider must commit to storing data
-    pub term_min: ChainEpoch,
-    // The max period after term_start for which provider can earn QA-power for the data
-    pub term_max: ChainEpoch,
-    // The epoch at which the (first range of the) piece was committed.
-    pub term_start: ChainEpoch,
-    // ID of the provider's sector in which the data is committed.
-    pub sector: SectorNumber,
-}
-
 #[derive(Serialize_tuple, Deserialize_tuple, Clone, Debug, PartialEq, Eq)]
 pub struct Allocation {
     // The verified client which allocated the DataCap.
@@ -6,12 +6,9 @@ use fil_actors_runtime::runtime::policy_constants::{
     MINIMUM_VERIFIED_ALLOCATION_SIZE, MINIMUM_VERIFIED_ALLOCATION_TERM,
 };
 use fil_actors_runtime::shared::HAMT_BIT_WIDTH;
-use fil_actors_runtime::{
-    make_map_with_root_and_bitwidth, parse_uint_key, Map, MessageAccumulator,
-};
+use fil_actors_runtime::{make_map_with_root_and_bitwidth, parse_uint_key, MessageAccumulator};
 use fvm_ipld_blockstore::Blockstore;
 use fvm_shared::address::{Address, Protocol};
-use fvm_shared::bigint::bigint_ser::BigIntDe;
 use fvm_shared::clock::ChainEpoch;
 use fvm_shared::ActorID;
 use num_traits::Signed;
@@ -34,17 +31,17 @@ pub fn check_state_invariants<BS: Blockstore>(
 
     // Load and check verifiers
     let mut all_verifiers = HashMap::new();
-    match Map::<_, BigIntDe>::load(&state.verifiers, store) {
+    match state.load_verifiers(store) {
         Ok(verifiers) => {
-            let ret = verifiers.for_each(|key, cap| {
-                let verifier = Address::from_bytes(key)?;
+            let ret = verifiers.for_each(|verifier, cap| {
+                let verifier = *verifier;
                 let cap = &cap.0;
 
-                acc.require(
+                acc.invariant("verifreg.verifier.id_address").require(
                     verifier.protocol() == Protocol::ID,
                     format!("verifier {verifier} should have ID protocol"),
                 );
-                acc.require(
+                acc.invariant("verifreg.verifier.cap_non_negative").require(
                     !cap.is_negative(),
                     format!("verifier {verifier} cap {cap} is negative"),
                 );
@@ -52,9 +49,12 @@ pub fn check_state_invariants<BS: Blockstore>(
                 Ok(())
             });
 
-            acc.require_no_error(ret, "error iterating verifiers");
+            acc.invariant("verifreg.verifiers.load")
+                .require_no_error(ret, "error iterating verifiers");
+        }
+        Err(e) => {
+            acc.invariant("verifreg.verifiers.load").add(format!("error loading verifiers {e}"))
         }
-        Err(e) => acc.add(format!("error loading verifiers {e}")),
     }
 
     // Load and check allocations
@@ -79,19 +79,24 @@ pub fn check_state_invariants<BS: Blockstore>(
                             all_allocations.insert(allocation_id, allocation.clone());
                             Ok(())
                         });
-                        acc.require_no_error(
+                        acc.invariant("verifreg.allocations.load").require_no_error(
                             ret,
                             format!("error iterating allocations inner for {client_id}"),
                         );
                     }
-                    Err(e) => acc.add(format!("error loading allocations {e}")),
+                    Err(e) => acc
+                        .invariant("verifreg.allocations.load")
+                        .add(format!("error loading allocations {e}")),
                 }
                 Ok(())
             });
 
-            acc.require_no_error(ret, "error iterating allocations outer");
+            acc.invariant("verifreg.allocations.load")
+                .require_no_error(ret, "error iterating allocations outer");
         }
-        Err(e) => acc.add(format!("error loading allocations from {e}")),
+        Err(e) => acc
+            .invariant("verifreg.allocations.load")
+            .add(format!("error loading allocations from {e}")),
     }
 
     let mut all_claims = HashMap::new();
@@ -114,19 +119,22 @@ pub fn check_state_invariants<BS: Blockstore>(
                             all_claims.insert(claim_id, claim.clone());
                             Ok(())
                         });
-                        acc.require_no_error(
+                        acc.invariant("verifreg.claims.load").require_no_error(
                             ret,
                             format!("error iterating allocations inner for {provider_id}"),
                         );
                     }
-                    Err(e) => acc.add(format!("error loading allocations {e}")),
+                    Err(e) => acc
+                        .invariant("verifreg.claims.load")
+                        .add(format!("error loading allocations {e}")),
                 }
                 Ok(())
             });
 
-            acc.require_no_error(ret, "error iterating allocations outer");
+            acc.invariant("verifreg.claims.load")
+                .require_no_error(ret, "error iterating allocations outer");
         }
-        Err(e) => acc.add(format!("error loading claims {e}")),
+        Err(e) => acc.invariant("verifreg.claims.load").add(format!("error loading claims {e}")),
     }
 
     (
@@ -143,28 +151,32 @@ fn check_allocation_state(
     prior_epoch: ChainEpoch,
     acc: &MessageAccumulator,
 ) {
-    acc.require(id < next_alloc_id, format!("allocation id {} exceeds next {}", id, next_alloc_id));
-    acc.require(
+    let acc = acc.related("allocation", id);
+    acc.invariant("verifreg.allocation.id_below_next").require(
+        id < next_alloc_id,
+        format!("allocation id {} exceeds next {}", id, next_alloc_id),
+    );
+    acc.invariant("verifreg.allocation.client_matches_key").require(
         alloc.client == client,
         format!("allocation {} client {} doesn't match key {}", id, alloc.client, client),
     );
-    acc.require(
+    acc.invariant("verifreg.allocation.size_min").require(
         alloc.size.0 >= MINIMUM_VERIFIED_ALLOCATION_SIZE as u64,
         format!("allocation {} size {} too small", id, alloc.size.0),
     );
-    acc.require(
+    acc.invariant("verifreg.allocation.term_min").require(
         alloc.term_min >= MINIMUM_VERIFIED_ALLOCATION_TERM,
         format!("allocation {} term min {} too small", id, alloc.term_min),
     );
-    acc.require(
+    acc.invariant("verifreg.allocation.term_max").require(
         alloc.term_max <= MAXIMUM_VERIFIED_ALLOCATION_TERM,
         format!("allocation {} term max {} too large ", id, alloc.term_max),
     );
-    acc.require(
+    acc.invariant("verifreg.allocation.term_min_below_max").require(
         alloc.term_min <= alloc.term_max,
         format!("allocation {} term min {} exceeds max {}", id, alloc.term_min, alloc.term_max),
     );
-    acc.require(
+    acc.invariant("verifreg.allocation.expiration").require(
         alloc.expiration <= prior_epoch + MAXIMUM_VERIFIED_ALLOCATION_EXPIRATION,
         format!(
             "allocation {} expiration {} too far from now {}",
@@ -181,19 +193,21 @@ fn check_claim_state(
     prior_epoch: ChainEpoch,
     acc: &MessageAccumulator,
 ) {
-    acc.require(id < next_alloc_id, format!("claim id {} exceeds next {}", id, next_alloc_id));
-    acc.require(
+    let acc = acc.related("claim", id);
+    acc.invariant("verifreg.claim.id_below_next")
+        .require(id < next_alloc_id, format!("claim id {} exceeds next {}", id, next_alloc_id));
+    acc.invariant("verifreg.claim.provider_matches_key").require(
         claim.provider == provider,
         format!("claim {} provider {} doesn't match key {}", id, claim.provider, provider),
     );
-    acc.require(
+    acc.invariant("verifreg.claim.size_min").require(
         claim.size.0 >= MINIMUM_VERIFIED_ALLOCATION_SIZE as u64,
         format!(
             "claim {} size {} below minimum {}",
             id, claim.size.0, MINIMUM_VERIFIED_ALLOCATION_SIZE
         ),
     );
-    acc.require(
+    acc.invariant("verifreg.claim.term_min").require(
         claim.term_min >= MINIMUM_VERIFIED_ALLOCATION_TERM,
         format!(
             "claim {} term min {} below minimum {}",
@@ -202,11 +216,11 @@ fn check_claim_state(
     );
     // The maximum term is not limited because it can be extended
     // arbitrarily long by a client spending new datacap.
-    acc.require(
+    acc.invariant("verifreg.claim.term_min_below_max").require(
         claim.term_min <= claim.term_max,
         format!("claim {} term min {} exceeds max {}", id, claim.term_min, claim.term_max),
     );
-    acc.require(
+    acc.invariant("verifreg.claim.term_start").require(
         claim.term_start <= prior_epoch,
         format!("claim {} term start {} after now {}", id, claim.term_start, prior_epoch),
     );
@@ -2,21 +2,20 @@
 // SPDX-License-Identifier: Apache-2.0, MIT
 
 use cid::Cid;
-use fil_actors_runtime::BatchReturn;
+use fil_actors_runtime::{BatchReturn, MapKey};
 use fvm_ipld_encoding::tuple::*;
 use fvm_shared::address::Address;
-use fvm_shared::bigint::{bigint_ser, BigInt};
+use fvm_shared::bigint::bigint_ser;
 use fvm_shared::clock::ChainEpoch;
 use fvm_shared::crypto::signature::Signature;
 use fvm_shared::piece::PaddedPieceSize;
-use fvm_shared::sector::SectorNumber;
 use fvm_shared::sector::StoragePower;
 use fvm_shared::ActorID;
 
-use crate::Claim;
-
-pub type AllocationID = u64;
-pub type ClaimID = u64;
+pub use fil_actors_runtime::builtin::api::verifreg::{
+    AllocationID, ClaimAllocationsParams, ClaimAllocationsReturn, ClaimID, GetClaimsParams,
+    GetClaimsReturn, SectorAllocationClaim,
+};
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
 pub struct VerifierParams {
@@ -70,6 +69,7 @@ pub struct RemoveDataCapProposal {
     pub removal_proposal_id: RemoveDataCapProposalID,
 }
 
+#[derive(Debug)]
 pub struct AddrPairKey {
     pub first: Address,
     pub second: Address,
@@ -88,6 +88,25 @@ impl AddrPairKey {
     }
 }
 
+/// The key is the concatenation of the two addresses' bytes.
+/// Each address encoding determines its own length, so the key splits where both halves
+/// are valid addresses.
+impl MapKey for AddrPairKey {
+    fn from_key_bytes(b: &[u8]) -> Result<Self, String> {
+        (1..b.len())
+            .find_map(|i| {
+                let first = Address::from_bytes(&b[..i]).ok()?;
+                let second = Address::from_bytes(&b[i..]).ok()?;
+                Some(AddrPairKey { first, second })
+            })
+            .ok_or_else(|| format!("failed to decode address pair key from {:?}", b))
+    }
+
+    fn to_key_bytes(&self) -> Result<Vec<u8>, String> {
+        Ok(self.to_bytes())
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
 pub struct RemoveExpiredAllocationsParams {
     // Client for which to remove expired allocations.
@@ -108,29 +127,6 @@ pub struct RemoveExpiredAllocationsReturn {
     pub datacap_recovered: DataCap,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
-pub struct SectorAllocationClaim {
-    pub client: ActorID,
-    pub allocation_id: AllocationID,
-    pub data: Cid,
-    pub size: PaddedPieceSize,
-    pub sector: SectorNumber,
-    pub sector_expiry: ChainEpoch,
-}
-
-#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
-pub struct ClaimAllocationsParams {
-    pub sectors: Vec<SectorAllocationClaim>,
-    pub all_or_nothing: bool,
-}
-
-#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
-pub struct ClaimAllocationsReturn {
-    pub batch_info: BatchReturn,
-    #[serde(with = "bigint_ser")]
-    pub claimed_space: BigInt,
-}
-
 #[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
 pub struct ClaimTerm {
     pub provider: ActorID,
@@ -188,18 +184,6 @@ pub struct AllocationsResponse {
     pub new_allocations: Vec<AllocationID>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
-pub struct GetClaimsParams {
-    pub provider: ActorID,
-    pub claim_ids: Vec<ClaimID>,
-}
-
-#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
-pub struct GetClaimsReturn {
-    pub batch_info: BatchReturn,
-    pub claims: Vec<Claim>,
-}
-
 #[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
 pub struct RemoveExpiredClaimsParams {
     // Provider to clean up (need not be the caller)
@@ -13,6 +13,7 @@ use fvm_shared::sector::SectorNumber;
 use fvm_shared::{ActorID, MethodNum, HAMT_BIT_WIDTH};
 use num_traits::{ToPrimitive, Zero};
 
+use fil_actor_verifreg::expiration::{check_expired, find_expired};
 use fil_actor_verifreg::testing::check_state_invariants;
 use fil_actor_verifreg::{
     ext, Actor as VerifregActor, AddVerifiedClientParams, AddVerifierParams, Allocation,
@@ -22,16 +23,17 @@ use fil_actor_verifreg::{
     RemoveExpiredAllocationsParams, RemoveExpiredAllocationsReturn, RemoveExpiredClaimsParams,
     RemoveExpiredClaimsReturn, SectorAllocationClaim, State,
 };
-use fil_actors_runtime::cbor::serialize;
+use fil_actors_runtime::cbor::{deserialize, serialize};
 use fil_actors_runtime::runtime::builtins::Type;
 use fil_actors_runtime::runtime::policy_constants::{
     MAXIMUM_VERIFIED_ALLOCATION_TERM, MINIMUM_VERIFIED_ALLOCATION_TERM,
 };
-use fil_actors_runtime::runtime::Runtime;
+use fil_actors_runtime::runtime::{Runtime, SendFlags};
 use fil_actors_runtime::test_utils::*;
 use fil_actors_runtime::{
-    make_empty_map, ActorError, AsActorError, BatchReturn, DATACAP_TOKEN_ACTOR_ADDR,
-    STORAGE_MARKET_ACTOR_ADDR, SYSTEM_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR,
+    make_empty_map, ActorError, AsActorError, BatchReturn, EventBuilder, DATACAP_TOKEN_ACTOR_ADDR,
+    STORAGE_MARKET_ACTOR_ADDR, SYSTEM_ACTOR_ADDR, USER_RECEIVER_GAS_LIMIT,
+    VERIFIED_REGISTRY_ACTOR_ADDR,
 };
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 
@@ -153,14 +155,14 @@ impl Harness {
 
     pub fn get_verifier_allowance(&self, rt: &MockRuntime, verifier: &Address) -> DataCap {
         let verifiers = rt.get_state::<State>().load_verifiers(&rt.store).unwrap();
-        let BigIntDe(allowance) = verifiers.get(&verifier.to_bytes()).unwrap().unwrap();
+        let BigIntDe(allowance) = verifiers.get(verifier).unwrap().unwrap();
         allowance.clone()
     }
 
     pub fn assert_verifier_removed(&self, rt: &MockRuntime, verifier: &Address) {
         let verifier_id_addr = rt.get_id_address(verifier).unwrap();
         let verifiers = rt.get_state::<State>().load_verifiers(&rt.store).unwrap();
-        assert!(!verifiers.contains_key(&verifier_id_addr.to_bytes()).unwrap())
+        assert!(!verifiers.contains_key(&verifier_id_addr).unwrap())
     }
 
     pub fn add_client(
@@ -180,11 +182,13 @@ impl Harness {
             amount: TokenAmount::from_whole(allowance.to_i64().unwrap()),
             operators: vec![STORAGE_MARKET_ACTOR_ADDR],
         };
-        rt.expect_send(
+        rt.expect_send_generalized(
             DATACAP_TOKEN_ACTOR_ADDR,
             ext::datacap::Method::Mint as MethodNum,
             IpldBlock::serialize_cbor(&mint_params).unwrap(),
             TokenAmount::zero(),
+            Some(USER_RECEIVER_GAS_LIMIT),
+            SendFlags::empty(),
             None,
             ExitCode::OK,
         );
@@ -260,12 +264,47 @@ impl Harness {
             );
         }
 
+        // Each allocation which can be claimed is replaced by a claim with the same ID.
+        let mut claimed = Vec::<AllocationID>::new();
+        for req in &claim_allocs {
+            if claimed.contains(&req.allocation_id) {
+                continue;
+            }
+            let alloc = match self.load_alloc(rt, req.client, req.allocation_id) {
+                Some(alloc) => alloc,
+                None => continue,
+            };
+            let sector_lifetime = req.sector_expiry - rt.epoch;
+            if provider == alloc.provider
+                && req.client == alloc.client
+                && req.data == alloc.data
+                && req.size == alloc.size
+                && rt.epoch <= alloc.expiration
+                && sector_lifetime >= alloc.term_min
+                && sector_lifetime <= alloc.term_max
+            {
+                claimed.push(req.allocation_id);
+                expect_verifreg_event(
+                    rt,
+                    "allocation-removed",
+                    req.allocation_id,
+                    req.client,
+                    provider,
+                );
+                expect_verifreg_event(rt, "claim", req.allocation_id, req.client, provider);
+            }
+        }
+
         let params = ClaimAllocationsParams { sectors: claim_allocs, all_or_nothing };
         let ret = rt
             .call::<VerifregActor>(
                 Method::ClaimAllocations as MethodNum,
                 IpldBlock::serialize_cbor(&params).unwrap(),
-            )?
+            )
+            .map_err(|e| {
+                rt.clear_expected_events();
+                e
+            })?
             .unwrap()
             .deserialize()
             .expect("failed to deserialize claim allocations return");
@@ -283,7 +322,7 @@ impl Harness {
     ) -> Result<RemoveExpiredAllocationsReturn, ActorError> {
         rt.expect_validate_caller_any();
 
-        rt.expect_send(
+        rt.expect_send_generalized(
             DATACAP_TOKEN_ACTOR_ADDR,
             ext::datacap::Method::Transfer as MethodNum,
             IpldBlock::serialize_cbor(&TransferParams {
@@ -293,16 +332,42 @@ impl Harness {
             })
             .unwrap(),
             TokenAmount::zero(),
+            Some(USER_RECEIVER_GAS_LIMIT),
+            SendFlags::empty(),
             None,
             ExitCode::OK,
         );
 
+        let removed = {
+            let st: State = rt.get_state();
+            let mut allocs = st.load_allocs(rt.store()).unwrap();
+            let ids = if allocation_ids.is_empty() {
+                find_expired(&mut allocs, client, rt.epoch).unwrap()
+            } else {
+                check_expired(&mut allocs, &allocation_ids, client, rt.epoch)
+                    .unwrap()
+                    .successes(&allocation_ids)
+            };
+            let mut removed = Vec::new();
+            for id in ids {
+                removed.push((id, allocs.get(client, id).unwrap().unwrap().provider));
+            }
+            removed
+        };
+        for (id, provider) in removed {
+            expect_verifreg_event(rt, "allocation-removed", id, client, provider);
+        }
+
         let params = RemoveExpiredAllocationsParams { client, allocation_ids };
         let ret = rt
             .call::<VerifregActor>(
                 Method::RemoveExpiredAllocations as MethodNum,
                 IpldBlock::serialize_cbor(&params).unwrap(),
-            )?
+            )
+            .map_err(|e| {
+                rt.clear_expected_events();
+                e
+            })?
             .unwrap()
             .deserialize()
             .expect("failed to deserialize remove expired allocations return");
@@ -319,12 +384,36 @@ impl Harness {
     ) -> Result<RemoveExpiredClaimsReturn, ActorError> {
         rt.expect_validate_caller_any();
 
+        let removed = {
+            let st: State = rt.get_state();
+            let mut claims = st.load_claims(rt.store()).unwrap();
+            let ids = if claim_ids.is_empty() {
+                find_expired(&mut claims, provider, rt.epoch).unwrap()
+            } else {
+                check_expired(&mut claims, &claim_ids, provider, rt.epoch)
+                    .unwrap()
+                    .successes(&claim_ids)
+            };
+            let mut removed = Vec::new();
+            for id in ids {
+                removed.push((id, claims.get(provider, id).unwrap().unwrap().client));
+            }
+            removed
+        };
+        for (id, client) in removed {
+            expect_verifreg_event(rt, "claim-removed", id, client, provider);
+        }
+
         let params = RemoveExpiredClaimsParams { provider, claim_ids };
         let ret = rt
             .call::<VerifregActor>(
                 Method::RemoveExpiredClaims as MethodNum,
                 IpldBlock::serialize_cbor(&params).unwrap(),
-            )?
+            )
+            .map_err(|e| {
+                rt.clear_expected_events();
+                e
+            })?
             .unwrap()
             .deserialize()
             .expect("failed to deserialize remove expired claims return");
@@ -372,11 +461,24 @@ impl Harness {
             );
         }
 
+        if let Ok(reqs) =
+            deserialize::<AllocationRequests>(&payload.operator_data, "allocation requests")
+        {
+            for (id, req) in expected_alloc_ids.iter().zip(reqs.allocations) {
+                expect_verifreg_event(rt, "allocation", *id, payload.from, req.provider);
+            }
+        }
+
         rt.expect_validate_caller_addr(vec![DATACAP_TOKEN_ACTOR_ADDR]);
-        let ret = rt.call::<VerifregActor>(
-            Method::UniversalReceiverHook as MethodNum,
-            IpldBlock::serialize_cbor(&params).unwrap(),
-        )?;
+        let ret = rt
+            .call::<VerifregActor>(
+                Method::UniversalReceiverHook as MethodNum,
+                IpldBlock::serialize_cbor(&params).unwrap(),
+            )
+            .map_err(|e| {
+                rt.clear_expected_events();
+                e
+            })?;
         assert_eq!(
             AllocationsResponse {
                 allocation_results: expected_alloc_results,
@@ -442,6 +544,25 @@ impl Harness {
     }
 }
 
+/// Expects the registry to emit an event of type `typ` for an allocation or claim.
+pub fn expect_verifreg_event(
+    rt: &mut MockRuntime,
+    typ: &str,
+    id: u64,
+    client: ActorID,
+    provider: ActorID,
+) {
+    rt.expect_emitted_event(
+        EventBuilder::new()
+            .typ(typ)
+            .field_indexed("id", &id)
+            .field_indexed("client", &client)
+            .field_indexed("provider", &provider)
+            .build()
+            .unwrap(),
+    );
+}
+
 pub fn make_alloc(data_id: &str, client: ActorID, provider: ActorID, size: u64) -> Allocation {
     Allocation {
         client,
@@ -249,8 +249,11 @@ mod clients {
     use fil_actor_verifreg::{
         ext, Actor as VerifregActor, AddVerifiedClientParams, DataCap, Method,
     };
+    use fil_actors_runtime::runtime::SendFlags;
     use fil_actors_runtime::test_utils::*;
-    use fil_actors_runtime::{DATACAP_TOKEN_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR};
+    use fil_actors_runtime::{
+        DATACAP_TOKEN_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR, USER_RECEIVER_GAS_LIMIT,
+    };
 
     use fvm_ipld_encoding::ipld_block::IpldBlock;
     use harness::*;
@@ -415,11 +418,13 @@ mod clients {
             amount: TokenAmount::from_whole(allowance_client.to_i64().unwrap()),
             operators: vec![STORAGE_MARKET_ACTOR_ADDR],
         };
-        rt.expect_send(
+        rt.expect_send_generalized(
             DATACAP_TOKEN_ACTOR_ADDR,
             ext::datacap::Method::Mint as MethodNum,
             IpldBlock::serialize_cbor(&mint_params).unwrap(),
             TokenAmount::zero(),
+            Some(USER_RECEIVER_GAS_LIMIT),
+            SendFlags::empty(),
             None,
             ExitCode::OK,
         );
@@ -499,7 +504,7 @@ mod allocs_claims {
 
     use fil_actor_verifreg::{
         Actor, AllocationID, ClaimTerm, DataCap, ExtendClaimTermsParams, GetClaimsParams,
-        GetClaimsReturn, Method, State,
+        GetClaimsReturn, Method, RemoveExpiredAllocationsParams, State,
     };
     use fil_actor_verifreg::{Claim, ExtendClaimTermsReturn};
     use fil_actors_runtime::runtime::policy_constants::{
@@ -509,7 +514,7 @@ mod allocs_claims {
     use fil_actors_runtime::test_utils::{
         expect_abort_contains_message, make_identity_cid, ACCOUNT_ACTOR_CODE_ID,
     };
-    use fil_actors_runtime::FailCode;
+    use fil_actors_runtime::{EventBuilder, FailCode, EXIT_CODE_READ_ONLY};
     use harness::*;
 
     use crate::*;
@@ -615,6 +620,64 @@ mod allocs_claims {
         h.check_state(&rt);
     }
 
+    #[test]
+    fn allocation_and_claim_lifecycle_emits_events() {
+        let (h, mut rt) = new_harness();
+        let alloc1 = make_alloc("1", CLIENT1, PROVIDER1, ALLOC_SIZE);
+        let alloc2 = make_alloc("2", CLIENT1, PROVIDER1, ALLOC_SIZE);
+        let id1 = h.create_alloc(&mut rt, &alloc1).unwrap();
+        let id2 = h.create_alloc(&mut rt, &alloc2).unwrap();
+
+        let reqs = vec![make_claim_req(id1, &alloc1, 1000, MINIMUM_VERIFIED_ALLOCATION_TERM)];
+        h.claim_allocations(&mut rt, PROVIDER1, reqs, ALLOC_SIZE, false).unwrap();
+
+        rt.set_epoch(alloc2.expiration);
+        h.remove_expired_allocations(&mut rt, CLIENT1, vec![], ALLOC_SIZE).unwrap();
+
+        let claim = h.load_claim(&mut rt, PROVIDER1, id1).unwrap();
+        rt.set_epoch(claim.term_start + claim.term_max);
+        h.remove_expired_claims(&mut rt, PROVIDER1, vec![]).unwrap();
+
+        let event = |typ: &str, id: u64| {
+            EventBuilder::new()
+                .typ(typ)
+                .field_indexed("id", &id)
+                .field_indexed("client", &CLIENT1)
+                .field_indexed("provider", &PROVIDER1)
+                .build()
+                .unwrap()
+        };
+        assert_eq!(
+            vec![
+                event("allocation-removed", id1),
+                event("claim", id1),
+                event("allocation-removed", id2),
+                event("claim-removed", id1),
+            ],
+            *rt.emitted_events.borrow()
+        );
+        h.check_state(&rt);
+    }
+
+    #[test]
+    fn state_write_rejected_in_read_only_mode() {
+        let (h, mut rt) = new_harness();
+        let alloc = make_alloc("1", CLIENT1, PROVIDER1, ALLOC_SIZE);
+        let id = h.create_alloc(&mut rt, &alloc).unwrap();
+        rt.set_epoch(alloc.expiration);
+
+        // A read-only invocation of a method that writes state is rejected without effect.
+        let params = RemoveExpiredAllocationsParams { client: CLIENT1, allocation_ids: vec![id] };
+        let res = rt.call_read_only::<Actor>(
+            Method::RemoveExpiredAllocationsExported as MethodNum,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        );
+
+        expect_abort_contains_message(EXIT_CODE_READ_ONLY, "read-only mode", res);
+        assert_eq!(Some(alloc), h.load_alloc(&mut rt, CLIENT1, id));
+        h.check_state(&rt);
+    }
+
     #[test]
     fn claim_allocs() {
         let (h, mut rt) = new_harness();
@@ -759,6 +822,22 @@ mod allocs_claims {
                 ret.batch_info.fail_codes
             );
         }
+        {
+            // Read-only
+            rt.expect_validate_caller_any();
+            let params = GetClaimsParams { provider: PROVIDER1, claim_ids: vec![id1] };
+            let ret: GetClaimsReturn = rt
+                .call_read_only::<Actor>(
+                    Method::GetClaimsExported as MethodNum,
+                    IpldBlock::serialize_cbor(&params).unwrap(),
+                )
+                .unwrap()
+                .unwrap()
+                .deserialize()
+                .unwrap();
+            rt.verify();
+            assert_eq!(vec![claim1], ret.claims);
+        }
         h.check_state(&rt);
     }
 
@@ -1448,3 +1527,26 @@ mod datacap {
         }
     }
 }
+
+mod addr_pair_key {
+    use fil_actor_verifreg::AddrPairKey;
+    use fil_actors_runtime::MapKey;
+    use fvm_shared::address::{Address, BLS_PUB_LEN};
+
+    #[test]
+    fn key_round_trips() {
+        let bls = Address::new_bls(&[7; BLS_PUB_LEN]).unwrap();
+        let secp = Address::new_secp256k1(&[3; 65]).unwrap();
+        for (first, second) in [
+            (*super::VERIFIER, *super::CLIENT),
+            (Address::new_id(u64::MAX), Address::new_id(0)),
+            (bls, *super::CLIENT),
+            (*super::VERIFIER, secp),
+        ] {
+            let bytes = AddrPairKey::new(first, second).to_key_bytes().unwrap();
+            let key = AddrPairKey::from_key_bytes(&bytes).unwrap();
+            assert_eq!((first, second), (key.first, key.second));
+        }
+        assert!(AddrPairKey::from_key_bytes(&super::VERIFIER.to_bytes()).is_err());
+    }
+}
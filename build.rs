@@ -1,5 +1,7 @@
 use fil_actor_bundler::Bundler;
 use fil_actors_runtime::runtime::builtins::Type;
+use fil_actors_runtime::runtime::policy::NETWORKS;
+use fil_actors_runtime::runtime::Policy;
 use num_traits::cast::FromPrimitive;
 use std::error::Error;
 use std::io::{BufRead, BufReader};
@@ -65,6 +67,20 @@ fn network_name() -> String {
     }.to_owned()
 }
 
+/// Validates the default policy of every network the actors can be built for.
+fn validate_policies() -> Result<(), Box<dyn Error>> {
+    for (network, _) in NETWORKS {
+        let policy = Policy::for_network(network).expect("network has no policy");
+        if let Err(violations) = policy.validate() {
+            for violation in violations {
+                println!("cargo:warning={}: {}", network, violation);
+            }
+            return Err(format!("invalid policy for network {}", network).into());
+        }
+    }
+    Ok(())
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
     // Cargo executable location.
     let cargo = std::env::var_os("CARGO").expect("no CARGO env var");
@@ -93,6 +109,9 @@ fn main() -> Result<(), Box<dyn Error>> {
     // Make sure we re-build if the network name changes.
     println!("cargo:rerun-if-env-changed={}", NETWORK_ENV);
 
+    // Refuse to bundle actors if the policy parameters of any network are inconsistent.
+    validate_policies()?;
+
     // Rerun if the source, dependencies, build options, build script _or_ actors have changed. We
     // need to check if the actors have changed because otherwise, when building in a workspace, we
     // won't re-run the build script and therefore won't re-compile them.
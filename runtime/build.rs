@@ -1,49 +1,4 @@
-static NETWORKS: &[(&str, &[&str])] = &[
-    ("mainnet", &["sector-32g", "sector-64g"]),
-    (
-        "caterpillarnet",
-        &[
-            "sector-512m",
-            "sector-32g",
-            "sector-64g",
-            "small-deals",
-            "short-precommit",
-            "min-power-2k",
-        ],
-    ),
-    ("butterflynet", &["sector-512m", "sector-32g", "sector-64g", "min-power-2g"]),
-    ("calibrationnet", &["sector-32g", "sector-64g", "min-power-32g"]),
-    ("devnet", &["sector-2k", "sector-8m", "small-deals", "short-precommit", "min-power-2k"]),
-    (
-        "testing",
-        &[
-            "sector-2k",
-            "sector-8m",
-            "sector-512m",
-            "sector-32g",
-            "sector-64g",
-            "small-deals",
-            "short-precommit",
-            "min-power-2k",
-            "no-provider-deal-collateral",
-        ],
-    ),
-    (
-        "testing-fake-proofs",
-        &[
-            "sector-2k",
-            "sector-8m",
-            "sector-512m",
-            "sector-32g",
-            "sector-64g",
-            "small-deals",
-            "short-precommit",
-            "min-power-2k",
-            "no-provider-deal-collateral",
-            "fake-proofs",
-        ],
-    ),
-];
+static NETWORKS: &[(&str, &[&str])] = include!("networks.rs");
 const NETWORK_ENV: &str = "BUILD_FIL_NETWORK";
 
 fn main() {
@@ -55,4 +10,5 @@ fn main() {
     for feature in features {
         println!("cargo:rustc-cfg=feature=\"{}\"", feature);
     }
+    println!("cargo:rustc-env=FIL_BUILD_NETWORK={}", network);
 }
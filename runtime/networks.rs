@@ -0,0 +1,48 @@
+// The networks for which the actors can be built, each with the features selecting its policy.
+// Shared by the build script, which enables the features, and `Policy::for_network`.
+&[
+    ("mainnet", &["sector-32g", "sector-64g"]),
+    (
+        "caterpillarnet",
+        &[
+            "sector-512m",
+            "sector-32g",
+            "sector-64g",
+            "small-deals",
+            "short-precommit",
+            "min-power-2k",
+        ],
+    ),
+    ("butterflynet", &["sector-512m", "sector-32g", "sector-64g", "min-power-2g"]),
+    ("calibrationnet", &["sector-32g", "sector-64g", "min-power-32g"]),
+    ("devnet", &["sector-2k", "sector-8m", "small-deals", "short-precommit", "min-power-2k"]),
+    (
+        "testing",
+        &[
+            "sector-2k",
+            "sector-8m",
+            "sector-512m",
+            "sector-32g",
+            "sector-64g",
+            "small-deals",
+            "short-precommit",
+            "min-power-2k",
+            "no-provider-deal-collateral",
+        ],
+    ),
+    (
+        "testing-fake-proofs",
+        &[
+            "sector-2k",
+            "sector-8m",
+            "sector-512m",
+            "sector-32g",
+            "sector-64g",
+            "small-deals",
+            "short-precommit",
+            "min-power-2k",
+            "no-provider-deal-collateral",
+            "fake-proofs",
+        ],
+    ),
+]
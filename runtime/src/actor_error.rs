@@ -2,53 +2,78 @@ use fvm_ipld_encoding::de::DeserializeOwned;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use std::fmt::Display;
 
+use fvm_shared::address::Address;
+use fvm_shared::deal::DealID;
 use fvm_shared::error::ExitCode;
+use fvm_shared::sector::SectorNumber;
+use fvm_shared::MethodNum;
 use thiserror::Error;
 
+/// The exit code for an attempt to mutate state, transfer value, or otherwise cause a
+/// side-effect during a read-only invocation.
+pub const EXIT_CODE_READ_ONLY: ExitCode = ExitCode::new(25);
+
 /// The error type returned by actor method calls.
-#[derive(Error, Debug, Clone, PartialEq, Eq)]
+///
+/// Besides the exit code and flat message recorded on chain, an error carries the chain of
+/// context added to it as it propagated, for precise logging and test assertions.
+/// Errors are equal if their exit codes and messages are equal, regardless of the context data.
+#[derive(Error, Debug, Clone)]
 #[error("ActorError(exit_code: {exit_code:?}, msg: {msg})")]
 pub struct ActorError {
     /// The exit code for this invocation.
     /// Codes less than `FIRST_USER_EXIT_CODE` are prohibited and will be overwritten by the VM.
     exit_code: ExitCode,
-    /// Message for debugging purposes,
+    /// Message for debugging purposes, prefixed by the message of each context frame.
     msg: String,
+    /// Context added to the error, outermost first.
+    context: Vec<ErrorContext>,
 }
 
+impl PartialEq for ActorError {
+    fn eq(&self, other: &Self) -> bool {
+        self.exit_code == other.exit_code && self.msg == other.msg
+    }
+}
+
+impl Eq for ActorError {}
+
 impl ActorError {
     /// Creates a new ActorError. This method does not check that the code is in the
     /// range of valid actor abort codes.
     pub fn unchecked(code: ExitCode, msg: String) -> Self {
-        Self { exit_code: code, msg }
+        Self { exit_code: code, msg, context: vec![] }
     }
 
     pub fn illegal_argument(msg: String) -> Self {
-        Self { exit_code: ExitCode::USR_ILLEGAL_ARGUMENT, msg }
+        Self::unchecked(ExitCode::USR_ILLEGAL_ARGUMENT, msg)
     }
     pub fn not_found(msg: String) -> Self {
-        Self { exit_code: ExitCode::USR_NOT_FOUND, msg }
+        Self::unchecked(ExitCode::USR_NOT_FOUND, msg)
     }
     pub fn forbidden(msg: String) -> Self {
-        Self { exit_code: ExitCode::USR_FORBIDDEN, msg }
+        Self::unchecked(ExitCode::USR_FORBIDDEN, msg)
     }
     pub fn insufficient_funds(msg: String) -> Self {
-        Self { exit_code: ExitCode::USR_INSUFFICIENT_FUNDS, msg }
+        Self::unchecked(ExitCode::USR_INSUFFICIENT_FUNDS, msg)
     }
     pub fn illegal_state(msg: String) -> Self {
-        Self { exit_code: ExitCode::USR_ILLEGAL_STATE, msg }
+        Self::unchecked(ExitCode::USR_ILLEGAL_STATE, msg)
     }
     pub fn serialization(msg: String) -> Self {
-        Self { exit_code: ExitCode::USR_SERIALIZATION, msg }
+        Self::unchecked(ExitCode::USR_SERIALIZATION, msg)
     }
     pub fn unhandled_message(msg: String) -> Self {
-        Self { exit_code: ExitCode::USR_UNHANDLED_MESSAGE, msg }
+        Self::unchecked(ExitCode::USR_UNHANDLED_MESSAGE, msg)
     }
     pub fn unspecified(msg: String) -> Self {
-        Self { exit_code: ExitCode::USR_UNSPECIFIED, msg }
+        Self::unchecked(ExitCode::USR_UNSPECIFIED, msg)
     }
     pub fn assertion_failed(msg: String) -> Self {
-        Self { exit_code: ExitCode::USR_ASSERTION_FAILED, msg }
+        Self::unchecked(ExitCode::USR_ASSERTION_FAILED, msg)
+    }
+    pub fn read_only(msg: String) -> Self {
+        Self::unchecked(EXIT_CODE_READ_ONLY, msg)
     }
 
     /// Returns the exit code of the error.
@@ -56,22 +81,82 @@ impl ActorError {
         self.exit_code
     }
 
-    /// Error message of the actor error.
+    /// Error message of the actor error, including its context.
     pub fn msg(&self) -> &str {
         &self.msg
     }
 
+    /// Error message of the actor error, without its context.
+    pub fn cause(&self) -> &str {
+        let prefix_len: usize = self.context.iter().map(|c| c.message.len() + 2).sum();
+        &self.msg[prefix_len..]
+    }
+
+    /// Context added to the error, outermost first.
+    pub fn context(&self) -> &[ErrorContext] {
+        &self.context
+    }
+
     /// Prefix error message with a string message.
-    pub fn wrap(mut self, msg: impl AsRef<str>) -> Self {
-        self.msg = format!("{}: {}", msg.as_ref(), self.msg);
+    pub fn wrap(self, msg: impl AsRef<str>) -> Self {
+        self.wrap_context(ErrorContext::new(msg.as_ref()))
+    }
+
+    /// Adds a frame of context to the error, prefixing the error message with its message.
+    pub fn wrap_context(mut self, context: ErrorContext) -> Self {
+        self.msg = format!("{}: {}", context.message, self.msg);
+        self.context.insert(0, context);
+        self
+    }
+}
+
+/// A frame of context added to an error, with optional structured data about the
+/// operation that failed, such as the actor, method, sector or deal concerned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorContext {
+    /// Message prefixed to the error message.
+    pub message: String,
+    /// Data about the operation, as key-value pairs.
+    pub data: Vec<(&'static str, String)>,
+}
+
+impl ErrorContext {
+    pub fn new(message: impl Display) -> Self {
+        Self { message: message.to_string(), data: vec![] }
+    }
+
+    /// Attaches a data item to the context.
+    pub fn with(mut self, key: &'static str, value: impl Display) -> Self {
+        self.data.push((key, value.to_string()));
         self
     }
+
+    pub fn actor(self, addr: &Address) -> Self {
+        self.with("actor", addr)
+    }
+
+    pub fn method(self, method: MethodNum) -> Self {
+        self.with("method", method)
+    }
+
+    pub fn sector(self, sector: SectorNumber) -> Self {
+        self.with("sector", sector)
+    }
+
+    pub fn deal(self, deal: DealID) -> Self {
+        self.with("deal", deal)
+    }
+
+    /// Returns the value of a data item, if present.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.data.iter().find(|(k, _)| *k == key).map(|(_, v)| v.as_str())
+    }
 }
 
 /// Converts a raw encoding error into an ErrSerialization.
 impl From<fvm_ipld_encoding::Error> for ActorError {
     fn from(e: fvm_ipld_encoding::Error) -> Self {
-        Self { exit_code: ExitCode::USR_SERIALIZATION, msg: e.to_string() }
+        Self::unchecked(ExitCode::USR_SERIALIZATION, e.to_string())
     }
 }
 
@@ -80,7 +165,7 @@ impl From<fvm_ipld_encoding::Error> for ActorError {
 #[cfg(feature = "fil-actor")]
 impl From<fvm_sdk::error::ActorDeleteError> for ActorError {
     fn from(e: fvm_sdk::error::ActorDeleteError) -> Self {
-        Self { exit_code: ExitCode::USR_ILLEGAL_ARGUMENT, msg: e.to_string() }
+        Self::unchecked(ExitCode::USR_ILLEGAL_ARGUMENT, e.to_string())
     }
 }
 
@@ -89,7 +174,7 @@ impl From<fvm_sdk::error::ActorDeleteError> for ActorError {
 #[cfg(feature = "fil-actor")]
 impl From<fvm_sdk::error::NoStateError> for ActorError {
     fn from(e: fvm_sdk::error::NoStateError) -> Self {
-        Self { exit_code: ExitCode::USR_ILLEGAL_STATE, msg: e.to_string() }
+        Self::unchecked(ExitCode::USR_ILLEGAL_STATE, e.to_string())
     }
 }
 
@@ -123,6 +208,11 @@ pub trait ActorContext<T> {
     where
         C: Display + 'static,
         F: FnOnce() -> C;
+
+    /// Adds a frame of context with structured data, computed only if there is an error.
+    fn with_context_data<F>(self, f: F) -> Result<T, ActorError>
+    where
+        F: FnOnce() -> ErrorContext;
 }
 
 impl<T> ActorContext<T> for Result<T, ActorError> {
@@ -130,10 +220,7 @@ impl<T> ActorContext<T> for Result<T, ActorError> {
     where
         C: Display + 'static,
     {
-        self.map_err(|mut err| {
-            err.msg = format!("{}: {}", context, err.msg);
-            err
-        })
+        self.map_err(|err| err.wrap_context(ErrorContext::new(context)))
     }
 
     fn with_context<C, F>(self, f: F) -> Result<T, ActorError>
@@ -141,10 +228,14 @@ impl<T> ActorContext<T> for Result<T, ActorError> {
         C: Display + 'static,
         F: FnOnce() -> C,
     {
-        self.map_err(|mut err| {
-            err.msg = format!("{}: {}", f(), err.msg);
-            err
-        })
+        self.map_err(|err| err.wrap_context(ErrorContext::new(f())))
+    }
+
+    fn with_context_data<F>(self, f: F) -> Result<T, ActorError>
+    where
+        F: FnOnce() -> ErrorContext,
+    {
+        self.map_err(|err| err.wrap_context(f()))
     }
 }
 
@@ -165,14 +256,16 @@ pub trait AsActorError<T>: Sized {
 // Note: E should be std::error::Error, revert to this after anyhow:Error is no longer used.
 impl<T, E: Display> AsActorError<T> for Result<T, E> {
     fn exit_code(self, code: ExitCode) -> Result<T, ActorError> {
-        self.map_err(|err| ActorError { exit_code: code, msg: err.to_string() })
+        self.map_err(|err| ActorError::unchecked(code, err.to_string()))
     }
 
     fn context_code<C>(self, code: ExitCode, context: C) -> Result<T, ActorError>
     where
         C: Display + 'static,
     {
-        self.map_err(|err| ActorError { exit_code: code, msg: format!("{}: {}", context, err) })
+        self.map_err(|err| {
+            ActorError::unchecked(code, err.to_string()).wrap_context(ErrorContext::new(context))
+        })
     }
 
     fn with_context_code<C, F>(self, code: ExitCode, f: F) -> Result<T, ActorError>
@@ -180,20 +273,22 @@ impl<T, E: Display> AsActorError<T> for Result<T, E> {
         C: Display + 'static,
         F: FnOnce() -> C,
     {
-        self.map_err(|err| ActorError { exit_code: code, msg: format!("{}: {}", f(), err) })
+        self.map_err(|err| {
+            ActorError::unchecked(code, err.to_string()).wrap_context(ErrorContext::new(f()))
+        })
     }
 }
 
 impl<T> AsActorError<T> for Option<T> {
     fn exit_code(self, code: ExitCode) -> Result<T, ActorError> {
-        self.ok_or_else(|| ActorError { exit_code: code, msg: "None".to_string() })
+        self.ok_or_else(|| ActorError::unchecked(code, "None".to_string()))
     }
 
     fn context_code<C>(self, code: ExitCode, context: C) -> Result<T, ActorError>
     where
         C: Display + 'static,
     {
-        self.ok_or_else(|| ActorError { exit_code: code, msg: context.to_string() })
+        self.ok_or_else(|| ActorError::unchecked(code, context.to_string()))
     }
 
     fn with_context_code<C, F>(self, code: ExitCode, f: F) -> Result<T, ActorError>
@@ -201,7 +296,7 @@ impl<T> AsActorError<T> for Option<T> {
         C: Display + 'static,
         F: FnOnce() -> C,
     {
-        self.ok_or_else(|| ActorError { exit_code: code, msg: f().to_string() })
+        self.ok_or_else(|| ActorError::unchecked(code, f().to_string()))
     }
 }
 
@@ -0,0 +1,28 @@
+// Copyright 2019-2022 ChainSafe Systems
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use fvm_ipld_encoding::serde_bytes;
+use fvm_ipld_encoding::tuple::*;
+use fvm_shared::address::Address;
+use fvm_shared::MethodNum;
+
+use crate::actor_client;
+
+pub const PUBKEY_ADDRESS_METHOD: MethodNum = 2;
+pub const AUTHENTICATE_MESSAGE_METHOD: MethodNum =
+    frc42_dispatch::method_hash!("AuthenticateMessage");
+
+#[derive(Debug, Serialize_tuple, Deserialize_tuple)]
+pub struct AuthenticateMessageParams {
+    #[serde(with = "serde_bytes")]
+    pub signature: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    pub message: Vec<u8>,
+}
+
+actor_client! {
+    /// Client for an account actor, whose address is passed to each method.
+    pub struct AccountClient;
+    fn pubkey_address() -> Address = PUBKEY_ADDRESS_METHOD;
+    fn authenticate_message(AuthenticateMessageParams) = AUTHENTICATE_MESSAGE_METHOD;
+}
@@ -0,0 +1,15 @@
+// Copyright 2019-2022 ChainSafe Systems
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use frc46_token::token::types::{TransferFromParams, TransferFromReturn};
+use fvm_shared::MethodNum;
+
+use crate::{actor_client, DATACAP_TOKEN_ACTOR_ADDR};
+
+pub const TRANSFER_FROM_METHOD: MethodNum = frc42_dispatch::method_hash!("TransferFrom");
+
+actor_client! {
+    /// Client for the singleton data cap token actor.
+    pub struct DatacapClient = DATACAP_TOKEN_ACTOR_ADDR;
+    fn transfer_from(TransferFromParams) -> TransferFromReturn = TRANSFER_FROM_METHOD;
+}
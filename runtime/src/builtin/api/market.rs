@@ -0,0 +1,110 @@
+// Copyright 2019-2022 ChainSafe Systems
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use cid::Cid;
+use fvm_ipld_encoding::tuple::*;
+use fvm_shared::bigint::{bigint_ser, BigInt};
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::deal::DealID;
+use fvm_shared::piece::PaddedPieceSize;
+use fvm_shared::sector::RegisteredSealProof;
+use fvm_shared::{ActorID, MethodNum};
+
+use super::verifreg::AllocationID;
+use crate::{actor_client, STORAGE_MARKET_ACTOR_ADDR};
+
+pub const VERIFY_DEALS_FOR_ACTIVATION_METHOD: MethodNum = 5;
+pub const ACTIVATE_DEALS_METHOD: MethodNum = 6;
+pub const ON_MINER_SECTORS_TERMINATE_METHOD: MethodNum = 7;
+
+// Changed since V2:
+// - Array of Sectors rather than just one
+// - Removed SectorStart
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct VerifyDealsForActivationParams {
+    pub sectors: Vec<SectorDeals>,
+}
+
+/// Borrowed form of `VerifyDealsForActivationParams`, with the same encoding.
+#[derive(Serialize_tuple)]
+pub struct VerifyDealsForActivationParamsRef<'a> {
+    pub sectors: &'a [SectorDeals],
+}
+
+impl<'a> From<&'a VerifyDealsForActivationParams> for VerifyDealsForActivationParamsRef<'a> {
+    fn from(params: &'a VerifyDealsForActivationParams) -> Self {
+        // Destructured so that a field added to the owned form must be added here too.
+        let VerifyDealsForActivationParams { sectors } = params;
+        Self { sectors }
+    }
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct SectorDeals {
+    pub sector_type: RegisteredSealProof,
+    pub sector_expiry: ChainEpoch,
+    pub deal_ids: Vec<DealID>,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq, Default)]
+pub struct VerifyDealsForActivationReturn {
+    pub sectors: Vec<SectorDealData>,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq, Default)]
+pub struct SectorDealData {
+    /// Option::None signifies commitment to empty sector, meaning no deals.
+    pub commd: Option<Cid>,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct ActivateDealsParams {
+    pub deal_ids: Vec<DealID>,
+    pub sector_expiry: ChainEpoch,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct VerifiedDealInfo {
+    pub client: ActorID,
+    pub allocation_id: AllocationID,
+    pub data: Cid,
+    pub size: PaddedPieceSize,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct ActivateDealsResult {
+    #[serde(with = "bigint_ser")]
+    pub nonverified_deal_space: BigInt,
+    pub verified_infos: Vec<VerifiedDealInfo>,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct OnMinerSectorsTerminateParams {
+    pub epoch: ChainEpoch,
+    pub deal_ids: Vec<DealID>,
+}
+
+/// Borrowed form of `OnMinerSectorsTerminateParams`, with the same encoding.
+#[derive(Serialize_tuple)]
+pub struct OnMinerSectorsTerminateParamsRef<'a> {
+    pub epoch: ChainEpoch,
+    pub deal_ids: &'a [DealID],
+}
+
+impl<'a> From<&'a OnMinerSectorsTerminateParams> for OnMinerSectorsTerminateParamsRef<'a> {
+    fn from(params: &'a OnMinerSectorsTerminateParams) -> Self {
+        // Destructured so that a field added to the owned form must be added here too.
+        let OnMinerSectorsTerminateParams { epoch, deal_ids } = params;
+        Self { epoch: *epoch, deal_ids }
+    }
+}
+
+actor_client! {
+    /// Client for the singleton storage market actor.
+    pub struct MarketClient = STORAGE_MARKET_ACTOR_ADDR;
+    fn verify_deals_for_activation(VerifyDealsForActivationParamsRef<'_>)
+        -> VerifyDealsForActivationReturn = VERIFY_DEALS_FOR_ACTIVATION_METHOD;
+    fn activate_deals(ActivateDealsParams) -> ActivateDealsResult = ACTIVATE_DEALS_METHOD;
+    fn on_miner_sectors_terminate(OnMinerSectorsTerminateParamsRef<'_>)
+        = ON_MINER_SECTORS_TERMINATE_METHOD;
+}
@@ -0,0 +1,23 @@
+// Copyright 2019-2022 ChainSafe Systems
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use fvm_ipld_encoding::tuple::*;
+use fvm_shared::address::Address;
+use fvm_shared::MethodNum;
+
+use crate::actor_client;
+
+pub const CONTROL_ADDRESSES_METHOD: MethodNum = 2;
+
+#[derive(Debug, Serialize_tuple, Deserialize_tuple)]
+pub struct GetControlAddressesReturn {
+    pub owner: Address,
+    pub worker: Address,
+    pub control_addresses: Vec<Address>,
+}
+
+actor_client! {
+    /// Client for a miner actor, whose address is passed to each method.
+    pub struct MinerClient;
+    fn control_addresses() -> GetControlAddressesReturn = CONTROL_ADDRESSES_METHOD;
+}
@@ -0,0 +1,17 @@
+// Copyright 2019-2022 ChainSafe Systems
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+//! Method numbers, parameter and return types, and clients for the methods which built-in
+//! actors call on each other.
+//!
+//! Each callee's dispatch table and each caller's client name the same definitions here, so a
+//! change to a method on one side which isn't matched on the other fails to compile.
+//! The callee actors re-export these types as part of their own API.
+
+pub mod account;
+pub mod datacap;
+pub mod market;
+pub mod miner;
+pub mod power;
+pub mod reward;
+pub mod verifreg;
@@ -0,0 +1,60 @@
+// Copyright 2019-2022 ChainSafe Systems
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use fvm_ipld_encoding::tuple::*;
+use fvm_ipld_encoding::RawBytes;
+use fvm_shared::bigint::bigint_ser;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::sector::{SealVerifyInfo, StoragePower};
+use fvm_shared::smooth::FilterEstimate;
+use fvm_shared::MethodNum;
+use serde::{Deserialize, Serialize};
+
+use crate::{actor_client, STORAGE_POWER_ACTOR_ADDR};
+
+pub const UPDATE_CLAIMED_POWER_METHOD: MethodNum = 3;
+pub const ENROLL_CRON_EVENT_METHOD: MethodNum = 4;
+pub const UPDATE_PLEDGE_TOTAL_METHOD: MethodNum = 6;
+pub const SUBMIT_POREP_FOR_BULK_VERIFY_METHOD: MethodNum = 8;
+pub const CURRENT_TOTAL_POWER_METHOD: MethodNum = 9;
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct UpdateClaimedPowerParams {
+    #[serde(with = "bigint_ser")]
+    pub raw_byte_delta: StoragePower,
+    #[serde(with = "bigint_ser")]
+    pub quality_adjusted_delta: StoragePower,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct EnrollCronEventParams {
+    pub event_epoch: ChainEpoch,
+    pub payload: RawBytes,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Default)]
+#[serde(transparent)]
+pub struct UpdatePledgeTotalParams {
+    pub pledge_delta: TokenAmount,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, PartialEq)]
+pub struct CurrentTotalPowerReturn {
+    #[serde(with = "bigint_ser")]
+    pub raw_byte_power: StoragePower,
+    #[serde(with = "bigint_ser")]
+    pub quality_adj_power: StoragePower,
+    pub pledge_collateral: TokenAmount,
+    pub quality_adj_power_smoothed: FilterEstimate,
+}
+
+actor_client! {
+    /// Client for the singleton storage power actor.
+    pub struct PowerClient = STORAGE_POWER_ACTOR_ADDR;
+    fn update_claimed_power(UpdateClaimedPowerParams) = UPDATE_CLAIMED_POWER_METHOD;
+    fn enroll_cron_event(EnrollCronEventParams) = ENROLL_CRON_EVENT_METHOD;
+    fn update_pledge_total(UpdatePledgeTotalParams) = UPDATE_PLEDGE_TOTAL_METHOD;
+    fn submit_porep_for_bulk_verify(SealVerifyInfo) = SUBMIT_POREP_FOR_BULK_VERIFY_METHOD;
+    fn current_total_power() -> CurrentTotalPowerReturn = CURRENT_TOTAL_POWER_METHOD;
+}
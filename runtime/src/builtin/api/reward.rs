@@ -0,0 +1,19 @@
+// Copyright 2019-2022 ChainSafe Systems
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use fvm_shared::bigint::bigint_ser::BigIntSer;
+use fvm_shared::reward::ThisEpochRewardReturn;
+use fvm_shared::MethodNum;
+
+use crate::{actor_client, REWARD_ACTOR_ADDR};
+
+pub const THIS_EPOCH_REWARD_METHOD: MethodNum = 3;
+pub const UPDATE_NETWORK_KPI_METHOD: MethodNum = 4;
+
+actor_client! {
+    /// Client for the singleton reward actor.
+    pub struct RewardClient = REWARD_ACTOR_ADDR;
+    fn this_epoch_reward() -> ThisEpochRewardReturn = THIS_EPOCH_REWARD_METHOD;
+    /// The reward actor reads the realized power as the `BigIntDe` counterpart of this parameter.
+    fn update_network_kpi(BigIntSer<'_>) = UPDATE_NETWORK_KPI_METHOD;
+}
@@ -0,0 +1,81 @@
+// Copyright 2019-2022 ChainSafe Systems
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use cid::Cid;
+use fvm_ipld_encoding::tuple::*;
+use fvm_shared::bigint::{bigint_ser, BigInt};
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::piece::PaddedPieceSize;
+use fvm_shared::sector::SectorNumber;
+use fvm_shared::{ActorID, MethodNum};
+
+use crate::{actor_client, BatchReturn, VERIFIED_REGISTRY_ACTOR_ADDR};
+
+pub const CLAIM_ALLOCATIONS_METHOD: MethodNum = 9;
+pub const GET_CLAIMS_METHOD: MethodNum = 10;
+
+pub type AllocationID = u64;
+pub type ClaimID = u64;
+
+#[derive(Serialize_tuple, Deserialize_tuple, Clone, Debug, PartialEq, Eq)]
+pub struct Claim {
+    // The provider storing the data (from allocation).
+    pub provider: ActorID,
+    // The client which allocated the DataCap (from allocation).
+    pub client: ActorID,
+    // Identifier of the data committed (from allocation).
+    pub data: Cid,
+    // The (padded) size of data (from allocation).
+    pub size: PaddedPieceSize,
+    // The min period after term_start which the provider must commit to storing data
+    pub term_min: ChainEpoch,
+    // The max period after term_start for which provider can earn QA-power for the data
+    pub term_max: ChainEpoch,
+    // The epoch at which the (first range of the) piece was committed.
+    pub term_start: ChainEpoch,
+    // ID of the provider's sector in which the data is committed.
+    pub sector: SectorNumber,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct SectorAllocationClaim {
+    pub client: ActorID,
+    pub allocation_id: AllocationID,
+    pub data: Cid,
+    pub size: PaddedPieceSize,
+    pub sector: SectorNumber,
+    pub sector_expiry: ChainEpoch,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct ClaimAllocationsParams {
+    pub sectors: Vec<SectorAllocationClaim>,
+    pub all_or_nothing: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct ClaimAllocationsReturn {
+    pub batch_info: BatchReturn,
+    #[serde(with = "bigint_ser")]
+    pub claimed_space: BigInt,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct GetClaimsParams {
+    pub provider: ActorID,
+    pub claim_ids: Vec<ClaimID>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct GetClaimsReturn {
+    pub batch_info: BatchReturn,
+    pub claims: Vec<Claim>,
+}
+
+actor_client! {
+    /// Client for the singleton verified registry actor.
+    pub struct VerifregClient = VERIFIED_REGISTRY_ACTOR_ADDR;
+    fn claim_allocations(ClaimAllocationsParams) -> ClaimAllocationsReturn
+        = CLAIM_ALLOCATIONS_METHOD;
+    fn get_claims(GetClaimsParams) -> GetClaimsReturn = GET_CLAIMS_METHOD;
+}
@@ -5,6 +5,7 @@ pub use self::network::*;
 pub use self::shared::*;
 pub use self::singletons::*;
 
+pub mod api;
 pub mod network;
 pub mod shared;
 pub mod singletons;
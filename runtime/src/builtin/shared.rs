@@ -2,16 +2,24 @@
 // SPDX-License-Identifier: Apache-2.0, MIT
 
 use crate::{actor_error, ActorContext, ActorError};
+use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_shared::address::Address;
+use fvm_shared::error::ExitCode;
 use fvm_shared::METHOD_SEND;
 use fvm_shared::{ActorID, MethodNum};
 
-use crate::runtime::Runtime;
+use crate::runtime::{Response, Runtime};
 
 pub const HAMT_BIT_WIDTH: u32 = 5;
 
 pub const FIRST_ACTOR_SPECIFIC_EXIT_CODE: u32 = 32;
 
+/// The gas limit forwarded with a send to a receiver chosen by a user rather than the protocol,
+/// such as the recipient of a value transfer or a data cap receiver hook.
+/// This bounds the work such a receiver can do at the expense of the sender, wherever the VM
+/// applies send gas limits (see `Runtime::send_generalized`).
+pub const USER_RECEIVER_GAS_LIMIT: u64 = 100_000_000;
+
 /// ResolveToActorID resolves the given address to its actor ID.
 /// If an actor ID for the given address doesn't exist yet, it tries to create one by sending
 /// a zero balance to the given address.
@@ -25,8 +33,12 @@ pub fn resolve_to_actor_id(
     }
 
     // send 0 balance to the account so an ID address for it is created and then try to resolve
-    rt.send(address, METHOD_SEND, Default::default(), Default::default())
-        .with_context(|| format!("failed to send zero balance to address {}", address))?;
+    extract_send_result(
+        address,
+        METHOD_SEND,
+        rt.send(address, METHOD_SEND, Default::default(), Default::default()),
+    )
+    .with_context(|| format!("failed to send zero balance to address {}", address))?;
 
     if let Some(id) = rt.resolve_address(address) {
         return Ok(id);
@@ -35,6 +47,45 @@ pub fn resolve_to_actor_id(
     Err(actor_error!(illegal_argument, "failed to resolve or initialize address {}", address))
 }
 
+/// Converts the result of a send to `to` invoking `method` into the return value of a
+/// successful invocation.
+/// A non-zero exit code from the callee becomes an error carrying that code, for call sites
+/// which have no better way to handle the callee's failure than to abort in turn.
+pub fn extract_send_result(
+    to: &Address,
+    method: MethodNum,
+    res: Result<Response, ActorError>,
+) -> Result<Option<IpldBlock>, ActorError> {
+    let ret = res?;
+    if ret.exit_code.is_success() {
+        return Ok(ret.return_data);
+    }
+    let mut msg = format!("send to {} method {} aborted with code {}", to, method, ret.exit_code);
+    if let Some(callee_msg) = ret.error_msg {
+        msg = format!("{}: {}", msg, callee_msg);
+    }
+    Err(callee_exit_error(ret.exit_code, msg))
+}
+
+/// Returns an error with which to abort after a callee exited with a non-zero code.
+pub fn callee_exit_error(callee_exit_code: ExitCode, msg: String) -> ActorError {
+    // The returned code can't be simply propagated as it may be a system exit code.
+    // TODO: improve propagation once we return a RuntimeError.
+    // Ref https://github.com/filecoin-project/builtin-actors/issues/144
+    let exit_code = match callee_exit_code {
+        // This means the called actor did something wrong. We can't "make up" a
+        // reasonable exit code.
+        ExitCode::SYS_MISSING_RETURN
+        | ExitCode::SYS_ILLEGAL_INSTRUCTION
+        | ExitCode::SYS_ILLEGAL_EXIT_CODE => ExitCode::USR_UNSPECIFIED,
+        // We don't expect any other system errors.
+        code if code.is_system_error() => ExitCode::USR_ASSERTION_FAILED,
+        // Otherwise, pass it through.
+        code => code,
+    };
+    ActorError::unchecked(exit_code, msg)
+}
+
 // The lowest FRC-42 method number.
 pub const FIRST_EXPORTED_METHOD_NUMBER: MethodNum = 1 << 24;
 
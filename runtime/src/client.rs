@@ -0,0 +1,96 @@
+/// Declare a typed client for the methods of another actor:
+///
+/// ```ignore
+/// actor_client! {
+///     /// Client for the singleton storage market actor.
+///     pub struct MarketClient = STORAGE_MARKET_ACTOR_ADDR;
+///     fn activate_deals(ActivateDealsParams) -> ActivateDealsResult = ACTIVATE_DEALS_METHOD;
+///     fn cron_tick() = CRON_TICK_METHOD;
+/// }
+///
+/// actor_client! {
+///     /// Client for a miner actor, whose address is passed to each method.
+///     pub struct MinerClient;
+///     fn control_addresses() -> GetControlAddressesReturn = CONTROL_ADDRESSES_METHOD;
+/// }
+///
+/// let ret = MarketClient::activate_deals(rt, &params)?;
+/// let addrs = MinerClient::control_addresses(rt, &miner)?;
+/// ```
+///
+/// Each method serializes its parameters, sends a message carrying no value and
+/// deserializes the return value. A non-zero exit code from the callee is returned as an
+/// error with that code, as by `extract_send_result`.
+/// Declaring the method number, parameter and return types of a method together in one place
+/// means a call site can't pair a method with the wrong types.
+/// Clients for the built-in actors are declared in `builtin::api`, with the same types the
+/// callee dispatches on.
+#[macro_export]
+macro_rules! actor_client {
+    (@ret) => { () };
+    (@ret $ret:ty) => { $ret };
+
+    (@params) => { None };
+    (@params $params:ident) => {
+        $crate::fvm_ipld_encoding::ipld_block::IpldBlock::serialize_cbor($params)?
+    };
+
+    (@decode $ret:ident) => {{
+        let _ = $ret;
+        Ok(())
+    }};
+    (@decode $ret:ident $ty:ty) => { $crate::deserialize_block($ret) };
+
+    (@send $rt:ident, $to:expr, $method:expr, [$($params:ident: $pty:ty)?], [$($ty:ty)?]) => {{
+        let ret = $crate::extract_send_result($to, $method, $rt.send(
+            $to,
+            $method,
+            $crate::actor_client!(@params $($params)?),
+            Default::default(),
+        ))?;
+        $crate::actor_client!(@decode ret $($ty)?)
+    }};
+
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $client:ident = $addr:expr;
+        $($(#[$fmeta:meta])* fn $name:ident($($params:ty)?) $(-> $ret:ty)? = $method:expr;)*
+    ) => {
+        $(#[$meta])*
+        $vis struct $client;
+
+        impl $client {
+            $(
+                $(#[$fmeta])*
+                pub fn $name(
+                    rt: &impl $crate::runtime::Runtime,
+                    $(params: &$params,)?
+                ) -> Result<$crate::actor_client!(@ret $($ret)?), $crate::ActorError> {
+                    $crate::actor_client!(@send rt, &$addr, $method, [$(params: $params)?], [$($ret)?])
+                }
+            )*
+        }
+    };
+
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $client:ident;
+        $($(#[$fmeta:meta])* fn $name:ident($($params:ty)?) $(-> $ret:ty)? = $method:expr;)*
+    ) => {
+        $(#[$meta])*
+        $vis struct $client;
+
+        impl $client {
+            $(
+                $(#[$fmeta])*
+                pub fn $name(
+                    rt: &impl $crate::runtime::Runtime,
+                    to: &$crate::fvm_shared::address::Address,
+                    $(params: &$params,)?
+                ) -> Result<$crate::actor_client!(@ret $($ret)?), $crate::ActorError> {
+                    $crate::actor_client!(@send rt, to, $method, [$(params: $params)?], [$($ret)?])
+                }
+            )*
+        }
+    };
+}
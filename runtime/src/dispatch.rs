@@ -14,17 +14,56 @@ use crate::ActorError;
 /// #[repr(u64)]
 /// enum Method {
 ///     Constructor = 1,
+///     GetBalance = 2,
+///     Transfer = 3,
 /// }
 /// impl ActorCode for Actor {
 ///     type Methods = Method;
 ///     actor_dispatch! {
-///         Constructor => constructor,
+///         Constructor => constructor(ConstructorParams) [caller: is(SYSTEM_ACTOR_ADDR)],
+///         GetBalance => get_balance(Address) -> TokenAmount [caller: any, read_only],
+///         Transfer => transfer(TransferParams) -> TransferReturn,
 ///     }
 /// }
 /// ```
+///
+/// Each method declares the type of its parameters, if it takes any, and its return type,
+/// if it returns a value. These must match the method's signature, and are recorded along with
+/// the method's name and number in `ActorCode::METHODS`.
+///
+/// Each method may be annotated with the callers permitted to invoke it, which is checked
+/// before the method runs:
+///
+/// - `caller: any` accepts any caller.
+/// - `caller: is(ADDR, ...)` accepts only the listed addresses, such as singleton actors.
+/// - `caller: types(Type::Miner, ...)` accepts only built-in actors of the listed types.
+/// - `caller: receiver` accepts only the receiving actor itself,
+///   i.e. a message the actor's signers have approved.
+///
+/// A method without a caller annotation must validate its caller itself,
+/// for example because the permitted callers depend on the actor's state.
+///
+/// A method annotated `read_only` may be invoked in read-only mode.
+/// Invoking any other method in read-only mode fails before the method runs.
+///
+/// The read-only check comes first, then the caller check, and both come before the parameters
+/// are deserialized. So a forbidden caller fails with `USR_FORBIDDEN` even if its parameters
+/// are malformed. The methods are only checked when invoked through dispatch, so an actor's
+/// methods should be private to it rather than callable directly.
 #[macro_export]
 macro_rules! actor_dispatch {
-    ($($method:ident => $func:ident,)*) => {
+    ($($method:ident => $func:ident $(($params:ty))? $(-> $ret:ty)? $([$($attr:tt)*])?,)*) => {
+        const METHODS: &'static [$crate::runtime::MethodMeta] = &[$(
+            $crate::runtime::MethodMeta {
+                name: stringify!($method),
+                number: Self::Methods::$method as MethodNum,
+                exported: Self::Methods::$method as MethodNum >= $crate::FIRST_EXPORTED_METHOD_NUMBER,
+                read_only: $crate::__dispatch_read_only!($($($attr)*)?),
+                params: $crate::__dispatch_type_name!($($params)?),
+                returns: $crate::__dispatch_type_name!($($ret)?),
+            },
+        )*];
+
         fn invoke_method<RT>(
             rt: &mut RT,
             method: MethodNum,
@@ -36,13 +75,79 @@ macro_rules! actor_dispatch {
         {
             restrict_internal_api(rt, method)?;
             match FromPrimitive::from_u64(method) {
-                $(Some(Self::Methods::$method) => $crate::dispatch(rt, Self::$func, &args),)*
+                $(Some(Self::Methods::$method) => {
+                    if rt.read_only() && !$crate::__dispatch_read_only!($($($attr)*)?) {
+                        return Err($crate::ActorError::read_only(format!(
+                            "method {} cannot be invoked in read-only mode",
+                            method
+                        )));
+                    }
+                    $crate::__dispatch_caller!(rt; $($($attr)*)?);
+                    let func: fn(&mut RT $(, $params)?) -> Result<$crate::__dispatch_ret!($($ret)?), ActorError> = Self::$func;
+                    $crate::dispatch(rt, func, &args)
+                })*
                 None => Err(actor_error!(unhandled_message; "invalid method: {}", method)),
             }
         }
     };
 }
 
+/// The type a method returns, which is `()` if not declared.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __dispatch_ret {
+    () => {
+        ()
+    };
+    ($ret:ty) => {
+        $ret
+    };
+}
+
+/// The name of a method's declared parameter or return type, if any.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __dispatch_type_name {
+    () => {
+        None
+    };
+    ($typ:ty) => {
+        Some(stringify!($typ))
+    };
+}
+
+/// Whether a method's dispatch annotations include `read_only`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __dispatch_read_only {
+    () => { false };
+    (read_only $($rest:tt)*) => { true };
+    ($other:tt $($rest:tt)*) => { $crate::__dispatch_read_only!($($rest)*) };
+}
+
+/// Validates the immediate caller against a method's `caller:` dispatch annotation, if any.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __dispatch_caller {
+    ($rt:ident;) => {};
+    ($rt:ident; caller: any $($rest:tt)*) => {
+        $rt.validate_immediate_caller_accept_any()?;
+    };
+    ($rt:ident; caller: is($($addr:expr),+ $(,)?) $($rest:tt)*) => {
+        $rt.validate_immediate_caller_is([$(&$addr),+])?;
+    };
+    ($rt:ident; caller: types($($typ:expr),+ $(,)?) $($rest:tt)*) => {
+        $rt.validate_immediate_caller_type([$(&$typ),+])?;
+    };
+    ($rt:ident; caller: receiver $($rest:tt)*) => {
+        let receiver = $rt.message().receiver();
+        $rt.validate_immediate_caller_is(std::iter::once(&receiver))?;
+    };
+    ($rt:ident; $other:tt $($rest:tt)*) => {
+        $crate::__dispatch_caller!($rt; $($rest)*)
+    };
+}
+
 pub trait Dispatch<'de, RT> {
     fn call(
         self,
@@ -12,7 +12,7 @@ pub use fvm_shared::BLOCKS_PER_EPOCH as EXPECTED_LEADERS_PER_EPOCH;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use unsigned_varint::decode::Error as UVarintError;
-pub use {fvm_ipld_amt, fvm_ipld_hamt};
+pub use {fvm_ipld_amt, fvm_ipld_encoding, fvm_ipld_hamt, fvm_shared};
 
 pub use self::actor_error::*;
 pub use self::builtin::*;
@@ -30,9 +30,12 @@ pub mod builtin;
 pub mod runtime;
 pub mod util;
 
+mod client;
 mod dispatch;
 pub use dispatch::dispatch;
 #[cfg(feature = "test_utils")]
+pub mod test_blockstores;
+#[cfg(feature = "test_utils")]
 pub mod test_utils;
 
 #[macro_export]
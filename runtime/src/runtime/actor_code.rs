@@ -7,9 +7,32 @@ use fvm_shared::MethodNum;
 
 use crate::{ActorError, Runtime};
 
+/// Describes a method exposed by an actor, for tools and client libraries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MethodMeta {
+    /// Name of the method in the actor's method enumeration.
+    pub name: &'static str,
+    /// Method number, which is an FRC-0042 hash for exported methods.
+    pub number: MethodNum,
+    /// Whether the method may be invoked by actors other than built-in actors.
+    pub exported: bool,
+    /// Whether the method may be invoked in read-only mode.
+    pub read_only: bool,
+    /// Name of the CBOR parameter type, if the method takes parameters.
+    pub params: Option<&'static str>,
+    /// Name of the CBOR return type, if the method returns a value.
+    pub returns: Option<&'static str>,
+}
+
 /// Interface for invoking methods on an Actor
 pub trait ActorCode {
     type Methods;
+    /// The methods the actor dispatches, in the order of its dispatch table.
+    const METHODS: &'static [MethodMeta];
+    /// Whether a method is declared read-only in the actor's dispatch table.
+    fn is_read_only_method(method: MethodNum) -> bool {
+        Self::METHODS.iter().any(|m| m.number == method && m.read_only)
+    }
     /// Invokes method with runtime on the actor's code. Method number will match one
     /// defined by the Actor, and parameters will be serialized and used in execution
     fn invoke_method<RT>(
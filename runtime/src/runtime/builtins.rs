@@ -23,6 +23,22 @@ pub enum Type {
 }
 
 impl Type {
+    /// Every builtin actor type, in order of their IDs.
+    pub const ALL: [Type; 12] = [
+        Type::System,
+        Type::Init,
+        Type::Cron,
+        Type::Account,
+        Type::Power,
+        Type::Miner,
+        Type::Market,
+        Type::PaymentChannel,
+        Type::Multisig,
+        Type::Reward,
+        Type::VerifiedRegistry,
+        Type::DataCap,
+    ];
+
     pub fn name(&self) -> &'static str {
         match *self {
             Type::System => "system",
@@ -21,7 +21,7 @@ use fvm_shared::sector::{
 };
 use fvm_shared::version::NetworkVersion;
 use fvm_shared::{ActorID, MethodNum};
-use num_traits::FromPrimitive;
+use num_traits::{FromPrimitive, Zero};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 #[cfg(feature = "fake-proofs")]
@@ -30,8 +30,8 @@ use sha2::{Digest, Sha256};
 use crate::runtime::actor_blockstore::ActorBlockstore;
 use crate::runtime::builtins::Type;
 use crate::runtime::{
-    ActorCode, ConsensusFault, DomainSeparationTag, MessageInfo, Policy, Primitives, RuntimePolicy,
-    Verifier,
+    ActorCode, ConsensusFault, DomainSeparationTag, MessageInfo, Policy, Primitives, Response,
+    RuntimePolicy, SendFlags, Verifier,
 };
 use crate::{actor_error, ActorError, Runtime};
 
@@ -45,7 +45,9 @@ pub struct FvmRuntime<B = ActorBlockstore> {
     in_transaction: bool,
     /// Indicates that the caller has been validated.
     caller_validated: bool,
-    /// The runtime policy
+    /// Indicates that the invocation may not cause side-effects.
+    read_only: bool,
+    /// The runtime policy, which is always the default policy of this build.
     policy: Policy,
 }
 
@@ -55,6 +57,7 @@ impl Default for FvmRuntime {
             blockstore: ActorBlockstore,
             in_transaction: false,
             caller_validated: false,
+            read_only: false,
             policy: Policy::default(),
         }
     }
@@ -231,6 +234,9 @@ where
     }
 
     fn create<T: Serialize>(&mut self, obj: &T) -> Result<(), ActorError> {
+        if self.read_only {
+            return Err(actor_error!(read_only; "cannot create state in read-only mode"));
+        }
         let root = fvm::sself::root()?;
         if root != EMPTY_ARR_CID {
             return Err(
@@ -256,6 +262,9 @@ where
         S: Serialize + DeserializeOwned,
         F: FnOnce(&mut S, &mut Self) -> Result<RT, ActorError>,
     {
+        if self.read_only {
+            return Err(actor_error!(read_only; "cannot mutate state in read-only mode"));
+        }
         let state_cid = fvm::sself::root()
             .map_err(|_| actor_error!(illegal_argument; "failed to get actor root state CID"))?;
 
@@ -285,38 +294,41 @@ where
         method: MethodNum,
         params: Option<IpldBlock>,
         value: TokenAmount,
-    ) -> Result<Option<IpldBlock>, ActorError> {
+    ) -> Result<Response, ActorError> {
+        self.send_generalized(to, method, params, value, None, SendFlags::empty())
+    }
+
+    fn send_generalized(
+        &self,
+        to: &Address,
+        method: MethodNum,
+        params: Option<IpldBlock>,
+        value: TokenAmount,
+        gas_limit: Option<u64>,
+        flags: SendFlags,
+    ) -> Result<Response, ActorError> {
         if self.in_transaction {
             return Err(actor_error!(assertion_failed; "send is not allowed during transaction"));
         }
+        // The v2 send syscall carries neither a gas limit nor flags. A limit can't be imposed on
+        // the callee, so a send requesting one fails rather than letting the callee consume all
+        // remaining gas. A read-only send is approximated by refusing to transfer value.
+        if let Some(limit) = gas_limit {
+            return Err(actor_error!(
+                assertion_failed;
+                "send to {} method {} with gas limit {} is not supported by this FVM",
+                to, method, limit
+            ));
+        }
+        if (self.read_only || flags.read_only()) && !value.is_zero() {
+            return Err(actor_error!(read_only; "cannot transfer value in a read-only send"));
+        }
         match fvm::send::send(to, method, params, value) {
-            Ok(ret) => {
-                if ret.exit_code.is_success() {
-                    Ok(ret.return_data)
-                } else {
-                    // The returned code can't be simply propagated as it may be a system exit code.
-                    // TODO: improve propagation once we return a RuntimeError.
-                    // Ref https://github.com/filecoin-project/builtin-actors/issues/144
-                    let exit_code = match ret.exit_code {
-                        // This means the called actor did something wrong. We can't "make up" a
-                        // reasonable exit code.
-                        ExitCode::SYS_MISSING_RETURN
-                        | ExitCode::SYS_ILLEGAL_INSTRUCTION
-                        | ExitCode::SYS_ILLEGAL_EXIT_CODE => ExitCode::USR_UNSPECIFIED,
-                        // We don't expect any other system errors.
-                        code if code.is_system_error() => ExitCode::USR_ASSERTION_FAILED,
-                        // Otherwise, pass it through.
-                        code => code,
-                    };
-                    Err(ActorError::unchecked(
-                        exit_code,
-                        format!(
-                            "send to {} method {} aborted with code {}",
-                            to, method, ret.exit_code
-                        ),
-                    ))
-                }
-            }
+            Ok(ret) => Ok(Response {
+                exit_code: ret.exit_code,
+                return_data: ret.return_data,
+                error_msg: None,
+            }),
             Err(err) => Err(match err {
                 // Some of these errors are from operations in the Runtime or SDK layer
                 // before or after the underlying VM send syscall.
@@ -354,6 +366,9 @@ where
                 actor_error!(assertion_failed; "create_actor is not allowed during transaction"),
             );
         }
+        if self.read_only {
+            return Err(actor_error!(read_only; "cannot create actor in read-only mode"));
+        }
         fvm::actor::create_actor(actor_id, &code_id).map_err(|e| match e {
             ErrorNumber::IllegalArgument => {
                 ActorError::illegal_argument("failed to create actor".into())
@@ -368,6 +383,9 @@ where
                 actor_error!(assertion_failed; "delete_actor is not allowed during transaction"),
             );
         }
+        if self.read_only {
+            return Err(actor_error!(read_only; "cannot delete actor in read-only mode"));
+        }
         Ok(fvm::sself::self_destruct(beneficiary)?)
     }
 
@@ -382,6 +400,21 @@ where
     fn base_fee(&self) -> TokenAmount {
         fvm::network::base_fee()
     }
+
+    fn read_only(&self) -> bool {
+        self.read_only
+    }
+
+    fn send_read_only(
+        &self,
+        to: &Address,
+        method: MethodNum,
+        params: Option<IpldBlock>,
+    ) -> Result<Response, ActorError> {
+        // Without a read-only send syscall the restriction can't be imposed on the callee. It
+        // holds only if the callee runs read-only anyway, being a method declared read-only.
+        self.send_generalized(to, method, params, TokenAmount::zero(), None, SendFlags::READ_ONLY)
+    }
 }
 
 impl<B> Primitives for FvmRuntime<B>
@@ -559,8 +592,9 @@ pub fn trampoline<C: ActorCode>(params: u32) -> u32 {
     let method = fvm::message::method_number();
     let params = fvm::message::params_raw(params).expect("params block invalid");
 
-    // Construct a new runtime.
-    let mut rt = FvmRuntime::default();
+    // Construct a new runtime. The FVM has no read-only invocations, so a method runs in
+    // read-only mode exactly when its dispatch table declares it read-only.
+    let mut rt = FvmRuntime { read_only: C::is_read_only_method(method), ..Default::default() };
     // Invoke the method, aborting if the actor returns an errored exit code.
     let ret = C::invoke_method(&mut rt, method, params)
         .unwrap_or_else(|err| fvm::vm::abort(err.exit_code().value(), Some(err.msg())));
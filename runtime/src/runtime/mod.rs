@@ -22,13 +22,17 @@ use serde::Serialize;
 pub use self::actor_code::*;
 pub use self::policy::*;
 pub use self::randomness::DomainSeparationTag;
+pub use self::send::*;
 use crate::runtime::builtins::Type;
 use crate::ActorError;
+#[cfg(not(feature = "fil-actor"))]
+use crate::ActorEvent;
 
 mod actor_code;
 pub mod builtins;
 pub mod policy;
 mod randomness;
+mod send;
 
 #[cfg(feature = "fil-actor")]
 mod actor_blockstore;
@@ -123,16 +127,34 @@ pub trait Runtime: Primitives + Verifier + RuntimePolicy {
     /// Sends a message to another actor, returning the exit code and return value envelope.
     /// If the invoked method does not return successfully, its state changes
     /// (and that of any messages it sent in turn) will be rolled back.
-    /// Note that the current return type cannot distinguish between a successful invocation
-    /// that returns an error code, and an error originating from the syscall prior to
-    /// invoking the target actor/method.
+    /// A callee that returns an error code yields a Response carrying that code, while an
+    /// error is returned only for a failure originating from the syscall prior to invoking
+    /// the target actor/method. See `extract_send_result` to treat both as an error.
     fn send(
         &self,
         to: &Address,
         method: MethodNum,
         params: Option<IpldBlock>,
         value: TokenAmount,
-    ) -> Result<Option<IpldBlock>, ActorError>;
+    ) -> Result<Response, ActorError>;
+
+    /// Sends a message to another actor with an optional limit on the gas made available to
+    /// the callee, and flags modifying the send.
+    /// Returns the callee's exit code and return value if the message was dispatched, whether
+    /// or not the callee succeeded. An error is returned only if the send itself failed,
+    /// in which case the callee was never invoked.
+    /// A callee exhausting the gas limit exits with SYS_OUT_OF_GAS, leaving the caller the rest
+    /// of its gas. The FVM send syscall this runtime targets takes no gas limit, so on chain
+    /// `FvmRuntime` fails a send with a gas limit rather than sending it without one.
+    fn send_generalized(
+        &self,
+        to: &Address,
+        method: MethodNum,
+        params: Option<IpldBlock>,
+        value: TokenAmount,
+        gas_limit: Option<u64>,
+        flags: SendFlags,
+    ) -> Result<Response, ActorError>;
 
     /// Computes an address for a new actor. The returned address is intended to uniquely refer to
     /// the actor even in the event of a chain re-org (whereas an ID-address might refer to a
@@ -172,6 +194,33 @@ pub trait Runtime: Primitives + Verifier + RuntimePolicy {
 
     /// Returns the gas base fee (cost per unit) for the current epoch.
     fn base_fee(&self) -> TokenAmount;
+
+    /// Emits an event denoting that something externally noteworthy has occurred.
+    ///
+    /// Not available to actors built for the FVM until it has a syscall to record events.
+    /// Actors emit through `EventBuilder::emit`, which works in both builds.
+    #[cfg(not(feature = "fil-actor"))]
+    fn emit_event(&self, event: &ActorEvent) -> Result<(), ActorError>;
+
+    /// Returns whether the current invocation is read-only. A read-only invocation may not
+    /// modify state, transfer value, create or delete actors, or emit events, and any
+    /// invocation it makes is read-only too.
+    ///
+    /// The FVM this runtime targets has no read-only invocations, so on chain a method runs in
+    /// read-only mode only if its dispatch table declares it read-only.
+    fn read_only(&self) -> bool;
+
+    /// Sends a message to another actor in read-only mode, carrying no value.
+    /// The callee (and anything it calls) is prevented from causing side-effects.
+    ///
+    /// On chain, this is enforced only if the callee is declared read-only in its dispatch table
+    /// (see `read_only`). Any other callee runs with the usual permissions.
+    fn send_read_only(
+        &self,
+        to: &Address,
+        method: MethodNum,
+        params: Option<IpldBlock>,
+    ) -> Result<Response, ActorError>;
 }
 
 /// Message information available to the actor about executing message.
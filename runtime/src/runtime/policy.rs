@@ -1,17 +1,20 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use fvm_shared::clock::ChainEpoch;
 use fvm_shared::sector::{RegisteredPoStProof, RegisteredSealProof, StoragePower};
 use num_traits::FromPrimitive;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 // A trait for runtime policy configuration
 pub trait RuntimePolicy {
     fn policy(&self) -> &Policy;
 }
 
-// The policy itself
+// The policy itself.
+// A serialized policy need only specify the parameters that differ from the default policy.
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct Policy {
     /// Maximum amount of sectors that can be aggregated.
     pub max_aggregated_sectors: u64,
@@ -253,6 +256,284 @@ impl Default for Policy {
     }
 }
 
+/// An error loading a policy from a serialized parameter set.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum PolicyError {
+    #[error("failed to decode policy: {0}")]
+    Decode(String),
+    #[error("invalid policy: {}", .0.iter().map(|v| v.to_string()).collect::<Vec<_>>().join("; "))]
+    Invalid(Vec<PolicyViolation>),
+}
+
+/// A relationship between policy parameters that a policy fails to satisfy.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("{message} ({})", .fields.join(", "))]
+pub struct PolicyViolation {
+    /// The parameters involved in the relationship.
+    pub fields: Vec<&'static str>,
+    pub message: String,
+}
+
+impl Policy {
+    /// Loads a policy from a CBOR-encoded map of parameters.
+    /// Parameters missing from the map take their default values.
+    pub fn from_cbor(bytes: &[u8]) -> Result<Policy, PolicyError> {
+        let policy: Policy =
+            fvm_ipld_encoding::from_slice(bytes).map_err(|e| PolicyError::Decode(e.to_string()))?;
+        policy.validate().map_err(PolicyError::Invalid)?;
+        Ok(policy)
+    }
+
+    /// Returns the default policy of a network, as the actors would use if built for it,
+    /// or `None` if the network is unknown.
+    ///
+    /// The default policy of this build is that of the network it was built for, but the
+    /// policies of the other networks are available to tools which check them all.
+    pub fn for_network(network_name: &str) -> Option<Policy> {
+        use policy_constants::*;
+
+        let features = NETWORKS.iter().find(|(name, _)| *name == network_name)?.1;
+        let has = |feature: &str| features.contains(&feature);
+        let sectors = SECTOR_FEATURES.iter().filter(|(feature, _, _)| has(feature));
+
+        let minimum_consensus_power = if has("min-power-2k") {
+            MINIMUM_CONSENSUS_POWER_2K
+        } else if has("min-power-2g") {
+            MINIMUM_CONSENSUS_POWER_2G
+        } else if has("min-power-32g") {
+            MINIMUM_CONSENSUS_POWER_32G
+        } else {
+            MINIMUM_CONSENSUS_POWER_10T
+        };
+        Some(Policy {
+            valid_post_proof_type: sectors.clone().map(|(_, post, _)| *post).collect(),
+            valid_pre_commit_proof_type: sectors.map(|(_, _, seal)| *seal).collect(),
+            pre_commit_challenge_delay: if has("short-precommit") {
+                SHORT_PRE_COMMIT_CHALLENGE_DELAY
+            } else {
+                LONG_PRE_COMMIT_CHALLENGE_DELAY
+            },
+            minimum_verified_allocation_size: StoragePower::from(if has("small-deals") {
+                SMALL_MINIMUM_VERIFIED_ALLOCATION_SIZE
+            } else {
+                LARGE_MINIMUM_VERIFIED_ALLOCATION_SIZE
+            }),
+            prov_collateral_percent_supply_num: if has("no-provider-deal-collateral") {
+                0
+            } else {
+                1
+            },
+            minimum_consensus_power: StoragePower::from(minimum_consensus_power),
+            ..Default::default()
+        })
+    }
+
+    /// Checks the relationships between parameters that the actors rely on,
+    /// returning every relationship the policy fails to satisfy.
+    pub fn validate(&self) -> Result<(), Vec<PolicyViolation>> {
+        let mut violations = Vec::new();
+        let mut check = |ok: bool, fields: &[&'static str], message: String| {
+            if !ok {
+                violations.push(PolicyViolation { fields: fields.to_vec(), message });
+            }
+        };
+
+        // Proving periods and deadlines.
+        check(
+            self.wpost_challenge_window > 0 && self.wpost_period_deadlines > 0,
+            &["wpost_challenge_window", "wpost_period_deadlines"],
+            "challenge window and number of deadlines must be positive".to_string(),
+        );
+        check(
+            self.wpost_proving_period
+                == self.wpost_challenge_window * self.wpost_period_deadlines as ChainEpoch,
+            &["wpost_proving_period", "wpost_challenge_window", "wpost_period_deadlines"],
+            format!(
+                "proving period {} is not {} deadlines of {} epochs",
+                self.wpost_proving_period, self.wpost_period_deadlines, self.wpost_challenge_window
+            ),
+        );
+        check(
+            self.fault_declaration_cutoff >= self.wpost_challenge_lookback,
+            &["fault_declaration_cutoff", "wpost_challenge_lookback"],
+            format!(
+                "fault declaration cutoff {} is less than challenge lookback {}",
+                self.fault_declaration_cutoff, self.wpost_challenge_lookback
+            ),
+        );
+        check(
+            self.wpost_dispute_window < self.wpost_proving_period,
+            &["wpost_dispute_window", "wpost_proving_period"],
+            format!(
+                "dispute window {} is not shorter than proving period {}",
+                self.wpost_dispute_window, self.wpost_proving_period
+            ),
+        );
+
+        // Batches and aggregates.
+        check(
+            0 < self.min_aggregated_sectors
+                && self.min_aggregated_sectors <= self.max_aggregated_sectors,
+            &["min_aggregated_sectors", "max_aggregated_sectors"],
+            format!(
+                "aggregated sectors range [{}, {}] is empty",
+                self.min_aggregated_sectors, self.max_aggregated_sectors
+            ),
+        );
+        check(
+            self.declarations_max <= self.addressed_partitions_max,
+            &["declarations_max", "addressed_partitions_max"],
+            format!(
+                "max declarations {} exceeds max addressed partitions {}",
+                self.declarations_max, self.addressed_partitions_max
+            ),
+        );
+
+        // Sector lifetimes.
+        check(
+            self.min_sector_expiration <= self.max_sector_expiration_extension,
+            &["min_sector_expiration", "max_sector_expiration_extension"],
+            format!(
+                "min sector expiration {} exceeds max expiration extension {}",
+                self.min_sector_expiration, self.max_sector_expiration_extension
+            ),
+        );
+        for proof in &self.valid_pre_commit_proof_type {
+            check(
+                proof
+                    .registered_window_post_proof()
+                    .map_or(false, |post| self.valid_post_proof_type.contains(&post)),
+                &["valid_pre_commit_proof_type", "valid_post_proof_type"],
+                format!("window PoSt proof for seal proof {:?} is not allowed", proof),
+            );
+        }
+
+        // Verified allocations and claims.
+        check(
+            self.minimum_verified_allocation_term <= self.maximum_verified_allocation_term,
+            &["minimum_verified_allocation_term", "maximum_verified_allocation_term"],
+            format!(
+                "minimum allocation term {} exceeds maximum allocation term {}",
+                self.minimum_verified_allocation_term, self.maximum_verified_allocation_term
+            ),
+        );
+        check(
+            self.minimum_verified_allocation_term <= self.max_sector_expiration_extension,
+            &["minimum_verified_allocation_term", "max_sector_expiration_extension"],
+            format!(
+                "minimum allocation term {} exceeds max sector expiration extension {}",
+                self.minimum_verified_allocation_term, self.max_sector_expiration_extension
+            ),
+        );
+        check(
+            self.end_of_life_claim_drop_period < self.minimum_verified_allocation_term,
+            &["end_of_life_claim_drop_period", "minimum_verified_allocation_term"],
+            format!(
+                "claim drop period {} is not shorter than minimum allocation term {}",
+                self.end_of_life_claim_drop_period, self.minimum_verified_allocation_term
+            ),
+        );
+
+        // Market.
+        check(
+            self.minimum_verified_allocation_term + self.market_default_allocation_term_buffer
+                <= self.maximum_verified_allocation_term,
+            &[
+                "minimum_verified_allocation_term",
+                "market_default_allocation_term_buffer",
+                "maximum_verified_allocation_term",
+            ],
+            format!(
+                "allocation term buffer {} leaves no valid term for a deal of the minimum term {}",
+                self.market_default_allocation_term_buffer, self.minimum_verified_allocation_term
+            ),
+        );
+        check(
+            self.deal_updates_interval > 0,
+            &["deal_updates_interval"],
+            "deal updates interval must be positive".to_string(),
+        );
+        check(
+            0 <= self.prov_collateral_percent_supply_num
+                && self.prov_collateral_percent_supply_num
+                    <= self.prov_collateral_percent_supply_denom,
+            &["prov_collateral_percent_supply_num", "prov_collateral_percent_supply_denom"],
+            format!(
+                "provider collateral fraction {}/{} is not between 0 and 1",
+                self.prov_collateral_percent_supply_num, self.prov_collateral_percent_supply_denom
+            ),
+        );
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+}
+
+/// The networks for which the actors can be built, by name,
+/// with the features that select each network's default policy.
+pub static NETWORKS: &[(&str, &[&str])] = include!("../../networks.rs");
+
+/// The proof types each sector size feature enables.
+const SECTOR_FEATURES: &[(&str, RegisteredPoStProof, RegisteredSealProof)] = &[
+    (
+        "sector-2k",
+        RegisteredPoStProof::StackedDRGWindow2KiBV1,
+        RegisteredSealProof::StackedDRG2KiBV1P1,
+    ),
+    (
+        "sector-8m",
+        RegisteredPoStProof::StackedDRGWindow8MiBV1,
+        RegisteredSealProof::StackedDRG8MiBV1P1,
+    ),
+    (
+        "sector-512m",
+        RegisteredPoStProof::StackedDRGWindow512MiBV1,
+        RegisteredSealProof::StackedDRG512MiBV1P1,
+    ),
+    (
+        "sector-32g",
+        RegisteredPoStProof::StackedDRGWindow32GiBV1,
+        RegisteredSealProof::StackedDRG32GiBV1P1,
+    ),
+    (
+        "sector-64g",
+        RegisteredPoStProof::StackedDRGWindow64GiBV1,
+        RegisteredSealProof::StackedDRG64GiBV1P1,
+    ),
+];
+
+/// Policies for networks whose parameters differ from the default policy, by network name,
+/// for tools which check or inspect state off chain.
+/// Networks without a loaded policy use the default policy.
+///
+/// Actors always use the default policy of the build they run in.
+#[derive(Debug, Default, Clone)]
+pub struct NetworkPolicies {
+    policies: HashMap<String, Policy>,
+}
+
+impl NetworkPolicies {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Loads the policy for a network from a CBOR-encoded map of parameters,
+    /// replacing any policy previously loaded for that network.
+    pub fn load(&mut self, network_name: &str, bytes: &[u8]) -> Result<(), PolicyError> {
+        let policy = Policy::from_cbor(bytes)?;
+        self.policies.insert(network_name.to_string(), policy);
+        Ok(())
+    }
+
+    /// Returns the policy for a network, or the default policy if none was loaded.
+    pub fn get(&self, network_name: &str) -> Policy {
+        self.policies.get(network_name).cloned().unwrap_or_default()
+    }
+}
+
 pub mod policy_constants {
     use fvm_shared::clock::ChainEpoch;
     use fvm_shared::clock::EPOCH_DURATION_SECONDS;
@@ -330,9 +611,13 @@ pub mod policy_constants {
     /// Number of epochs between publishing the precommit and when the challenge for interactive PoRep is drawn
     /// used to ensure it is not predictable by miner.
     #[cfg(not(feature = "short-precommit"))]
-    pub const PRE_COMMIT_CHALLENGE_DELAY: ChainEpoch = 150;
+    pub const PRE_COMMIT_CHALLENGE_DELAY: ChainEpoch = LONG_PRE_COMMIT_CHALLENGE_DELAY;
     #[cfg(feature = "short-precommit")]
-    pub const PRE_COMMIT_CHALLENGE_DELAY: ChainEpoch = 10;
+    pub const PRE_COMMIT_CHALLENGE_DELAY: ChainEpoch = SHORT_PRE_COMMIT_CHALLENGE_DELAY;
+    /// Pre-commit challenge delay of networks built without the `short-precommit` feature.
+    pub const LONG_PRE_COMMIT_CHALLENGE_DELAY: ChainEpoch = 150;
+    /// Pre-commit challenge delay of networks built with the `short-precommit` feature.
+    pub const SHORT_PRE_COMMIT_CHALLENGE_DELAY: ChainEpoch = 10;
 
     /// Lookback from the deadline's challenge window opening from which to sample chain randomness for the challenge seed.
 
@@ -380,9 +665,13 @@ pub mod policy_constants {
     pub const CHAIN_FINALITY: ChainEpoch = 900;
 
     #[cfg(not(feature = "small-deals"))]
-    pub const MINIMUM_VERIFIED_ALLOCATION_SIZE: i32 = 1 << 20;
+    pub const MINIMUM_VERIFIED_ALLOCATION_SIZE: i32 = LARGE_MINIMUM_VERIFIED_ALLOCATION_SIZE;
     #[cfg(feature = "small-deals")]
-    pub const MINIMUM_VERIFIED_ALLOCATION_SIZE: i32 = 256;
+    pub const MINIMUM_VERIFIED_ALLOCATION_SIZE: i32 = SMALL_MINIMUM_VERIFIED_ALLOCATION_SIZE;
+    /// Minimum verified allocation size of networks built without the `small-deals` feature.
+    pub const LARGE_MINIMUM_VERIFIED_ALLOCATION_SIZE: i32 = 1 << 20;
+    /// Minimum verified allocation size of networks built with the `small-deals` feature.
+    pub const SMALL_MINIMUM_VERIFIED_ALLOCATION_SIZE: i32 = 256;
     pub const MINIMUM_VERIFIED_ALLOCATION_TERM: i64 = 180 * EPOCHS_IN_DAY;
     pub const MAXIMUM_VERIFIED_ALLOCATION_TERM: i64 = 5 * EPOCHS_IN_YEAR;
     pub const MAXIMUM_VERIFIED_ALLOCATION_EXPIRATION: i64 = 60 * EPOCHS_IN_DAY;
@@ -405,15 +694,21 @@ pub mod policy_constants {
     pub const MARKET_DEFAULT_ALLOCATION_TERM_BUFFER: i64 = 90 * EPOCHS_IN_DAY;
 
     #[cfg(feature = "min-power-2k")]
-    pub const MINIMUM_CONSENSUS_POWER: i64 = 2 << 10;
+    pub const MINIMUM_CONSENSUS_POWER: i64 = MINIMUM_CONSENSUS_POWER_2K;
     #[cfg(feature = "min-power-2g")]
-    pub const MINIMUM_CONSENSUS_POWER: i64 = 2 << 30;
+    pub const MINIMUM_CONSENSUS_POWER: i64 = MINIMUM_CONSENSUS_POWER_2G;
     #[cfg(feature = "min-power-32g")]
-    pub const MINIMUM_CONSENSUS_POWER: i64 = 32 << 30;
+    pub const MINIMUM_CONSENSUS_POWER: i64 = MINIMUM_CONSENSUS_POWER_32G;
     #[cfg(not(any(
         feature = "min-power-2k",
         feature = "min-power-2g",
         feature = "min-power-32g"
     )))]
-    pub const MINIMUM_CONSENSUS_POWER: i64 = 10 << 40;
+    pub const MINIMUM_CONSENSUS_POWER: i64 = MINIMUM_CONSENSUS_POWER_10T;
+    /// Minimum consensus power of networks built with the `min-power-2k`, `min-power-2g` and
+    /// `min-power-32g` features, and of networks built with none of them.
+    pub const MINIMUM_CONSENSUS_POWER_2K: i64 = 2 << 10;
+    pub const MINIMUM_CONSENSUS_POWER_2G: i64 = 2 << 30;
+    pub const MINIMUM_CONSENSUS_POWER_32G: i64 = 32 << 30;
+    pub const MINIMUM_CONSENSUS_POWER_10T: i64 = 10 << 40;
 }
@@ -0,0 +1,51 @@
+// Copyright 2019-2022 ChainSafe Systems
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use std::ops::BitOr;
+
+use fvm_ipld_encoding::ipld_block::IpldBlock;
+use fvm_shared::error::ExitCode;
+
+/// Flags modifying the behaviour of a send.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SendFlags(u64);
+
+impl SendFlags {
+    /// Invoke the callee in read-only mode.
+    pub const READ_ONLY: SendFlags = SendFlags(0b1);
+
+    pub const fn empty() -> Self {
+        SendFlags(0)
+    }
+
+    pub const fn bits(self) -> u64 {
+        self.0
+    }
+
+    pub const fn contains(self, other: SendFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub const fn read_only(self) -> bool {
+        self.contains(Self::READ_ONLY)
+    }
+}
+
+impl BitOr for SendFlags {
+    type Output = SendFlags;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        SendFlags(self.0 | rhs.0)
+    }
+}
+
+/// The outcome of a message that was dispatched to its recipient.
+/// A non-zero exit code is the callee's own, and never an error in the send itself.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Response {
+    pub exit_code: ExitCode,
+    pub return_data: Option<IpldBlock>,
+    /// The callee's description of its failure, if the runtime has one.
+    /// The FVM doesn't return it to the caller, so it is only available off chain.
+    pub error_msg: Option<String>,
+}
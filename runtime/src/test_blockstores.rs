@@ -0,0 +1,138 @@
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::ops::{Add, Sub};
+use std::rc::Rc;
+use std::sync::Mutex;
+
+use anyhow::Result;
+use cid::Cid;
+use fvm_ipld_blockstore::Blockstore;
+
+/// Counts of the blocks read and written through a blockstore, and their sizes in bytes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BlockstoreStats {
+    /// Number of blocks read.
+    pub reads: usize,
+    /// Number of blocks written.
+    pub writes: usize,
+    /// Total bytes of blocks read.
+    pub bytes_read: usize,
+    /// Total bytes of blocks written.
+    pub bytes_written: usize,
+}
+
+impl Add for BlockstoreStats {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            reads: self.reads + rhs.reads,
+            writes: self.writes + rhs.writes,
+            bytes_read: self.bytes_read + rhs.bytes_read,
+            bytes_written: self.bytes_written + rhs.bytes_written,
+        }
+    }
+}
+
+/// Subtracting an earlier snapshot gives the operations performed since that snapshot.
+impl Sub for BlockstoreStats {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            reads: self.reads - rhs.reads,
+            writes: self.writes - rhs.writes,
+            bytes_read: self.bytes_read - rhs.bytes_read,
+            bytes_written: self.bytes_written - rhs.bytes_written,
+        }
+    }
+}
+
+/// A blockstore wrapper that records the number and size of blocks read and written.
+///
+/// Statistics accumulate from construction, so callers measure an operation by taking a snapshot
+/// before and after it, or by resetting the counts with `take_stats`.
+/// For example, a `MockRuntime<TrackingBlockstore<MemoryBlockstore>>` can measure a single call.
+/// Clones share their counts, so operations through an actor's cloned store are recorded too.
+#[derive(Clone, Debug, Default)]
+pub struct TrackingBlockstore<BS> {
+    base: BS,
+    stats: Rc<Cell<BlockstoreStats>>,
+}
+
+impl<BS> TrackingBlockstore<BS> {
+    pub fn new(base: BS) -> Self {
+        Self { base, stats: Rc::new(Cell::new(BlockstoreStats::default())) }
+    }
+
+    /// Returns the underlying blockstore.
+    pub fn base(&self) -> &BS {
+        &self.base
+    }
+
+    /// Returns the operations recorded so far.
+    pub fn stats(&self) -> BlockstoreStats {
+        self.stats.get()
+    }
+
+    /// Returns the operations recorded so far and resets the counts.
+    pub fn take_stats(&self) -> BlockstoreStats {
+        self.stats.take()
+    }
+
+    fn record(&self, f: impl FnOnce(&mut BlockstoreStats)) {
+        let mut stats = self.stats.get();
+        f(&mut stats);
+        self.stats.set(stats);
+    }
+}
+
+impl<BS: Blockstore> Blockstore for TrackingBlockstore<BS> {
+    fn get(&self, k: &Cid) -> Result<Option<Vec<u8>>> {
+        let block = self.base.get(k)?;
+        self.record(|s| {
+            s.reads += 1;
+            s.bytes_read += block.as_ref().map_or(0, Vec::len);
+        });
+        Ok(block)
+    }
+
+    fn put_keyed(&self, k: &Cid, block: &[u8]) -> Result<()> {
+        self.record(|s| {
+            s.writes += 1;
+            s.bytes_written += block.len();
+        });
+        self.base.put_keyed(k, block)
+    }
+
+    fn has(&self, k: &Cid) -> Result<bool> {
+        self.base.has(k)
+    }
+}
+
+/// A memory blockstore that can be shared between threads, e.g. to check a state tree in parallel.
+#[derive(Debug, Default)]
+pub struct SyncMemoryBlockstore {
+    blocks: Mutex<HashMap<Cid, Vec<u8>>>,
+}
+
+impl SyncMemoryBlockstore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Blockstore for SyncMemoryBlockstore {
+    fn get(&self, k: &Cid) -> Result<Option<Vec<u8>>> {
+        Ok(self.blocks.lock().unwrap().get(k).cloned())
+    }
+
+    fn put_keyed(&self, k: &Cid, block: &[u8]) -> Result<()> {
+        self.blocks.lock().unwrap().insert(*k, block.to_vec());
+        Ok(())
+    }
+
+    fn has(&self, k: &Cid) -> Result<bool> {
+        Ok(self.blocks.lock().unwrap().contains_key(k))
+    }
+}
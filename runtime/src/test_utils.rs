@@ -31,16 +31,17 @@ use fvm_shared::{ActorID, MethodNum};
 
 use multihash::derive::Multihash;
 use multihash::MultihashDigest;
+use num_traits::Zero;
 
 use rand::prelude::*;
 use serde::Serialize;
 
 use crate::runtime::builtins::Type;
 use crate::runtime::{
-    ActorCode, DomainSeparationTag, MessageInfo, Policy, Primitives, Runtime, RuntimePolicy,
-    Verifier,
+    ActorCode, DomainSeparationTag, MessageInfo, Policy, Primitives, Response, Runtime,
+    RuntimePolicy, SendFlags, Verifier,
 };
-use crate::{actor_error, ActorError};
+use crate::{actor_error, ActorError, ActorEvent};
 
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 
@@ -129,6 +130,7 @@ pub struct MockRuntime<BS = MemoryBlockstore> {
     pub in_call: bool,
     pub store: Rc<BS>,
     pub in_transaction: bool,
+    pub read_only: bool,
 
     // Expectations
     pub expectations: RefCell<Expectations>,
@@ -137,6 +139,9 @@ pub struct MockRuntime<BS = MemoryBlockstore> {
     pub policy: Policy,
 
     pub circulating_supply: TokenAmount,
+
+    // Every event emitted, in order, whether or not it was expected.
+    pub emitted_events: RefCell<Vec<ActorEvent>>,
 }
 
 #[derive(Default)]
@@ -158,6 +163,7 @@ pub struct Expectations {
     pub expect_aggregate_verify_seals: Option<ExpectAggregateVerifySeals>,
     pub expect_replica_verify: Option<ExpectReplicaVerify>,
     pub expect_gas_charge: VecDeque<i64>,
+    pub expect_emitted_events: VecDeque<ActorEvent>,
     skip_verification_on_drop: bool,
 }
 
@@ -254,6 +260,11 @@ impl Expectations {
             "expect_gas_charge {:?}, not received",
             this.expect_gas_charge
         );
+        assert!(
+            this.expect_emitted_events.is_empty(),
+            "expect_emitted_events {:?}, not received",
+            this.expect_emitted_events
+        );
     }
 }
 
@@ -283,9 +294,11 @@ impl<BS> MockRuntime<BS> {
             in_call: Default::default(),
             store: Rc::new(store),
             in_transaction: Default::default(),
+            read_only: false,
             expectations: Default::default(),
             policy: Default::default(),
             circulating_supply: Default::default(),
+            emitted_events: Default::default(),
         }
     }
 }
@@ -306,6 +319,8 @@ pub struct ExpectedMessage {
     // returns from applying expectedMessage
     pub send_return: Option<IpldBlock>,
     pub exit_code: ExitCode,
+    pub gas_limit: Option<u64>,
+    pub send_flags: SendFlags,
 }
 
 #[derive(Debug)]
@@ -403,6 +418,30 @@ pub fn expect_abort_contains_message<T: fmt::Debug>(
     );
 }
 
+/// Asserts that the result is an abort with the given exit code and that the error's context
+/// chain, outermost first, consists of exactly the given messages.
+pub fn expect_abort_context<T: fmt::Debug>(
+    expect_exit_code: ExitCode,
+    expect_context: &[&str],
+    res: Result<T, ActorError>,
+) -> ActorError {
+    let err = res.expect_err(&format!(
+        "expected abort with exit code {}, but call succeeded",
+        expect_exit_code
+    ));
+    assert_eq!(
+        err.exit_code(),
+        expect_exit_code,
+        "expected failure with exit code {}, but failed with exit code {}; error message: {}",
+        expect_exit_code,
+        err.exit_code(),
+        err.msg(),
+    );
+    let context: Vec<&str> = err.context().iter().map(|c| c.message.as_str()).collect();
+    assert_eq!(context, expect_context, "unexpected context for error: {}", err.msg());
+    err
+}
+
 pub fn expect_abort<T: fmt::Debug>(exit_code: ExitCode, res: Result<T, ActorError>) {
     expect_abort_contains_message(exit_code, "", res);
 }
@@ -474,6 +513,18 @@ impl<BS: Blockstore> MockRuntime<BS> {
         res
     }
 
+    /// Invokes a method as if it were called in read-only mode.
+    pub fn call_read_only<A: ActorCode>(
+        &mut self,
+        method_num: MethodNum,
+        params: Option<IpldBlock>,
+    ) -> Result<Option<IpldBlock>, ActorError> {
+        self.read_only = true;
+        let res = self.call::<A>(method_num, params);
+        self.read_only = false;
+        res
+    }
+
     /// Verifies that all mock expectations have been met (and resets the expectations).
     pub fn verify(&mut self) {
         self.expectations.borrow_mut().verify()
@@ -562,6 +613,54 @@ impl<BS: Blockstore> MockRuntime<BS> {
             value,
             send_return,
             exit_code,
+            gas_limit: None,
+            send_flags: SendFlags::empty(),
+        })
+    }
+
+    #[allow(dead_code)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn expect_send_generalized(
+        &mut self,
+        to: Address,
+        method: MethodNum,
+        params: Option<IpldBlock>,
+        value: TokenAmount,
+        gas_limit: Option<u64>,
+        send_flags: SendFlags,
+        send_return: Option<IpldBlock>,
+        exit_code: ExitCode,
+    ) {
+        self.expectations.borrow_mut().expect_sends.push_back(ExpectedMessage {
+            to,
+            method,
+            params,
+            value,
+            send_return,
+            exit_code,
+            gas_limit,
+            send_flags,
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn expect_send_read_only(
+        &mut self,
+        to: Address,
+        method: MethodNum,
+        params: Option<IpldBlock>,
+        send_return: Option<IpldBlock>,
+        exit_code: ExitCode,
+    ) {
+        self.expectations.borrow_mut().expect_sends.push_back(ExpectedMessage {
+            to,
+            method,
+            params,
+            value: TokenAmount::zero(),
+            send_return,
+            exit_code,
+            gas_limit: None,
+            send_flags: SendFlags::READ_ONLY,
         })
     }
 
@@ -658,8 +757,89 @@ impl<BS: Blockstore> MockRuntime<BS> {
         self.expectations.borrow_mut().expect_gas_charge.push_back(value);
     }
 
+    /// Expects the next emitted event to equal `event`.
+    /// Emitting an event which isn't expected fails the test. All events are recorded in
+    /// `emitted_events`.
+    #[allow(dead_code)]
+    pub fn expect_emitted_event(&mut self, event: ActorEvent) {
+        self.expectations.borrow_mut().expect_emitted_events.push_back(event);
+    }
+
+    /// Clears the expected events, e.g. after a call which was expected to emit them aborted.
+    #[allow(dead_code)]
+    pub fn clear_expected_events(&mut self) {
+        self.expectations.borrow_mut().expect_emitted_events.clear();
+    }
+
     ///// Private helpers /////
 
+    fn send_inner(
+        &self,
+        to: &Address,
+        method: MethodNum,
+        params: Option<IpldBlock>,
+        value: TokenAmount,
+        gas_limit: Option<u64>,
+        mut send_flags: SendFlags,
+    ) -> Result<Response, ActorError> {
+        self.require_in_call();
+        if self.in_transaction {
+            return Err(actor_error!(assertion_failed; "side-effect within transaction"));
+        }
+        // A send from a read-only invocation is itself read-only.
+        if self.read_only {
+            send_flags = send_flags | SendFlags::READ_ONLY;
+        }
+        if send_flags.read_only() && !value.is_zero() {
+            return Err(actor_error!(read_only; "cannot transfer value in read-only mode"));
+        }
+
+        assert!(
+            !self.expectations.borrow_mut().expect_sends.is_empty(),
+            "unexpected message to: {:?} method: {:?}, value: {:?}, params: {:?}",
+            to,
+            method,
+            value,
+            params
+        );
+
+        let expected_msg = self.expectations.borrow_mut().expect_sends.pop_front().unwrap();
+
+        assert!(
+            expected_msg.to == *to
+                && expected_msg.method == method
+                && expected_msg.params == params
+                && expected_msg.value == value,
+        );
+        assert_eq!(
+            expected_msg.gas_limit, gas_limit,
+            "expected gas limit {:?}, got {:?}",
+            expected_msg.gas_limit, gas_limit
+        );
+        assert_eq!(
+            expected_msg.send_flags, send_flags,
+            "expected send flags {:?}, got {:?}",
+            expected_msg.send_flags, send_flags
+        );
+
+        {
+            let mut balance = self.balance.borrow_mut();
+            if value > *balance {
+                return Err(ActorError::unchecked(
+                    ExitCode::SYS_SENDER_STATE_INVALID,
+                    format!("cannot send value: {:?} exceeds balance: {:?}", value, *balance),
+                ));
+            }
+            *balance -= value;
+        }
+
+        Ok(Response {
+            exit_code: expected_msg.exit_code,
+            return_data: expected_msg.send_return,
+            error_msg: None,
+        })
+    }
+
     fn require_in_call(&self) {
         assert!(self.in_call, "invalid runtime invocation outside of method call")
     }
@@ -872,6 +1052,9 @@ impl<BS: Blockstore> Runtime for MockRuntime<BS> {
     }
 
     fn create<T: Serialize>(&mut self, obj: &T) -> Result<(), ActorError> {
+        if self.read_only {
+            return Err(actor_error!(read_only; "cannot create state in read-only mode"));
+        }
         if self.state.is_some() {
             return Err(actor_error!(illegal_state; "state already constructed"));
         }
@@ -891,11 +1074,14 @@ impl<BS: Blockstore> Runtime for MockRuntime<BS> {
         if self.in_transaction {
             return Err(actor_error!(assertion_failed; "nested transaction"));
         }
-        let mut read_only = self.state()?;
+        if self.read_only {
+            return Err(actor_error!(read_only; "cannot mutate state in read-only mode"));
+        }
+        let mut st = self.state()?;
         self.in_transaction = true;
-        let ret = f(&mut read_only, self);
+        let ret = f(&mut st, self);
         if ret.is_ok() {
-            self.state = Some(self.store_put(&read_only));
+            self.state = Some(self.store_put(&st));
         }
         self.in_transaction = false;
         ret
@@ -911,45 +1097,29 @@ impl<BS: Blockstore> Runtime for MockRuntime<BS> {
         method: MethodNum,
         params: Option<IpldBlock>,
         value: TokenAmount,
-    ) -> Result<Option<IpldBlock>, ActorError> {
-        self.require_in_call();
-        if self.in_transaction {
-            return Err(actor_error!(assertion_failed; "side-effect within transaction"));
-        }
-
-        assert!(
-            !self.expectations.borrow_mut().expect_sends.is_empty(),
-            "unexpected message to: {:?} method: {:?}, value: {:?}, params: {:?}",
-            to,
-            method,
-            value,
-            params
-        );
-
-        let expected_msg = self.expectations.borrow_mut().expect_sends.pop_front().unwrap();
-
-        assert!(
-            expected_msg.to == *to
-                && expected_msg.method == method
-                && expected_msg.params == params
-                && expected_msg.value == value,
-        );
+    ) -> Result<Response, ActorError> {
+        self.send_inner(to, method, params, value, None, SendFlags::empty())
+    }
 
-        {
-            let mut balance = self.balance.borrow_mut();
-            if value > *balance {
-                return Err(ActorError::unchecked(
-                    ExitCode::SYS_SENDER_STATE_INVALID,
-                    format!("cannot send value: {:?} exceeds balance: {:?}", value, *balance),
-                ));
-            }
-            *balance -= value;
-        }
+    fn send_read_only(
+        &self,
+        to: &Address,
+        method: MethodNum,
+        params: Option<IpldBlock>,
+    ) -> Result<Response, ActorError> {
+        self.send_inner(to, method, params, TokenAmount::zero(), None, SendFlags::READ_ONLY)
+    }
 
-        match expected_msg.exit_code {
-            ExitCode::OK => Ok(expected_msg.send_return),
-            x => Err(ActorError::unchecked(x, "Expected message Fail".to_string())),
-        }
+    fn send_generalized(
+        &self,
+        to: &Address,
+        method: MethodNum,
+        params: Option<IpldBlock>,
+        value: TokenAmount,
+        gas_limit: Option<u64>,
+        flags: SendFlags,
+    ) -> Result<Response, ActorError> {
+        self.send_inner(to, method, params, value, gas_limit, flags)
     }
 
     fn new_actor_address(&mut self) -> Result<Address, ActorError> {
@@ -964,6 +1134,9 @@ impl<BS: Blockstore> Runtime for MockRuntime<BS> {
         if self.in_transaction {
             return Err(actor_error!(assertion_failed; "side-effect within transaction"));
         }
+        if self.read_only {
+            return Err(actor_error!(read_only; "cannot create actor in read-only mode"));
+        }
         let expect_create_actor = self
             .expectations
             .borrow_mut()
@@ -980,6 +1153,9 @@ impl<BS: Blockstore> Runtime for MockRuntime<BS> {
         if self.in_transaction {
             return Err(actor_error!(assertion_failed; "side-effect within transaction"));
         }
+        if self.read_only {
+            return Err(actor_error!(read_only; "cannot delete actor in read-only mode"));
+        }
         let exp_act = self.expectations.borrow_mut().expect_delete_actor.take();
         if exp_act.is_none() {
             panic!("unexpected call to delete actor: {}", addr);
@@ -1018,6 +1194,22 @@ impl<BS: Blockstore> Runtime for MockRuntime<BS> {
     fn base_fee(&self) -> TokenAmount {
         self.base_fee.clone()
     }
+
+    fn emit_event(&self, event: &ActorEvent) -> Result<(), ActorError> {
+        self.require_in_call();
+        if self.read_only {
+            return Err(actor_error!(read_only; "cannot emit events in read-only mode"));
+        }
+        let expected = self.expectations.borrow_mut().expect_emitted_events.pop_front();
+        let expected = expected.unwrap_or_else(|| panic!("unexpected event {:?}", event));
+        assert_eq!(&expected, event, "expected event {:?}, got {:?}", expected, event);
+        self.emitted_events.borrow_mut().push(event.clone());
+        Ok(())
+    }
+
+    fn read_only(&self) -> bool {
+        self.read_only
+    }
 }
 
 impl<BS> Primitives for MockRuntime<BS> {
@@ -1281,6 +1473,21 @@ pub fn make_sealed_cid(input: &[u8]) -> Cid {
     make_cid_poseidon(input, FIL_COMMITMENT_SEALED)
 }
 
+/// Asserts that a value of a type an actor declares in its `ext` module for another actor's
+/// method decodes as the callee's own type `C`, which encodes back to the same bytes.
+/// Giving each field a distinct value catches fields declared in a different order.
+pub fn assert_same_encoding<C: Serialize + DeserializeOwned>(value: &impl Serialize) {
+    let bytes = fvm_ipld_encoding::to_vec(value).unwrap();
+    let decoded: C = fvm_ipld_encoding::from_slice(&bytes)
+        .unwrap_or_else(|e| panic!("failed to decode as {}: {}", std::any::type_name::<C>(), e));
+    assert_eq!(
+        bytes,
+        fvm_ipld_encoding::to_vec(&decoded).unwrap(),
+        "{} encodes differently",
+        std::any::type_name::<C>()
+    );
+}
+
 pub fn new_bls_addr(s: u8) -> Address {
     let seed = [s; 32];
     let mut rng: StdRng = SeedableRng::from_seed(seed);
@@ -0,0 +1,167 @@
+// Copyright 2019-2022 ChainSafe Systems
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+
+use cid::Cid;
+use fvm_ipld_blockstore::Blockstore;
+use fvm_ipld_hamt::Error;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::Set;
+use crate::{Array, BytesKey, Map};
+
+/// A collection that is stored inside another collection by its root CID.
+pub trait InnerCollection {
+    /// Flushes the collection's contents to the store, returning the new root.
+    fn flush_root(&mut self) -> Result<Cid, Error>;
+    /// Returns whether the collection has no entries.
+    fn is_empty_collection(&self) -> bool;
+}
+
+impl<'a, BS, V> InnerCollection for Map<'a, BS, V>
+where
+    BS: Blockstore,
+    V: Serialize + DeserializeOwned,
+{
+    fn flush_root(&mut self) -> Result<Cid, Error> {
+        self.flush()
+    }
+
+    fn is_empty_collection(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+impl<'a, BS: Blockstore> InnerCollection for Set<'a, BS> {
+    fn flush_root(&mut self) -> Result<Cid, Error> {
+        self.root()
+    }
+
+    fn is_empty_collection(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+impl<'a, BS, V> InnerCollection for Array<'a, V, BS>
+where
+    BS: Blockstore,
+    V: Serialize + DeserializeOwned,
+{
+    fn flush_root(&mut self) -> Result<Cid, Error> {
+        Ok(self.flush().map_err(|e| anyhow::anyhow!(e))?)
+    }
+
+    fn is_empty_collection(&self) -> bool {
+        self.count() == 0
+    }
+}
+
+struct CacheEntry<T> {
+    value: T,
+    dirty: bool,
+}
+
+/// Write-back cache of the inner collections of a nested collection (a HAMT of HAMTs or AMTs).
+///
+/// Inner collections are loaded at most once and modified in memory. Each entry tracks whether it
+/// has been modified, and only modified entries are flushed and written back to the outer map,
+/// so an inner collection that is updated many times is written to the store once.
+pub struct WriteBackCache<T> {
+    entries: BTreeMap<Vec<u8>, CacheEntry<T>>,
+    // Whether inner collections left empty are deleted from the outer map on flush,
+    // rather than written as an empty root.
+    prune_empty: bool,
+}
+
+impl<T: InnerCollection> WriteBackCache<T> {
+    /// Creates a cache that writes back empty inner collections as empty roots.
+    pub fn new() -> Self {
+        Self { entries: BTreeMap::new(), prune_empty: false }
+    }
+
+    /// Creates a cache that deletes empty inner collections from the outer map on flush.
+    pub fn pruning() -> Self {
+        Self { entries: BTreeMap::new(), prune_empty: true }
+    }
+
+    /// Returns the cached collection for a key, if it has been loaded.
+    pub fn get(&self, key: &[u8]) -> Option<&T> {
+        self.entries.get(key).map(|e| &e.value)
+    }
+
+    /// Returns whether a collection for a key is cached.
+    pub fn contains_key(&self, key: &[u8]) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// Returns the collection for a key, loading it into the cache on a miss.
+    /// The entry is not marked as modified.
+    pub fn load<F>(&mut self, key: &[u8], load: F) -> Result<&T, Error>
+    where
+        F: FnOnce() -> Result<T, Error>,
+    {
+        Ok(&self.entry(key, load)?.value)
+    }
+
+    /// Returns the collection for a key for modification, loading it into the cache on a miss.
+    /// The entry is marked as modified and will be written back on the next flush.
+    pub fn load_mut<F>(&mut self, key: &[u8], load: F) -> Result<&mut T, Error>
+    where
+        F: FnOnce() -> Result<T, Error>,
+    {
+        let entry = self.entry(key, load)?;
+        entry.dirty = true;
+        Ok(&mut entry.value)
+    }
+
+    /// Drops the cached collection for a key, discarding any unflushed modifications.
+    /// Returns the dropped collection, if it was cached.
+    pub fn remove(&mut self, key: &[u8]) -> Option<T> {
+        self.entries.remove(key).map(|e| e.value)
+    }
+
+    /// Iterates over cached collections in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (&[u8], &T)> {
+        self.entries.iter().map(|(k, e)| (k.as_slice(), &e.value))
+    }
+
+    /// Returns the number of cached collections that have been modified since the last flush.
+    pub fn dirty_count(&self) -> usize {
+        self.entries.values().filter(|e| e.dirty).count()
+    }
+
+    /// Flushes each modified collection and writes its root back to the outer map.
+    /// Unmodified collections remain cached and are not re-flushed.
+    /// The outer map itself is not flushed.
+    pub fn flush<BS: Blockstore>(&mut self, outer: &mut Map<BS, Cid>) -> Result<(), Error> {
+        for (k, entry) in self.entries.iter_mut().filter(|(_, e)| e.dirty) {
+            if self.prune_empty && entry.value.is_empty_collection() {
+                outer.delete(k.as_slice())?;
+            } else {
+                let root = entry.value.flush_root()?;
+                outer.set(BytesKey(k.clone()), root)?;
+            }
+            entry.dirty = false;
+        }
+        Ok(())
+    }
+
+    fn entry<F>(&mut self, key: &[u8], load: F) -> Result<&mut CacheEntry<T>, Error>
+    where
+        F: FnOnce() -> Result<T, Error>,
+    {
+        match self.entries.entry(key.to_vec()) {
+            Entry::Occupied(e) => Ok(e.into_mut()),
+            Entry::Vacant(e) => Ok(e.insert(CacheEntry { value: load()?, dirty: false })),
+        }
+    }
+}
+
+impl<T: InnerCollection> Default for WriteBackCache<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -23,9 +23,7 @@ impl ActorDowncast for anyhow::Error {
     fn downcast_default(self, default_exit_code: ExitCode, msg: impl AsRef<str>) -> ActorError {
         match downcast_util(self) {
             Ok(actor_error) => actor_error.wrap(msg),
-            Err(other) => {
-                ActorError::unchecked(default_exit_code, format!("{}: {}", msg.as_ref(), other))
-            }
+            Err(other) => ActorError::unchecked(default_exit_code, other.to_string()).wrap(msg),
         }
     }
     fn downcast_wrap(self, msg: impl AsRef<str>) -> anyhow::Error {
@@ -40,9 +38,7 @@ impl ActorDowncast for AmtError {
     fn downcast_default(self, default_exit_code: ExitCode, msg: impl AsRef<str>) -> ActorError {
         match self {
             AmtError::Dynamic(e) => e.downcast_default(default_exit_code, msg),
-            other => {
-                ActorError::unchecked(default_exit_code, format!("{}: {}", msg.as_ref(), other))
-            }
+            other => ActorError::unchecked(default_exit_code, other.to_string()).wrap(msg),
         }
     }
     fn downcast_wrap(self, msg: impl AsRef<str>) -> anyhow::Error {
@@ -57,9 +53,7 @@ impl ActorDowncast for HamtError {
     fn downcast_default(self, default_exit_code: ExitCode, msg: impl AsRef<str>) -> ActorError {
         match self {
             HamtError::Dynamic(e) => e.downcast_default(default_exit_code, msg),
-            other => {
-                ActorError::unchecked(default_exit_code, format!("{}: {}", msg.as_ref(), other))
-            }
+            other => ActorError::unchecked(default_exit_code, other.to_string()).wrap(msg),
         }
     }
     fn downcast_wrap(self, msg: impl AsRef<str>) -> anyhow::Error {
@@ -0,0 +1,107 @@
+use fvm_ipld_encoding::tuple::*;
+use fvm_ipld_encoding::RawBytes;
+use serde::Serialize;
+
+use crate::runtime::Runtime;
+use crate::ActorError;
+
+/// Index the key of this entry.
+pub const FLAG_INDEXED_KEY: u64 = 0b01;
+/// Index the value of this entry.
+pub const FLAG_INDEXED_VALUE: u64 = 0b10;
+/// Index both the key and the value of this entry.
+pub const FLAG_INDEXED_ALL: u64 = FLAG_INDEXED_KEY | FLAG_INDEXED_VALUE;
+
+/// The key of the entry naming the type of an event emitted by a built-in actor.
+pub const EVENT_TYPE_KEY: &str = "$type";
+
+/// An event emitted by an actor, made up of an ordered list of key/value entries.
+#[derive(Serialize_tuple, Deserialize_tuple, Clone, Debug, PartialEq, Eq, Default)]
+pub struct ActorEvent {
+    pub entries: Vec<EventEntry>,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Clone, Debug, PartialEq, Eq)]
+pub struct EventEntry {
+    /// Indexing hints for the entry (a combination of the FLAG_INDEXED_* values).
+    pub flags: u64,
+    pub key: String,
+    /// CBOR-encoded value.
+    pub value: RawBytes,
+}
+
+impl ActorEvent {
+    /// Returns the value of the `$type` entry, if present and a string.
+    pub fn event_type(&self) -> Option<String> {
+        self.entries
+            .iter()
+            .find(|e| e.key == EVENT_TYPE_KEY)
+            .and_then(|e| e.value.deserialize::<String>().ok())
+    }
+}
+
+/// Builds an ActorEvent from typed values.
+/// Encoding errors are deferred until `build` so that fields can be chained.
+#[derive(Default)]
+pub struct EventBuilder {
+    entries: Vec<EventEntry>,
+    error: Option<ActorError>,
+}
+
+impl EventBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the event type, which is always indexed.
+    pub fn typ(self, typ: &str) -> Self {
+        self.push(FLAG_INDEXED_ALL, EVENT_TYPE_KEY, &typ)
+    }
+
+    /// Appends an entry whose key and value are indexed.
+    pub fn field_indexed<T: Serialize + ?Sized>(self, key: &str, value: &T) -> Self {
+        self.push(FLAG_INDEXED_ALL, key, value)
+    }
+
+    /// Appends an entry which is not indexed.
+    pub fn field<T: Serialize + ?Sized>(self, key: &str, value: &T) -> Self {
+        self.push(0, key, value)
+    }
+
+    pub fn build(self) -> Result<ActorEvent, ActorError> {
+        match self.error {
+            Some(e) => Err(e),
+            None => Ok(ActorEvent { entries: self.entries }),
+        }
+    }
+
+    /// Builds the event and emits it through the runtime.
+    ///
+    /// The FVM has no syscall to record events yet, so actors built for it only build the
+    /// event, failing the same way as elsewhere if it can't be encoded, and record nothing.
+    #[cfg(not(feature = "fil-actor"))]
+    pub fn emit(self, rt: &impl Runtime) -> Result<(), ActorError> {
+        rt.emit_event(&self.build()?)
+    }
+
+    #[cfg(feature = "fil-actor")]
+    pub fn emit(self, _rt: &impl Runtime) -> Result<(), ActorError> {
+        self.build().map(|_| ())
+    }
+
+    fn push<T: Serialize + ?Sized>(mut self, flags: u64, key: &str, value: &T) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+        match RawBytes::serialize(value) {
+            Ok(value) => self.entries.push(EventEntry { flags, key: key.to_string(), value }),
+            Err(e) => {
+                self.error = Some(ActorError::serialization(format!(
+                    "failed to serialize event field {}: {}",
+                    key, e
+                )))
+            }
+        }
+        self
+    }
+}
@@ -0,0 +1,238 @@
+use crate::{actor_error, ActorDowncast, ActorError, AsActorError, Map};
+use cid::Cid;
+use fvm_ipld_blockstore::Blockstore;
+use fvm_ipld_hamt::BytesKey;
+use fvm_shared::address::Address;
+use fvm_shared::error::ExitCode;
+use fvm_shared::HAMT_BIT_WIDTH;
+use integer_encoding::VarInt;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+/// Type-level configuration of a HAMT's shape.
+/// A map's bit-width is part of its type, so a map can't be loaded with a different
+/// bit-width from the one it was written with.
+pub trait MapConfig {
+    const BIT_WIDTH: u32;
+}
+
+/// Configuration for maps with the default HAMT bit-width.
+#[derive(Debug)]
+pub struct DefaultMapConfig;
+
+impl MapConfig for DefaultMapConfig {
+    const BIT_WIDTH: u32 = HAMT_BIT_WIDTH;
+}
+
+/// A key type that can be encoded as, and decoded from, a HAMT key.
+pub trait MapKey: Sized + Debug {
+    fn from_key_bytes(b: &[u8]) -> Result<Self, String>;
+    fn to_key_bytes(&self) -> Result<Vec<u8>, String>;
+}
+
+/// A HAMT with typed keys and a fixed bit-width.
+/// Errors from the underlying HAMT are returned as `ActorError`s naming the collection.
+pub struct Map2<'bs, BS, K, V, C = DefaultMapConfig>
+where
+    BS: Blockstore,
+    K: MapKey,
+    V: DeserializeOwned + Serialize,
+    C: MapConfig,
+{
+    hamt: Map<'bs, BS, V>,
+    name: &'static str,
+    key_type: PhantomData<(K, C)>,
+}
+
+impl<'bs, BS, K, V, C> Map2<'bs, BS, K, V, C>
+where
+    BS: Blockstore,
+    K: MapKey,
+    V: DeserializeOwned + Serialize,
+    C: MapConfig,
+{
+    /// Creates a new, empty map.
+    pub fn empty(store: &'bs BS, name: &'static str) -> Self {
+        Self { hamt: Map::new_with_bit_width(store, C::BIT_WIDTH), name, key_type: PhantomData }
+    }
+
+    /// Creates a new empty map and flushes it to the store.
+    /// Returns the CID of the empty map root.
+    pub fn flush_empty(store: &'bs BS) -> Result<Cid, ActorError> {
+        Self::empty(store, "empty").flush()
+    }
+
+    /// Loads a map from the store.
+    pub fn load(store: &'bs BS, root: &Cid, name: &'static str) -> Result<Self, ActorError> {
+        Ok(Self {
+            hamt: Map::load_with_bit_width(root, store, C::BIT_WIDTH)
+                .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                    format!("failed to load HAMT '{}'", name)
+                })?,
+            name,
+            key_type: PhantomData,
+        })
+    }
+
+    /// Flushes the map's contents to the store.
+    /// Returns the root node CID.
+    pub fn flush(&mut self) -> Result<Cid, ActorError> {
+        self.hamt.flush().with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+            format!("failed to flush HAMT '{}'", self.name)
+        })
+    }
+
+    /// Returns whether the map is empty.
+    pub fn is_empty(&self) -> bool {
+        self.hamt.is_empty()
+    }
+
+    /// Returns a reference to the value associated with a key, if present.
+    pub fn get(&self, key: &K) -> Result<Option<&V>, ActorError> {
+        let k = self.key(key)?;
+        self.hamt.get(&k).with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+            format!("failed to get key {:?} from HAMT '{}'", key, self.name)
+        })
+    }
+
+    /// Returns whether the map contains a key.
+    pub fn contains_key(&self, key: &K) -> Result<bool, ActorError> {
+        let k = self.key(key)?;
+        self.hamt.contains_key(&k).with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+            format!("failed to check key {:?} in HAMT '{}'", key, self.name)
+        })
+    }
+
+    /// Inserts a key-value pair into the map.
+    /// Returns any value previously associated with the key.
+    pub fn set(&mut self, key: &K, value: V) -> Result<Option<V>, ActorError>
+    where
+        V: PartialEq,
+    {
+        let k = self.key(key)?;
+        self.hamt.set(k, value).with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+            format!("failed to set key {:?} in HAMT '{}'", key, self.name)
+        })
+    }
+
+    /// Inserts a key-value pair only if the key isn't already present.
+    /// Returns whether the value was inserted.
+    pub fn set_if_absent(&mut self, key: &K, value: V) -> Result<bool, ActorError>
+    where
+        V: PartialEq,
+    {
+        let k = self.key(key)?;
+        self.hamt.set_if_absent(k, value).with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+            format!("failed to set key {:?} in HAMT '{}'", key, self.name)
+        })
+    }
+
+    /// Removes a key from the map.
+    /// Returns the value that was associated with the key, if present.
+    pub fn delete(&mut self, key: &K) -> Result<Option<V>, ActorError> {
+        let k = self.key(key)?;
+        self.hamt
+            .delete(&k)
+            .map(|deleted| deleted.map(|(_, v)| v))
+            .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                format!("failed to delete key {:?} from HAMT '{}'", key, self.name)
+            })
+    }
+
+    /// Iterates over all key-value pairs in the map, decoding each key.
+    /// Stops at, and returns, the first error returned by the callback.
+    pub fn for_each<F>(&self, mut f: F) -> Result<(), ActorError>
+    where
+        F: FnMut(&K, &V) -> Result<(), ActorError>,
+    {
+        self.hamt
+            .for_each(|k, v| {
+                let key = K::from_key_bytes(&k.0).map_err(|e| {
+                    actor_error!(illegal_state, "invalid key in HAMT '{}': {}", self.name, e)
+                })?;
+                f(&key, v).map_err(anyhow::Error::from)
+            })
+            .map_err(|e| {
+                e.downcast_default(
+                    ExitCode::USR_ILLEGAL_STATE,
+                    format!("failed to iterate over HAMT '{}'", self.name),
+                )
+            })
+    }
+
+    fn key(&self, key: &K) -> Result<BytesKey, ActorError> {
+        key.to_key_bytes().map(BytesKey).map_err(|e| {
+            actor_error!(illegal_argument, "invalid key {:?} for HAMT '{}': {}", key, self.name, e)
+        })
+    }
+}
+
+impl MapKey for Vec<u8> {
+    fn from_key_bytes(b: &[u8]) -> Result<Self, String> {
+        Ok(b.to_vec())
+    }
+
+    fn to_key_bytes(&self) -> Result<Vec<u8>, String> {
+        Ok(self.clone())
+    }
+}
+
+impl MapKey for String {
+    fn from_key_bytes(b: &[u8]) -> Result<Self, String> {
+        String::from_utf8(b.to_vec()).map_err(|e| e.to_string())
+    }
+
+    fn to_key_bytes(&self) -> Result<Vec<u8>, String> {
+        Ok(self.as_bytes().to_vec())
+    }
+}
+
+/// Unsigned integers are encoded as varints, matching `u64_key`.
+impl MapKey for u64 {
+    fn from_key_bytes(b: &[u8]) -> Result<Self, String> {
+        match u64::decode_var(b) {
+            Some((v, n)) if n == b.len() => Ok(v),
+            _ => Err(format!("failed to decode u64 key from {:?}", b)),
+        }
+    }
+
+    fn to_key_bytes(&self) -> Result<Vec<u8>, String> {
+        Ok(self.encode_var_vec())
+    }
+}
+
+/// Signed integers are encoded as zig-zag varints, matching the encoding of epoch keys.
+impl MapKey for i64 {
+    fn from_key_bytes(b: &[u8]) -> Result<Self, String> {
+        match i64::decode_var(b) {
+            Some((v, n)) if n == b.len() => Ok(v),
+            _ => Err(format!("failed to decode i64 key from {:?}", b)),
+        }
+    }
+
+    fn to_key_bytes(&self) -> Result<Vec<u8>, String> {
+        Ok(self.encode_var_vec())
+    }
+}
+
+impl MapKey for Address {
+    fn from_key_bytes(b: &[u8]) -> Result<Self, String> {
+        Address::from_bytes(b).map_err(|e| e.to_string())
+    }
+
+    fn to_key_bytes(&self) -> Result<Vec<u8>, String> {
+        Ok(self.to_bytes())
+    }
+}
+
+impl MapKey for Cid {
+    fn from_key_bytes(b: &[u8]) -> Result<Self, String> {
+        Cid::try_from(b).map_err(|e| e.to_string())
+    }
+
+    fn to_key_bytes(&self) -> Result<Vec<u8>, String> {
+        Ok(self.to_bytes())
+    }
+}
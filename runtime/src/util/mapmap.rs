@@ -1,22 +1,21 @@
-use crate::{make_empty_map, make_map_with_root_and_bitwidth, Keyer, Map};
+use crate::{make_empty_map, make_map_with_root_and_bitwidth, Keyer, Map, WriteBackCache};
 use cid::Cid;
 use fvm_ipld_blockstore::Blockstore;
 use fvm_ipld_hamt::{BytesKey, Error};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use serde::__private::PhantomData;
-use std::collections::btree_map::Entry::{Occupied, Vacant};
-use std::collections::BTreeMap;
 
 // MapMap stores multiple values per key in a Hamt of Hamts
 // Every element stored has a primary and secondary key
 pub struct MapMap<'a, BS, V, K1, K2> {
     outer: Map<'a, BS, Cid>,
     inner_bitwidth: u32,
-    // cache all inner maps loaded since last load/flush
+    // cache all inner maps loaded since last load
     // get/put/remove operations load the inner map into the cache first and modify in memory
-    // flush writes all inner maps in the cache to the outer map before flushing the outer map
-    cache: BTreeMap<Vec<u8>, Map<'a, BS, V>>,
+    // flush writes only the inner maps modified since the last flush to the outer map,
+    // before flushing the outer map
+    cache: WriteBackCache<Map<'a, BS, V>>,
     key_types: PhantomData<(K1, K2)>,
 }
 impl<'a, BS, V, K1, K2> MapMap<'a, BS, V, K1, K2>
@@ -30,7 +29,7 @@ where
         MapMap {
             outer: make_empty_map(bs, outer_bitwidth),
             inner_bitwidth,
-            cache: BTreeMap::<Vec<u8>, Map<BS, V>>::new(),
+            cache: WriteBackCache::pruning(),
             key_types: PhantomData,
         }
     }
@@ -44,61 +43,32 @@ where
         Ok(MapMap {
             outer: make_map_with_root_and_bitwidth(cid, bs, outer_bitwidth)?,
             inner_bitwidth,
-            cache: BTreeMap::<Vec<u8>, Map<BS, V>>::new(),
+            cache: WriteBackCache::pruning(),
             key_types: PhantomData,
         })
     }
 
     pub fn flush(&mut self) -> Result<Cid, Error> {
-        for (k, in_map) in self.cache.iter_mut() {
-            if in_map.is_empty() {
-                self.outer.delete(&BytesKey(k.to_vec()))?;
-            } else {
-                let new_in_root = in_map.flush()?;
-                self.outer.set(BytesKey(k.to_vec()), new_in_root)?;
-            }
-        }
+        // empty inner maps are deleted from the outer map
+        self.cache.flush(&mut self.outer)?;
         self.outer.flush()
     }
 
-    // load inner map while memoizing
-    // 1. ensure inner map is loaded into cache
-    // 2. return (inner map is empty, inner map)
-    fn load_inner_map(&mut self, k: K1) -> Result<(bool, &mut Map<'a, BS, V>), Error> {
-        let in_map_thunk = || -> Result<(bool, Map<BS, V>), Error> {
-            // lazy to avoid ipld operations in case of cache hit
-            match self.outer.get(&k.key())? {
-                // flush semantics guarantee all written inner maps are non empty
-                Some(root) => Ok((
-                    false,
-                    make_map_with_root_and_bitwidth::<BS, V>(
-                        root,
-                        *self.outer.store(),
-                        self.inner_bitwidth,
-                    )?,
-                )),
-                None => Ok((true, make_empty_map(*self.outer.store(), self.inner_bitwidth))),
-            }
-        };
-        let raw_k = k.key().0;
-        match self.cache.entry(raw_k) {
-            Occupied(entry) => {
-                let in_map = entry.into_mut();
-                // cached map could be empty
-                Ok((in_map.is_empty(), in_map))
-            }
-            Vacant(entry) => {
-                let (empty, in_map) = in_map_thunk()?;
-                Ok((empty, entry.insert(in_map)))
-            }
-        }
+    // load inner map for reading while memoizing
+    fn load_inner_map(&mut self, k: &BytesKey) -> Result<&Map<'a, BS, V>, Error> {
+        let (outer, inner_bitwidth) = (&self.outer, self.inner_bitwidth);
+        // lazy to avoid ipld operations in case of cache hit
+        self.cache.load(k, || load_inner(outer, k, inner_bitwidth))
+    }
+
+    // load inner map for modification while memoizing, marking it to be written back on flush
+    fn load_inner_map_mut(&mut self, k: &BytesKey) -> Result<&mut Map<'a, BS, V>, Error> {
+        let (outer, inner_bitwidth) = (&self.outer, self.inner_bitwidth);
+        self.cache.load_mut(k, || load_inner(outer, k, inner_bitwidth))
     }
 
     pub fn get(&mut self, outside_k: K1, inside_k: K2) -> Result<Option<&V>, Error> {
-        let (is_empty, in_map) = self.load_inner_map(outside_k)?;
-        if is_empty {
-            return Ok(None);
-        }
+        let in_map = self.load_inner_map(&outside_k.key())?;
         in_map.get(&inside_k.key())
     }
 
@@ -107,8 +77,8 @@ where
     where
         F: FnMut(&BytesKey, &V) -> anyhow::Result<()>,
     {
-        let (is_empty, in_map) = self.load_inner_map(outside_k)?;
-        if is_empty {
+        let in_map = self.load_inner_map(&outside_k.key())?;
+        if in_map.is_empty() {
             return Ok(());
         }
         in_map.for_each(f)
@@ -117,7 +87,7 @@ where
     // Puts a key value pair in the MapMap, overwriting any existing value.
     // Returns the previous value, if any.
     pub fn put(&mut self, outside_k: K1, inside_k: K2, value: V) -> Result<Option<V>, Error> {
-        let in_map = self.load_inner_map(outside_k)?.1;
+        let in_map = self.load_inner_map_mut(&outside_k.key())?;
         // defer flushing cached inner map until flush call
         in_map.set(inside_k.key(), value)
     }
@@ -125,7 +95,7 @@ where
     // Puts a key value pair in the MapMap if it is not already set.  Returns true
     // if key is newly set, false if it was already set.
     pub fn put_if_absent(&mut self, outside_k: K1, inside_k: K2, value: V) -> Result<bool, Error> {
-        let in_map = self.load_inner_map(outside_k)?.1;
+        let in_map = self.load_inner_map_mut(&outside_k.key())?;
 
         // defer flushing cached inner map until flush call
         in_map.set_if_absent(inside_k.key(), value)
@@ -137,7 +107,7 @@ where
     where
         I: Iterator<Item = (K2, V)>,
     {
-        let in_map = self.load_inner_map(outside_k)?.1;
+        let in_map = self.load_inner_map_mut(&outside_k.key())?;
         for (k, v) in values {
             in_map.set(k.key(), v)?;
         }
@@ -148,12 +118,30 @@ where
     /// Removes a key from the MapMap, returning the value at the key if the key
     /// was previously set.
     pub fn remove(&mut self, outside_k: K1, inside_k: K2) -> Result<Option<V>, Error> {
-        let (is_empty, in_map) = self.load_inner_map(outside_k)?;
-        if is_empty {
+        let k = outside_k.key();
+        if self.load_inner_map(&k)?.is_empty() {
             return Ok(None);
         }
+        let in_map = self.load_inner_map_mut(&k)?;
         in_map
             .delete(&inside_k.key())
             .map(|o: Option<(BytesKey, V)>| -> Option<V> { o.map(|p: (BytesKey, V)| -> V { p.1 }) })
     }
 }
+
+// loads the inner map stored under a key, or an empty map if there is none
+fn load_inner<'a, BS, V>(
+    outer: &Map<'a, BS, Cid>,
+    k: &BytesKey,
+    inner_bitwidth: u32,
+) -> Result<Map<'a, BS, V>, Error>
+where
+    BS: Blockstore,
+    V: Serialize + DeserializeOwned,
+{
+    match outer.get(k)? {
+        // flush semantics guarantee all written inner maps are non empty
+        Some(root) => make_map_with_root_and_bitwidth(root, *outer.store(), inner_bitwidth),
+        None => Ok(make_empty_map(*outer.store(), inner_bitwidth)),
+    }
+}
@@ -1,17 +1,72 @@
 use itertools::Itertools;
-use std::{cell::RefCell, fmt::Display, rc::Rc};
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    sync::{Arc, Mutex, MutexGuard},
+};
 
+use fvm_shared::address::Address;
 use regex::Regex;
+use serde::{Serialize, Serializer};
+
+/// The invariant ID of violations added without one.
+pub const UNCLASSIFIED_INVARIANT: &str = "unclassified";
+
+/// How serious a violation is.
+/// Every invariant currently checked is an error, but reports record the severity so that
+/// consumers need not assume it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The state is inconsistent.
+    Error,
+}
+
+/// A broken invariant.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Violation {
+    /// Identifies the invariant, e.g. `reward.state_epoch`.
+    /// An invariant's ID never changes between releases, so that it can be tracked over time.
+    pub invariant: &'static str,
+    pub severity: Severity,
+    /// The actor whose state breaks the invariant, when checked as part of a state tree.
+    #[serde(serialize_with = "serialize_address")]
+    pub actor: Option<Address>,
+    pub actor_type: Option<&'static str>,
+    /// IDs of the objects involved, by kind, e.g. `deal` or `sector`.
+    pub related: BTreeMap<&'static str, u64>,
+    pub message: String,
+}
+
+fn serialize_address<S: Serializer>(
+    address: &Option<Address>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    address.as_ref().map(|a| a.to_string()).serialize(serializer)
+}
+
+/// The context recorded with each violation added to an accumulator.
+#[derive(Clone, Debug, Default)]
+struct Context {
+    invariant: Option<&'static str>,
+    actor: Option<Address>,
+    actor_type: Option<&'static str>,
+    related: BTreeMap<&'static str, u64>,
+}
 
 /// Accumulates a sequence of messages (e.g. validation failures).
+///
+/// Each message is recorded as a [`Violation`] of an invariant. Accumulators derived with
+/// `invariant()`, `related()` and `with_actor()` record the context of the violations added to them.
 #[derive(Debug, Default)]
 pub struct MessageAccumulator {
-    /// Accumulated messages.
-    /// This is a `Rc<RefCell>` to support accumulators derived from `with_prefix()` accumulating to
-    /// the same underlying collection.
-    msgs: Rc<RefCell<Vec<String>>>,
+    /// Accumulated violations.
+    /// This is an `Arc<Mutex>` to support accumulators derived from `with_prefix()` accumulating to
+    /// the same underlying collection, including from other threads.
+    violations: Arc<Mutex<Vec<Violation>>>,
     /// Optional prefix to all new messages, e.g. describing higher level context.
     prefix: String,
+    context: Context,
 }
 
 impl MessageAccumulator {
@@ -19,32 +74,90 @@ impl MessageAccumulator {
     /// a formatted string.
     pub fn with_prefix<S: AsRef<str>>(&self, prefix: S) -> Self {
         MessageAccumulator {
-            msgs: self.msgs.clone(),
+            violations: self.violations.clone(),
             prefix: self.prefix.to_owned() + prefix.as_ref(),
+            context: self.context.clone(),
         }
     }
 
+    /// Returns a new accumulator backed by the same collection, that records new messages as
+    /// violations of the invariant `id`.
+    pub fn invariant(&self, id: &'static str) -> Self {
+        self.with_context(|c| c.invariant = Some(id))
+    }
+
+    /// Returns a new accumulator backed by the same collection, that records new messages as
+    /// involving the object of a kind with an ID, e.g. `related("deal", deal_id)`.
+    pub fn related(&self, kind: &'static str, id: u64) -> Self {
+        self.with_context(|c| {
+            c.related.insert(kind, id);
+        })
+    }
+
+    /// Returns a new accumulator backed by the same collection, that records new messages, and
+    /// those added from other accumulators, as violations by an actor of a type.
+    pub fn with_actor(&self, actor: Address, actor_type: &'static str) -> Self {
+        self.with_context(|c| {
+            c.actor = Some(actor);
+            c.actor_type = Some(actor_type);
+        })
+    }
+
     pub fn is_empty(&self) -> bool {
-        self.msgs.borrow().is_empty()
+        self.lock().is_empty()
     }
 
     pub fn messages(&self) -> Vec<String> {
-        self.msgs.borrow().to_owned()
+        self.lock().iter().map(|v| v.message.clone()).collect()
+    }
+
+    /// Returns the accumulated violations.
+    pub fn violations(&self) -> Vec<Violation> {
+        self.lock().to_owned()
     }
 
     /// Returns the number of accumulated messages
     pub fn len(&self) -> usize {
-        self.msgs.borrow().len()
+        self.lock().len()
     }
 
     /// Adds a message to the accumulator
     pub fn add<S: AsRef<str>>(&self, msg: S) {
-        self.msgs.borrow_mut().push(format!("{}{}", self.prefix, msg.as_ref()));
+        let c = &self.context;
+        self.lock().push(Violation {
+            invariant: c.invariant.unwrap_or(UNCLASSIFIED_INVARIANT),
+            severity: Severity::Error,
+            actor: c.actor,
+            actor_type: c.actor_type,
+            related: c.related.clone(),
+            message: format!("{}{}", self.prefix, msg.as_ref()),
+        });
     }
 
-    /// Adds messages from another accumulator to this one
+    /// Adds messages from another accumulator to this one.
+    /// The messages are added unchanged, except for recording this accumulator's actor and related
+    /// IDs where they have none.
     pub fn add_all(&self, other: &Self) {
-        self.msgs.borrow_mut().extend_from_slice(&other.msgs.borrow());
+        // Copy the other violations first, in case both accumulators share a collection.
+        self.add_violations(other.violations());
+    }
+
+    /// Adds violations recorded elsewhere, e.g. by an earlier check.
+    /// The violations are added unchanged, except for recording this accumulator's actor and
+    /// related IDs where they have none.
+    pub fn add_violations<I: IntoIterator<Item = Violation>>(&self, violations: I) {
+        let violations: Vec<_> = violations
+            .into_iter()
+            .map(|mut v| {
+                v.actor = v.actor.or(self.context.actor);
+                v.actor_type = v.actor_type.or(self.context.actor_type);
+                for (kind, id) in &self.context.related {
+                    v.related.entry(*kind).or_insert(*id);
+                }
+                v
+            })
+            .collect();
+        self.lock().extend(violations);
     }
 
     /// Adds a message if predicate is false
@@ -67,6 +180,34 @@ impl MessageAccumulator {
         assert!(self.is_empty(), "{}", self.messages().join("\n"))
     }
 
+    fn with_context(&self, f: impl FnOnce(&mut Context)) -> Self {
+        let mut context = self.context.clone();
+        f(&mut context);
+        MessageAccumulator {
+            violations: self.violations.clone(),
+            prefix: self.prefix.clone(),
+            context,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<Vec<Violation>> {
+        // A panic while holding the lock cannot leave the violations inconsistent.
+        self.violations.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Asserts the accumulator contains violations of the given invariants *in the given order*.
+    #[track_caller]
+    pub fn assert_invariants(&self, expected_invariants: &[&str]) {
+        let violations = self.violations();
+        let invariants: Vec<_> = violations.iter().map(|v| v.invariant).collect();
+        assert!(
+            invariants == expected_invariants,
+            "Unexpected violations. Actual: {}.\nExpected: {}",
+            violations.iter().map(|v| format!("{}: {}", v.invariant, v.message)).join("\n"),
+            expected_invariants.join("\n")
+        );
+    }
+
     /// Asserts the accumulator contains messages matching provided pattern *in the given order*.
     #[track_caller]
     pub fn assert_expected(&self, expected_patterns: &[Regex]) {
@@ -162,4 +303,58 @@ mod test {
         assert_eq!(2, acc3.len());
         assert_eq!(acc3.messages(), vec!["Cthulhu", "Azathoth"]);
     }
+
+    #[test]
+    fn records_violation_context() {
+        let acc = MessageAccumulator::default();
+        acc.invariant("gods.awake").related("god", 1).add("Cthulhu");
+
+        let shrine = MessageAccumulator::default();
+        shrine.invariant("gods.asleep").add("Azathoth");
+        acc.with_actor(Address::new_id(1000), "shrine").related("god", 2).add_all(&shrine);
+
+        assert_eq!(
+            acc.violations(),
+            vec![
+                Violation {
+                    invariant: "gods.awake",
+                    severity: Severity::Error,
+                    actor: None,
+                    actor_type: None,
+                    related: [("god", 1)].into_iter().collect(),
+                    message: "Cthulhu".to_owned(),
+                },
+                Violation {
+                    invariant: "gods.asleep",
+                    severity: Severity::Error,
+                    actor: Some(Address::new_id(1000)),
+                    actor_type: Some("shrine"),
+                    related: [("god", 2)].into_iter().collect(),
+                    message: "Azathoth".to_owned(),
+                },
+            ]
+        );
+        acc.assert_invariants(&["gods.awake", "gods.asleep"]);
+
+        acc.add("Nyarlathotep");
+        assert_eq!(UNCLASSIFIED_INVARIANT, acc.violations()[2].invariant);
+    }
+
+    #[test]
+    fn accumulates_across_threads() {
+        let acc = MessageAccumulator::default();
+        std::thread::scope(|s| {
+            for god in ["Cthulhu", "Azathoth", "Nyarlathotep"] {
+                let acc = acc.with_prefix("elder god -> ");
+                s.spawn(move || acc.add(god));
+            }
+        });
+
+        let mut msgs = acc.messages();
+        msgs.sort();
+        assert_eq!(
+            msgs,
+            vec!["elder god -> Azathoth", "elder god -> Cthulhu", "elder god -> Nyarlathotep"]
+        );
+    }
 }
@@ -4,16 +4,24 @@
 pub use self::batch_return::BatchReturn;
 pub use self::batch_return::BatchReturnGen;
 pub use self::batch_return::FailCode;
+pub use self::cache::{InnerCollection, WriteBackCache};
 pub use self::downcast::*;
+pub use self::events::*;
+pub use self::map::*;
 pub use self::mapmap::MapMap;
-pub use self::message_accumulator::MessageAccumulator;
+pub use self::message_accumulator::{
+    MessageAccumulator, Severity, Violation, UNCLASSIFIED_INVARIANT,
+};
 pub use self::multimap::*;
 pub use self::set::Set;
 pub use self::set_multimap::SetMultimap;
 
 mod batch_return;
+mod cache;
 pub mod cbor;
 mod downcast;
+mod events;
+mod map;
 mod mapmap;
 mod message_accumulator;
 mod multimap;
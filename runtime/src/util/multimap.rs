@@ -7,20 +7,39 @@ use fvm_ipld_hamt::Error;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
-use crate::{make_empty_map, make_map_with_root_and_bitwidth, Array, BytesKey, Map};
+use crate::{
+    make_empty_map, make_map_with_root_and_bitwidth, Array, BytesKey, Map, WriteBackCache,
+};
 
 /// Multimap stores multiple values per key in a Hamt of Amts.
 /// The order of insertion of values for each key is retained.
-pub struct Multimap<'a, BS>(Map<'a, BS, Cid>, u32);
-impl<'a, BS> Multimap<'a, BS>
+///
+/// Arrays are loaded into a write-back cache when modified and are only flushed
+/// to the store, along with the outer map, when the root is retrieved.
+pub struct Multimap<'a, BS, V>
 where
     BS: Blockstore,
+    V: Serialize + DeserializeOwned,
+{
+    outer: Map<'a, BS, Cid>,
+    inner_bitwidth: u32,
+    cache: WriteBackCache<Array<'a, V, BS>>,
+}
+
+impl<'a, BS, V> Multimap<'a, BS, V>
+where
+    BS: Blockstore,
+    V: Serialize + DeserializeOwned,
 {
     /// Initializes a new empty multimap.
     /// The outer_bitwidth is the width of the HAMT and the
     /// inner_bitwidth is the width of the AMTs inside of it.
     pub fn new(bs: &'a BS, outer_bitwidth: u32, inner_bitwidth: u32) -> Self {
-        Self(make_empty_map(bs, outer_bitwidth), inner_bitwidth)
+        Self {
+            outer: make_empty_map(bs, outer_bitwidth),
+            inner_bitwidth,
+            cache: WriteBackCache::new(),
+        }
     }
 
     /// Initializes a multimap from a root Cid
@@ -30,46 +49,43 @@ where
         outer_bitwidth: u32,
         inner_bitwidth: u32,
     ) -> Result<Self, Error> {
-        Ok(Self(make_map_with_root_and_bitwidth(cid, bs, outer_bitwidth)?, inner_bitwidth))
+        Ok(Self {
+            outer: make_map_with_root_and_bitwidth(cid, bs, outer_bitwidth)?,
+            inner_bitwidth,
+            cache: WriteBackCache::new(),
+        })
     }
 
-    /// Retrieve root from the multimap.
+    /// Flushes modified arrays and retrieves root from the multimap.
     #[inline]
     pub fn root(&mut self) -> Result<Cid, Error> {
-        self.0.flush()
+        self.cache.flush(&mut self.outer)?;
+        self.outer.flush()
     }
 
     /// Adds a value for a key.
-    pub fn add<V>(&mut self, key: BytesKey, value: V) -> Result<(), Error>
-    where
-        V: Serialize + DeserializeOwned,
-    {
+    pub fn add(&mut self, key: BytesKey, value: V) -> Result<(), Error> {
+        let (outer, inner_bitwidth) = (&self.outer, self.inner_bitwidth);
         // Get construct amt from retrieved cid or create new
-        let mut arr = self
-            .get::<V>(&key)?
-            .unwrap_or_else(|| Array::new_with_bit_width(self.0.store(), self.1));
+        let arr = self.cache.load_mut(&key, || match outer.get(&key)? {
+            Some(cid) => load_array(outer, cid),
+            None => Ok(Array::new_with_bit_width(*outer.store(), inner_bitwidth)),
+        })?;
 
-        // Set value at next index
+        // Set value at next index, deferring the flush until the root is retrieved
         arr.set(arr.count(), value).map_err(|e| anyhow::anyhow!(e))?;
-
-        // flush to get new array root to put in hamt
-        let new_root = arr.flush().map_err(|e| anyhow::anyhow!(e))?;
-
-        // Set hamt node to array root
-        self.0.set(key, new_root)?;
         Ok(())
     }
 
     /// Gets the Array of value type `V` using the multimap store.
     #[inline]
-    pub fn get<V>(&self, key: &[u8]) -> Result<Option<Array<'a, V, BS>>, Error>
-    where
-        V: DeserializeOwned + Serialize,
-    {
-        match self.0.get(key)? {
-            Some(cid) => {
-                Ok(Some(Array::load(cid, *self.0.store()).map_err(|e| anyhow::anyhow!(e))?))
-            }
+    pub fn get(&mut self, key: &[u8]) -> Result<Option<&Array<'a, V, BS>>, Error> {
+        let outer = &self.outer;
+        if self.cache.contains_key(key) {
+            return Ok(self.cache.get(key));
+        }
+        match outer.get(key)? {
+            Some(cid) => Ok(Some(self.cache.load(key, || load_array(outer, cid))?)),
             None => Ok(None),
         }
     }
@@ -77,36 +93,53 @@ where
     /// Removes all values for a key.
     #[inline]
     pub fn remove_all(&mut self, key: &[u8]) -> Result<(), Error> {
-        // Remove entry from table
-        self.0.delete(key)?.ok_or("failed to delete from multimap")?;
+        // Discard any cached values and remove entry from table
+        let cached = self.cache.remove(key).is_some();
+        let deleted = self.outer.delete(key)?.is_some();
+        if !cached && !deleted {
+            return Err("failed to delete from multimap".into());
+        }
 
         Ok(())
     }
 
     /// Iterates through all values in the array at a given key.
-    pub fn for_each<F, V>(&self, key: &[u8], f: F) -> Result<(), Error>
+    pub fn for_each<F>(&self, key: &[u8], f: F) -> Result<(), Error>
     where
-        V: Serialize + DeserializeOwned,
         F: FnMut(u64, &V) -> anyhow::Result<()>,
     {
-        if let Some(amt) = self.get::<V>(key)? {
+        if let Some(amt) = self.cache.get(key) {
             amt.for_each(f).map_err(|e| anyhow::anyhow!(e))?;
+        } else if let Some(cid) = self.outer.get(key)? {
+            load_array(&self.outer, cid)?.for_each(f).map_err(|e| anyhow::anyhow!(e))?;
         }
 
         Ok(())
     }
 
-    /// Iterates through all arrays in the multimap
-    pub fn for_all<F, V>(&self, mut f: F) -> Result<(), Error>
+    /// Iterates through all arrays in the multimap, including arrays that have not been flushed.
+    pub fn for_all<F>(&self, mut f: F) -> Result<(), Error>
     where
-        V: Serialize + DeserializeOwned,
         F: FnMut(&BytesKey, &Array<V, BS>) -> anyhow::Result<()>,
     {
-        self.0.for_each::<_>(|key, arr_root| {
-            let arr = Array::load(arr_root, *self.0.store())?;
-            f(key, &arr)
+        self.outer.for_each::<_>(|key, arr_root| match self.cache.get(key) {
+            Some(arr) => f(key, arr),
+            None => f(key, &load_array(&self.outer, arr_root)?),
         })?;
+        for (key, arr) in self.cache.iter() {
+            if !self.outer.contains_key(key)? {
+                f(&BytesKey(key.to_vec()), arr)?;
+            }
+        }
 
         Ok(())
     }
 }
+
+fn load_array<'a, BS, V>(outer: &Map<'a, BS, Cid>, root: &Cid) -> Result<Array<'a, V, BS>, Error>
+where
+    BS: Blockstore,
+    V: Serialize + DeserializeOwned,
+{
+    Ok(Array::load(root, *outer.store()).map_err(|e| anyhow::anyhow!(e))?)
+}
@@ -66,6 +66,12 @@ where
         }
     }
 
+    /// Returns whether the set is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
     /// Iterates through all keys in the set.
     pub fn for_each<F>(&self, mut f: F) -> Result<(), Error>
     where
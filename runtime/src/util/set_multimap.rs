@@ -11,11 +11,17 @@ use fvm_shared::deal::DealID;
 use fvm_shared::HAMT_BIT_WIDTH;
 
 use super::Set;
-use crate::{make_empty_map, make_map_with_root, parse_uint_key, u64_key, Map};
+use crate::{make_empty_map, make_map_with_root, parse_uint_key, u64_key, Map, WriteBackCache};
 
 /// SetMultimap is a hamt with values that are also a hamt but are of the set variant.
 /// This allows hash sets to be indexable by an address.
-pub struct SetMultimap<'a, BS>(pub Map<'a, BS, Cid>);
+///
+/// Sets are loaded into a write-back cache when modified and are only flushed
+/// to the store, along with the outer map, when the root is retrieved.
+pub struct SetMultimap<'a, BS: Blockstore> {
+    outer: Map<'a, BS, Cid>,
+    cache: WriteBackCache<Set<'a, BS>>,
+}
 
 impl<'a, BS> SetMultimap<'a, BS>
 where
@@ -23,104 +29,131 @@ where
 {
     /// Initializes a new empty SetMultimap.
     pub fn new(bs: &'a BS) -> Self {
-        Self(make_empty_map(bs, HAMT_BIT_WIDTH))
+        Self { outer: make_empty_map(bs, HAMT_BIT_WIDTH), cache: WriteBackCache::new() }
     }
 
     /// Initializes a SetMultimap from a root Cid.
     pub fn from_root(bs: &'a BS, cid: &Cid) -> Result<Self, Error> {
-        Ok(Self(make_map_with_root(cid, bs)?))
+        Ok(Self { outer: make_map_with_root(cid, bs)?, cache: WriteBackCache::new() })
     }
 
-    /// Retrieve root from the SetMultimap.
+    /// Flushes modified sets and retrieves root from the SetMultimap.
     #[inline]
     pub fn root(&mut self) -> Result<Cid, Error> {
-        self.0.flush()
+        self.cache.flush(&mut self.outer)?;
+        self.outer.flush()
     }
 
     /// Puts the DealID in the hash set of the key.
     pub fn put(&mut self, key: ChainEpoch, value: DealID) -> Result<(), Error> {
-        // Get construct amt from retrieved cid or create new
-        let mut set = self.get(key)?.unwrap_or_else(|| Set::new(self.0.store()));
-
-        set.put(u64_key(value))?;
-
-        // Save and calculate new root
-        let new_root = set.root()?;
-
-        // Set hamt node to set new root
-        self.0.set(u64_key(key as u64), new_root)?;
-        Ok(())
+        let set = self.load_set_mut(key)?;
+        // defer flushing the set until the root is retrieved
+        set.put(u64_key(value))
     }
 
     /// Puts slice of DealIDs in the hash set of the key.
     pub fn put_many(&mut self, key: ChainEpoch, values: &[DealID]) -> Result<(), Error> {
-        // Get construct amt from retrieved cid or create new
-        let mut set = self.get(key)?.unwrap_or_else(|| Set::new(self.0.store()));
-
+        let set = self.load_set_mut(key)?;
         for &v in values {
             set.put(u64_key(v))?;
         }
-
-        // Save and calculate new root
-        let new_root = set.root()?;
-
-        // Set hamt node to set new root
-        self.0.set(u64_key(key as u64), new_root)?;
         Ok(())
     }
 
     /// Gets the set at the given index of the `SetMultimap`
     #[inline]
-    pub fn get(&self, key: ChainEpoch) -> Result<Option<Set<'a, BS>>, Error> {
-        match self.0.get(&u64_key(key as u64))? {
-            Some(cid) => Ok(Some(Set::from_root(*self.0.store(), cid)?)),
-            None => Ok(None),
+    pub fn get(&mut self, key: ChainEpoch) -> Result<Option<&Set<'a, BS>>, Error> {
+        let k = u64_key(key as u64);
+        let outer = &self.outer;
+        if !self.cache.contains_key(&k) && !outer.contains_key(&k)? {
+            return Ok(None);
         }
+        Ok(Some(self.cache.load(&k, || load_set(outer, &k))?))
     }
 
     /// Removes a DealID from a key hash set.
     #[inline]
     pub fn remove(&mut self, key: ChainEpoch, v: DealID) -> Result<(), Error> {
-        // Get construct amt from retrieved cid and return if no set exists
-        let mut set = match self.get(key)? {
-            Some(s) => s,
-            None => return Ok(()),
-        };
-
-        set.delete(u64_key(v).borrow())?;
-
-        // Save and calculate new root
-        let new_root = set.root()?;
-        self.0.set(u64_key(key as u64), new_root)?;
+        // Return if no set exists
+        if self.get(key)?.is_none() {
+            return Ok(());
+        }
+        self.load_set_mut(key)?.delete(u64_key(v).borrow())?;
         Ok(())
     }
 
     /// Removes set at index.
     #[inline]
     pub fn remove_all(&mut self, key: ChainEpoch) -> Result<(), Error> {
-        // Remove entry from table
-        self.0.delete(&u64_key(key as u64))?;
+        let k = u64_key(key as u64);
+        // Discard any cached modifications and remove entry from table
+        self.cache.remove(&k);
+        self.outer.delete(&k)?;
 
         Ok(())
     }
 
     /// Iterates through keys and converts them to a DealID to call a function on each.
-    pub fn for_each<F>(&self, key: ChainEpoch, mut f: F) -> Result<(), Error>
+    pub fn for_each<F>(&self, key: ChainEpoch, f: F) -> Result<(), Error>
     where
         F: FnMut(DealID) -> Result<(), Error>,
     {
-        // Get construct amt from retrieved cid and return if no set exists
-        let set = match self.get(key)? {
-            Some(s) => s,
-            None => return Ok(()),
-        };
-
-        set.for_each(|k| {
-            let v = parse_uint_key(k)
-                .map_err(|e| anyhow::anyhow!("Could not parse key: {:?}, ({})", &k.0, e))?;
-
-            // Run function on all parsed keys
-            Ok(f(v)?)
-        })
+        let k = u64_key(key as u64);
+        if let Some(set) = self.cache.get(&k) {
+            return for_each_deal(set, f);
+        }
+        // Construct set from retrieved cid and return if no set exists
+        match self.outer.get(&k)? {
+            Some(cid) => for_each_deal(&Set::from_root(*self.outer.store(), cid)?, f),
+            None => Ok(()),
+        }
+    }
+
+    /// Iterates through all keys that have a set, including sets that have not been flushed.
+    pub fn for_each_key<F>(&self, mut f: F) -> Result<(), Error>
+    where
+        F: FnMut(ChainEpoch) -> anyhow::Result<()>,
+    {
+        self.outer.for_each(|k, _| f(parse_epoch_key(k)?))?;
+        for (k, _) in self.cache.iter() {
+            if !self.outer.contains_key(k)? {
+                f(parse_epoch_key(k)?)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn load_set_mut(&mut self, key: ChainEpoch) -> Result<&mut Set<'a, BS>, Error> {
+        let k = u64_key(key as u64);
+        let outer = &self.outer;
+        self.cache.load_mut(&k, || load_set(outer, &k))
+    }
+}
+
+// Loads the set stored under a key, or an empty set if there is none.
+fn load_set<'a, BS: Blockstore>(outer: &Map<'a, BS, Cid>, k: &[u8]) -> Result<Set<'a, BS>, Error> {
+    match outer.get(k)? {
+        Some(cid) => Set::from_root(*outer.store(), cid),
+        None => Ok(Set::new(*outer.store())),
     }
 }
+
+fn for_each_deal<BS, F>(set: &Set<BS>, mut f: F) -> Result<(), Error>
+where
+    BS: Blockstore,
+    F: FnMut(DealID) -> Result<(), Error>,
+{
+    set.for_each(|k| {
+        let v = parse_uint_key(k)
+            .map_err(|e| anyhow::anyhow!("Could not parse key: {:?}, ({})", &k.0, e))?;
+
+        // Run function on all parsed keys
+        Ok(f(v)?)
+    })
+}
+
+fn parse_epoch_key(k: &[u8]) -> anyhow::Result<ChainEpoch> {
+    let epoch =
+        parse_uint_key(k).map_err(|e| anyhow::anyhow!("Could not parse key: {:?}, ({})", k, e))?;
+    Ok(epoch as ChainEpoch)
+}
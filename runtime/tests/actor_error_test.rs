@@ -0,0 +1,51 @@
+use fil_actors_runtime::{actor_error, ActorContext, ActorError, AsActorError, ErrorContext};
+use fvm_shared::error::ExitCode;
+
+fn load_sector() -> Result<(), ActorError> {
+    Err(actor_error!(not_found, "no such sector"))
+}
+
+#[test]
+fn context_renders_flat_message() {
+    let err = load_sector()
+        .with_context_data(|| ErrorContext::new("failed to load sector 7").sector(7))
+        .context("failed to prove commit")
+        .unwrap_err();
+
+    assert_eq!(ExitCode::USR_NOT_FOUND, err.exit_code());
+    assert_eq!("failed to prove commit: failed to load sector 7: no such sector", err.msg());
+    assert_eq!("no such sector", err.cause());
+    assert_eq!(
+        actor_error!(not_found, "failed to prove commit: failed to load sector 7: no such sector"),
+        err
+    );
+}
+
+#[test]
+fn context_frames_keep_structured_data() {
+    let err = load_sector()
+        .with_context_data(|| ErrorContext::new("failed to load sector").sector(7).deal(3))
+        .with_context(|| "failed to prove commit")
+        .unwrap_err();
+
+    let frames = err.context();
+    assert_eq!(2, frames.len());
+    assert_eq!("failed to prove commit", frames[0].message);
+    assert!(frames[0].data.is_empty());
+    assert_eq!("failed to load sector", frames[1].message);
+    assert_eq!(Some("7"), frames[1].get("sector"));
+    assert_eq!(Some("3"), frames[1].get("deal"));
+    assert_eq!(None, frames[1].get("actor"));
+}
+
+#[test]
+fn foreign_errors_record_context() {
+    let res: Result<(), String> = Err("bad bitfield".to_string());
+    let err =
+        res.context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load partition").unwrap_err();
+
+    assert_eq!("failed to load partition: bad bitfield", err.msg());
+    assert_eq!("bad bitfield", err.cause());
+    assert_eq!(1, err.context().len());
+    assert_eq!("failed to load partition", err.context()[0].message);
+}
@@ -0,0 +1,42 @@
+use fil_actors_runtime::{
+    ActorEvent, EventBuilder, EventEntry, FLAG_INDEXED_ALL, FLAG_INDEXED_KEY, FLAG_INDEXED_VALUE,
+};
+use fvm_ipld_encoding::RawBytes;
+
+#[test]
+fn build_event() {
+    let event =
+        EventBuilder::new().typ("deal-published").field_indexed("id", &7u64).field("note", "hi");
+    let event = event.build().unwrap();
+
+    assert_eq!(
+        ActorEvent {
+            entries: vec![
+                EventEntry {
+                    flags: FLAG_INDEXED_ALL,
+                    key: "$type".to_string(),
+                    value: RawBytes::serialize("deal-published").unwrap(),
+                },
+                EventEntry {
+                    flags: FLAG_INDEXED_ALL,
+                    key: "id".to_string(),
+                    value: RawBytes::serialize(7u64).unwrap(),
+                },
+                EventEntry {
+                    flags: 0,
+                    key: "note".to_string(),
+                    value: RawBytes::serialize("hi").unwrap(),
+                },
+            ]
+        },
+        event
+    );
+    assert_eq!(Some("deal-published".to_string()), event.event_type());
+}
+
+#[test]
+fn event_type_absent() {
+    let event = EventBuilder::new().field("id", &1u64).build().unwrap();
+    assert_eq!(None, event.event_type());
+    assert_eq!(FLAG_INDEXED_ALL, FLAG_INDEXED_KEY | FLAG_INDEXED_VALUE);
+}
@@ -0,0 +1,93 @@
+use fil_actors_runtime::{
+    actor_error, make_map_with_root_and_bitwidth, u64_key, Map, Map2, MapConfig,
+};
+use fvm_ipld_blockstore::MemoryBlockstore;
+use fvm_shared::address::Address;
+use fvm_shared::error::ExitCode;
+
+#[test]
+fn basic_put_get() {
+    let bs = MemoryBlockstore::new();
+    let mut m = Map2::<_, u64, String>::empty(&bs, "basic");
+    assert!(m.is_empty());
+    m.set(&1234, "1234".to_string()).unwrap();
+    assert!(m.get(&2222).unwrap().is_none());
+    assert_eq!(&"1234".to_string(), m.get(&1234).unwrap().unwrap());
+    assert!(m.contains_key(&1234).unwrap());
+    assert!(!m.set_if_absent(&1234, "other".to_string()).unwrap());
+    assert_eq!(Some("1234".to_string()), m.delete(&1234).unwrap());
+    assert!(m.get(&1234).unwrap().is_none());
+}
+
+#[test]
+fn flush_and_load() {
+    let bs = MemoryBlockstore::new();
+    let mut m = Map2::<_, Address, u64>::empty(&bs, "flush");
+    m.set(&Address::new_id(100), 1).unwrap();
+    m.set(&Address::new_id(101), 2).unwrap();
+    let root = m.flush().unwrap();
+
+    let m = Map2::<_, Address, u64>::load(&bs, &root, "flush").unwrap();
+    assert_eq!(Some(&1), m.get(&Address::new_id(100)).unwrap());
+    assert_eq!(Some(&2), m.get(&Address::new_id(101)).unwrap());
+}
+
+#[test]
+fn keys_match_raw_encoding() {
+    let bs = MemoryBlockstore::new();
+    let mut m = Map2::<_, u64, u64>::empty(&bs, "keys");
+    m.set(&300, 3).unwrap();
+    let root = m.flush().unwrap();
+
+    let raw: Map<_, u64> = make_map_with_root_and_bitwidth(&root, &bs, 5).unwrap();
+    assert_eq!(Some(&3), raw.get(&u64_key(300)).unwrap());
+}
+
+struct WideConfig;
+
+impl MapConfig for WideConfig {
+    const BIT_WIDTH: u32 = 8;
+}
+
+#[test]
+fn custom_bit_width() {
+    let bs = MemoryBlockstore::new();
+    let mut m = Map2::<_, i64, u64, WideConfig>::empty(&bs, "wide");
+    m.set(&-1, 1).unwrap();
+    let root = m.flush().unwrap();
+
+    let raw: Map<_, u64> = make_map_with_root_and_bitwidth(&root, &bs, 8).unwrap();
+    assert!(!raw.is_empty());
+    let m = Map2::<_, i64, u64, WideConfig>::load(&bs, &root, "wide").unwrap();
+    assert_eq!(Some(&1), m.get(&-1).unwrap());
+}
+
+#[test]
+fn for_each_decodes_keys() {
+    let bs = MemoryBlockstore::new();
+    let mut m = Map2::<_, Address, u64>::empty(&bs, "iter");
+    for id in 100..110 {
+        m.set(&Address::new_id(id), id * 2).unwrap();
+    }
+
+    let mut found = Vec::new();
+    m.for_each(|addr, v| {
+        found.push((*addr, *v));
+        Ok(())
+    })
+    .unwrap();
+    found.sort_by_key(|(_, v)| *v);
+    let expected: Vec<_> = (100..110).map(|id| (Address::new_id(id), id * 2)).collect();
+    assert_eq!(expected, found);
+}
+
+#[test]
+fn for_each_propagates_callback_error() {
+    let bs = MemoryBlockstore::new();
+    let mut m = Map2::<_, u64, u64>::empty(&bs, "errors");
+    m.set(&1, 1).unwrap();
+
+    let err = m.for_each(|_, _| Err(actor_error!(not_found, "stop"))).unwrap_err();
+    assert_eq!(ExitCode::USR_NOT_FOUND, err.exit_code());
+    assert_eq!("failed to iterate over HAMT 'errors': stop", err.msg());
+}
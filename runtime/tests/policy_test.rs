@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+
+use fil_actors_runtime::runtime::policy::NETWORKS;
+use fil_actors_runtime::runtime::{NetworkPolicies, Policy, PolicyError};
+
+fn policy_document(params: &[(&str, i64)]) -> Vec<u8> {
+    let params: HashMap<&str, i64> = params.iter().cloned().collect();
+    fvm_ipld_encoding::to_vec(&params).unwrap()
+}
+
+#[test]
+fn load_policy_overrides_defaults() {
+    let doc = policy_document(&[("pre_commit_challenge_delay", 10), ("chain_finality", 20)]);
+    let policy = Policy::from_cbor(&doc).unwrap();
+    assert_eq!(10, policy.pre_commit_challenge_delay);
+    assert_eq!(20, policy.chain_finality);
+    assert_eq!(
+        Policy { pre_commit_challenge_delay: 10, chain_finality: 20, ..Default::default() },
+        policy
+    );
+}
+
+#[test]
+fn load_policy_rejects_unknown_parameters() {
+    let doc = policy_document(&[("chain_finalty", 20)]);
+    assert!(matches!(Policy::from_cbor(&doc), Err(PolicyError::Decode(_))));
+}
+
+#[test]
+fn load_policy_rejects_inconsistent_parameters() {
+    let doc =
+        policy_document(&[("wpost_challenge_lookback", 20), ("fault_declaration_cutoff", 10)]);
+    assert!(matches!(Policy::from_cbor(&doc), Err(PolicyError::Invalid(_))));
+
+    let doc = policy_document(&[("wpost_challenge_window", 30)]);
+    assert!(matches!(Policy::from_cbor(&doc), Err(PolicyError::Invalid(_))));
+}
+
+#[test]
+fn network_policies_by_name() {
+    let mut policies = NetworkPolicies::new();
+    policies.load("devnet", &policy_document(&[("min_sector_expiration", 100)])).unwrap();
+    assert_eq!(100, policies.get("devnet").min_sector_expiration);
+    assert_eq!(Policy::default(), policies.get("mainnet"));
+}
+
+#[test]
+fn default_policy_is_valid() {
+    assert_eq!(Ok(()), Policy::default().validate());
+}
+
+#[test]
+fn validate_reports_every_violation() {
+    let policy = Policy {
+        fault_declaration_cutoff: 10,
+        wpost_challenge_lookback: 20,
+        min_aggregated_sectors: 1000,
+        ..Default::default()
+    };
+    let violations = policy.validate().unwrap_err();
+    assert_eq!(2, violations.len());
+    assert_eq!(vec!["fault_declaration_cutoff", "wpost_challenge_lookback"], violations[0].fields);
+    assert_eq!(vec!["min_aggregated_sectors", "max_aggregated_sectors"], violations[1].fields);
+}
+
+#[test]
+fn build_network_policy_is_default() {
+    assert_eq!(Some(Policy::default()), Policy::for_network(env!("FIL_BUILD_NETWORK")));
+}
+
+#[test]
+fn network_policies_are_valid() {
+    for (network, _) in NETWORKS {
+        let policy = Policy::for_network(network).unwrap();
+        assert_eq!(Ok(()), policy.validate(), "invalid policy for network {}", network);
+    }
+    assert_eq!(None, Policy::for_network("nonet"));
+}
@@ -0,0 +1,63 @@
+use fil_actors_runtime::runtime::Response;
+use fil_actors_runtime::{actor_error, extract_send_result};
+use fvm_ipld_encoding::ipld_block::IpldBlock;
+use fvm_shared::address::Address;
+use fvm_shared::error::ExitCode;
+use fvm_shared::MethodNum;
+
+const TO: Address = Address::new_id(1000);
+const METHOD: MethodNum = 2;
+
+#[test]
+fn extract_successful_response() {
+    let data = IpldBlock::serialize_cbor(&42u64).unwrap();
+    let res = Ok(Response { exit_code: ExitCode::OK, return_data: data.clone(), error_msg: None });
+    assert_eq!(data, extract_send_result(&TO, METHOD, res).unwrap());
+}
+
+#[test]
+fn callee_exit_code_becomes_error() {
+    let res =
+        Ok(Response { exit_code: ExitCode::USR_FORBIDDEN, return_data: None, error_msg: None });
+    let err = extract_send_result(&TO, METHOD, res).unwrap_err();
+    assert_eq!(ExitCode::USR_FORBIDDEN, err.exit_code());
+    assert_eq!("send to f01000 method 2 aborted with code 18", err.msg());
+
+    // The callee's message is included where the runtime has one.
+    let res = Ok(Response {
+        exit_code: ExitCode::USR_FORBIDDEN,
+        return_data: None,
+        error_msg: Some("caller not allowed".to_string()),
+    });
+    let err = extract_send_result(&TO, METHOD, res).unwrap_err();
+    assert_eq!("send to f01000 method 2 aborted with code 18: caller not allowed", err.msg());
+}
+
+#[test]
+fn callee_system_exit_code_is_not_propagated() {
+    let res = Ok(Response {
+        exit_code: ExitCode::SYS_MISSING_RETURN,
+        return_data: None,
+        error_msg: None,
+    });
+    assert_eq!(
+        ExitCode::USR_UNSPECIFIED,
+        extract_send_result(&TO, METHOD, res).unwrap_err().exit_code()
+    );
+
+    let res =
+        Ok(Response { exit_code: ExitCode::SYS_OUT_OF_GAS, return_data: None, error_msg: None });
+    assert_eq!(
+        ExitCode::USR_ASSERTION_FAILED,
+        extract_send_result(&TO, METHOD, res).unwrap_err().exit_code()
+    );
+}
+
+#[test]
+fn send_failure_passes_through() {
+    let res = Err(actor_error!(insufficient_funds; "not enough funds"));
+    assert_eq!(
+        ExitCode::USR_INSUFFICIENT_FUNDS,
+        extract_send_result(&TO, METHOD, res).unwrap_err().exit_code()
+    );
+}
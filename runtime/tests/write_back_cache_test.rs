@@ -0,0 +1,100 @@
+// Copyright 2019-2022 ChainSafe Systems
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use cid::Cid;
+use fil_actors_runtime::{
+    make_empty_map, u64_key, Map, Multimap, Set, SetMultimap, WriteBackCache,
+};
+use fvm_ipld_blockstore::MemoryBlockstore;
+use fvm_ipld_hamt::BytesKey;
+use fvm_shared::HAMT_BIT_WIDTH;
+
+#[test]
+fn flushes_only_dirty_entries() {
+    let store = MemoryBlockstore::default();
+    let mut outer: Map<_, Cid> = make_empty_map(&store, HAMT_BIT_WIDTH);
+    let mut cache: WriteBackCache<Set<_>> = WriteBackCache::new();
+
+    cache.load(b"a", || Ok(Set::new(&store))).unwrap();
+    cache.load_mut(b"b", || Ok(Set::new(&store))).unwrap().put(u64_key(1)).unwrap();
+    assert_eq!(1, cache.dirty_count());
+
+    cache.flush(&mut outer).unwrap();
+    assert_eq!(0, cache.dirty_count());
+    // only the modified set was written back
+    assert!(!outer.contains_key(&BytesKey(b"a".to_vec())).unwrap());
+    assert!(outer.contains_key(&BytesKey(b"b".to_vec())).unwrap());
+    // flushed entries stay cached
+    assert!(cache.get(b"a").is_some());
+    assert!(cache.get(b"b").unwrap().has(&u64_key(1)).unwrap());
+}
+
+#[test]
+fn pruning_cache_deletes_empty_entries() {
+    let store = MemoryBlockstore::default();
+    let mut outer: Map<_, Cid> = make_empty_map(&store, HAMT_BIT_WIDTH);
+    let mut cache: WriteBackCache<Set<_>> = WriteBackCache::pruning();
+
+    cache.load_mut(b"a", || Ok(Set::new(&store))).unwrap().put(u64_key(1)).unwrap();
+    cache.flush(&mut outer).unwrap();
+    assert!(outer.contains_key(&BytesKey(b"a".to_vec())).unwrap());
+
+    cache.load_mut(b"a", || unreachable!()).unwrap().delete(&u64_key(1)).unwrap();
+    cache.flush(&mut outer).unwrap();
+    assert!(!outer.contains_key(&BytesKey(b"a".to_vec())).unwrap());
+}
+
+#[test]
+fn set_multimap_changes_visible_before_flush() {
+    let store = MemoryBlockstore::default();
+    let mut smm = SetMultimap::new(&store);
+    smm.put(1, 10).unwrap();
+    smm.put_many(2, &[20, 21]).unwrap();
+
+    let mut epochs = Vec::new();
+    smm.for_each_key(|epoch| {
+        epochs.push(epoch);
+        Ok(())
+    })
+    .unwrap();
+    epochs.sort_unstable();
+    assert_eq!(vec![1, 2], epochs);
+
+    let root = smm.root().unwrap();
+    let mut loaded = SetMultimap::from_root(&store, &root).unwrap();
+    let mut deals = Vec::new();
+    loaded
+        .for_each(2, |id| {
+            deals.push(id);
+            Ok(())
+        })
+        .unwrap();
+    deals.sort_unstable();
+    assert_eq!(vec![20, 21], deals);
+
+    // unflushed modifications are discarded by remove_all
+    loaded.put(3, 30).unwrap();
+    loaded.remove_all(3).unwrap();
+    assert_eq!(root, loaded.root().unwrap());
+}
+
+#[test]
+fn multimap_remove_all_unflushed_key() {
+    let store = MemoryBlockstore::default();
+    let mut mm = Multimap::<_, u64>::new(&store, HAMT_BIT_WIDTH, 3);
+    let empty = mm.root().unwrap();
+
+    mm.add("k".into(), 1).unwrap();
+    mm.add("k".into(), 2).unwrap();
+    let mut count = 0;
+    mm.for_all(|_, arr| {
+        count += arr.count();
+        Ok(())
+    })
+    .unwrap();
+    assert_eq!(2, count);
+
+    mm.remove_all(b"k").unwrap();
+    assert!(mm.remove_all(b"k").is_err());
+    assert_eq!(empty, mm.root().unwrap());
+}
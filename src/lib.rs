@@ -21,3 +21,53 @@
 /// The Filecoin client must import the contents of CAR into the blockstore, but
 /// may opt to exclude the index data structure.
 pub const BUNDLE_CAR: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/bundle/bundle.car"));
+
+#[cfg(not(target_arch = "wasm32"))]
+use fil_actors_runtime::runtime::{ActorCode, MethodMeta};
+
+/// Method tables of the bundled actors, keyed by the actor names used in the bundle.
+#[cfg(not(target_arch = "wasm32"))]
+pub const ABI: &[(&str, &[MethodMeta])] = &[
+    ("system", fil_actor_system::Actor::METHODS),
+    ("init", fil_actor_init::Actor::METHODS),
+    ("cron", fil_actor_cron::Actor::METHODS),
+    ("account", fil_actor_account::Actor::METHODS),
+    ("storagepower", fil_actor_power::Actor::METHODS),
+    ("storageminer", fil_actor_miner::Actor::METHODS),
+    ("storagemarket", fil_actor_market::Actor::METHODS),
+    ("paymentchannel", fil_actor_paych::Actor::METHODS),
+    ("multisig", fil_actor_multisig::Actor::METHODS),
+    ("reward", fil_actor_reward::Actor::METHODS),
+    ("verifiedregistry", fil_actor_verifreg::Actor::METHODS),
+    ("datacap", fil_actor_datacap::Actor::METHODS),
+];
+
+/// Renders a JSON description of the methods each bundled actor exposes, keyed by actor name.
+///
+/// Each method is described by its name, number, whether it is exported and may be invoked
+/// in read-only mode, and the names of its CBOR parameter and return types (or null).
+#[cfg(not(target_arch = "wasm32"))]
+pub fn abi_json() -> String {
+    use serde_json::{json, Map, Value};
+
+    let abi: Map<String, Value> = ABI
+        .iter()
+        .map(|&(name, methods)| {
+            let methods = methods
+                .iter()
+                .map(|m| {
+                    json!({
+                        "name": m.name,
+                        "number": m.number,
+                        "exported": m.exported,
+                        "read_only": m.read_only,
+                        "params": m.params,
+                        "returns": m.returns,
+                    })
+                })
+                .collect();
+            (name.to_owned(), Value::Array(methods))
+        })
+        .collect();
+    serde_json::to_string_pretty(&abi).expect("failed to serialize actor ABI")
+}
@@ -1,7 +1,7 @@
 use clap::Parser;
 use std::io::Write;
 
-use fil_builtin_actors_bundle::BUNDLE_CAR;
+use fil_builtin_actors_bundle::{abi_json, BUNDLE_CAR};
 
 #[derive(Parser)]
 #[clap(name = env!("CARGO_PKG_NAME"))]
@@ -11,10 +11,17 @@ struct Cli {
     /// The output car path. Defaults to STDOUT.
     #[clap(short, long, required = false)]
     output: Option<String>,
+
+    /// Also writes a JSON description of the actors' methods to this path.
+    #[clap(long, required = false)]
+    abi: Option<String>,
 }
 
 fn main() -> Result<(), std::io::Error> {
     let cli = Cli::parse();
+    if let Some(path) = cli.abi {
+        std::fs::write(path, abi_json())?;
+    }
     match cli.output {
         Some(path) => std::fs::write(path, BUNDLE_CAR),
         None => std::io::stdout().write_all(BUNDLE_CAR),
@@ -0,0 +1,72 @@
+use std::path::PathBuf;
+use std::process;
+
+use clap::Parser;
+use fil_builtin_actors_state::car::load_actors_root;
+use fil_builtin_actors_state::check::{check_state_invariants, violations_to_json, Tree};
+use fil_builtin_actors_state::manifest::{load_manifest, load_policy};
+use fvm_ipld_blockstore::MemoryBlockstore;
+use fvm_shared::bigint::BigInt;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::TOTAL_FILECOIN;
+
+#[derive(Parser)]
+#[clap(name = "check-state")]
+#[clap(version = env!("CARGO_PKG_VERSION"))]
+#[clap(about = "Checks the invariants of a state tree exported to a CAR file.", long_about = None)]
+struct Cli {
+    /// The CAR file, whose root is a chain state root or the root of the actors HAMT.
+    car: PathBuf,
+
+    /// A JSON object mapping actor code CIDs to actor type names, e.g. "storageminer".
+    #[clap(short, long)]
+    manifest: PathBuf,
+
+    /// The epoch at which the state was computed.
+    #[clap(short, long)]
+    epoch: ChainEpoch,
+
+    /// The network's policy parameters, CBOR-encoded. Defaults to the policy of this build.
+    #[clap(short, long, required = false, conflicts_with = "network")]
+    policy: Option<PathBuf>,
+
+    /// The name of a network whose default policy to use, e.g. "calibrationnet", instead of a
+    /// policy file.
+    #[clap(short, long, required = false)]
+    network: Option<String>,
+
+    /// The total balance of all actors, in attoFIL. Defaults to the total supply of FIL.
+    #[clap(long, required = false)]
+    total_balance: Option<BigInt>,
+
+    /// Prints the broken invariants as JSON.
+    #[clap(long)]
+    json: bool,
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let manifest = load_manifest(&cli.manifest)?;
+    let policy = load_policy(cli.network.as_deref(), cli.policy.as_deref())?;
+    let total_balance =
+        cli.total_balance.map_or_else(|| TOTAL_FILECOIN.clone(), TokenAmount::from_atto);
+
+    let store = MemoryBlockstore::new();
+    let tree = Tree::load(&store, &load_actors_root(&store, &cli.car)?)?;
+
+    // The checks take the epoch before that at which the state was computed.
+    let acc = check_state_invariants(&manifest, &policy, tree, &total_balance, cli.epoch - 1)?;
+    if cli.json {
+        println!("{}", violations_to_json(&acc)?);
+    } else {
+        for violation in acc.violations() {
+            println!("{}: {}", violation.invariant, violation.message);
+        }
+        println!("{} invariants broken", acc.len());
+    }
+    if !acc.is_empty() {
+        process::exit(1);
+    }
+    Ok(())
+}
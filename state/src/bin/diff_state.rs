@@ -0,0 +1,50 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use fil_builtin_actors_state::car::load_actors_root;
+use fil_builtin_actors_state::diff::diff_state_trees;
+use fil_builtin_actors_state::manifest::{load_manifest, load_policy};
+use fvm_ipld_blockstore::MemoryBlockstore;
+
+#[derive(Parser)]
+#[clap(name = "diff-state")]
+#[clap(version = env!("CARGO_PKG_VERSION"))]
+#[clap(about = "Prints the differences between two state trees exported to CAR files.", long_about = None)]
+struct Cli {
+    /// The CAR file of the old state, whose root is a chain state root or the root of the actors HAMT.
+    old: PathBuf,
+
+    /// The CAR file of the new state.
+    new: PathBuf,
+
+    /// A JSON object mapping actor code CIDs to actor type names, e.g. "storageminer".
+    #[clap(short, long)]
+    manifest: PathBuf,
+
+    /// The network's policy parameters, CBOR-encoded. Defaults to the policy of this build.
+    #[clap(short, long, required = false, conflicts_with = "network")]
+    policy: Option<PathBuf>,
+
+    /// The name of a network whose default policy to use, e.g. "calibrationnet", instead of a
+    /// policy file.
+    #[clap(short, long, required = false)]
+    network: Option<String>,
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let manifest = load_manifest(&cli.manifest)?;
+    let policy = load_policy(cli.network.as_deref(), cli.policy.as_deref())?;
+
+    // Both trees are loaded into one store, so that unchanged collections share their blocks.
+    let store = MemoryBlockstore::new();
+    let old = load_actors_root(&store, &cli.old)?;
+    let new = load_actors_root(&store, &cli.new)?;
+    let diff = diff_state_trees(&manifest, &policy, &store, &old, &new)?;
+    if diff.is_empty() {
+        println!("no differences");
+    } else {
+        print!("{}", diff);
+    }
+    Ok(())
+}
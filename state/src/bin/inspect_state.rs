@@ -0,0 +1,72 @@
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use clap::Parser;
+use fil_builtin_actors_state::car::load_actors_root;
+use fil_builtin_actors_state::check::Tree;
+use fil_builtin_actors_state::inspect::{inspect_actor, DEFAULT_ENTRY_LIMIT};
+use fil_builtin_actors_state::manifest::{load_manifest, load_policy};
+use fvm_ipld_blockstore::MemoryBlockstore;
+use fvm_shared::address::Address;
+
+#[derive(Parser)]
+#[clap(name = "inspect-state")]
+#[clap(version = env!("CARGO_PKG_VERSION"))]
+#[clap(about = "Prints the state of an actor in a state tree exported to a CAR file.", long_about = None)]
+struct Cli {
+    /// The CAR file, whose root is a chain state root or the root of the actors HAMT.
+    car: PathBuf,
+
+    /// The address of the actor, e.g. f01000. Other addresses are resolved to an ID address by
+    /// the init actor.
+    address: Address,
+
+    /// A JSON object mapping actor code CIDs to actor type names, e.g. "storageminer".
+    #[clap(short, long)]
+    manifest: PathBuf,
+
+    /// The network's policy parameters, CBOR-encoded. Defaults to the policy of this build.
+    #[clap(short, long, required = false, conflicts_with = "network")]
+    policy: Option<PathBuf>,
+
+    /// The name of a network whose default policy to use, e.g. "calibrationnet", instead of a
+    /// policy file.
+    #[clap(short, long, required = false)]
+    network: Option<String>,
+
+    /// The number of entries of each HAMT, AMT, bitfield or list to skip, to print the page of
+    /// entries after them.
+    #[clap(short, long, default_value_t = 0)]
+    offset: u64,
+
+    /// The number of entries of each HAMT, AMT, bitfield or list to print.
+    #[clap(short, long, default_value_t = DEFAULT_ENTRY_LIMIT)]
+    limit: usize,
+
+    /// Prints the state as JSON.
+    #[clap(long)]
+    json: bool,
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let manifest = load_manifest(&cli.manifest)?;
+    let policy = load_policy(cli.network.as_deref(), cli.policy.as_deref())?;
+
+    let store = MemoryBlockstore::new();
+    let tree = Tree::load(&store, &load_actors_root(&store, &cli.car)?)?;
+    let actor = tree
+        .resolve_address(&cli.address)?
+        .map(|id| tree.get_actor(&id))
+        .transpose()?
+        .flatten()
+        .ok_or_else(|| anyhow!("no actor at {}", cli.address))?;
+
+    let node = inspect_actor(&manifest, &policy, &store, &actor, cli.offset, cli.limit)?;
+    if cli.json {
+        println!("{}", serde_json::to_string_pretty(&node.to_json())?);
+    } else {
+        print!("{}", node);
+    }
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+use anyhow::{anyhow, Context};
+use cid::Cid;
+use futures::executor::block_on;
+use futures::io::AllowStdIo;
+use fvm_ipld_blockstore::Blockstore;
+use fvm_ipld_car::load_car;
+use fvm_ipld_encoding::from_slice;
+use fvm_ipld_encoding::tuple::*;
+
+/// The root of a chain's state tree, which wraps the actors HAMT.
+#[derive(Serialize_tuple, Deserialize_tuple, Clone, Debug)]
+pub struct StateRoot {
+    pub version: u64,
+    pub actors: Cid,
+    pub info: Cid,
+}
+
+/// Loads the blocks of a CAR file into a store, returning the file's single root.
+pub fn read_car<BS: Blockstore, R: Read + Send + Unpin>(
+    store: &BS,
+    reader: R,
+) -> anyhow::Result<Cid> {
+    let roots = block_on(load_car(store, AllowStdIo::new(reader)))?;
+    match roots.as_slice() {
+        [root] => Ok(*root),
+        _ => Err(anyhow!("expected a CAR file with one root, found {}", roots.len())),
+    }
+}
+
+/// Resolves the root of an actors HAMT from a root that may instead be a chain's `StateRoot`.
+pub fn actors_root<BS: Blockstore>(store: &BS, root: &Cid) -> anyhow::Result<Cid> {
+    let block = store.get(root)?.ok_or_else(|| anyhow!("missing root block {}", root))?;
+    Ok(from_slice::<StateRoot>(&block).map_or(*root, |st| st.actors))
+}
+
+/// Loads the blocks of a CAR file at a path into a store, returning the root of its actors HAMT.
+pub fn load_actors_root<BS: Blockstore>(store: &BS, path: &Path) -> anyhow::Result<Cid> {
+    let file = File::open(path).with_context(|| format!("opening {}", path.display()))?;
+    let root = read_car(store, BufReader::new(file))?;
+    actors_root(store, &root)
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 
 use anyhow::bail;
@@ -19,13 +19,16 @@ use fil_actor_paych::State as PaychState;
 use fil_actor_power::testing::MinerCronEvent;
 use fil_actor_power::State as PowerState;
 use fil_actor_reward::State as RewardState;
-use fil_actor_verifreg::{DataCap, State as VerifregState};
+use fil_actor_verifreg::{Claim, DataCap, State as VerifregState};
 
 use fil_actors_runtime::runtime::Policy;
+use fil_actors_runtime::INIT_ACTOR_ADDR;
+use fil_actors_runtime::STORAGE_MARKET_ACTOR_ADDR;
 use fil_actors_runtime::VERIFIED_REGISTRY_ACTOR_ADDR;
 
 use fil_actors_runtime::Map;
 use fil_actors_runtime::MessageAccumulator;
+use fil_actors_runtime::Violation;
 use fvm_ipld_blockstore::Blockstore;
 use fvm_ipld_encoding::from_slice;
 use fvm_ipld_encoding::CborStore;
@@ -33,10 +36,12 @@ use fvm_shared::address::Address;
 use fvm_shared::address::Protocol;
 
 use fvm_shared::clock::ChainEpoch;
+use fvm_shared::deal::DealID;
 use fvm_shared::econ::TokenAmount;
 use num_traits::Zero;
 
 use anyhow::anyhow;
+use frc46_token::token::state::StateSummary as DataCapSummary;
 use fvm_ipld_encoding::tuple::*;
 
 use fil_actor_account::testing as account;
@@ -83,6 +88,26 @@ impl<'a, BS: Blockstore> Tree<'a, BS> {
         Ok(Tree { map, store })
     }
 
+    /// Gets the actor at an ID address, if present.
+    pub fn get_actor(&self, address: &Address) -> anyhow::Result<Option<Actor>> {
+        Ok(self.map.get(&address.to_bytes())?.cloned())
+    }
+
+    /// Resolves an address to an ID address through the init actor's address map, or `None`
+    /// if no actor has the address.
+    pub fn resolve_address(&self, address: &Address) -> anyhow::Result<Option<Address>> {
+        if address.protocol() == Protocol::ID {
+            return Ok(Some(*address));
+        }
+        let init =
+            self.get_actor(&INIT_ACTOR_ADDR)?.ok_or_else(|| anyhow!("init actor not found"))?;
+        let init_state: InitState = self
+            .store
+            .get_cbor(&init.head)?
+            .ok_or_else(|| anyhow!("init actor state not found"))?;
+        init_state.resolve_address(self.store, address).map_err(|e| anyhow!(e))
+    }
+
     pub fn for_each<F>(&self, mut f: F) -> anyhow::Result<()>
     where
         F: FnMut(&Address, &Actor) -> anyhow::Result<()>,
@@ -97,9 +122,8 @@ impl<'a, BS: Blockstore> Tree<'a, BS> {
 }
 
 macro_rules! get_state {
-    ($tree:ident, $actor:ident, $state:ty) => {
-        $tree
-            .store
+    ($store:ident, $actor:ident, $state:ty) => {
+        $store
             .get_cbor::<$state>(&$actor.head)?
             .ok_or_else(|| anyhow!("{} is empty", stringify!($state)))?
     };
@@ -118,234 +142,606 @@ pub fn check_state_invariants<'a, BS: Blockstore + Debug>(
 ) -> anyhow::Result<MessageAccumulator> {
     let acc = MessageAccumulator::default();
     let mut total_fil = TokenAmount::zero();
+    let mut summaries = Vec::<(Address, ActorSummary)>::new();
+
+    tree.for_each(|key, actor| {
+        total_fil += &actor.balance;
+        let typ = manifest.get_by_left(&actor.code).copied();
+        let (summary, msgs) = check_actor(typ, policy, tree.store, key, actor, prior_epoch)?;
+        acc.add_all(&msgs);
+        summaries.push((*key, summary));
+        Ok(())
+    })?;
 
-    let mut init_summary: Option<init::StateSummary> = None;
-    let mut cron_summary: Option<cron::StateSummary> = None;
-    let mut account_summaries = Vec::<account::StateSummary>::new();
-    let mut power_summary: Option<power::StateSummary> = None;
-    let mut miner_summaries = HashMap::<Address, miner::StateSummary>::new();
-    let mut market_summary: Option<market::StateSummary> = None;
-    let mut paych_summaries = Vec::<paych::StateSummary>::new();
-    let mut multisig_summaries = Vec::<multisig::StateSummary>::new();
-    let mut reward_summary: Option<reward::StateSummary> = None;
-    let mut verifreg_summary: Option<verifreg::StateSummary> = None;
-    let mut datacap_summary: Option<frc46_token::token::state::StateSummary> = None;
+    check_cross_actor_invariants(&acc, &Summaries::new(summaries.iter().map(|(k, s)| (k, s))));
+    check_total_balance(&acc, &total_fil, expected_balance_total);
+    Ok(acc)
+}
 
+/// Checks state invariants like `check_state_invariants`, dividing the actors between `threads`
+/// threads to check their states. Broken invariants are reported in the same order.
+pub fn check_state_invariants_parallel<'a, BS: Blockstore + Debug + Sync>(
+    manifest: &BiBTreeMap<Cid, Type>,
+    policy: &Policy,
+    tree: Tree<'a, BS>,
+    expected_balance_total: &TokenAmount,
+    prior_epoch: ChainEpoch,
+    threads: usize,
+) -> anyhow::Result<MessageAccumulator> {
+    let mut total_fil = TokenAmount::zero();
+    let mut actors = Vec::<(Address, Actor, Option<Type>)>::new();
     tree.for_each(|key, actor| {
-        let acc = acc.with_prefix(format!("{key} "));
+        total_fil += &actor.balance;
+        actors.push((*key, actor.clone(), manifest.get_by_left(&actor.code).copied()));
+        Ok(())
+    })?;
+
+    let store = tree.store;
+    let threads = threads.max(1);
+    let chunk_size = ((actors.len() + threads - 1) / threads).max(1);
+    let checked = std::thread::scope(|s| {
+        // Every thread is spawned before any is joined.
+        #[allow(clippy::needless_collect)]
+        let handles: Vec<_> = actors
+            .chunks(chunk_size)
+            .map(|chunk| {
+                s.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|(key, actor, typ)| {
+                            check_actor(*typ, policy, store, key, actor, prior_epoch)
+                        })
+                        .collect::<anyhow::Result<Vec<_>>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|h| h.join().map_err(|_| anyhow!("invariant check thread panicked"))?)
+            .collect::<anyhow::Result<Vec<_>>>()
+    })?;
+    let checked: Vec<_> = checked.into_iter().flatten().collect();
+
+    let acc = MessageAccumulator::default();
+    for (_, msgs) in &checked {
+        acc.add_all(msgs);
+    }
+    let summaries = Summaries::new(
+        actors.iter().zip(&checked).map(|((key, _, _), (summary, _))| (key, summary)),
+    );
+    check_cross_actor_invariants(&acc, &summaries);
+    check_total_balance(&acc, &total_fil, expected_balance_total);
+    Ok(acc)
+}
+
+/// Renders the broken invariants recorded by an accumulator as a JSON array of violations, e.g.
+/// for monitoring to track specific invariants by ID.
+pub fn violations_to_json(acc: &MessageAccumulator) -> serde_json::Result<String> {
+    serde_json::to_string(&acc.violations())
+}
+
+/// The results of checking the invariants of a state tree, from which a later state of the
+/// tree can be checked incrementally.
+pub struct StateCheck {
+    root: Cid,
+    prior_epoch: ChainEpoch,
+    total_fil: TokenAmount,
+    actors: HashMap<Address, CheckedActor>,
+    cross_actor_msgs: HashMap<(CrossActorCheck, Address), Vec<Violation>>,
+    shared_msgs: Vec<Violation>,
+}
+
+impl StateCheck {
+    /// The root of the checked state tree.
+    pub fn root(&self) -> &Cid {
+        &self.root
+    }
+
+    fn accumulator(&self) -> MessageAccumulator {
+        let mut actors: Vec<_> = self.actors.iter().collect();
+        actors.sort_by_key(|(key, _)| address_order(key));
+        let mut cross_actor_msgs: Vec<_> = self.cross_actor_msgs.iter().collect();
+        cross_actor_msgs.sort_by_key(|((check, provider), _)| (*check, address_order(provider)));
+
+        let acc = MessageAccumulator::default();
+        acc.add_violations(
+            actors
+                .into_iter()
+                .flat_map(|(_, checked)| &checked.msgs)
+                .chain(cross_actor_msgs.into_iter().flat_map(|(_, msgs)| msgs))
+                .chain(&self.shared_msgs)
+                .cloned(),
+        );
+        acc
+    }
+}
 
-        if key.protocol() != Protocol::ID {
-            acc.add(format!("unexpected address protocol in state tree root: {key}"));
+struct CheckedActor {
+    actor: Actor,
+    msgs: Vec<Violation>,
+    summary: ActorSummary,
+}
+
+/// Checks the invariants of the state tree at `root`, re-using the results of a previous check of
+/// the same tree for the parts of it that have not changed.
+///
+/// Only actors whose code, head or balance changed are checked again, along with actors whose
+/// checks depend on the epoch when it changed. Cross-actor checks are re-run only for the miners
+/// whose summaries changed, unless the power, market or verified registry summary they are
+/// checked against changed.
+/// Returns the same broken invariants as `check_state_invariants`, with actors ordered by address,
+/// and the results to check the next state against.
+pub fn check_state_invariants_incremental<BS: Blockstore + Debug>(
+    manifest: &BiBTreeMap<Cid, Type>,
+    policy: &Policy,
+    store: &BS,
+    root: &Cid,
+    expected_balance_total: &TokenAmount,
+    prior_epoch: ChainEpoch,
+    previous: Option<StateCheck>,
+) -> anyhow::Result<(MessageAccumulator, StateCheck)> {
+    let (mut prev_actors, mut prev_cross_actor_msgs, prev_shared_msgs, prev_epoch) = match previous
+    {
+        Some(previous) if previous.root == *root && previous.prior_epoch == prior_epoch => {
+            let acc = previous.accumulator();
+            check_total_balance(&acc, &previous.total_fil, expected_balance_total);
+            return Ok((acc, previous));
         }
-        total_fil += &actor.balance;
+        Some(previous) => (
+            previous.actors,
+            previous.cross_actor_msgs,
+            Some(previous.shared_msgs),
+            Some(previous.prior_epoch),
+        ),
+        None => Default::default(),
+    };
+    let epoch_changed = prev_epoch != Some(prior_epoch);
 
-        match manifest.get_by_left(&actor.code) {
-            Some(Type::System) => (),
-            Some(Type::Init) => {
-                let state = get_state!(tree, actor, InitState);
-                let (summary, msgs) = init::check_state_invariants(&state, tree.store);
-                acc.with_prefix("init: ").add_all(&msgs);
-                init_summary = Some(summary);
-            }
-            Some(Type::Cron) => {
-                let state = get_state!(tree, actor, CronState);
-                let (summary, msgs) = cron::check_state_invariants(&state);
-                acc.with_prefix("cron: ").add_all(&msgs);
-                cron_summary = Some(summary);
-            }
-            Some(Type::Account) => {
-                let state = get_state!(tree, actor, AccountState);
-                let (summary, msgs) = account::check_state_invariants(&state, key);
-                acc.with_prefix("account: ").add_all(&msgs);
-                account_summaries.push(summary);
-            }
-            Some(Type::Power) => {
-                let state = get_state!(tree, actor, PowerState);
-                let (summary, msgs) = power::check_state_invariants(policy, &state, tree.store);
-                acc.with_prefix("power: ").add_all(&msgs);
-                power_summary = Some(summary);
-            }
-            Some(Type::Miner) => {
-                let state = get_state!(tree, actor, MinerState);
-                let (summary, msgs) =
-                    miner::check_state_invariants(policy, &state, tree.store, &actor.balance);
-                acc.with_prefix("miner: ").add_all(&msgs);
-                miner_summaries.insert(*key, summary);
-            }
-            Some(Type::Market) => {
-                let state = get_state!(tree, actor, MarketState);
-                let (summary, msgs) = market::check_state_invariants(
-                    &state,
-                    tree.store,
-                    &actor.balance,
-                    prior_epoch + 1,
-                );
-                acc.with_prefix("market: ").add_all(&msgs);
-                market_summary = Some(summary);
-            }
-            Some(Type::PaymentChannel) => {
-                let state = get_state!(tree, actor, PaychState);
-                let (summary, msgs) =
-                    paych::check_state_invariants(&state, tree.store, &actor.balance);
-                acc.with_prefix("paych: ").add_all(&msgs);
-                paych_summaries.push(summary);
+    let mut changes = Changes::default();
+    let mut total_fil = TokenAmount::zero();
+    let mut actors = HashMap::new();
+    Tree::load(store, root)?.for_each(|key, actor| {
+        total_fil += &actor.balance;
+        let typ = manifest.get_by_left(&actor.code).copied();
+        let checked = match prev_actors.remove(key) {
+            Some(prev) if prev.actor == *actor && !(epoch_changed && depends_on_epoch(typ)) => prev,
+            prev => {
+                let (summary, msgs) = check_actor(typ, policy, store, key, actor, prior_epoch)?;
+                if let Some(prev) = prev {
+                    changes.record(key, &prev.summary);
+                }
+                changes.record(key, &summary);
+                CheckedActor { actor: actor.clone(), msgs: msgs.violations(), summary }
             }
-            Some(Type::Multisig) => {
-                let state = get_state!(tree, actor, MultisigState);
-                let (summary, msgs) = multisig::check_state_invariants(&state, tree.store);
-                acc.with_prefix("multisig: ").add_all(&msgs);
-                multisig_summaries.push(summary);
+        };
+        actors.insert(*key, checked);
+        Ok(())
+    })?;
+    // Actors remaining from the previous check have been deleted.
+    for (key, prev) in &prev_actors {
+        changes.record(key, &prev.summary);
+    }
+
+    let mut cross_actor_msgs = HashMap::new();
+    let shared_msgs;
+    {
+        let summaries = Summaries::new(actors.iter().map(|(key, checked)| (key, &checked.summary)));
+        for check in CrossActorCheck::ALL {
+            let recheck_all = changes.affects(check);
+            for provider in summaries.providers(check) {
+                let msgs = match prev_cross_actor_msgs.remove(&(check, provider)) {
+                    Some(msgs) if !recheck_all && !changes.addresses.contains(&provider) => msgs,
+                    _ => {
+                        let acc = MessageAccumulator::default();
+                        check_provider(&acc, check, &provider, &summaries);
+                        acc.violations()
+                    }
+                };
+                cross_actor_msgs.insert((check, provider), msgs);
             }
-            Some(Type::Reward) => {
-                let state = get_state!(tree, actor, RewardState);
-                let (summary, msgs) =
-                    reward::check_state_invariants(&state, prior_epoch, &actor.balance);
-                acc.with_prefix("reward: ").add_all(&msgs);
-                reward_summary = Some(summary);
+        }
+        shared_msgs = match prev_shared_msgs {
+            Some(msgs) if !(changes.market || changes.verifreg || changes.datacap) => msgs,
+            _ => {
+                let acc = MessageAccumulator::default();
+                check_shared_invariants(&acc, &summaries);
+                acc.violations()
             }
-            Some(Type::VerifiedRegistry) => {
-                let state = get_state!(tree, actor, VerifregState);
-                let (summary, msgs) =
-                    verifreg::check_state_invariants(&state, tree.store, prior_epoch);
-                acc.with_prefix("verifreg: ").add_all(&msgs);
-                verifreg_summary = Some(summary);
+        };
+    }
+
+    let check =
+        StateCheck { root: *root, prior_epoch, total_fil, actors, cross_actor_msgs, shared_msgs };
+    let acc = check.accumulator();
+    check_total_balance(&acc, &check.total_fil, expected_balance_total);
+    Ok((acc, check))
+}
+
+/// The actors and summaries changed since a previous check.
+#[derive(Default)]
+struct Changes {
+    addresses: HashSet<Address>,
+    power: bool,
+    market: bool,
+    verifreg: bool,
+    datacap: bool,
+}
+
+impl Changes {
+    fn record(&mut self, key: &Address, summary: &ActorSummary) {
+        self.addresses.insert(*key);
+        match summary {
+            ActorSummary::Power(_) => self.power = true,
+            ActorSummary::Market(_) => self.market = true,
+            ActorSummary::VerifiedRegistry(_) => self.verifreg = true,
+            ActorSummary::DataCap(_) => self.datacap = true,
+            ActorSummary::Miner(_) | ActorSummary::Other => (),
+        }
+    }
+
+    /// Whether a cross-actor check must be re-run for every provider.
+    fn affects(&self, check: CrossActorCheck) -> bool {
+        match check {
+            CrossActorCheck::Power => self.power,
+            CrossActorCheck::Deals => self.market,
+            CrossActorCheck::Claims => self.verifreg,
+        }
+    }
+}
+
+fn depends_on_epoch(typ: Option<Type>) -> bool {
+    matches!(typ, Some(Type::Market | Type::Reward | Type::VerifiedRegistry))
+}
+
+/// Orders ID addresses by ID, ahead of any other addresses, which are ordered by their bytes.
+pub(crate) fn address_order(address: &Address) -> (u64, Vec<u8>) {
+    (address.id().unwrap_or(u64::MAX), address.to_bytes())
+}
+
+/// The summary of an actor's state, for the actors whose summaries are used by cross-actor checks.
+enum ActorSummary {
+    Power(power::StateSummary),
+    Miner(miner::StateSummary),
+    Market(market::StateSummary),
+    VerifiedRegistry(verifreg::StateSummary),
+    DataCap(DataCapSummary),
+    Other,
+}
+
+/// Checks the invariants of a single actor's state, returning its summary and broken invariants.
+fn check_actor<BS: Blockstore + Debug>(
+    typ: Option<Type>,
+    policy: &Policy,
+    store: &BS,
+    key: &Address,
+    actor: &Actor,
+    prior_epoch: ChainEpoch,
+) -> anyhow::Result<(ActorSummary, MessageAccumulator)> {
+    let msgs = MessageAccumulator::default();
+    let acc = msgs.with_prefix(format!("{key} "));
+    let acc = match typ {
+        Some(typ) => acc.with_actor(*key, typ.name()),
+        None => acc,
+    };
+
+    if key.protocol() != Protocol::ID {
+        acc.invariant("state.actor.id_address")
+            .add(format!("unexpected address protocol in state tree root: {key}"));
+    }
+
+    let summary = match typ {
+        Some(Type::System) => ActorSummary::Other,
+        Some(Type::Init) => {
+            let state = get_state!(store, actor, InitState);
+            let (_, msgs) = init::check_state_invariants(&state, store);
+            acc.add_all(&msgs);
+            ActorSummary::Other
+        }
+        Some(Type::Cron) => {
+            let state = get_state!(store, actor, CronState);
+            let (_, msgs) = cron::check_state_invariants(&state);
+            acc.add_all(&msgs);
+            ActorSummary::Other
+        }
+        Some(Type::Account) => {
+            let state = get_state!(store, actor, AccountState);
+            let (_, msgs) = account::check_state_invariants(&state, key);
+            acc.add_all(&msgs);
+            ActorSummary::Other
+        }
+        Some(Type::Power) => {
+            let state = get_state!(store, actor, PowerState);
+            let (summary, msgs) = power::check_state_invariants(policy, &state, store);
+            acc.add_all(&msgs);
+            ActorSummary::Power(summary)
+        }
+        Some(Type::Miner) => {
+            let state = get_state!(store, actor, MinerState);
+            let (summary, msgs) =
+                miner::check_state_invariants(policy, &state, store, &actor.balance);
+            acc.add_all(&msgs);
+            ActorSummary::Miner(summary)
+        }
+        Some(Type::Market) => {
+            let state = get_state!(store, actor, MarketState);
+            let (summary, msgs) =
+                market::check_state_invariants(&state, store, &actor.balance, prior_epoch + 1);
+            acc.add_all(&msgs);
+            ActorSummary::Market(summary)
+        }
+        Some(Type::PaymentChannel) => {
+            let state = get_state!(store, actor, PaychState);
+            let (_, msgs) = paych::check_state_invariants(&state, store, &actor.balance);
+            acc.add_all(&msgs);
+            ActorSummary::Other
+        }
+        Some(Type::Multisig) => {
+            let state = get_state!(store, actor, MultisigState);
+            let (_, msgs) = multisig::check_state_invariants(&state, store);
+            acc.add_all(&msgs);
+            ActorSummary::Other
+        }
+        Some(Type::Reward) => {
+            let state = get_state!(store, actor, RewardState);
+            let (_, msgs) = reward::check_state_invariants(&state, prior_epoch, &actor.balance);
+            acc.add_all(&msgs);
+            ActorSummary::Other
+        }
+        Some(Type::VerifiedRegistry) => {
+            let state = get_state!(store, actor, VerifregState);
+            let (summary, msgs) = verifreg::check_state_invariants(&state, store, prior_epoch);
+            acc.add_all(&msgs);
+            ActorSummary::VerifiedRegistry(summary)
+        }
+        Some(Type::DataCap) => {
+            let state = get_state!(store, actor, DataCapState);
+            let (summary, msgs) = datacap::check_state_invariants(&state, store);
+            acc.add_all(&msgs);
+            ActorSummary::DataCap(summary)
+        }
+        None => {
+            bail!("unexpected actor code CID {} for address {}", actor.code, key);
+        }
+    };
+
+    Ok((summary, msgs))
+}
+
+/// The checks of each miner, or storage provider, against other actors' summaries.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum CrossActorCheck {
+    Power,
+    Deals,
+    Claims,
+}
+
+impl CrossActorCheck {
+    const ALL: [CrossActorCheck; 3] =
+        [CrossActorCheck::Power, CrossActorCheck::Deals, CrossActorCheck::Claims];
+}
+
+/// The actor summaries used by cross-actor checks, with activated deals and claims indexed by
+/// provider.
+#[derive(Default)]
+struct Summaries<'a> {
+    power: Option<&'a power::StateSummary>,
+    miners: HashMap<Address, &'a miner::StateSummary>,
+    market: Option<&'a market::StateSummary>,
+    verifreg: Option<&'a verifreg::StateSummary>,
+    datacap: Option<&'a DataCapSummary>,
+    deals_by_provider: HashMap<Address, Vec<(&'a DealID, &'a market::DealSummary)>>,
+    claims_by_provider: HashMap<Address, Vec<&'a Claim>>,
+}
+
+impl<'a> Summaries<'a> {
+    fn new(actors: impl Iterator<Item = (&'a Address, &'a ActorSummary)>) -> Self {
+        let mut summaries = Summaries::default();
+        for (key, summary) in actors {
+            match summary {
+                ActorSummary::Power(summary) => summaries.power = Some(summary),
+                ActorSummary::Miner(summary) => {
+                    summaries.miners.insert(*key, summary);
+                }
+                ActorSummary::Market(summary) => summaries.market = Some(summary),
+                ActorSummary::VerifiedRegistry(summary) => summaries.verifreg = Some(summary),
+                ActorSummary::DataCap(summary) => summaries.datacap = Some(summary),
+                ActorSummary::Other => (),
             }
-            Some(Type::DataCap) => {
-                let state = get_state!(tree, actor, DataCapState);
-                let (summary, msgs) = datacap::check_state_invariants(&state, tree.store);
-                acc.with_prefix("datacap: ").add_all(&msgs);
-                datacap_summary = Some(summary);
+        }
+        if let Some(market_summary) = summaries.market {
+            for (deal_id, deal) in &market_summary.deals {
+                // deal hasn't been activated yet, make no assertions about sector state
+                if deal.sector_start_epoch != -1 {
+                    summaries
+                        .deals_by_provider
+                        .entry(deal.provider)
+                        .or_default()
+                        .push((deal_id, deal));
+                }
             }
-            None => {
-                bail!("unexpected actor code CID {} for address {}", actor.code, key);
+        }
+        if let Some(verifreg_summary) = summaries.verifreg {
+            for claim in verifreg_summary.claims.values() {
+                let provider = Address::new_id(claim.provider);
+                summaries.claims_by_provider.entry(provider).or_default().push(claim);
             }
-        };
+        }
+        summaries
+    }
 
-        Ok(())
-    })?;
+    /// The providers to run a cross-actor check for, ordered by address.
+    fn providers(&self, check: CrossActorCheck) -> Vec<Address> {
+        let mut providers: Vec<Address> = match check {
+            CrossActorCheck::Power if self.power.is_some() => self.miners.keys().copied().collect(),
+            CrossActorCheck::Power => vec![],
+            CrossActorCheck::Deals => self.deals_by_provider.keys().copied().collect(),
+            CrossActorCheck::Claims => self.claims_by_provider.keys().copied().collect(),
+        };
+        providers.sort_by_key(address_order);
+        providers
+    }
+}
 
-    // Perform cross-actor checks from state summaries here.
-    if let Some(power_summary) = power_summary {
-        check_miner_against_power(&acc, &miner_summaries, &power_summary);
+/// Checks each provider against the other actors' summaries, then the checks between those actors.
+fn check_cross_actor_invariants(acc: &MessageAccumulator, summaries: &Summaries) {
+    for check in CrossActorCheck::ALL {
+        for provider in summaries.providers(check) {
+            check_provider(acc, check, &provider, summaries);
+        }
     }
+    check_shared_invariants(acc, summaries);
+}
 
-    if let Some(market_summary) = market_summary.clone() {
-        check_deal_states_against_sectors(&acc, &miner_summaries, &market_summary);
+fn check_provider(
+    acc: &MessageAccumulator,
+    check: CrossActorCheck,
+    provider: &Address,
+    summaries: &Summaries,
+) {
+    let acc = &acc.with_actor(*provider, Type::Miner.name());
+    let miner_summary = summaries.miners.get(provider).copied();
+    match check {
+        CrossActorCheck::Power => {
+            if let (Some(miner_summary), Some(power_summary)) = (miner_summary, summaries.power) {
+                check_miner_against_power(acc, provider, miner_summary, power_summary);
+            }
+        }
+        CrossActorCheck::Deals => {
+            if let Some(deals) = summaries.deals_by_provider.get(provider) {
+                check_deal_states_against_sectors(acc, provider, miner_summary, deals);
+            }
+        }
+        CrossActorCheck::Claims => {
+            if let Some(claims) = summaries.claims_by_provider.get(provider) {
+                check_verifreg_against_miner(acc, provider, miner_summary, claims);
+            }
+        }
     }
+}
 
-    if let Some(verifreg_summary) = verifreg_summary {
-        if let Some(datacap_summary) = datacap_summary {
-            check_verifreg_against_datacap(&acc, &verifreg_summary, &datacap_summary);
+/// Checks between the market, verified registry and datacap actors, which don't depend on miners.
+fn check_shared_invariants(acc: &MessageAccumulator, summaries: &Summaries) {
+    if let Some(verifreg_summary) = summaries.verifreg {
+        if let Some(datacap_summary) = summaries.datacap {
+            let acc = acc.with_actor(VERIFIED_REGISTRY_ACTOR_ADDR, Type::VerifiedRegistry.name());
+            check_verifreg_against_datacap(&acc, verifreg_summary, datacap_summary);
         }
-        if let Some(market_summary) = market_summary {
-            check_market_against_verifreg(&acc, &market_summary, &verifreg_summary);
+        if let Some(market_summary) = summaries.market {
+            let acc = acc.with_actor(STORAGE_MARKET_ACTOR_ADDR, Type::Market.name());
+            check_market_against_verifreg(&acc, market_summary, verifreg_summary);
         }
-        check_verifreg_against_miners(&acc, &verifreg_summary, &miner_summaries);
     }
+}
 
-    acc.require(
-        &total_fil == expected_balance_total,
+fn check_total_balance(
+    acc: &MessageAccumulator,
+    total_fil: &TokenAmount,
+    expected_balance_total: &TokenAmount,
+) {
+    acc.invariant("state.total_balance").require(
+        total_fil == expected_balance_total,
         format!("total token balance is {total_fil}, expected {expected_balance_total}"),
     );
-
-    Ok(acc)
 }
 
 fn check_miner_against_power(
     acc: &MessageAccumulator,
-    miner_summaries: &HashMap<Address, miner::StateSummary>,
+    address: &Address,
+    miner_summary: &miner::StateSummary,
     power_summary: &power::StateSummary,
 ) {
-    for (address, miner_summary) in miner_summaries {
-        //check claim
-        if let Some(claim) = power_summary.claims.get(address) {
-            let claim_power =
-                PowerPair::new(claim.raw_byte_power.clone(), claim.quality_adj_power.clone());
-            acc.require(miner_summary.active_power == claim_power, format!("miner {address} computed active power {:?} does not match claim {claim_power:?}", miner_summary.active_power));
-            acc.require(
-                miner_summary.window_post_proof_type == claim.window_post_proof_type,
-                format!(
-                    "miner seal proof type {:?} does not match claim proof type {:?}",
-                    miner_summary.window_post_proof_type, claim.window_post_proof_type
-                ),
-            );
-        } else {
-            acc.add(format!("miner {address} has no power claim"));
-        }
+    //check claim
+    if let Some(claim) = power_summary.claims.get(address) {
+        let claim_power =
+            PowerPair::new(claim.raw_byte_power.clone(), claim.quality_adj_power.clone());
+        acc.invariant("power.claim.active_power").require(
+            miner_summary.active_power == claim_power,
+            format!(
+                "miner {address} computed active power {:?} does not match claim {claim_power:?}",
+                miner_summary.active_power
+            ),
+        );
+        acc.invariant("power.claim.post_proof_type").require(
+            miner_summary.window_post_proof_type == claim.window_post_proof_type,
+            format!(
+                "miner seal proof type {:?} does not match claim proof type {:?}",
+                miner_summary.window_post_proof_type, claim.window_post_proof_type
+            ),
+        );
+    } else {
+        acc.invariant("power.claim.exists").add(format!("miner {address} has no power claim"));
+    }
 
-        //check crons
-        let mut proving_period_cron: Option<&MinerCronEvent> = None;
-        if let Some(crons) = power_summary.crons.get(address) {
-            for event in crons {
-                match from_slice::<CronEventPayload>(event.payload.bytes()) {
-                    Ok(payload) => {
-                        acc.require(
-                            matches!(
-                                payload.event_type,
-                                CRON_EVENT_PROCESS_EARLY_TERMINATIONS | CRON_EVENT_PROVING_DEADLINE
-                            ),
-                            format!(
-                                "miner {address} has unexpected cron event type {}",
-                                payload.event_type
-                            ),
-                        );
-                        if payload.event_type == CRON_EVENT_PROVING_DEADLINE {
-                            if proving_period_cron.is_some() {
-                                acc.add(format!("miner {address} has duplicate proving period crons at epoch {} and {}", proving_period_cron.as_ref().unwrap().epoch, event.epoch));
-                            }
-                            proving_period_cron = Some(event);
+    //check crons
+    let mut proving_period_cron: Option<&MinerCronEvent> = None;
+    if let Some(crons) = power_summary.crons.get(address) {
+        for event in crons {
+            match from_slice::<CronEventPayload>(event.payload.bytes()) {
+                Ok(payload) => {
+                    acc.invariant("power.cron.event_type").require(
+                        matches!(
+                            payload.event_type,
+                            CRON_EVENT_PROCESS_EARLY_TERMINATIONS | CRON_EVENT_PROVING_DEADLINE
+                        ),
+                        format!(
+                            "miner {address} has unexpected cron event type {}",
+                            payload.event_type
+                        ),
+                    );
+                    if payload.event_type == CRON_EVENT_PROVING_DEADLINE {
+                        if proving_period_cron.is_some() {
+                            acc.invariant("power.cron.unique_proving_period").add(format!("miner {address} has duplicate proving period crons at epoch {} and {}", proving_period_cron.as_ref().unwrap().epoch, event.epoch));
                         }
+                        proving_period_cron = Some(event);
                     }
-                    Err(e) => acc.add(format!(
-                        "miner {address} registered cron at epoch {} with wrong or corrupt payload: {e}",
-                        event.epoch
-                    )),
                 }
-                acc.require(proving_period_cron.is_some() == miner_summary.deadline_cron_active, format!("miner {address} has invalid deadline_cron_active ({}) for proving_period_cron status ({})", miner_summary.deadline_cron_active, proving_period_cron.is_some()));
-                acc.require(
-                    proving_period_cron.is_some(),
-                    format!("miner {address} has no proving period cron"),
-                );
+                Err(e) => acc.invariant("power.cron.payload").add(format!(
+                    "miner {address} registered cron at epoch {} with wrong or corrupt payload: {e}",
+                    event.epoch
+                )),
             }
-        } else {
-            // with deferred and discontinued crons it is normal for a miner actor to have no cron
-            // events
-            acc.require(
-                !miner_summary.deadline_cron_active,
-                format!("miner {address} has no cron events but the deadline cron is active"),
+            acc.invariant("power.cron.deadline_cron_active").require(proving_period_cron.is_some() == miner_summary.deadline_cron_active, format!("miner {address} has invalid deadline_cron_active ({}) for proving_period_cron status ({})", miner_summary.deadline_cron_active, proving_period_cron.is_some()));
+            acc.invariant("power.cron.proving_period").require(
+                proving_period_cron.is_some(),
+                format!("miner {address} has no proving period cron"),
             );
         }
+    } else {
+        // with deferred and discontinued crons it is normal for a miner actor to have no cron
+        // events
+        acc.invariant("power.cron.deadline_cron_inactive").require(
+            !miner_summary.deadline_cron_active,
+            format!("miner {address} has no cron events but the deadline cron is active"),
+        );
     }
 }
 
 fn check_deal_states_against_sectors(
     acc: &MessageAccumulator,
-    miner_summaries: &HashMap<Address, miner::StateSummary>,
-    market_summary: &market::StateSummary,
+    provider: &Address,
+    miner_summary: Option<&miner::StateSummary>,
+    deals: &[(&DealID, &market::DealSummary)],
 ) {
     // Check that all active deals are included within a non-terminated sector.
     // We cannot check that all deals referenced within a sector are in the market, because deals
     // can be terminated independently of the sector in which they are included.
-    for (deal_id, deal) in &market_summary.deals {
-        if deal.sector_start_epoch == -1 {
-            // deal hasn't been activated yet, make no assertions about sector state
-            continue;
+    let miner_summary = if let Some(miner_summary) = miner_summary {
+        miner_summary
+    } else {
+        for (deal_id, _) in deals {
+            acc.related("deal", **deal_id)
+                .invariant("market.deal.provider_is_miner")
+                .add(format!("provider {} for deal {} not found among miners", provider, deal_id));
         }
+        return;
+    };
 
-        let miner_summary = if let Some(miner_summary) = miner_summaries.get(&deal.provider) {
-            miner_summary
-        } else {
-            acc.add(format!(
-                "provider {} for deal {} not found among miners",
-                deal.provider, &deal_id
-            ));
-            continue;
-        };
-
+    for (deal_id, deal) in deals {
+        let acc = acc.related("deal", **deal_id);
         let sector_deal = if let Some(sector_deal) = miner_summary.deals.get(deal_id) {
             sector_deal
         } else {
-            acc.require(
+            acc.invariant("market.deal.in_active_sector").require(
                 deal.slash_epoch >= 0,
                 format!(
                     "un-slashed deal {deal_id} not referenced in active sectors of miner {}",
@@ -355,7 +751,7 @@ fn check_deal_states_against_sectors(
             continue;
         };
 
-        acc.require(
+        acc.invariant("market.deal.sector_start").require(
             deal.sector_start_epoch == sector_deal.sector_start,
             format!(
                 "deal state start {} does not match sector start {} for miner {}",
@@ -363,7 +759,7 @@ fn check_deal_states_against_sectors(
             ),
         );
 
-        acc.require(
+        acc.invariant("market.deal.start_before_sector_expiration").require(
             deal.sector_start_epoch <= sector_deal.sector_expiration,
             format!(
                 "deal state start {} activated after sector expiration {} for miner {}",
@@ -371,7 +767,7 @@ fn check_deal_states_against_sectors(
             ),
         );
 
-        acc.require(
+        acc.invariant("market.deal.updated_before_sector_expiration").require(
             deal.last_update_epoch <= sector_deal.sector_expiration,
             format!(
                 "deal state update at {} after sector expiration {} for miner {}",
@@ -379,7 +775,7 @@ fn check_deal_states_against_sectors(
             ),
         );
 
-        acc.require(
+        acc.invariant("market.deal.slashed_before_sector_expiration").require(
             deal.slash_epoch <= sector_deal.sector_expiration,
             format!(
                 "deal state slashed at {} after sector expiration {} for miner {}",
@@ -392,11 +788,11 @@ fn check_deal_states_against_sectors(
 fn check_verifreg_against_datacap(
     acc: &MessageAccumulator,
     verifreg_summary: &verifreg::StateSummary,
-    datacap_summary: &frc46_token::token::state::StateSummary,
+    datacap_summary: &DataCapSummary,
 ) {
     // Verifier and datacap token holders are distinct.
     for verifier in verifreg_summary.verifiers.keys() {
-        acc.require(
+        acc.invariant("verifreg.verifier.not_datacap_holder").require(
             !datacap_summary.balance_map.as_ref().unwrap().contains_key(&verifier.id().unwrap()),
             format!("verifier {} is also a datacap token holder", verifier),
         );
@@ -411,7 +807,7 @@ fn check_verifreg_against_datacap(
         .get(&VERIFIED_REGISTRY_ACTOR_ADDR.id().unwrap())
         .cloned()
         .unwrap_or_else(TokenAmount::zero);
-    acc.require(
+    acc.invariant("verifreg.datacap_balance.pending_allocations").require(
         TokenAmount::from_whole(pending_alloc_total.clone()) == verifreg_balance,
         format!(
             "verifreg datacap balance {} does not match pending allocation size {}",
@@ -428,10 +824,12 @@ fn check_market_against_verifreg(
     // all activated verified deals with claim ids reference a claim in verifreg state
     // note that it is possible for claims to exist with no matching deal if the deal expires
     for (claim_id, deal_id) in &market_summary.claim_id_to_deal_id {
+        let acc = acc.related("claim", *claim_id).related("deal", *deal_id);
         // claim is found
         let claim = match verifreg_summary.claims.get(claim_id) {
             None => {
-                acc.add(format!("claim {} not found for activated deal {}", claim_id, deal_id));
+                acc.invariant("market.deal.claim_exists")
+                    .add(format!("claim {} not found for activated deal {}", claim_id, deal_id));
                 continue;
             }
             Some(claim) => claim,
@@ -439,7 +837,7 @@ fn check_market_against_verifreg(
 
         let info = match market_summary.deals.get(deal_id) {
             None => {
-                acc.add(format!(
+                acc.invariant("market.deal.has_proposal").add(format!(
                     "internal invariant error invalid market state referrences missing deal {}",
                     deal_id
                 ));
@@ -448,7 +846,7 @@ fn check_market_against_verifreg(
             Some(info) => info,
         };
         // claim and proposal match
-        acc.require(
+        acc.invariant("market.deal.claim_provider").require(
             info.provider.id().unwrap() == claim.provider,
             format!(
                 "mismatched providers {} {} on claim {} and deal {}",
@@ -458,7 +856,7 @@ fn check_market_against_verifreg(
                 deal_id
             ),
         );
-        acc.require(
+        acc.invariant("market.deal.claim_piece_cid").require(
             info.piece_cid.unwrap() == claim.data,
             format!(
                 "mismatched piece cid {} {} on claim {} and deal {}",
@@ -474,10 +872,11 @@ fn check_market_against_verifreg(
     // note that it is possible for allocations to exist that don't match any deal
     // if they are created from a direct DataCap transfer
     for (allocation_id, deal_id) in &market_summary.alloc_id_to_deal_id {
+        let acc = acc.related("allocation", *allocation_id).related("deal", *deal_id);
         // allocation is found
         let alloc = match verifreg_summary.allocations.get(allocation_id) {
             None => {
-                acc.add(format!(
+                acc.invariant("market.deal.allocation_exists").add(format!(
                     "allocation {} not found for pending deal {}",
                     allocation_id, deal_id
                 ));
@@ -488,7 +887,7 @@ fn check_market_against_verifreg(
         // alloc and proposal match
         let info = match market_summary.deals.get(deal_id) {
             None => {
-                acc.add(format!(
+                acc.invariant("market.deal.has_proposal").add(format!(
                     "internal invariant error invalid market state referrences missing deal {}",
                     deal_id
                 ));
@@ -496,7 +895,7 @@ fn check_market_against_verifreg(
             }
             Some(info) => info,
         };
-        acc.require(
+        acc.invariant("market.deal.allocation_provider").require(
             info.provider.id().unwrap() == alloc.provider,
             format!(
                 "mismatched providers {} {} on alloc {} and deal {}",
@@ -506,7 +905,7 @@ fn check_market_against_verifreg(
                 deal_id
             ),
         );
-        acc.require(
+        acc.invariant("market.deal.allocation_piece_cid").require(
             info.piece_cid.unwrap() == alloc.data,
             format!(
                 "mismatched piece cid {} {} on alloc {} and deal {}",
@@ -519,24 +918,28 @@ fn check_market_against_verifreg(
     }
 }
 
-fn check_verifreg_against_miners(
+fn check_verifreg_against_miner(
     acc: &MessageAccumulator,
-    verifreg_summary: &verifreg::StateSummary,
-    miner_summaries: &HashMap<Address, miner::StateSummary>,
+    maddr: &Address,
+    miner_summary: Option<&miner::StateSummary>,
+    claims: &[&Claim],
 ) {
-    for claim in verifreg_summary.claims.values() {
-        // all claims are indexed by valid providers
-        let maddr = Address::new_id(claim.provider);
-        let miner_summary = match miner_summaries.get(&maddr) {
-            None => {
-                acc.add(format!("claim provider {} is not found in miner summaries", maddr));
-                continue;
+    // all claims are indexed by valid providers
+    let miner_summary = match miner_summary {
+        None => {
+            for _ in claims {
+                acc.invariant("verifreg.claim.provider_is_miner")
+                    .add(format!("claim provider {} is not found in miner summaries", maddr));
             }
-            Some(summary) => summary,
-        };
+            return;
+        }
+        Some(summary) => summary,
+    };
 
+    for claim in claims {
+        let acc = acc.related("sector", claim.sector);
         // all claims are linked to a valid sector number
-        acc.require(
+        acc.invariant("verifreg.claim.sector_has_deals").require(
             miner_summary.sectors_with_deals.get(&claim.sector).is_some(),
             format!(
                 "claim sector number {} not recorded as a sector with deals for miner {}",
@@ -0,0 +1,580 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Debug, Display};
+
+use anyhow::anyhow;
+use bimap::BiBTreeMap;
+use cid::Cid;
+use fil_actor_datacap::State as DataCapState;
+use fil_actor_market::{DealProposal, DealState, State as MarketState};
+use fil_actor_miner::{
+    Deadline, Partition, SectorOnChainInfo, SectorPreCommitOnChainInfo, State as MinerState,
+};
+use fil_actor_multisig::{State as MultisigState, Transaction};
+use fil_actor_power::{Claim as PowerClaim, State as PowerState};
+use fil_actor_verifreg::{Allocation, Claim, State as VerifregState};
+use fil_actors_runtime::runtime::builtins::Type;
+use fil_actors_runtime::runtime::Policy;
+use fil_actors_runtime::MapKey;
+use fvm_ipld_bitfield::BitField;
+use fvm_ipld_blockstore::Blockstore;
+use fvm_ipld_encoding::de::DeserializeOwned;
+use fvm_ipld_encoding::CborStore;
+use fvm_shared::address::Address;
+use fvm_shared::bigint::bigint_ser::BigIntDe;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::ActorID;
+
+use crate::check::Actor;
+use crate::walk::{amt_entries, map_entries};
+
+/// The differences between two state trees, by actor ID.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StateDiff {
+    pub actors: BTreeMap<ActorID, ActorDiff>,
+}
+
+impl StateDiff {
+    pub fn is_empty(&self) -> bool {
+        self.actors.is_empty()
+    }
+}
+
+/// The difference between an actor's entries in two state trees.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ActorDiff {
+    Added {
+        typ: Option<Type>,
+        actor: Actor,
+    },
+    Removed {
+        typ: Option<Type>,
+        actor: Actor,
+    },
+    /// The changes to an actor present in both trees. If its code changed, `typ` is its new type.
+    Changed {
+        typ: Option<Type>,
+        changes: Vec<StateChange>,
+    },
+}
+
+/// A change at a path within an actor, e.g. `balance`, `sectors/12` or
+/// `deadlines/3/partitions/0/faults`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StateChange {
+    pub path: String,
+    pub change: Change,
+}
+
+/// A change to a value, or an entry of a collection. Values are rendered with `Debug`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Change {
+    Added(String),
+    Removed(String),
+    Changed { old: String, new: String },
+}
+
+/// Computes the semantic differences between two state trees in one store, identified by the
+/// roots of their actors HAMTs.
+///
+/// The actors HAMT and the HAMTs and AMTs of actor states are walked in step, loading only the
+/// nodes whose CIDs differ. The states of the builtin actors with collections are compared
+/// through their typed state; other actors report a changed head.
+pub fn diff_state_trees<BS: Blockstore>(
+    manifest: &BiBTreeMap<Cid, Type>,
+    policy: &Policy,
+    store: &BS,
+    old_root: &Cid,
+    new_root: &Cid,
+) -> anyhow::Result<StateDiff> {
+    let mut diff = StateDiff::default();
+    if old_root == new_root {
+        return Ok(diff);
+    }
+    let (old, new) = map_entries::<_, IdAddress, Actor>(store, Some(old_root), Some(new_root))?;
+    let typ = |actor: &Actor| manifest.get_by_left(&actor.code).copied();
+
+    for (address, old_actor) in &old {
+        let actor_diff = match new.get(address) {
+            None => ActorDiff::Removed { typ: typ(old_actor), actor: old_actor.clone() },
+            Some(new_actor) if new_actor == old_actor => continue,
+            Some(new_actor) => {
+                let changes = diff_actor(
+                    typ(old_actor),
+                    typ(new_actor),
+                    policy,
+                    store,
+                    old_actor,
+                    new_actor,
+                )?;
+                ActorDiff::Changed { typ: typ(new_actor), changes }
+            }
+        };
+        diff.actors.insert(address.0, actor_diff);
+    }
+    for (address, new_actor) in new {
+        if !old.contains_key(&address) {
+            diff.actors
+                .insert(address.0, ActorDiff::Added { typ: typ(&new_actor), actor: new_actor });
+        }
+    }
+    Ok(diff)
+}
+
+fn diff_actor<BS: Blockstore>(
+    old_typ: Option<Type>,
+    new_typ: Option<Type>,
+    policy: &Policy,
+    store: &BS,
+    old: &Actor,
+    new: &Actor,
+) -> anyhow::Result<Vec<StateChange>> {
+    let mut changes = Changes::default();
+    changes.value("code", &old.code, &new.code);
+    changes.value("call_seq_num", &old.call_seq_num, &new.call_seq_num);
+    changes.value("balance", &old.balance, &new.balance);
+    if old.head == new.head {
+        return Ok(changes.0);
+    }
+
+    let (old_head, new_head) = (&old.head, &new.head);
+    match old_typ.filter(|_| old_typ == new_typ) {
+        Some(Type::Miner) => diff_miner(
+            &mut changes,
+            policy,
+            store,
+            &load_state(store, old_head)?,
+            &load_state(store, new_head)?,
+        )?,
+        Some(Type::Market) => diff_market(
+            &mut changes,
+            store,
+            &load_state(store, old_head)?,
+            &load_state(store, new_head)?,
+        )?,
+        Some(Type::Power) => diff_power(
+            &mut changes,
+            store,
+            &load_state(store, old_head)?,
+            &load_state(store, new_head)?,
+        )?,
+        Some(Type::VerifiedRegistry) => diff_verifreg(
+            &mut changes,
+            store,
+            &load_state(store, old_head)?,
+            &load_state(store, new_head)?,
+        )?,
+        Some(Type::DataCap) => diff_datacap(
+            &mut changes,
+            store,
+            &load_state(store, old_head)?,
+            &load_state(store, new_head)?,
+        )?,
+        Some(Type::Multisig) => diff_multisig(
+            &mut changes,
+            store,
+            &load_state(store, old_head)?,
+            &load_state(store, new_head)?,
+        )?,
+        _ => changes.value("head", old_head, new_head),
+    }
+    Ok(changes.0)
+}
+
+fn diff_miner<BS: Blockstore>(
+    changes: &mut Changes,
+    policy: &Policy,
+    store: &BS,
+    old: &MinerState,
+    new: &MinerState,
+) -> anyhow::Result<()> {
+    if old.info != new.info {
+        changes.value("info", &old.get_info(store)?, &new.get_info(store)?);
+    }
+    changes.value("pre_commit_deposits", &old.pre_commit_deposits, &new.pre_commit_deposits);
+    changes.value("locked_funds", &old.locked_funds, &new.locked_funds);
+    changes.value("fee_debt", &old.fee_debt, &new.fee_debt);
+    changes.value("initial_pledge", &old.initial_pledge, &new.initial_pledge);
+    changes.value("proving_period_start", &old.proving_period_start, &new.proving_period_start);
+    changes.value("current_deadline", &old.current_deadline, &new.current_deadline);
+    changes.value("deadline_cron_active", &old.deadline_cron_active, &new.deadline_cron_active);
+    if old.allocated_sectors != new.allocated_sectors {
+        changes.bitfield(
+            "allocated_sectors",
+            &load_state(store, &old.allocated_sectors)?,
+            &load_state(store, &new.allocated_sectors)?,
+        );
+    }
+    changes.hamt::<_, u64, SectorPreCommitOnChainInfo>(
+        "pre_committed_sectors",
+        store,
+        &old.pre_committed_sectors,
+        &new.pre_committed_sectors,
+    )?;
+    changes.amt::<_, SectorOnChainInfo>("sectors", store, &old.sectors, &new.sectors)?;
+
+    if old.deadlines != new.deadlines {
+        let old_deadlines = old.load_deadlines(store)?;
+        let new_deadlines = new.load_deadlines(store)?;
+        for (i, (old_cid, new_cid)) in old_deadlines.due.iter().zip(&new_deadlines.due).enumerate()
+        {
+            if old_cid != new_cid {
+                diff_deadline(
+                    changes,
+                    &format!("deadlines/{}", i),
+                    store,
+                    &old_deadlines.load_deadline(policy, store, i as u64)?,
+                    &new_deadlines.load_deadline(policy, store, i as u64)?,
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn diff_deadline<BS: Blockstore>(
+    changes: &mut Changes,
+    path: &str,
+    store: &BS,
+    old: &Deadline,
+    new: &Deadline,
+) -> anyhow::Result<()> {
+    changes.value(format!("{}/live_sectors", path), &old.live_sectors, &new.live_sectors);
+    changes.value(format!("{}/total_sectors", path), &old.total_sectors, &new.total_sectors);
+    changes.value(format!("{}/faulty_power", path), &old.faulty_power, &new.faulty_power);
+    changes.bitfield(
+        format!("{}/partitions_posted", path),
+        &old.partitions_posted,
+        &new.partitions_posted,
+    );
+    if old.partitions == new.partitions {
+        return Ok(());
+    }
+
+    let (old_partitions, new_partitions) =
+        amt_entries::<_, Partition>(store, Some(&old.partitions), Some(&new.partitions))?;
+    for (i, old_partition) in &old_partitions {
+        let path = format!("{}/partitions/{}", path, i);
+        match new_partitions.get(i) {
+            Some(new_partition) => diff_partition(changes, &path, old_partition, new_partition),
+            None => changes.removed(path, &Sectors(&old_partition.sectors)),
+        }
+    }
+    for (i, new_partition) in &new_partitions {
+        if !old_partitions.contains_key(i) {
+            changes.added(format!("{}/partitions/{}", path, i), &Sectors(&new_partition.sectors));
+        }
+    }
+    Ok(())
+}
+
+fn diff_partition(changes: &mut Changes, path: &str, old: &Partition, new: &Partition) {
+    changes.bitfield(format!("{}/sectors", path), &old.sectors, &new.sectors);
+    changes.bitfield(format!("{}/unproven", path), &old.unproven, &new.unproven);
+    changes.bitfield(format!("{}/faults", path), &old.faults, &new.faults);
+    changes.bitfield(format!("{}/recoveries", path), &old.recoveries, &new.recoveries);
+    changes.bitfield(format!("{}/terminated", path), &old.terminated, &new.terminated);
+    changes.value(format!("{}/live_power", path), &old.live_power, &new.live_power);
+    changes.value(format!("{}/unproven_power", path), &old.unproven_power, &new.unproven_power);
+    changes.value(format!("{}/faulty_power", path), &old.faulty_power, &new.faulty_power);
+    changes.value(
+        format!("{}/recovering_power", path),
+        &old.recovering_power,
+        &new.recovering_power,
+    );
+}
+
+fn diff_market<BS: Blockstore>(
+    changes: &mut Changes,
+    store: &BS,
+    old: &MarketState,
+    new: &MarketState,
+) -> anyhow::Result<()> {
+    changes.value("next_id", &old.next_id, &new.next_id);
+    changes.value(
+        "total_client_locked_collateral",
+        &old.total_client_locked_collateral,
+        &new.total_client_locked_collateral,
+    );
+    changes.value(
+        "total_provider_locked_collateral",
+        &old.total_provider_locked_collateral,
+        &new.total_provider_locked_collateral,
+    );
+    changes.value(
+        "total_client_storage_fee",
+        &old.total_client_storage_fee,
+        &new.total_client_storage_fee,
+    );
+    changes.amt::<_, DealProposal>("proposals", store, &old.proposals, &new.proposals)?;
+    changes.amt::<_, DealState>("states", store, &old.states, &new.states)?;
+    changes.hamt::<_, IdAddress, BigIntDe>(
+        "escrow_table",
+        store,
+        &old.escrow_table,
+        &new.escrow_table,
+    )?;
+    changes.hamt::<_, IdAddress, BigIntDe>(
+        "locked_table",
+        store,
+        &old.locked_table,
+        &new.locked_table,
+    )?;
+    changes.hamt::<_, u64, u64>(
+        "pending_deal_allocation_ids",
+        store,
+        &old.pending_deal_allocation_ids,
+        &new.pending_deal_allocation_ids,
+    )
+}
+
+fn diff_power<BS: Blockstore>(
+    changes: &mut Changes,
+    store: &BS,
+    old: &PowerState,
+    new: &PowerState,
+) -> anyhow::Result<()> {
+    changes.value("total_raw_byte_power", &old.total_raw_byte_power, &new.total_raw_byte_power);
+    changes.value(
+        "total_quality_adj_power",
+        &old.total_quality_adj_power,
+        &new.total_quality_adj_power,
+    );
+    changes.value(
+        "total_pledge_collateral",
+        &old.total_pledge_collateral,
+        &new.total_pledge_collateral,
+    );
+    changes.value("miner_count", &old.miner_count, &new.miner_count);
+    changes.hamt::<_, IdAddress, PowerClaim>("claims", store, &old.claims, &new.claims)
+}
+
+fn diff_verifreg<BS: Blockstore>(
+    changes: &mut Changes,
+    store: &BS,
+    old: &VerifregState,
+    new: &VerifregState,
+) -> anyhow::Result<()> {
+    changes.value("root_key", &old.root_key, &new.root_key);
+    changes.hamt::<_, IdAddress, BigIntDe>("verifiers", store, &old.verifiers, &new.verifiers)?;
+    changes.nested_hamt::<_, Allocation>(
+        "allocations",
+        store,
+        &old.allocations,
+        &new.allocations,
+    )?;
+    changes.nested_hamt::<_, Claim>("claims", store, &old.claims, &new.claims)
+}
+
+fn diff_datacap<BS: Blockstore>(
+    changes: &mut Changes,
+    store: &BS,
+    old: &DataCapState,
+    new: &DataCapState,
+) -> anyhow::Result<()> {
+    changes.value("governor", &old.governor, &new.governor);
+    changes.value("supply", &old.token.supply, &new.token.supply);
+    // The token's balances are keyed by the varint bytes of actor IDs, as are u64 `MapKey`s.
+    changes.hamt::<_, u64, TokenAmount>("balances", store, &old.token.balances, &new.token.balances)
+}
+
+fn diff_multisig<BS: Blockstore>(
+    changes: &mut Changes,
+    store: &BS,
+    old: &MultisigState,
+    new: &MultisigState,
+) -> anyhow::Result<()> {
+    changes.value("signers", &old.signers, &new.signers);
+    changes.value(
+        "num_approvals_threshold",
+        &old.num_approvals_threshold,
+        &new.num_approvals_threshold,
+    );
+    changes.value("next_tx_id", &old.next_tx_id.0, &new.next_tx_id.0);
+    changes.hamt::<_, i64, Transaction>("pending_txs", store, &old.pending_txs, &new.pending_txs)
+}
+
+/// Records the changes to an actor.
+#[derive(Default)]
+struct Changes(Vec<StateChange>);
+
+impl Changes {
+    fn push(&mut self, path: impl Display, change: Change) {
+        self.0.push(StateChange { path: path.to_string(), change });
+    }
+
+    fn added(&mut self, path: impl Display, value: &impl Debug) {
+        self.push(path, Change::Added(format!("{:?}", value)));
+    }
+
+    fn removed(&mut self, path: impl Display, value: &impl Debug) {
+        self.push(path, Change::Removed(format!("{:?}", value)));
+    }
+
+    fn value<T: PartialEq + Debug>(&mut self, path: impl Display, old: &T, new: &T) {
+        if old != new {
+            let (old, new) = (format!("{:?}", old), format!("{:?}", new));
+            self.push(path, Change::Changed { old, new });
+        }
+    }
+
+    /// Records the sectors added to and removed from a bitfield.
+    fn bitfield(&mut self, path: impl Display, old: &BitField, new: &BitField) {
+        let added = new - old;
+        let removed = old - new;
+        if !added.is_empty() {
+            self.added(&path, &Sectors(&added));
+        }
+        if !removed.is_empty() {
+            self.removed(&path, &Sectors(&removed));
+        }
+    }
+
+    fn entries<K: Ord + Display, V: PartialEq + Debug>(
+        &mut self,
+        path: &str,
+        old: &BTreeMap<K, V>,
+        new: &BTreeMap<K, V>,
+    ) {
+        for (key, old_value) in old {
+            match new.get(key) {
+                Some(new_value) => self.value(format!("{}/{}", path, key), old_value, new_value),
+                None => self.removed(format!("{}/{}", path, key), old_value),
+            }
+        }
+        for (key, new_value) in new {
+            if !old.contains_key(key) {
+                self.added(format!("{}/{}", path, key), new_value);
+            }
+        }
+    }
+
+    fn hamt<BS, K, V>(&mut self, path: &str, store: &BS, old: &Cid, new: &Cid) -> anyhow::Result<()>
+    where
+        BS: Blockstore,
+        K: MapKey + Ord + Display,
+        V: DeserializeOwned + PartialEq + Debug,
+    {
+        let (old, new) = map_entries::<_, K, V>(store, Some(old), Some(new))?;
+        self.entries(path, &old, &new);
+        Ok(())
+    }
+
+    /// Diffs a HAMT of actor IDs to HAMTs of IDs, as in the verified registry.
+    fn nested_hamt<BS, V>(
+        &mut self,
+        path: &str,
+        store: &BS,
+        old: &Cid,
+        new: &Cid,
+    ) -> anyhow::Result<()>
+    where
+        BS: Blockstore,
+        V: DeserializeOwned + PartialEq + Debug,
+    {
+        let (old, new) = map_entries::<_, u64, Cid>(store, Some(old), Some(new))?;
+        let ids: BTreeSet<_> = old.keys().chain(new.keys()).collect();
+        for id in ids {
+            let (old_inner, new_inner) = map_entries::<_, u64, V>(store, old.get(id), new.get(id))?;
+            self.entries(&format!("{}/{}", path, id), &old_inner, &new_inner);
+        }
+        Ok(())
+    }
+
+    fn amt<BS, V>(&mut self, path: &str, store: &BS, old: &Cid, new: &Cid) -> anyhow::Result<()>
+    where
+        BS: Blockstore,
+        V: DeserializeOwned + PartialEq + Debug,
+    {
+        let (old, new) = amt_entries::<_, V>(store, Some(old), Some(new))?;
+        self.entries(path, &old, &new);
+        Ok(())
+    }
+}
+
+/// An ID address keying a HAMT, ordered by ID. The state tree and the builtin actors' tables keyed
+/// by address are keyed by ID addresses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct IdAddress(ActorID);
+
+impl MapKey for IdAddress {
+    fn from_key_bytes(b: &[u8]) -> Result<Self, String> {
+        let address = Address::from_bytes(b).map_err(|e| e.to_string())?;
+        address.id().map(IdAddress).map_err(|_| format!("{} is not an ID address", address))
+    }
+
+    fn to_key_bytes(&self) -> Result<Vec<u8>, String> {
+        Ok(Address::new_id(self.0).to_bytes())
+    }
+}
+
+impl Display for IdAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&Address::new_id(self.0), f)
+    }
+}
+
+pub(crate) fn load_state<BS: Blockstore, S: DeserializeOwned>(
+    store: &BS,
+    cid: &Cid,
+) -> anyhow::Result<S> {
+    store.get_cbor(cid)?.ok_or_else(|| anyhow!("missing state {}", cid))
+}
+
+/// Renders the sectors of a bitfield as runs, e.g. `[3, 5..=9]`, so that the rendering is bounded
+/// by the size of the bitfield's encoding rather than its number of sectors.
+struct Sectors<'a>(&'a BitField);
+
+impl Debug for Sectors<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut list = f.debug_list();
+        for range in self.0.ranges() {
+            match range.end - range.start {
+                1 => list.entry(&range.start),
+                _ => list.entry(&format_args!("{}..={}", range.start, range.end - 1)),
+            };
+        }
+        list.finish()
+    }
+}
+
+impl Display for StateDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = |typ: &Option<Type>| typ.map_or("unknown", |typ| typ.name());
+        for (id, diff) in &self.actors {
+            let address = Address::new_id(*id);
+            match diff {
+                ActorDiff::Added { typ, actor } => writeln!(
+                    f,
+                    "{} ({}): added with balance {}",
+                    address,
+                    name(typ),
+                    actor.balance
+                )?,
+                ActorDiff::Removed { typ, actor } => writeln!(
+                    f,
+                    "{} ({}): removed with balance {}",
+                    address,
+                    name(typ),
+                    actor.balance
+                )?,
+                ActorDiff::Changed { typ, changes } => {
+                    writeln!(f, "{} ({}):", address, name(typ))?;
+                    for change in changes {
+                        writeln!(f, "  {}", change)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for StateChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.change {
+            Change::Added(value) => write!(f, "{}: added {}", self.path, value),
+            Change::Removed(value) => write!(f, "{}: removed {}", self.path, value),
+            Change::Changed { old, new } => write!(f, "{}: {} -> {}", self.path, old, new),
+        }
+    }
+}
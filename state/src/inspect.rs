@@ -0,0 +1,911 @@
+use std::fmt::{self, Display};
+
+use anyhow::anyhow;
+use bimap::BiBTreeMap;
+use cid::Cid;
+use fil_actor_account::State as AccountState;
+use fil_actor_cron::{Entry as CronEntry, State as CronState};
+use fil_actor_datacap::State as DataCapState;
+use fil_actor_init::State as InitState;
+use fil_actor_market::{DealProposal, DealState, Label, State as MarketState};
+use fil_actor_miner::{
+    BeneficiaryTerm, Deadline, MinerInfo, Partition, PendingBeneficiaryChange, PowerPair,
+    SectorOnChainInfo, SectorPreCommitInfo, SectorPreCommitOnChainInfo, State as MinerState,
+    WorkerKeyChange,
+};
+use fil_actor_multisig::{State as MultisigState, Transaction};
+use fil_actor_paych::{LaneState, State as PaychState};
+use fil_actor_power::{Claim as PowerClaim, CronEvent, State as PowerState};
+use fil_actor_reward::State as RewardState;
+use fil_actor_system::State as SystemState;
+use fil_actor_verifreg::{
+    AddrPairKey, Allocation, Claim, RemoveDataCapProposalID, State as VerifregState,
+};
+use fil_actors_runtime::runtime::builtins::Type;
+use fil_actors_runtime::runtime::Policy;
+use fil_actors_runtime::{Array, MapKey};
+use fvm_ipld_bitfield::BitField;
+use fvm_ipld_blockstore::Blockstore;
+use fvm_ipld_encoding::de::DeserializeOwned;
+use fvm_ipld_encoding::ser::Serialize;
+use fvm_ipld_encoding::serde_bytes::ByteBuf;
+use fvm_shared::address::Address;
+use fvm_shared::bigint::bigint_ser::BigIntDe;
+use fvm_shared::bigint::BigInt;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::piece::PaddedPieceSize;
+use fvm_shared::sector::{RegisteredPoStProof, RegisteredSealProof, SealVerifyInfo, SectorSize};
+use fvm_shared::smooth::FilterEstimate;
+use serde_json::{json, Map, Value};
+
+use crate::check::Actor;
+use crate::diff::load_state;
+use crate::walk::hamt_for_each_while;
+
+/// The number of entries of each collection expanded by default.
+pub const DEFAULT_ENTRY_LIMIT: usize = 20;
+
+/// A rendered piece of actor state.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Node {
+    /// A number, string, boolean or null. Addresses and CIDs are strings, as are amounts of FIL,
+    /// in FIL, and of storage power, which may exceed the range of JSON numbers.
+    Value(Value),
+    /// The fields of a structure, in declaration order.
+    Fields(Vec<(String, Node)>),
+    /// A page of the entries of a HAMT, AMT or list, in iteration order, starting at `offset`.
+    /// The `total` is `None` if the collection has entries after the page which weren't counted.
+    Entries { total: Option<u64>, offset: u64, entries: Vec<(String, Node)> },
+    /// A page of the bits set in a bitfield, starting at `offset` of the `total` set.
+    Bits { total: u64, offset: u64, bits: Vec<u64> },
+}
+
+impl Node {
+    /// Renders the node as JSON. Fields become objects, entries become an object of their
+    /// `total`, `offset` and an array of `key` and `value` objects, and bits become an object of
+    /// their `total`, `offset` and an array of the bits.
+    pub fn to_json(&self) -> Value {
+        match self {
+            Node::Value(value) => value.clone(),
+            Node::Fields(fields) => Value::Object(
+                fields.iter().map(|(k, v)| (k.clone(), v.to_json())).collect::<Map<_, _>>(),
+            ),
+            Node::Entries { total, offset, entries } => {
+                let entries: Vec<_> = entries
+                    .iter()
+                    .map(|(k, v)| json!({ "key": k, "value": v.to_json() }))
+                    .collect();
+                json!({ "total": total, "offset": offset, "entries": entries })
+            }
+            Node::Bits { total, offset, bits } => {
+                json!({ "total": total, "offset": offset, "bits": bits })
+            }
+        }
+    }
+
+    fn write_tree(&self, f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result {
+        let fields = match self {
+            Node::Value(_) | Node::Bits { .. } => {
+                return writeln!(f, "{:indent$}{}", "", Inline(self), indent = indent)
+            }
+            Node::Fields(fields) => fields,
+            Node::Entries { entries, .. } => entries,
+        };
+        for (name, node) in fields {
+            write!(f, "{:indent$}{}", "", name, indent = indent)?;
+            match node {
+                Node::Value(_) | Node::Bits { .. } => writeln!(f, ": {}", Inline(node))?,
+                Node::Fields(_) => {
+                    writeln!(f, ":")?;
+                    node.write_tree(f, indent + 2)?;
+                }
+                Node::Entries { total, offset, entries } => {
+                    let shown = *offset + entries.len() as u64;
+                    match total {
+                        Some(total) => writeln!(f, " ({} entries):", total)?,
+                        None => writeln!(f, " (over {} entries):", shown)?,
+                    }
+                    if *offset > 0 {
+                        writeln!(f, "{:indent$}... {} skipped", "", offset, indent = indent + 2)?;
+                    }
+                    node.write_tree(f, indent + 2)?;
+                    match total {
+                        Some(total) if *total > shown => {
+                            let more = total - shown;
+                            writeln!(f, "{:indent$}... {} more", "", more, indent = indent + 2)?
+                        }
+                        Some(_) => {}
+                        None => writeln!(f, "{:indent$}... more", "", indent = indent + 2)?,
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Renders the node as an indented tree, one field or entry per line.
+impl Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_tree(f, 0)
+    }
+}
+
+/// Renders a value or bitfield on one line, e.g. `[1, 2, 3] and 10 more`.
+struct Inline<'a>(&'a Node);
+
+impl Display for Inline<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Node::Value(Value::String(s)) => write!(f, "{}", s),
+            Node::Value(value) => write!(f, "{}", value),
+            Node::Bits { total, offset, bits } => {
+                if *offset > 0 {
+                    write!(f, "{} skipped, ", offset)?;
+                }
+                write!(f, "{:?}", bits)?;
+                let more = total.saturating_sub(*offset + bits.len() as u64);
+                if more > 0 {
+                    write!(f, " and {} more", more)?;
+                }
+                Ok(())
+            }
+            node => write!(f, "{:?}", node),
+        }
+    }
+}
+
+/// Renders an actor and its state, as `inspect_state`.
+pub fn inspect_actor<BS: Blockstore>(
+    manifest: &BiBTreeMap<Cid, Type>,
+    policy: &Policy,
+    store: &BS,
+    actor: &Actor,
+    offset: u64,
+    limit: usize,
+) -> anyhow::Result<Node> {
+    let typ = manifest
+        .get_by_left(&actor.code)
+        .ok_or_else(|| anyhow!("actor code {} is not in the manifest", actor.code))?;
+    Ok(fields(vec![
+        ("type", typ.name().to_string().render()),
+        ("code", actor.code.render()),
+        ("head", actor.head.render()),
+        ("call_seq_num", actor.call_seq_num.render()),
+        ("balance", actor.balance.render()),
+        ("state", inspect_state(manifest, policy, store, &actor.code, &actor.head, offset, limit)?),
+    ]))
+}
+
+/// Renders the state of a builtin actor, whose type is found by its code CID in the manifest.
+///
+/// HAMTs, AMTs and bitfields in the state are loaded and expanded a page at a time, skipping
+/// `offset` entries of each and expanding up to `limit` after them, so that large states such as
+/// the market's can be inspected. Collections nested within the entries of another are expanded
+/// from their first entry.
+pub fn inspect_state<BS: Blockstore>(
+    manifest: &BiBTreeMap<Cid, Type>,
+    policy: &Policy,
+    store: &BS,
+    code: &Cid,
+    head: &Cid,
+    offset: u64,
+    limit: usize,
+) -> anyhow::Result<Node> {
+    let typ = manifest
+        .get_by_left(code)
+        .ok_or_else(|| anyhow!("actor code {} is not in the manifest", code))?;
+    let r = Renderer { store, offset, limit };
+    match typ {
+        Type::System => r.system(&load_state(store, head)?),
+        Type::Init => r.init(&load_state(store, head)?),
+        Type::Cron => Ok(r.cron(&load_state(store, head)?)),
+        Type::Account => Ok(r.account(&load_state(store, head)?)),
+        Type::Power => r.power(&load_state(store, head)?),
+        Type::Miner => r.miner(policy, &load_state(store, head)?),
+        Type::Market => r.market(&load_state(store, head)?),
+        Type::PaymentChannel => r.paych(&load_state(store, head)?),
+        Type::Multisig => r.multisig(&load_state(store, head)?),
+        Type::Reward => Ok(r.reward(&load_state(store, head)?)),
+        Type::VerifiedRegistry => r.verifreg(&load_state(store, head)?),
+        Type::DataCap => r.datacap(&load_state(store, head)?),
+    }
+}
+
+struct Renderer<'a, BS> {
+    store: &'a BS,
+    offset: u64,
+    limit: usize,
+}
+
+impl<'a, BS: Blockstore> Renderer<'a, BS> {
+    /// A renderer for the collections within an entry of a collection, from their first entry.
+    fn nested(&self) -> Self {
+        Renderer { store: self.store, offset: 0, limit: self.limit }
+    }
+
+    fn system(&self, st: &SystemState) -> anyhow::Result<Node> {
+        let actors: Vec<(String, Cid)> = load_state(self.store, &st.builtin_actors)?;
+        Ok(fields(vec![(
+            "builtin_actors",
+            self.list(actors.iter().map(|(name, code)| (name.clone(), code.render()))),
+        )]))
+    }
+
+    fn init(&self, st: &InitState) -> anyhow::Result<Node> {
+        Ok(fields(vec![
+            ("address_map", self.hamt::<Address, u64>(&st.address_map)?),
+            ("next_id", st.next_id.render()),
+            ("network_name", st.network_name.render()),
+        ]))
+    }
+
+    fn cron(&self, st: &CronState) -> Node {
+        let entries = st.entries.iter().enumerate().map(|(i, e)| (i.to_string(), e.render()));
+        fields(vec![("entries", self.list(entries))])
+    }
+
+    fn account(&self, st: &AccountState) -> Node {
+        fields(vec![("address", st.address.render())])
+    }
+
+    fn power(&self, st: &PowerState) -> anyhow::Result<Node> {
+        // The cron queue is a HAMT of epochs to AMTs of events, and the batch of proofs to verify
+        // a HAMT of miners to AMTs of their proofs.
+        let cron_event_queue = self.hamt_with::<i64, Cid>(&st.cron_event_queue, |events| {
+            self.nested().amt::<CronEvent>(events)
+        })?;
+        let proof_validation_batch = match &st.proof_validation_batch {
+            Some(batch) => self.hamt_with::<Address, Cid>(batch, |proofs| {
+                self.nested().amt::<SealVerifyInfo>(proofs)
+            })?,
+            None => Node::Value(Value::Null),
+        };
+        Ok(fields(vec![
+            ("total_raw_byte_power", st.total_raw_byte_power.render()),
+            ("total_bytes_committed", st.total_bytes_committed.render()),
+            ("total_quality_adj_power", st.total_quality_adj_power.render()),
+            ("total_qa_bytes_committed", st.total_qa_bytes_committed.render()),
+            ("total_pledge_collateral", st.total_pledge_collateral.render()),
+            ("this_epoch_raw_byte_power", st.this_epoch_raw_byte_power.render()),
+            ("this_epoch_quality_adj_power", st.this_epoch_quality_adj_power.render()),
+            ("this_epoch_pledge_collateral", st.this_epoch_pledge_collateral.render()),
+            ("this_epoch_qa_power_smoothed", st.this_epoch_qa_power_smoothed.render()),
+            ("miner_count", st.miner_count.render()),
+            ("miner_above_min_power_count", st.miner_above_min_power_count.render()),
+            ("cron_event_queue", cron_event_queue),
+            ("first_cron_epoch", st.first_cron_epoch.render()),
+            ("claims", self.hamt::<Address, PowerClaim>(&st.claims)?),
+            ("proof_validation_batch", proof_validation_batch),
+        ]))
+    }
+
+    fn miner(&self, policy: &Policy, st: &MinerState) -> anyhow::Result<Node> {
+        let vesting = st.load_vesting_funds(self.store)?;
+        let vesting = vesting.funds.iter().map(|f| (f.epoch.to_string(), f.amount.render()));
+
+        // Only deadlines with sectors are expanded.
+        let deadlines = st.load_deadlines(self.store)?;
+        let mut nonempty = Vec::new();
+        for idx in 0..deadlines.due.len() as u64 {
+            let deadline = deadlines.load_deadline(policy, self.store, idx)?;
+            if deadline.total_sectors > 0 {
+                nonempty.push((idx, deadline));
+            }
+        }
+        let deadlines = nonempty
+            .iter()
+            .map(|(idx, deadline)| Ok((idx.to_string(), self.nested().deadline(deadline)?)))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(fields(vec![
+            ("info", st.get_info(self.store)?.render()),
+            ("pre_commit_deposits", st.pre_commit_deposits.render()),
+            ("locked_funds", st.locked_funds.render()),
+            ("vesting_funds", self.list(vesting)),
+            ("fee_debt", st.fee_debt.render()),
+            ("initial_pledge", st.initial_pledge.render()),
+            (
+                "pre_committed_sectors",
+                self.hamt::<u64, SectorPreCommitOnChainInfo>(&st.pre_committed_sectors)?,
+            ),
+            (
+                "pre_committed_sectors_cleanup",
+                self.amt_with::<BitField>(&st.pre_committed_sectors_cleanup, |sectors| {
+                    Ok(self.nested().bitfield(sectors))
+                })?,
+            ),
+            ("allocated_sectors", self.bitfield(&load_state(self.store, &st.allocated_sectors)?)),
+            ("sectors", self.amt::<SectorOnChainInfo>(&st.sectors)?),
+            ("proving_period_start", st.proving_period_start.render()),
+            ("current_deadline", st.current_deadline.render()),
+            ("deadlines", self.list(deadlines.into_iter())),
+            ("early_terminations", self.bitfield(&st.early_terminations)),
+            ("deadline_cron_active", st.deadline_cron_active.render()),
+        ]))
+    }
+
+    fn deadline(&self, deadline: &Deadline) -> anyhow::Result<Node> {
+        Ok(fields(vec![
+            (
+                "partitions",
+                self.amt_with::<Partition>(&deadline.partitions, |p| Ok(self.partition(p)))?,
+            ),
+            ("partitions_posted", self.bitfield(&deadline.partitions_posted)),
+            ("early_terminations", self.bitfield(&deadline.early_terminations)),
+            ("live_sectors", deadline.live_sectors.render()),
+            ("total_sectors", deadline.total_sectors.render()),
+            ("faulty_power", deadline.faulty_power.render()),
+        ]))
+    }
+
+    fn partition(&self, partition: &Partition) -> Node {
+        let r = self.nested();
+        fields(vec![
+            ("sectors", r.bitfield(&partition.sectors)),
+            ("unproven", r.bitfield(&partition.unproven)),
+            ("faults", r.bitfield(&partition.faults)),
+            ("recoveries", r.bitfield(&partition.recoveries)),
+            ("terminated", r.bitfield(&partition.terminated)),
+            ("live_power", partition.live_power.render()),
+            ("unproven_power", partition.unproven_power.render()),
+            ("faulty_power", partition.faulty_power.render()),
+            ("recovering_power", partition.recovering_power.render()),
+        ])
+    }
+
+    fn market(&self, st: &MarketState) -> anyhow::Result<Node> {
+        // Deal operations are a HAMT of epochs to sets of deal IDs.
+        let deal_ops_by_epoch = self.hamt_with::<u64, Cid>(&st.deal_ops_by_epoch, |deals| {
+            self.nested().set::<u64>(deals)
+        })?;
+        Ok(fields(vec![
+            ("proposals", self.amt::<DealProposal>(&st.proposals)?),
+            ("states", self.amt::<DealState>(&st.states)?),
+            ("pending_proposals", self.set::<Cid>(&st.pending_proposals)?),
+            ("escrow_table", self.hamt::<Address, TokenAmount>(&st.escrow_table)?),
+            ("locked_table", self.hamt::<Address, TokenAmount>(&st.locked_table)?),
+            ("next_id", st.next_id.render()),
+            ("deal_ops_by_epoch", deal_ops_by_epoch),
+            ("last_cron", st.last_cron.render()),
+            ("total_client_locked_collateral", st.total_client_locked_collateral.render()),
+            ("total_provider_locked_collateral", st.total_provider_locked_collateral.render()),
+            ("total_client_storage_fee", st.total_client_storage_fee.render()),
+            (
+                "pending_deal_allocation_ids",
+                self.hamt::<u64, u64>(&st.pending_deal_allocation_ids)?,
+            ),
+        ]))
+    }
+
+    fn paych(&self, st: &PaychState) -> anyhow::Result<Node> {
+        Ok(fields(vec![
+            ("from", st.from.render()),
+            ("to", st.to.render()),
+            ("to_send", st.to_send.render()),
+            ("settling_at", st.settling_at.render()),
+            ("min_settle_height", st.min_settle_height.render()),
+            ("lane_states", self.amt::<LaneState>(&st.lane_states)?),
+        ]))
+    }
+
+    fn multisig(&self, st: &MultisigState) -> anyhow::Result<Node> {
+        Ok(fields(vec![
+            ("signers", st.signers.render()),
+            ("num_approvals_threshold", st.num_approvals_threshold.render()),
+            ("next_tx_id", st.next_tx_id.0.render()),
+            ("initial_balance", st.initial_balance.render()),
+            ("start_epoch", st.start_epoch.render()),
+            ("unlock_duration", st.unlock_duration.render()),
+            ("pending_txs", self.hamt::<i64, Transaction>(&st.pending_txs)?),
+        ]))
+    }
+
+    fn reward(&self, st: &RewardState) -> Node {
+        fields(vec![
+            ("cumsum_baseline", st.cumsum_baseline.render()),
+            ("cumsum_realized", st.cumsum_realized.render()),
+            ("effective_network_time", st.effective_network_time.render()),
+            ("effective_baseline_power", st.effective_baseline_power.render()),
+            ("this_epoch_reward", st.this_epoch_reward.render()),
+            ("this_epoch_reward_smoothed", st.this_epoch_reward_smoothed.render()),
+            ("this_epoch_baseline_power", st.this_epoch_baseline_power.render()),
+            ("epoch", st.epoch.render()),
+            ("total_storage_power_reward", st.total_storage_power_reward.render()),
+            ("simple_total", st.simple_total.render()),
+            ("baseline_total", st.baseline_total.render()),
+        ])
+    }
+
+    fn verifreg(&self, st: &VerifregState) -> anyhow::Result<Node> {
+        Ok(fields(vec![
+            ("root_key", st.root_key.render()),
+            ("verifiers", self.hamt::<Address, BigIntDe>(&st.verifiers)?),
+            (
+                "remove_data_cap_proposal_ids",
+                self.hamt::<AddrPairKey, RemoveDataCapProposalID>(
+                    &st.remove_data_cap_proposal_ids,
+                )?,
+            ),
+            ("allocations", self.nested_hamt::<Allocation>(&st.allocations)?),
+            ("next_allocation_id", st.next_allocation_id.render()),
+            ("claims", self.nested_hamt::<Claim>(&st.claims)?),
+        ]))
+    }
+
+    fn datacap(&self, st: &DataCapState) -> anyhow::Result<Node> {
+        // The token's balances are keyed by the varint bytes of actor IDs, as are u64 map keys,
+        // and its allowances by owner and then operator.
+        Ok(fields(vec![
+            ("governor", st.governor.render()),
+            ("supply", st.token.supply.render()),
+            ("balances", self.hamt::<u64, TokenAmount>(&st.token.balances)?),
+            ("allowances", self.nested_hamt::<TokenAmount>(&st.token.allowances)?),
+        ]))
+    }
+
+    /// Renders a page of the entries of a HAMT.
+    fn hamt<K, V>(&self, root: &Cid) -> anyhow::Result<Node>
+    where
+        K: Key,
+        V: DeserializeOwned + Render,
+    {
+        self.hamt_with::<K, V>(root, |v| Ok(v.render()))
+    }
+
+    /// Renders a page of a HAMT of actor IDs to HAMTs of IDs, as in the verified registry.
+    fn nested_hamt<V>(&self, root: &Cid) -> anyhow::Result<Node>
+    where
+        V: DeserializeOwned + Render,
+    {
+        self.hamt_with::<u64, Cid>(root, |inner| self.nested().hamt::<u64, V>(inner))
+    }
+
+    /// Renders a page of the keys of a HAMT used as a set, by their position in the set.
+    fn set<K: Key>(&self, root: &Cid) -> anyhow::Result<Node> {
+        let mut position = self.offset;
+        self.hamt_entries::<()>(root, |key, _| {
+            let key = K::from_key_bytes(key).map_err(|e| anyhow!(e))?;
+            position += 1;
+            Ok(((position - 1).to_string(), Node::Value(Value::String(key.key()))))
+        })
+    }
+
+    /// Renders a page of the entries of a HAMT in iteration order. Entries after the page are
+    /// counted only if there are no more than a page of them, so that a large HAMT isn't loaded
+    /// to render its first entries.
+    fn hamt_with<K, V>(
+        &self,
+        root: &Cid,
+        mut render: impl FnMut(&V) -> anyhow::Result<Node>,
+    ) -> anyhow::Result<Node>
+    where
+        K: Key,
+        V: DeserializeOwned,
+    {
+        self.hamt_entries::<V>(root, |key, value| {
+            let key = K::from_key_bytes(key).map_err(|e| anyhow!(e))?;
+            Ok((key.key(), render(value)?))
+        })
+    }
+
+    fn hamt_entries<V>(
+        &self,
+        root: &Cid,
+        mut render: impl FnMut(&[u8], &V) -> anyhow::Result<(String, Node)>,
+    ) -> anyhow::Result<Node>
+    where
+        V: DeserializeOwned,
+    {
+        // The HAMT's bit width isn't needed to walk its nodes in order.
+        let mut count = 0;
+        let mut complete = true;
+        let mut entries = Vec::new();
+        hamt_for_each_while::<_, ByteBuf, V, _>(self.store, root, |key, value| {
+            if count >= self.offset {
+                if entries.len() == self.limit {
+                    complete = false;
+                    return Ok(false);
+                }
+                entries.push(render(&key, &value)?);
+            }
+            count += 1;
+            Ok(true)
+        })?;
+        Ok(Node::Entries { total: complete.then_some(count), offset: self.offset, entries })
+    }
+
+    /// Renders a page of the entries of an AMT.
+    fn amt<V>(&self, root: &Cid) -> anyhow::Result<Node>
+    where
+        V: DeserializeOwned + Serialize + Render,
+    {
+        self.amt_with::<V>(root, |v| Ok(v.render()))
+    }
+
+    fn amt_with<V>(
+        &self,
+        root: &Cid,
+        mut render: impl FnMut(&V) -> anyhow::Result<Node>,
+    ) -> anyhow::Result<Node>
+    where
+        V: DeserializeOwned + Serialize,
+    {
+        let array = Array::<V, BS>::load(root, self.store)?;
+        let total = array.count();
+        let mut entries = Vec::new();
+        if self.offset < total && self.limit > 0 {
+            let mut count = 0;
+            array.for_each_while(|i, value| {
+                if count >= self.offset {
+                    entries.push((i.to_string(), render(value)?));
+                }
+                count += 1;
+                Ok(entries.len() < self.limit)
+            })?;
+        }
+        Ok(Node::Entries { total: Some(total), offset: self.offset, entries })
+    }
+
+    fn list(&self, items: impl ExactSizeIterator<Item = (String, Node)>) -> Node {
+        let total = items.len() as u64;
+        let entries = items.skip(self.offset as usize).take(self.limit).collect();
+        Node::Entries { total: Some(total), offset: self.offset, entries }
+    }
+
+    fn bitfield(&self, bitfield: &BitField) -> Node {
+        let bits = bitfield.iter().skip(self.offset as usize).take(self.limit).collect();
+        Node::Bits { total: bitfield.len(), offset: self.offset, bits }
+    }
+}
+
+fn fields(fields: Vec<(&str, Node)>) -> Node {
+    Node::Fields(fields.into_iter().map(|(name, node)| (name.to_string(), node)).collect())
+}
+
+/// Renders bytes as hex.
+fn bytes(bytes: &[u8]) -> Node {
+    Node::Value(Value::String(bytes.iter().map(|b| format!("{:02x}", b)).collect()))
+}
+
+/// A key of a HAMT, rendered as the key of its entry.
+trait Key: MapKey {
+    fn key(&self) -> String;
+}
+
+macro_rules! display_key {
+    ($($t:ty),*) => {
+        $(impl Key for $t {
+            fn key(&self) -> String {
+                self.to_string()
+            }
+        })*
+    };
+}
+
+display_key!(u64, i64, Address, Cid);
+
+impl Key for AddrPairKey {
+    fn key(&self) -> String {
+        format!("{}/{}", self.first, self.second)
+    }
+}
+
+/// A piece of state rendered without loading anything from the store.
+trait Render {
+    fn render(&self) -> Node;
+}
+
+macro_rules! json_value {
+    ($($t:ty),*) => {
+        $(impl Render for $t {
+            fn render(&self) -> Node {
+                Node::Value(Value::from(self.clone()))
+            }
+        })*
+    };
+}
+
+macro_rules! display_value {
+    ($($t:ty),*) => {
+        $(impl Render for $t {
+            fn render(&self) -> Node {
+                Node::Value(Value::String(self.to_string()))
+            }
+        })*
+    };
+}
+
+macro_rules! debug_value {
+    ($($t:ty),*) => {
+        $(impl Render for $t {
+            fn render(&self) -> Node {
+                Node::Value(Value::String(format!("{:?}", self)))
+            }
+        })*
+    };
+}
+
+json_value!(u64, i64, bool, String);
+display_value!(Address, Cid, TokenAmount, BigInt);
+debug_value!(RegisteredSealProof, RegisteredPoStProof);
+
+impl Render for SectorSize {
+    fn render(&self) -> Node {
+        (*self as u64).render()
+    }
+}
+
+impl Render for BigIntDe {
+    fn render(&self) -> Node {
+        self.0.render()
+    }
+}
+
+impl Render for PaddedPieceSize {
+    fn render(&self) -> Node {
+        self.0.render()
+    }
+}
+
+impl Render for Node {
+    fn render(&self) -> Node {
+        self.clone()
+    }
+}
+
+impl<T: Render> Render for Option<T> {
+    fn render(&self) -> Node {
+        self.as_ref().map_or(Node::Value(Value::Null), Render::render)
+    }
+}
+
+impl<T: Render> Render for Vec<T> {
+    fn render(&self) -> Node {
+        let entries = self.iter().enumerate().map(|(i, v)| (i.to_string(), v.render())).collect();
+        Node::Entries { total: Some(self.len() as u64), offset: 0, entries }
+    }
+}
+
+impl Render for CronEntry {
+    fn render(&self) -> Node {
+        fields(vec![("receiver", self.receiver.render()), ("method_num", self.method_num.render())])
+    }
+}
+
+impl Render for FilterEstimate {
+    fn render(&self) -> Node {
+        fields(vec![("position", self.position.render()), ("velocity", self.velocity.render())])
+    }
+}
+
+impl Render for PowerClaim {
+    fn render(&self) -> Node {
+        fields(vec![
+            ("window_post_proof_type", self.window_post_proof_type.render()),
+            ("raw_byte_power", self.raw_byte_power.render()),
+            ("quality_adj_power", self.quality_adj_power.render()),
+        ])
+    }
+}
+
+impl Render for CronEvent {
+    fn render(&self) -> Node {
+        fields(vec![
+            ("miner_addr", self.miner_addr.render()),
+            ("callback_payload", bytes(self.callback_payload.bytes())),
+        ])
+    }
+}
+
+impl Render for SealVerifyInfo {
+    fn render(&self) -> Node {
+        fields(vec![
+            ("registered_proof", self.registered_proof.render()),
+            (
+                "sector_id",
+                fields(vec![
+                    ("miner", self.sector_id.miner.render()),
+                    ("number", self.sector_id.number.render()),
+                ]),
+            ),
+            ("deal_ids", self.deal_ids.render()),
+            ("randomness", bytes(&self.randomness.0)),
+            ("interactive_randomness", bytes(&self.interactive_randomness.0)),
+            ("proof", bytes(&self.proof)),
+            ("sealed_cid", self.sealed_cid.render()),
+            ("unsealed_cid", self.unsealed_cid.render()),
+        ])
+    }
+}
+
+impl Render for MinerInfo {
+    fn render(&self) -> Node {
+        let multi_address: Vec<_> = self.multi_address.iter().map(|a| bytes(&a.0)).collect();
+        fields(vec![
+            ("owner", self.owner.render()),
+            ("worker", self.worker.render()),
+            ("control_addresses", self.control_addresses.render()),
+            ("pending_worker_key", self.pending_worker_key.render()),
+            ("peer_id", bytes(&self.peer_id)),
+            ("multi_address", multi_address.render()),
+            ("window_post_proof_type", self.window_post_proof_type.render()),
+            ("sector_size", self.sector_size.render()),
+            ("window_post_partition_sectors", self.window_post_partition_sectors.render()),
+            ("consensus_fault_elapsed", self.consensus_fault_elapsed.render()),
+            ("pending_owner_address", self.pending_owner_address.render()),
+            ("beneficiary", self.beneficiary.render()),
+            ("beneficiary_term", self.beneficiary_term.render()),
+            ("pending_beneficiary_term", self.pending_beneficiary_term.render()),
+        ])
+    }
+}
+
+impl Render for WorkerKeyChange {
+    fn render(&self) -> Node {
+        fields(vec![
+            ("new_worker", self.new_worker.render()),
+            ("effective_at", self.effective_at.render()),
+        ])
+    }
+}
+
+impl Render for BeneficiaryTerm {
+    fn render(&self) -> Node {
+        fields(vec![
+            ("quota", self.quota.render()),
+            ("used_quota", self.used_quota.render()),
+            ("expiration", self.expiration.render()),
+        ])
+    }
+}
+
+impl Render for PendingBeneficiaryChange {
+    fn render(&self) -> Node {
+        fields(vec![
+            ("new_beneficiary", self.new_beneficiary.render()),
+            ("new_quota", self.new_quota.render()),
+            ("new_expiration", self.new_expiration.render()),
+            ("approved_by_beneficiary", self.approved_by_beneficiary.render()),
+            ("approved_by_nominee", self.approved_by_nominee.render()),
+        ])
+    }
+}
+
+impl Render for SectorPreCommitInfo {
+    fn render(&self) -> Node {
+        fields(vec![
+            ("seal_proof", self.seal_proof.render()),
+            ("sector_number", self.sector_number.render()),
+            ("sealed_cid", self.sealed_cid.render()),
+            ("seal_rand_epoch", self.seal_rand_epoch.render()),
+            ("deal_ids", self.deal_ids.render()),
+            ("expiration", self.expiration.render()),
+            ("unsealed_cid", self.unsealed_cid.0.render()),
+        ])
+    }
+}
+
+impl Render for SectorPreCommitOnChainInfo {
+    fn render(&self) -> Node {
+        fields(vec![
+            ("info", self.info.render()),
+            ("pre_commit_deposit", self.pre_commit_deposit.render()),
+            ("pre_commit_epoch", self.pre_commit_epoch.render()),
+        ])
+    }
+}
+
+impl Render for SectorOnChainInfo {
+    fn render(&self) -> Node {
+        fields(vec![
+            ("sector_number", self.sector_number.render()),
+            ("seal_proof", self.seal_proof.render()),
+            ("sealed_cid", self.sealed_cid.render()),
+            ("deal_ids", self.deal_ids.render()),
+            ("activation", self.activation.render()),
+            ("expiration", self.expiration.render()),
+            ("deal_weight", self.deal_weight.render()),
+            ("verified_deal_weight", self.verified_deal_weight.render()),
+            ("initial_pledge", self.initial_pledge.render()),
+            ("expected_day_reward", self.expected_day_reward.render()),
+            ("expected_storage_pledge", self.expected_storage_pledge.render()),
+            ("replaced_sector_age", self.replaced_sector_age.render()),
+            ("replaced_day_reward", self.replaced_day_reward.render()),
+            ("sector_key_cid", self.sector_key_cid.render()),
+            ("simple_qa_power", self.simple_qa_power.render()),
+        ])
+    }
+}
+
+impl Render for PowerPair {
+    fn render(&self) -> Node {
+        fields(vec![("raw", self.raw.render()), ("qa", self.qa.render())])
+    }
+}
+
+impl Render for DealProposal {
+    fn render(&self) -> Node {
+        let label = match &self.label {
+            Label::String(label) => label.render(),
+            Label::Bytes(label) => bytes(label),
+        };
+        fields(vec![
+            ("piece_cid", self.piece_cid.render()),
+            ("piece_size", self.piece_size.render()),
+            ("verified_deal", self.verified_deal.render()),
+            ("client", self.client.render()),
+            ("provider", self.provider.render()),
+            ("label", label),
+            ("start_epoch", self.start_epoch.render()),
+            ("end_epoch", self.end_epoch.render()),
+            ("storage_price_per_epoch", self.storage_price_per_epoch.render()),
+            ("provider_collateral", self.provider_collateral.render()),
+            ("client_collateral", self.client_collateral.render()),
+        ])
+    }
+}
+
+impl Render for DealState {
+    fn render(&self) -> Node {
+        fields(vec![
+            ("sector_start_epoch", self.sector_start_epoch.render()),
+            ("last_updated_epoch", self.last_updated_epoch.render()),
+            ("slash_epoch", self.slash_epoch.render()),
+            ("verified_claim", self.verified_claim.render()),
+        ])
+    }
+}
+
+impl Render for LaneState {
+    fn render(&self) -> Node {
+        fields(vec![("redeemed", self.redeemed.render()), ("nonce", self.nonce.render())])
+    }
+}
+
+impl Render for Transaction {
+    fn render(&self) -> Node {
+        fields(vec![
+            ("to", self.to.render()),
+            ("value", self.value.render()),
+            ("method", self.method.render()),
+            ("params", bytes(self.params.bytes())),
+            ("approved", self.approved.render()),
+        ])
+    }
+}
+
+impl Render for RemoveDataCapProposalID {
+    fn render(&self) -> Node {
+        self.id.render()
+    }
+}
+
+impl Render for Allocation {
+    fn render(&self) -> Node {
+        fields(vec![
+            ("client", self.client.render()),
+            ("provider", self.provider.render()),
+            ("data", self.data.render()),
+            ("size", self.size.render()),
+            ("term_min", self.term_min.render()),
+            ("term_max", self.term_max.render()),
+            ("expiration", self.expiration.render()),
+        ])
+    }
+}
+
+impl Render for Claim {
+    fn render(&self) -> Node {
+        fields(vec![
+            ("provider", self.provider.render()),
+            ("client", self.client.render()),
+            ("data", self.data.render()),
+            ("size", self.size.render()),
+            ("term_min", self.term_min.render()),
+            ("term_max", self.term_max.render()),
+            ("term_start", self.term_start.render()),
+            ("sector", self.sector.render()),
+        ])
+    }
+}
@@ -1 +1,6 @@
+pub mod car;
 pub mod check;
+pub mod diff;
+pub mod inspect;
+pub mod manifest;
+mod walk;
@@ -0,0 +1,58 @@
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use anyhow::{anyhow, Context};
+use bimap::BiBTreeMap;
+use cid::Cid;
+use fil_actors_runtime::runtime::builtins::Type;
+use fil_actors_runtime::runtime::Policy;
+
+/// Loads a manifest of actor code CIDs from a JSON object of CIDs to actor type names.
+pub fn load_manifest(path: &Path) -> anyhow::Result<BiBTreeMap<Cid, Type>> {
+    let file = File::open(path).with_context(|| format!("opening {}", path.display()))?;
+    let codes: BTreeMap<String, String> = serde_json::from_reader(BufReader::new(file))
+        .with_context(|| format!("invalid manifest {}", path.display()))?;
+    codes
+        .iter()
+        .map(|(code, name)| {
+            let code = Cid::try_from(code.as_str())
+                .with_context(|| format!("invalid code CID {} in manifest", code))?;
+            let typ = *Type::ALL
+                .iter()
+                .find(|typ| typ.name() == name)
+                .ok_or_else(|| anyhow!("unknown actor type {} in manifest", name))?;
+            Ok((code, typ))
+        })
+        .collect()
+}
+
+/// Loads the policy of a network, either from a file of CBOR-encoded policy parameters or by
+/// the name of a network the actors can be built for. Without either, the policy is that of
+/// this build.
+pub fn load_policy(network: Option<&str>, path: Option<&Path>) -> anyhow::Result<Policy> {
+    let policy = match (network, path) {
+        (Some(_), Some(_)) => {
+            return Err(anyhow!("a policy may be given by network or file, not both"))
+        }
+        (Some(network), None) => {
+            Policy::for_network(network).ok_or_else(|| anyhow!("unknown network {}", network))?
+        }
+        (None, Some(path)) => {
+            let bytes =
+                std::fs::read(path).with_context(|| format!("opening {}", path.display()))?;
+            Policy::from_cbor(&bytes)
+                .with_context(|| format!("invalid policy {}", path.display()))?
+        }
+        (None, None) => Policy::default(),
+    };
+    // Parameters are checked against each other however the policy was obtained, so that a
+    // tool never reports on state against a policy the actors could not have run with.
+    policy.validate().map_err(|violations| {
+        let violations: Vec<_> = violations.iter().map(|v| v.to_string()).collect();
+        anyhow!("invalid policy: {}", violations.join("; "))
+    })?;
+    Ok(policy)
+}
@@ -0,0 +1,213 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Display;
+
+use anyhow::anyhow;
+use cid::Cid;
+use fil_actors_runtime::MapKey;
+use fvm_ipld_blockstore::Blockstore;
+use fvm_ipld_encoding::de::DeserializeOwned;
+use fvm_ipld_encoding::serde_bytes::ByteBuf;
+use fvm_ipld_encoding::BytesDe;
+use libipld_core::ipld::Ipld;
+use libipld_core::serde::from_ipld;
+
+use crate::diff::load_state;
+
+/// The entries of two versions of a collection, excluding those in the subtrees they share.
+pub(crate) type Unshared<K, V> = (BTreeMap<K, V>, BTreeMap<K, V>);
+
+/// Returns the entries of two HAMTs outside the subtrees they share, with keys decoded from the
+/// byte keys of a runtime `Map`. A missing root is an empty HAMT.
+pub(crate) fn map_entries<BS, K, V>(
+    store: &BS,
+    old: Option<&Cid>,
+    new: Option<&Cid>,
+) -> anyhow::Result<Unshared<K, V>>
+where
+    BS: Blockstore,
+    K: MapKey + Ord,
+    V: DeserializeOwned,
+{
+    let (old, new) = hamt_entries::<_, ByteBuf, V>(store, old, new)?;
+    let decode = |entries: BTreeMap<ByteBuf, V>| {
+        entries
+            .into_iter()
+            .map(|(key, value)| Ok((K::from_key_bytes(&key).map_err(|e| anyhow!(e))?, value)))
+            .collect::<anyhow::Result<BTreeMap<_, _>>>()
+    };
+    Ok((decode(old)?, decode(new)?))
+}
+
+/// Returns the entries of two HAMTs outside the subtrees they share, with keys decoded as they
+/// are stored. A missing root is an empty HAMT.
+///
+/// The HAMTs' bit widths aren't needed: a key is stored once in a HAMT, so the entries of a node
+/// found in both are the same on each side, wherever the node sits.
+pub(crate) fn hamt_entries<BS, K, V>(
+    store: &BS,
+    old: Option<&Cid>,
+    new: Option<&Cid>,
+) -> anyhow::Result<Unshared<K, V>>
+where
+    BS: Blockstore,
+    K: DeserializeOwned + Ord,
+    V: DeserializeOwned,
+{
+    unshared(old.copied(), new.copied(), |cid, entries| {
+        // A node is its bitfield and pointers, each either a link or a bucket of entries.
+        let (_, pointers): (Ipld, Vec<Ipld>) = load_state(store, &cid)?;
+        let mut links = Vec::new();
+        for pointer in pointers {
+            match pointer {
+                Ipld::Link(link) => links.push(link),
+                bucket => {
+                    let bucket: Vec<(K, V)> = from_ipld(bucket)
+                        .map_err(|e| anyhow!("invalid HAMT node {}: {}", cid, e))?;
+                    entries.extend(bucket);
+                }
+            }
+        }
+        Ok(links)
+    })
+}
+
+/// Calls a function with the entries of a HAMT in iteration order, with keys decoded as they are
+/// stored, until it returns false. Nodes after the last entry visited aren't loaded.
+pub(crate) fn hamt_for_each_while<BS, K, V, F>(
+    store: &BS,
+    root: &Cid,
+    mut f: F,
+) -> anyhow::Result<()>
+where
+    BS: Blockstore,
+    K: DeserializeOwned,
+    V: DeserializeOwned,
+    F: FnMut(K, V) -> anyhow::Result<bool>,
+{
+    fn visit<BS, K, V, F>(store: &BS, cid: &Cid, f: &mut F) -> anyhow::Result<bool>
+    where
+        BS: Blockstore,
+        K: DeserializeOwned,
+        V: DeserializeOwned,
+        F: FnMut(K, V) -> anyhow::Result<bool>,
+    {
+        let (_, pointers): (Ipld, Vec<Ipld>) = load_state(store, cid)?;
+        for pointer in pointers {
+            let more = match pointer {
+                Ipld::Link(link) => visit(store, &link, f)?,
+                bucket => {
+                    let bucket: Vec<(K, V)> = from_ipld(bucket)
+                        .map_err(|e| anyhow!("invalid HAMT node {}: {}", cid, e))?;
+                    let mut more = true;
+                    for (key, value) in bucket {
+                        more = f(key, value)?;
+                        if !more {
+                            break;
+                        }
+                    }
+                    more
+                }
+            };
+            if !more {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+    visit(store, root, &mut f)?;
+    Ok(())
+}
+
+/// A node of an AMT, with the position of the indices it holds.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum AmtNode {
+    /// The root block, which holds the AMT's top node inline.
+    Root(Cid),
+    Link {
+        cid: Cid,
+        bit_width: u32,
+        height: u32,
+        offset: u64,
+    },
+}
+
+/// Returns the entries of two AMTs outside the subtrees they share. A missing root is an empty
+/// AMT.
+///
+/// Nodes are shared only at the same position, so AMTs of different heights are compared by
+/// their entries.
+pub(crate) fn amt_entries<BS, V>(
+    store: &BS,
+    old: Option<&Cid>,
+    new: Option<&Cid>,
+) -> anyhow::Result<Unshared<u64, V>>
+where
+    BS: Blockstore,
+    V: DeserializeOwned,
+{
+    let root = |cid: Option<&Cid>| cid.copied().map(AmtNode::Root);
+    unshared(root(old), root(new), |node, entries| {
+        let (cid, bit_width, height, offset, node) = match node {
+            AmtNode::Root(cid) => {
+                let (bit_width, height, _, node): (u32, u32, u64, Ipld) = load_state(store, &cid)?;
+                (cid, bit_width, height, 0, node)
+            }
+            AmtNode::Link { cid, bit_width, height, offset } => {
+                (cid, bit_width, height, offset, load_state(store, &cid)?)
+            }
+        };
+        let invalid = |e: &dyn Display| anyhow!("invalid AMT node {}: {}", cid, e);
+
+        // A node is a bitmap of its occupied slots, then links to children or values.
+        let (bitmap, links, values): (BytesDe, Vec<Cid>, Vec<Ipld>) =
+            from_ipld(node).map_err(|e| invalid(&e))?;
+        let width = 1u64.checked_shl(bit_width).ok_or_else(|| invalid(&"bit width too large"))?;
+        let stride = width.checked_pow(height).ok_or_else(|| invalid(&"height too large"))?;
+        let slots: Vec<u64> = (0..width)
+            .filter(|i| bitmap.0.get((i / 8) as usize).map_or(false, |b| b & (1 << (i % 8)) != 0))
+            .collect();
+        if slots.len() != links.len() + values.len() || (height == 0) != links.is_empty() {
+            return Err(invalid(&"bitmap doesn't match its links and values"));
+        }
+
+        let mut children = Vec::new();
+        for (slot, value) in slots.iter().zip(values) {
+            entries.insert(offset + slot, from_ipld(value).map_err(|e| invalid(&e))?);
+        }
+        for (slot, cid) in slots.iter().zip(links) {
+            let offset = offset + slot * stride;
+            children.push(AmtNode::Link { cid, bit_width, height: height - 1, offset });
+        }
+        Ok(children)
+    })
+}
+
+/// Walks two trees in step from their roots, expanding only the nodes not found on both sides,
+/// and collects the entries of the nodes expanded.
+fn unshared<N, K, V, F>(
+    old: impl IntoIterator<Item = N>,
+    new: impl IntoIterator<Item = N>,
+    mut expand: F,
+) -> anyhow::Result<Unshared<K, V>>
+where
+    N: Ord + Clone,
+    K: Ord,
+    F: FnMut(N, &mut BTreeMap<K, V>) -> anyhow::Result<Vec<N>>,
+{
+    let mut old_nodes: BTreeSet<N> = old.into_iter().collect();
+    let mut new_nodes: BTreeSet<N> = new.into_iter().collect();
+    let (mut old_entries, mut new_entries) = (BTreeMap::new(), BTreeMap::new());
+    while !old_nodes.is_empty() || !new_nodes.is_empty() {
+        let mut old_children = BTreeSet::new();
+        for node in old_nodes.difference(&new_nodes) {
+            old_children.extend(expand(node.clone(), &mut old_entries)?);
+        }
+        let mut new_children = BTreeSet::new();
+        for node in new_nodes.difference(&old_nodes) {
+            new_children.extend(expand(node.clone(), &mut new_entries)?);
+        }
+        old_nodes = old_children;
+        new_nodes = new_children;
+    }
+    Ok((old_entries, new_entries))
+}
@@ -0,0 +1,66 @@
+use std::collections::HashSet;
+use std::io::Write;
+
+use anyhow::anyhow;
+use cid::Cid;
+use futures::executor::block_on;
+use futures::io::AllowStdIo;
+use fvm_ipld_blockstore::Blockstore;
+use fvm_ipld_car::CarHeader;
+use fvm_ipld_encoding::{from_slice, DAG_CBOR};
+use libipld_core::ipld::Ipld;
+
+pub use fil_builtin_actors_state::car::{actors_root, read_car, StateRoot};
+
+const IPLD_RAW: u64 = 0x55;
+const IDENTITY_HASH: u64 = 0;
+
+/// Writes a CAR file with a single root and every block reachable from it.
+///
+/// Links are followed through DAG-CBOR blocks. Identity-hashed CIDs carry their data inline,
+/// and raw blocks (e.g. actor code) are included only if present, since state slices rarely
+/// carry them. Links with other codecs, such as sector commitments, don't address blocks.
+pub fn write_car<BS: Blockstore, W: Write + Send + Unpin>(
+    store: &BS,
+    root: Cid,
+    writer: W,
+) -> anyhow::Result<()> {
+    let blocks = reachable_blocks(store, root)?;
+    let header = CarHeader::from(vec![root]);
+    let mut writer = AllowStdIo::new(writer);
+    block_on(header.write_stream_async(&mut writer, &mut futures::stream::iter(blocks)))?;
+    Ok(())
+}
+
+/// Returns the blocks reachable from a root, in breadth-first order.
+fn reachable_blocks<BS: Blockstore>(store: &BS, root: Cid) -> anyhow::Result<Vec<(Cid, Vec<u8>)>> {
+    let mut seen = HashSet::from([root]);
+    let mut queue = vec![root];
+    let mut blocks = vec![];
+    let mut next = 0;
+    while next < queue.len() {
+        let cid = queue[next];
+        next += 1;
+        let block = match store.get(&cid)? {
+            Some(block) => block,
+            None if cid.codec() == DAG_CBOR => return Err(anyhow!("missing block {}", cid)),
+            None => continue,
+        };
+        if cid.codec() == DAG_CBOR {
+            let node: Ipld = from_slice(&block)?;
+            for ipld in node.iter() {
+                if let Ipld::Link(link) = ipld {
+                    if is_block_link(link) && seen.insert(*link) {
+                        queue.push(*link);
+                    }
+                }
+            }
+        }
+        blocks.push((cid, block));
+    }
+    Ok(blocks)
+}
+
+fn is_block_link(cid: &Cid) -> bool {
+    cid.hash().code() != IDENTITY_HASH && (cid.codec() == DAG_CBOR || cid.codec() == IPLD_RAW)
+}
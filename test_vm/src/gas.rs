@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+
+use fil_actors_runtime::test_blockstores::BlockstoreStats;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::crypto::signature::SignatureType;
+use fvm_shared::econ::TokenAmount;
+use num_traits::Zero;
+
+/// Gas prices for the operations the test VM can observe.
+/// The defaults approximate the FVM's price list at network version 16.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PriceList {
+    pub block_read_base: i64,
+    pub block_read_per_byte: i64,
+    pub block_write_base: i64,
+    pub block_write_per_byte: i64,
+    pub send_base: i64,
+    pub send_transfer_funds: i64,
+    pub hashing_base: i64,
+    pub compute_unsealed_sector_cid_base: i64,
+    pub verify_signature_secp256k1: i64,
+    pub verify_signature_bls: i64,
+    pub verify_seal_base: i64,
+    pub verify_aggregate_seal_per_proof: i64,
+    pub verify_post_base: i64,
+    pub verify_post_per_sector: i64,
+    pub verify_consensus_fault: i64,
+    pub verify_replica_update: i64,
+}
+
+impl Default for PriceList {
+    fn default() -> Self {
+        Self {
+            block_read_base: 114617,
+            block_read_per_byte: 10,
+            block_write_base: 353640,
+            block_write_per_byte: 1300,
+            send_base: 29233,
+            send_transfer_funds: 27500,
+            hashing_base: 31355,
+            compute_unsealed_sector_cid_base: 98647,
+            verify_signature_secp256k1: 1637292,
+            verify_signature_bls: 16598605,
+            verify_seal_base: 2000,
+            verify_aggregate_seal_per_proof: 449900,
+            verify_post_base: 748593537,
+            verify_post_per_sector: 85639,
+            verify_consensus_fault: 495422,
+            verify_replica_update: 36316136,
+        }
+    }
+}
+
+impl PriceList {
+    /// Gas for reading and writing the blocks recorded in `stats`.
+    pub fn on_store(&self, stats: BlockstoreStats) -> i64 {
+        let reads = self.block_read_base * stats.reads as i64
+            + self.block_read_per_byte * stats.bytes_read as i64;
+        let writes = self.block_write_base * stats.writes as i64
+            + self.block_write_per_byte * stats.bytes_written as i64;
+        reads + writes
+    }
+
+    pub fn on_send(&self, value: &TokenAmount) -> i64 {
+        if value.is_zero() {
+            self.send_base
+        } else {
+            self.send_base + self.send_transfer_funds
+        }
+    }
+
+    pub fn on_verify_signature(&self, sig_type: SignatureType) -> i64 {
+        match sig_type {
+            SignatureType::BLS => self.verify_signature_bls,
+            _ => self.verify_signature_secp256k1,
+        }
+    }
+
+    pub fn on_verify_post(&self, sectors: usize) -> i64 {
+        self.verify_post_base + self.verify_post_per_sector * sectors as i64
+    }
+}
+
+/// Optional gas accounting for the test VM.
+///
+/// Gas is metered but not paid: messages that exceed their gas limit abort with
+/// `SYS_OUT_OF_GAS`, but balances are not charged for gas used.
+#[derive(Clone, Debug)]
+pub struct GasModel {
+    pub prices: PriceList,
+    /// Gas limit for messages applied without an explicit limit.
+    pub default_gas_limit: u64,
+    /// Base fee from each epoch onwards, until the next entry. Zero before the first entry.
+    pub base_fee_schedule: BTreeMap<ChainEpoch, TokenAmount>,
+}
+
+impl Default for GasModel {
+    fn default() -> Self {
+        Self {
+            prices: PriceList::default(),
+            default_gas_limit: 10_000_000_000,
+            base_fee_schedule: BTreeMap::new(),
+        }
+    }
+}
+
+impl GasModel {
+    /// Sets a base fee that applies from `epoch` onwards.
+    pub fn with_base_fee(mut self, epoch: ChainEpoch, base_fee: TokenAmount) -> Self {
+        self.base_fee_schedule.insert(epoch, base_fee);
+        self
+    }
+
+    /// Returns the base fee at an epoch.
+    pub fn base_fee_at(&self, epoch: ChainEpoch) -> TokenAmount {
+        self.base_fee_schedule
+            .range(..=epoch)
+            .next_back()
+            .map_or_else(TokenAmount::zero, |(_, fee)| fee.clone())
+    }
+}
@@ -1,9 +1,16 @@
+use crate::gas::{GasModel, PriceList};
+use crate::manifest::{is_non_singleton, migrate_actor_code, Manifest, TEST_MANIFEST};
+use crate::randomness::{FixedRandomness, RandomnessKind, RandomnessSource};
+use crate::signatures::SignatureMode;
+use crate::verifier::ProofFaults;
 use anyhow::anyhow;
 use bimap::BiBTreeMap;
 use cid::multihash::Code;
 use cid::Cid;
 use fil_actor_account::{Actor as AccountActor, State as AccountState};
-use fil_actor_cron::{Actor as CronActor, Entry as CronEntry, State as CronState};
+use fil_actor_cron::{
+    Actor as CronActor, Entry as CronEntry, Method as CronMethod, State as CronState,
+};
 use fil_actor_datacap::{Actor as DataCapActor, State as DataCapState};
 use fil_actor_init::{Actor as InitActor, ExecReturn, State as InitState};
 use fil_actor_market::{Actor as MarketActor, Method as MarketMethod, State as MarketState};
@@ -11,25 +18,31 @@ use fil_actor_miner::{Actor as MinerActor, MinerInfo, State as MinerState};
 use fil_actor_multisig::Actor as MultisigActor;
 use fil_actor_paych::Actor as PaychActor;
 use fil_actor_power::{Actor as PowerActor, Method as MethodPower, State as PowerState};
-use fil_actor_reward::{Actor as RewardActor, State as RewardState};
+use fil_actor_reward::{
+    Actor as RewardActor, AwardBlockRewardParams, Method as RewardMethod, State as RewardState,
+};
 use fil_actor_system::{Actor as SystemActor, State as SystemState};
 use fil_actor_verifreg::{Actor as VerifregActor, State as VerifRegState};
 use fil_actors_runtime::actor_error;
 use fil_actors_runtime::cbor::serialize;
 use fil_actors_runtime::runtime::builtins::Type;
 use fil_actors_runtime::runtime::{
-    ActorCode, DomainSeparationTag, MessageInfo, Policy, Primitives, Runtime, RuntimePolicy,
-    Verifier, EMPTY_ARR_CID,
+    ActorCode, DomainSeparationTag, MessageInfo, Policy, PolicyError, Primitives, Response,
+    Runtime, RuntimePolicy, SendFlags, Verifier, EMPTY_ARR_CID,
 };
+use fil_actors_runtime::test_blockstores::{BlockstoreStats, TrackingBlockstore};
 use fil_actors_runtime::test_utils::*;
 use fil_actors_runtime::{
     ActorError, BURNT_FUNDS_ACTOR_ADDR, CRON_ACTOR_ADDR, FIRST_NON_SINGLETON_ADDR, INIT_ACTOR_ADDR,
     REWARD_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR, SYSTEM_ACTOR_ADDR,
     VERIFIED_REGISTRY_ACTOR_ADDR,
 };
-use fil_actors_runtime::{MessageAccumulator, DATACAP_TOKEN_ACTOR_ADDR};
-use fil_builtin_actors_state::check::check_state_invariants;
+use fil_actors_runtime::{ActorEvent, MessageAccumulator, DATACAP_TOKEN_ACTOR_ADDR};
 use fil_builtin_actors_state::check::Tree;
+use fil_builtin_actors_state::check::{
+    check_state_invariants, check_state_invariants_incremental, Actor as StateActor, StateCheck,
+};
+use fil_builtin_actors_state::inspect::{inspect_actor, DEFAULT_ENTRY_LIMIT};
 use fvm_ipld_blockstore::MemoryBlockstore;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_ipld_encoding::tuple::*;
@@ -56,16 +69,26 @@ use fvm_shared::{ActorID, MethodNum, METHOD_CONSTRUCTOR, METHOD_SEND};
 use regex::Regex;
 use serde::de::DeserializeOwned;
 use serde::{ser, Serialize};
-use std::cell::{RefCell, RefMut};
-use std::collections::HashMap;
+use std::cell::{Cell, RefCell, RefMut};
+use std::collections::{BTreeMap, HashMap};
 use std::error::Error;
 use std::fmt;
+use std::io::{Read, Write};
 use std::ops::Add;
+use std::rc::Rc;
 
+pub mod car;
+pub mod gas;
+pub mod manifest;
+pub mod randomness;
+pub mod signatures;
 pub mod util;
+pub mod verifier;
 
 pub struct VM<'bs> {
     pub store: &'bs MemoryBlockstore,
+    // The store as seen by actors, recording the blocks each invocation reads and writes.
+    actor_store: TrackingBlockstore<&'bs MemoryBlockstore>,
     pub state_root: RefCell<Cid>,
     total_fil: TokenAmount,
     actors_dirty: RefCell<bool>,
@@ -73,6 +96,14 @@ pub struct VM<'bs> {
     network_version: NetworkVersion,
     curr_epoch: ChainEpoch,
     invocations: RefCell<Vec<InvocationTrace>>,
+    policy: Policy,
+    gas_model: Option<GasModel>,
+    block_winners: Vec<BlockWinner>,
+    manifests: BTreeMap<NetworkVersion, Manifest>,
+    randomness: Rc<dyn RandomnessSource>,
+    signature_mode: SignatureMode,
+    proof_faults: RefCell<ProofFaults>,
+    state_check: RefCell<Option<StateCheck>>,
 }
 
 pub struct MinerBalances {
@@ -116,6 +147,7 @@ impl<'bs> VM<'bs> {
         let mut actors = Hamt::<&'bs MemoryBlockstore, Actor, BytesKey, Sha256>::new(store);
         VM {
             store,
+            actor_store: TrackingBlockstore::new(store),
             state_root: RefCell::new(actors.flush().unwrap()),
             total_fil: TokenAmount::zero(),
             actors_dirty: RefCell::new(false),
@@ -123,6 +155,14 @@ impl<'bs> VM<'bs> {
             network_version: NetworkVersion::V16,
             curr_epoch: ChainEpoch::zero(),
             invocations: RefCell::new(vec![]),
+            policy: validated(Policy::default()),
+            gas_model: None,
+            block_winners: vec![],
+            manifests: BTreeMap::new(),
+            randomness: Rc::new(FixedRandomness::default()),
+            signature_mode: SignatureMode::Mock,
+            proof_faults: RefCell::new(ProofFaults::default()),
+            state_check: RefCell::new(None),
         }
     }
 
@@ -130,6 +170,34 @@ impl<'bs> VM<'bs> {
         Self { total_fil, ..self }
     }
 
+    /// Uses the given policy for messages applied and invariants checked from now on.
+    pub fn with_policy(self, policy: Policy) -> Self {
+        Self { policy: validated(policy), ..self }
+    }
+
+    pub fn policy(&self) -> &Policy {
+        &self.policy
+    }
+
+    /// Meters gas for messages applied to this VM, with prices, limits and base fees
+    /// from the gas model.
+    pub fn with_gas_model(self, gas_model: GasModel) -> Self {
+        Self { gas_model: Some(gas_model), ..self }
+    }
+
+    /// Sets the miners awarded block rewards in each tipset applied with `apply_tipset`.
+    pub fn with_block_winners(self, block_winners: Vec<BlockWinner>) -> Self {
+        Self { block_winners, ..self }
+    }
+
+    /// The base fee at the current epoch, which is zero without a gas model.
+    pub fn base_fee(&self) -> TokenAmount {
+        match &self.gas_model {
+            Some(model) => model.base_fee_at(self.curr_epoch),
+            None => TokenAmount::zero(),
+        }
+    }
+
     pub fn new_with_singletons(store: &'bs MemoryBlockstore) -> VM<'bs> {
         let reward_total = TokenAmount::from_whole(1_100_000_000i64);
         let faucet_total = TokenAmount::from_whole(1_000_000_000i64);
@@ -262,6 +330,7 @@ impl<'bs> VM<'bs> {
         self.checkpoint();
         VM {
             store: self.store,
+            actor_store: TrackingBlockstore::new(self.store),
             state_root: self.state_root.clone(),
             total_fil: self.total_fil,
             actors_dirty: RefCell::new(false),
@@ -269,7 +338,149 @@ impl<'bs> VM<'bs> {
             network_version: self.network_version,
             curr_epoch: epoch,
             invocations: RefCell::new(vec![]),
+            policy: self.policy,
+            gas_model: self.gas_model,
+            block_winners: self.block_winners,
+            manifests: self.manifests,
+            randomness: self.randomness,
+            signature_mode: self.signature_mode,
+            proof_faults: self.proof_faults,
+            state_check: self.state_check,
+        }
+    }
+
+    pub fn with_network_version(self, network_version: NetworkVersion) -> Self {
+        Self { network_version, ..self }
+    }
+
+    pub fn network_version(&self) -> NetworkVersion {
+        self.network_version
+    }
+
+    /// Returns a copy of this VM that can be advanced independently.
+    ///
+    /// Both VMs share the blockstore, which only ever gains blocks, so a fork is as cheap as
+    /// a checkpoint. The fork starts from this VM's current state, epoch, network version and
+    /// configuration, with no invocation traces or incremental invariant check to build on, and
+    /// neither VM observes the other's changes.
+    pub fn fork(&self) -> VM<'bs> {
+        VM {
+            store: self.store,
+            actor_store: TrackingBlockstore::new(self.store),
+            state_root: RefCell::new(self.checkpoint()),
+            total_fil: self.total_fil.clone(),
+            actors_dirty: RefCell::new(false),
+            actors_cache: RefCell::new(HashMap::new()),
+            network_version: self.network_version,
+            curr_epoch: self.curr_epoch,
+            invocations: RefCell::new(vec![]),
+            policy: self.policy.clone(),
+            gas_model: self.gas_model.clone(),
+            block_winners: self.block_winners.clone(),
+            manifests: self.manifests.clone(),
+            randomness: self.randomness.clone(),
+            signature_mode: self.signature_mode,
+            proof_faults: self.proof_faults.clone(),
+            state_check: RefCell::new(None),
+        }
+    }
+
+    /// Sets the source of randomness returned to actors.
+    pub fn with_randomness(self, randomness: impl RandomnessSource + 'static) -> Self {
+        Self { randomness: Rc::new(randomness), ..self }
+    }
+
+    /// Sets how signatures are verified.
+    pub fn with_signature_mode(self, signature_mode: SignatureMode) -> Self {
+        Self { signature_mode, ..self }
+    }
+
+    /// The proof faults injected into the VM's proof verifier, which tests may change
+    /// between messages.
+    pub fn proof_faults(&self) -> RefMut<'_, ProofFaults> {
+        self.proof_faults.borrow_mut()
+    }
+
+    /// Registers the actor code CIDs for a network version and those after it, up to the next
+    /// registered version. Versions before any registration use `TEST_MANIFEST`.
+    pub fn with_manifest(mut self, network_version: NetworkVersion, manifest: Manifest) -> Self {
+        self.manifests.insert(network_version, manifest);
+        self
+    }
+
+    /// The actor code CIDs at the current network version.
+    pub fn manifest(&self) -> &Manifest {
+        self.manifest_at(self.network_version)
+    }
+
+    fn manifest_at(&self, network_version: NetworkVersion) -> &Manifest {
+        self.manifests
+            .range(..=network_version)
+            .next_back()
+            .map_or(&*TEST_MANIFEST, |(_, manifest)| manifest)
+    }
+
+    pub fn type_for_code(&self, code: &Cid) -> Option<Type> {
+        self.manifest().get_by_left(code).copied()
+    }
+
+    pub fn code_for_type(&self, typ: Type) -> Cid {
+        *self.manifest().get_by_right(&typ).unwrap()
+    }
+
+    /// Upgrades the VM to a new network version.
+    ///
+    /// Every actor's code is replaced with the code of the same type for the new version,
+    /// then `migration` may rewrite the state tree, as a network upgrade's state migration would.
+    /// Returns the result of checking state invariants after the upgrade.
+    pub fn upgrade<F>(
+        &mut self,
+        network_version: NetworkVersion,
+        migration: F,
+    ) -> anyhow::Result<MessageAccumulator>
+    where
+        F: FnOnce(&mut Tree<'bs, MemoryBlockstore>) -> anyhow::Result<()>,
+    {
+        if network_version <= self.network_version {
+            return Err(anyhow!(
+                "cannot upgrade from network version {} to {}",
+                self.network_version,
+                network_version
+            ));
         }
+        let mut tree = Tree::load(self.store, &self.checkpoint())?;
+        migrate_actor_code(&mut tree, self.manifest(), self.manifest_at(network_version))?;
+        migration(&mut tree)?;
+        self.rollback(tree.map.flush()?);
+        self.network_version = network_version;
+        self.check_state_invariants()
+    }
+
+    /// Loads a state tree from a CAR file into the store, and returns a VM with that state.
+    ///
+    /// The CAR's root may be an actors HAMT, as exported by `export_car`, or a chain `StateRoot`.
+    /// The VM's total FIL is the sum of the imported actors' balances.
+    pub fn import_car<R: Read + Send + Unpin>(
+        store: &'bs MemoryBlockstore,
+        reader: R,
+    ) -> anyhow::Result<VM<'bs>> {
+        let root = car::read_car(store, reader)?;
+        let actors_root = car::actors_root(store, &root)?;
+        let actors =
+            Hamt::<&'bs MemoryBlockstore, Actor, BytesKey, Sha256>::load(&actors_root, store)?;
+        let mut total_fil = TokenAmount::zero();
+        actors.for_each(|_, actor| {
+            total_fil += &actor.balance;
+            Ok(())
+        })?;
+        let v = VM::new(store).with_total_fil(total_fil);
+        v.state_root.replace(actors_root);
+        Ok(v)
+    }
+
+    /// Writes the VM's state root and all blocks reachable from it to a CAR file.
+    pub fn export_car<W: Write + Send + Unpin>(&self, writer: W) -> anyhow::Result<()> {
+        car::write_car(self.store, self.checkpoint(), writer)
     }
 
     pub fn get_miner_balance(&self, maddr: Address) -> MinerBalances {
@@ -321,6 +532,19 @@ impl<'bs> VM<'bs> {
         self.store.put_cbor(obj, Code::Blake2b256).unwrap()
     }
 
+    /// Resolves an address to the public key address of the account actor it refers to.
+    pub fn resolve_key_address(&self, addr: &Address) -> anyhow::Result<Address> {
+        if addr.protocol() != Protocol::ID {
+            return Ok(*addr);
+        }
+        let actor = self.get_actor(*addr).ok_or_else(|| anyhow!("no actor at {}", addr))?;
+        if self.type_for_code(&actor.code) != Some(Type::Account) {
+            return Err(anyhow!("actor {} is not an account", addr));
+        }
+        let st: AccountState = self.get_state(*addr).unwrap();
+        Ok(st.address)
+    }
+
     pub fn get_actor(&self, addr: Address) -> Option<Actor> {
         // check for inclusion in cache of changed actors
         if let Some(act) = self.actors_cache.borrow().get(&addr) {
@@ -404,6 +628,48 @@ impl<'bs> VM<'bs> {
         value: TokenAmount,
         method: MethodNum,
         params: Option<S>,
+    ) -> Result<MessageResult, TestVMError> {
+        self.apply_message_inner(from, to, value, method, serialize_params(params), None, false)
+    }
+
+    /// Applies a message with a gas limit, overriding the gas model's default limit.
+    /// The limit has no effect if the VM has no gas model.
+    pub fn apply_message_with_gas_limit<S: serde::Serialize>(
+        &self,
+        from: Address,
+        to: Address,
+        value: TokenAmount,
+        method: MethodNum,
+        params: Option<S>,
+        gas_limit: u64,
+    ) -> Result<MessageResult, TestVMError> {
+        let params = serialize_params(params);
+        self.apply_message_inner(from, to, value, method, params, Some(gas_limit), false)
+    }
+
+    /// Applies a message in read-only mode. The invoked method, and anything it calls,
+    /// fails with EXIT_CODE_READ_ONLY if it attempts to cause a side-effect.
+    pub fn apply_message_read_only<S: serde::Serialize>(
+        &self,
+        from: Address,
+        to: Address,
+        method: MethodNum,
+        params: Option<S>,
+    ) -> Result<MessageResult, TestVMError> {
+        let params = serialize_params(params);
+        self.apply_message_inner(from, to, TokenAmount::zero(), method, params, None, true)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn apply_message_inner(
+        &self,
+        from: Address,
+        to: Address,
+        value: TokenAmount,
+        method: MethodNum,
+        params: Option<IpldBlock>,
+        gas_limit: Option<u64>,
+        read_only: bool,
     ) -> Result<MessageResult, TestVMError> {
         let from_id = self.normalize_address(&from).unwrap();
         let mut a = self.get_actor(from_id).unwrap();
@@ -420,25 +686,31 @@ impl<'bs> VM<'bs> {
             originator_call_seq: call_seq,
             new_actor_addr_count: RefCell::new(0),
             circ_supply: TokenAmount::from_whole(1_000_000_000),
+            gas_used: Rc::new(Cell::new(0)),
+            charged_store_stats: Rc::new(Cell::new(self.actor_store.stats())),
         };
-        let msg = InternalMessage {
-            from: from_id,
-            to,
-            value,
-            method,
-            params: params.map(|p| IpldBlock::serialize_cbor(&p).unwrap().unwrap()),
+        let message_gas_limit = match &self.gas_model {
+            Some(model) => gas_limit.unwrap_or(model.default_gas_limit),
+            None => u64::MAX,
         };
+        let msg = InternalMessage { from: from_id, to, value, method, params, gas_limit };
         let mut new_ctx = InvocationCtx {
             v: self,
             top,
             msg,
             allow_side_effects: true,
             caller_validated: false,
-            policy: &Policy::default(),
+            policy: &self.policy,
             subinvocations: RefCell::new(vec![]),
+            events: RefCell::new(vec![]),
+            read_only,
+            start_store_stats: self.actor_store.stats(),
+            gas_limit: i64::try_from(message_gas_limit).unwrap_or(i64::MAX),
         };
         let res = new_ctx.invoke();
+        let gas_used = new_ctx.top.gas_used.get();
         let invoc = new_ctx.gather_trace(res.clone());
+        let store_stats = invoc.store_stats;
         RefMut::map(self.invocations.borrow_mut(), |invocs| {
             invocs.push(invoc);
             invocs
@@ -446,21 +718,173 @@ impl<'bs> VM<'bs> {
         match res {
             Err(ae) => {
                 self.rollback(prior_root);
-                Ok(MessageResult { code: ae.exit_code(), message: ae.msg().to_string(), ret: None })
+                Ok(MessageResult {
+                    code: ae.exit_code(),
+                    message: ae.msg().to_string(),
+                    ret: None,
+                    error: Some(ae),
+                    store_stats,
+                    gas_used,
+                })
             }
             Ok(ret) => {
                 self.checkpoint();
-                Ok(MessageResult { code: ExitCode::OK, message: "OK".to_string(), ret })
+                Ok(MessageResult {
+                    code: ExitCode::OK,
+                    message: "OK".to_string(),
+                    ret,
+                    error: None,
+                    store_stats,
+                    gas_used,
+                })
             }
         }
     }
 
+    /// Applies a tipset at `epoch`: the messages in order, then a block reward for each block
+    /// winner and the cron tick as implicit messages.
+    /// As on chain, implicit messages are not limited by the gas model's default gas limit.
+    /// Returns a receipt with the invocation trace of each message, and advances the VM to the
+    /// following epoch. The traces of the implicit messages remain for `take_invocations`.
+    /// Failed messages are reported in their receipts, but a failed implicit message is an error,
+    /// leaving the VM at the tipset's epoch with the messages before it applied.
+    pub fn apply_tipset(
+        &mut self,
+        messages: Vec<TipsetMessage>,
+        epoch: ChainEpoch,
+    ) -> Result<Vec<TipsetReceipt>, TestVMError> {
+        if epoch < self.curr_epoch {
+            return Err(vm_err(&format!(
+                "tipset epoch {} is before current epoch {}",
+                epoch, self.curr_epoch
+            )));
+        }
+        self.advance_epoch(epoch);
+        let mut receipts = Vec::with_capacity(messages.len());
+        for m in messages {
+            let result = self.apply_message_inner(
+                m.from,
+                m.to,
+                m.value,
+                m.method,
+                m.params,
+                m.gas_limit,
+                false,
+            )?;
+            let trace = self.invocations.borrow_mut().pop().unwrap();
+            receipts.push(TipsetReceipt { result, trace });
+        }
+
+        for winner in &self.block_winners {
+            let params = AwardBlockRewardParams {
+                miner: winner.miner,
+                penalty: TokenAmount::zero(),
+                gas_reward: TokenAmount::zero(),
+                win_count: winner.win_count,
+            };
+            self.apply_implicit_message(
+                REWARD_ACTOR_ADDR,
+                RewardMethod::AwardBlockReward as u64,
+                serialize_params(Some(params)),
+            )?;
+        }
+        self.apply_implicit_message(CRON_ACTOR_ADDR, CronMethod::EpochTick as u64, None)?;
+        self.advance_epoch(epoch + 1);
+        Ok(receipts)
+    }
+
+    // Moves the VM to a later epoch in place, keeping its invocation traces.
+    fn advance_epoch(&mut self, epoch: ChainEpoch) {
+        self.checkpoint();
+        self.curr_epoch = epoch;
+    }
+
+    fn apply_implicit_message(
+        &self,
+        to: Address,
+        method: MethodNum,
+        params: Option<IpldBlock>,
+    ) -> Result<(), TestVMError> {
+        let res = self.apply_message_inner(
+            SYSTEM_ACTOR_ADDR,
+            to,
+            TokenAmount::zero(),
+            method,
+            params,
+            Some(IMPLICIT_MESSAGE_GAS_LIMIT),
+            false,
+        )?;
+        if res.code != ExitCode::OK {
+            return Err(vm_err(&format!(
+                "implicit message to {} method {} failed: {}: {}",
+                to, method, res.code, res.message
+            )));
+        }
+        Ok(())
+    }
+
     pub fn take_invocations(&self) -> Vec<InvocationTrace> {
         self.invocations.take()
     }
 
+    /// Renders an actor and its state as an indented tree, expanding the first entries of each
+    /// HAMT, AMT and bitfield, e.g. to print the state of a miner when a test fails.
+    pub fn dump_actor(&self, addr: Address) -> anyhow::Result<String> {
+        let actor = self
+            .normalize_address(&addr)
+            .and_then(|id| self.get_actor(id))
+            .ok_or_else(|| anyhow!("no actor at {}", addr))?;
+        let actor = StateActor {
+            code: actor.code,
+            head: actor.head,
+            call_seq_num: actor.call_seq_num,
+            balance: actor.balance,
+        };
+        let node = inspect_actor(
+            self.manifest(),
+            &self.policy,
+            self.store,
+            &actor,
+            0,
+            DEFAULT_ENTRY_LIMIT,
+        )?;
+        Ok(node.to_string())
+    }
+
     /// Checks the state invariants and returns broken invariants.
     pub fn check_state_invariants(&self) -> anyhow::Result<MessageAccumulator> {
+        let manifest = self.invariant_manifest()?;
+        let state_tree = Tree::load(&self.store, &self.state_root.borrow()).unwrap();
+        check_state_invariants(
+            &manifest,
+            &self.policy,
+            state_tree,
+            &self.total_fil,
+            self.get_epoch() - 1,
+        )
+    }
+
+    /// Checks the state invariants like `check_state_invariants`, but only re-checks what changed
+    /// since this VM's last incremental check. This is much faster for checking state after each
+    /// epoch of a long scenario.
+    pub fn check_state_invariants_incremental(&self) -> anyhow::Result<MessageAccumulator> {
+        let manifest = self.invariant_manifest()?;
+        let (acc, check) = check_state_invariants_incremental(
+            &manifest,
+            &self.policy,
+            self.store,
+            &self.checkpoint(),
+            &self.total_fil,
+            self.get_epoch() - 1,
+            self.state_check.take(),
+        )?;
+        self.state_check.replace(Some(check));
+        Ok(acc)
+    }
+
+    /// The types of the actor codes in the state tree, which must all be in the manifest for the
+    /// current network version.
+    fn invariant_manifest(&self) -> anyhow::Result<BiBTreeMap<Cid, Type>> {
         self.checkpoint();
         let actors = Hamt::<&'bs MemoryBlockstore, Actor, BytesKey, Sha256>::load(
             &self.state_root.borrow(),
@@ -469,22 +893,20 @@ impl<'bs> VM<'bs> {
         .unwrap();
 
         let mut manifest = BiBTreeMap::new();
-        actors
-            .for_each(|_, actor| {
-                manifest.insert(actor.code, ACTOR_TYPES.get(&actor.code).unwrap().to_owned());
-                Ok(())
-            })
-            .unwrap();
-
-        let policy = Policy::default();
-        let state_tree = Tree::load(&self.store, &self.state_root.borrow()).unwrap();
-        check_state_invariants(
-            &manifest,
-            &policy,
-            state_tree,
-            &self.total_fil,
-            self.get_epoch() - 1,
-        )
+        actors.for_each(|key, actor| {
+            let addr = Address::from_bytes(key)?;
+            let typ = self.manifest().get_by_left(&actor.code).ok_or_else(|| {
+                anyhow!(
+                    "actor {} has code {} not in the manifest for network version {}",
+                    addr,
+                    actor.code,
+                    self.network_version
+                )
+            })?;
+            manifest.insert(actor.code, *typ);
+            Ok(())
+        })?;
+        Ok(manifest)
     }
 
     /// Asserts state invariants are held without any errors.
@@ -498,6 +920,12 @@ impl<'bs> VM<'bs> {
         self.check_state_invariants().unwrap().assert_expected(expected_patterns)
     }
 
+    /// Checks state, allowing expected invariants to fail, identified by their stable IDs.
+    /// The invariants *must* fail in the provided order.
+    pub fn expect_state_invariant_ids(&self, expected_invariants: &[&str]) {
+        self.check_state_invariants().unwrap().assert_invariants(expected_invariants)
+    }
+
     pub fn get_total_actor_balance(
         &self,
         store: &MemoryBlockstore,
@@ -519,6 +947,10 @@ pub struct TopCtx {
     originator_call_seq: u64,
     new_actor_addr_count: RefCell<u64>,
     circ_supply: TokenAmount,
+    // Gas used by the message so far, shared by all its invocations.
+    gas_used: Rc<Cell<i64>>,
+    // Blockstore stats at the last point storage gas was charged.
+    charged_store_stats: Rc<Cell<BlockstoreStats>>,
 }
 
 #[derive(Clone, Debug)]
@@ -528,12 +960,25 @@ pub struct InternalMessage {
     value: TokenAmount,
     method: MethodNum,
     params: Option<IpldBlock>,
+    gas_limit: Option<u64>,
 }
 
 impl InternalMessage {
+    pub fn to(&self) -> Address {
+        self.to
+    }
+
+    pub fn method(&self) -> MethodNum {
+        self.method
+    }
+
     pub fn value(&self) -> TokenAmount {
         self.value.clone()
     }
+
+    pub fn gas_limit(&self) -> Option<u64> {
+        self.gas_limit
+    }
 }
 
 impl MessageInfo for InvocationCtx<'_, '_> {
@@ -562,6 +1007,11 @@ pub struct InvocationCtx<'invocation, 'bs> {
     caller_validated: bool,
     policy: &'invocation Policy,
     subinvocations: RefCell<Vec<InvocationTrace>>,
+    events: RefCell<Vec<ActorEvent>>,
+    read_only: bool,
+    start_store_stats: BlockstoreStats,
+    // The message's total gas used beyond which this invocation runs out of gas.
+    gas_limit: i64,
 }
 
 impl<'invocation, 'bs> InvocationCtx<'invocation, 'bs> {
@@ -572,6 +1022,11 @@ impl<'invocation, 'bs> InvocationCtx<'invocation, 'bs> {
             }
         };
         // Address does not yet exist, create it
+        if self.read_only {
+            return Err(
+                actor_error!(read_only; "cannot create account {} in read-only mode", target),
+            );
+        }
         let protocol = target.protocol();
         match protocol {
             Protocol::Actor | Protocol::ID => {
@@ -595,6 +1050,7 @@ impl<'invocation, 'bs> InvocationCtx<'invocation, 'bs> {
             value: TokenAmount::zero(),
             method: METHOD_CONSTRUCTOR,
             params: IpldBlock::serialize_cbor(target).unwrap(),
+            gas_limit: None,
         };
         {
             let mut new_ctx = InvocationCtx {
@@ -605,8 +1061,12 @@ impl<'invocation, 'bs> InvocationCtx<'invocation, 'bs> {
                 caller_validated: false,
                 policy: self.policy,
                 subinvocations: RefCell::new(vec![]),
+                events: RefCell::new(vec![]),
+                read_only: false,
+                start_store_stats: self.v.actor_store.stats(),
+                gas_limit: self.gas_limit,
             };
-            new_ctx.create_actor(*ACCOUNT_ACTOR_CODE_ID, target_id).unwrap();
+            new_ctx.create_actor(self.v.code_for_type(Type::Account), target_id).unwrap();
             let res = new_ctx.invoke();
             let invoc = new_ctx.gather_trace(res);
             RefMut::map(self.subinvocations.borrow_mut(), |subinvocs| {
@@ -622,16 +1082,106 @@ impl<'invocation, 'bs> InvocationCtx<'invocation, 'bs> {
         &mut self,
         invoke_result: Result<Option<IpldBlock>, ActorError>,
     ) -> InvocationTrace {
-        let (ret, code) = match invoke_result {
-            Ok(rb) => (rb, ExitCode::OK),
-            Err(ae) => (None, ae.exit_code()),
+        let (ret, code, events) = match invoke_result {
+            Ok(rb) => (rb, ExitCode::OK, self.events.take()),
+            // Events emitted by an aborted invocation are discarded along with its state.
+            Err(ae) => (None, ae.exit_code(), vec![]),
         };
         let mut msg = self.msg.clone();
         msg.to = match self.resolve_target(&self.msg.to) {
             Ok((_, addr)) => addr, // use normalized address in trace
             _ => self.msg.to, // if target resolution fails don't fail whole invoke, just use non normalized
         };
-        InvocationTrace { msg, code, ret, subinvocations: self.subinvocations.take() }
+        let store_stats = self.v.actor_store.stats() - self.start_store_stats;
+        InvocationTrace {
+            msg,
+            code,
+            ret,
+            subinvocations: self.subinvocations.take(),
+            events,
+            store_stats,
+        }
+    }
+
+    fn send_inner(
+        &self,
+        to: &Address,
+        method: MethodNum,
+        params: Option<IpldBlock>,
+        value: TokenAmount,
+        gas_limit: Option<u64>,
+        flags: SendFlags,
+    ) -> Result<Response, ActorError> {
+        if !self.allow_side_effects {
+            return Err(ActorError::unchecked(
+                ExitCode::SYS_ASSERTION_FAILED,
+                "Calling send is not allowed during side-effect lock".to_string(),
+            ));
+        }
+        // A send from a read-only invocation is itself read-only.
+        let read_only = self.read_only || flags.read_only();
+        if read_only && !value.is_zero() {
+            return Err(actor_error!(read_only; "cannot transfer value in read-only mode"));
+        }
+
+        self.charge_store_gas();
+        // A gas limit on a send caps the gas available to the callee, including the send itself.
+        let callee_gas_limit = match gas_limit {
+            Some(limit) => self.gas_limit.min(
+                self.top.gas_used.get().saturating_add(i64::try_from(limit).unwrap_or(i64::MAX)),
+            ),
+            None => self.gas_limit,
+        };
+
+        let new_actor_msg =
+            InternalMessage { from: self.to(), to: *to, value, method, params, gas_limit };
+        let mut new_ctx = InvocationCtx {
+            v: self.v,
+            top: self.top.clone(),
+            msg: new_actor_msg,
+            allow_side_effects: true,
+            caller_validated: false,
+            policy: self.policy,
+            subinvocations: RefCell::new(vec![]),
+            events: RefCell::new(vec![]),
+            read_only,
+            start_store_stats: self.v.actor_store.stats(),
+            gas_limit: callee_gas_limit,
+        };
+        let res = new_ctx.try_invoke();
+
+        let invoc = new_ctx.gather_trace(res.clone().and_then(|r| r));
+        RefMut::map(self.subinvocations.borrow_mut(), |subinvocs| {
+            subinvocs.push(invoc);
+            subinvocs
+        });
+        Ok(match res? {
+            Ok(return_data) => Response { exit_code: ExitCode::OK, return_data, error_msg: None },
+            Err(e) => Response {
+                exit_code: e.exit_code(),
+                return_data: None,
+                error_msg: Some(e.msg().to_string()),
+            },
+        })
+    }
+
+    // Charges gas to the message, if the VM has a gas model.
+    fn charge_gas_with(&self, gas: impl FnOnce(&PriceList) -> i64) {
+        if let Some(model) = &self.v.gas_model {
+            let used = &self.top.gas_used;
+            used.set(used.get().saturating_add(gas(&model.prices)));
+        }
+    }
+
+    // Charges for the blocks actors have read and written since storage was last charged.
+    fn charge_store_gas(&self) {
+        let stats = self.v.actor_store.stats();
+        let uncharged = stats - self.top.charged_store_stats.replace(stats);
+        self.charge_gas_with(|p| p.on_store(uncharged));
+    }
+
+    fn out_of_gas(&self) -> bool {
+        self.v.gas_model.is_some() && self.top.gas_used.get() > self.gas_limit
     }
 
     fn to(&'_ self) -> Address {
@@ -639,6 +1189,16 @@ impl<'invocation, 'bs> InvocationCtx<'invocation, 'bs> {
     }
 
     fn invoke(&mut self) -> Result<Option<IpldBlock>, ActorError> {
+        self.try_invoke().and_then(|res| res)
+    }
+
+    // Invokes the message. The outer error is a failure to send the message, in which case the
+    // callee was never invoked, and the inner result is the callee's.
+    fn try_invoke(&mut self) -> Result<Result<Option<IpldBlock>, ActorError>, ActorError> {
+        self.charge_gas_with(|p| p.on_send(&self.msg.value));
+        if self.out_of_gas() {
+            return Ok(Err(out_of_gas()));
+        }
         let prior_root = self.v.checkpoint();
 
         // Transfer funds
@@ -662,57 +1222,69 @@ impl<'invocation, 'bs> InvocationCtx<'invocation, 'bs> {
         from_actor.balance -= &self.msg.value;
         self.v.set_actor(self.msg.from, from_actor);
 
-        let (mut to_actor, to_addr) = self.resolve_target(&self.msg.to)?;
+        let (mut to_actor, to_addr) = match self.resolve_target(&self.msg.to) {
+            Ok(target) => target,
+            Err(e) => {
+                self.v.rollback(prior_root);
+                return Err(e);
+            }
+        };
         to_actor.balance = to_actor.balance.add(&self.msg.value);
         self.v.set_actor(to_addr, to_actor);
 
         // Exit early on send
         if self.msg.method == METHOD_SEND {
-            return Ok(None);
+            return Ok(Ok(None));
         }
 
         // call target actor
         let to_actor = self.v.get_actor(to_addr).unwrap();
         let params = self.msg.params.clone();
-        let mut res = match ACTOR_TYPES.get(&to_actor.code).expect("Target actor is not a builtin")
-        {
-            Type::Account => AccountActor::invoke_method(self, self.msg.method, params),
-            Type::Cron => CronActor::invoke_method(self, self.msg.method, params),
-            Type::Init => InitActor::invoke_method(self, self.msg.method, params),
-            Type::Market => MarketActor::invoke_method(self, self.msg.method, params),
-            Type::Miner => MinerActor::invoke_method(self, self.msg.method, params),
-            Type::Multisig => MultisigActor::invoke_method(self, self.msg.method, params),
-            Type::System => SystemActor::invoke_method(self, self.msg.method, params),
-            Type::Reward => RewardActor::invoke_method(self, self.msg.method, params),
-            Type::Power => PowerActor::invoke_method(self, self.msg.method, params),
-            Type::PaymentChannel => PaychActor::invoke_method(self, self.msg.method, params),
-            Type::VerifiedRegistry => VerifregActor::invoke_method(self, self.msg.method, params),
-            // Type::EVM => panic!("no EVM"),
-            Type::DataCap => DataCapActor::invoke_method(self, self.msg.method, params),
-        };
+        let mut res =
+            match self.v.type_for_code(&to_actor.code).expect("Target actor is not a builtin") {
+                Type::Account => AccountActor::invoke_method(self, self.msg.method, params),
+                Type::Cron => CronActor::invoke_method(self, self.msg.method, params),
+                Type::Init => InitActor::invoke_method(self, self.msg.method, params),
+                Type::Market => MarketActor::invoke_method(self, self.msg.method, params),
+                Type::Miner => MinerActor::invoke_method(self, self.msg.method, params),
+                Type::Multisig => MultisigActor::invoke_method(self, self.msg.method, params),
+                Type::System => SystemActor::invoke_method(self, self.msg.method, params),
+                Type::Reward => RewardActor::invoke_method(self, self.msg.method, params),
+                Type::Power => PowerActor::invoke_method(self, self.msg.method, params),
+                Type::PaymentChannel => PaychActor::invoke_method(self, self.msg.method, params),
+                Type::VerifiedRegistry => {
+                    VerifregActor::invoke_method(self, self.msg.method, params)
+                }
+                // Type::EVM => panic!("no EVM"),
+                Type::DataCap => DataCapActor::invoke_method(self, self.msg.method, params),
+            };
         if res.is_ok() && !self.caller_validated {
             res = Err(actor_error!(assertion_failed, "failed to validate caller"));
         }
+        self.charge_store_gas();
+        if self.out_of_gas() {
+            res = Err(out_of_gas());
+        }
         if res.is_err() {
             self.v.rollback(prior_root)
         };
 
-        res
+        Ok(res)
     }
 }
 
 impl<'invocation, 'bs> Runtime for InvocationCtx<'invocation, 'bs> {
-    type Blockstore = &'bs MemoryBlockstore;
+    type Blockstore = TrackingBlockstore<&'bs MemoryBlockstore>;
 
     fn create_actor(&mut self, code_id: Cid, actor_id: ActorID) -> Result<(), ActorError> {
-        match NON_SINGLETON_CODES.get(&code_id) {
-            Some(_) => (),
-            None => {
-                return Err(ActorError::unchecked(
-                    ExitCode::SYS_ASSERTION_FAILED,
-                    "create_actor called with singleton builtin actor code cid".to_string(),
-                ));
-            }
+        if self.read_only {
+            return Err(actor_error!(read_only; "cannot create actor in read-only mode"));
+        }
+        if !self.v.type_for_code(&code_id).map_or(false, is_non_singleton) {
+            return Err(ActorError::unchecked(
+                ExitCode::SYS_ASSERTION_FAILED,
+                "create_actor called with singleton builtin actor code cid".to_string(),
+            ));
         }
         let addr = Address::new_id(actor_id);
         if self.v.get_actor(addr).is_some() {
@@ -726,8 +1298,8 @@ impl<'invocation, 'bs> Runtime for InvocationCtx<'invocation, 'bs> {
         Ok(())
     }
 
-    fn store(&self) -> &&'bs MemoryBlockstore {
-        &self.v.store
+    fn store(&self) -> &TrackingBlockstore<&'bs MemoryBlockstore> {
+        &self.v.actor_store
     }
 
     fn network_version(&self) -> NetworkVersion {
@@ -787,8 +1359,9 @@ impl<'invocation, 'bs> Runtime for InvocationCtx<'invocation, 'bs> {
             ));
         }
         self.caller_validated = true;
-        let to_match = ACTOR_TYPES.get(&self.v.get_actor(self.msg.from).unwrap().code).unwrap();
-        if types.into_iter().any(|t| *t == *to_match) {
+        let to_match =
+            self.v.type_for_code(&self.v.get_actor(self.msg.from).unwrap().code).unwrap();
+        if types.into_iter().any(|t| *t == to_match) {
             return Ok(());
         }
         Err(ActorError::unchecked(
@@ -824,53 +1397,69 @@ impl<'invocation, 'bs> Runtime for InvocationCtx<'invocation, 'bs> {
         method: MethodNum,
         params: Option<IpldBlock>,
         value: TokenAmount,
-    ) -> Result<Option<IpldBlock>, ActorError> {
-        if !self.allow_side_effects {
-            return Err(ActorError::unchecked(
-                ExitCode::SYS_ASSERTION_FAILED,
-                "Calling send is not allowed during side-effect lock".to_string(),
-            ));
-        }
+    ) -> Result<Response, ActorError> {
+        self.send_generalized(to, method, params, value, None, SendFlags::empty())
+    }
 
-        let new_actor_msg = InternalMessage { from: self.to(), to: *to, value, method, params };
-        let mut new_ctx = InvocationCtx {
-            v: self.v,
-            top: self.top.clone(),
-            msg: new_actor_msg,
-            allow_side_effects: true,
-            caller_validated: false,
-            policy: self.policy,
-            subinvocations: RefCell::new(vec![]),
-        };
-        let res = new_ctx.invoke();
+    fn send_read_only(
+        &self,
+        to: &Address,
+        method: MethodNum,
+        params: Option<IpldBlock>,
+    ) -> Result<Response, ActorError> {
+        self.send_generalized(to, method, params, TokenAmount::zero(), None, SendFlags::READ_ONLY)
+    }
 
-        let invoc = new_ctx.gather_trace(res.clone());
-        RefMut::map(self.subinvocations.borrow_mut(), |subinvocs| {
-            subinvocs.push(invoc);
-            subinvocs
-        });
-        res
+    fn send_generalized(
+        &self,
+        to: &Address,
+        method: MethodNum,
+        params: Option<IpldBlock>,
+        value: TokenAmount,
+        gas_limit: Option<u64>,
+        flags: SendFlags,
+    ) -> Result<Response, ActorError> {
+        let ret = self.send_inner(to, method, params, value, gas_limit, flags)?;
+        // A callee which exhausted the gas limit of the send exits with SYS_OUT_OF_GAS,
+        // but the caller only aborts if it is out of gas too.
+        if ret.exit_code == ExitCode::SYS_OUT_OF_GAS && self.out_of_gas() {
+            return Err(out_of_gas());
+        }
+        Ok(ret)
     }
 
     fn get_randomness_from_tickets(
         &self,
-        _personalization: DomainSeparationTag,
-        _rand_epoch: ChainEpoch,
-        _entropy: &[u8],
+        personalization: DomainSeparationTag,
+        rand_epoch: ChainEpoch,
+        entropy: &[u8],
     ) -> Result<[u8; RANDOMNESS_LENGTH], ActorError> {
-        Ok(TEST_VM_RAND_ARRAY)
+        Ok(self.v.randomness.get_randomness(
+            RandomnessKind::Tickets,
+            personalization,
+            rand_epoch,
+            entropy,
+        ))
     }
 
     fn get_randomness_from_beacon(
         &self,
-        _personalization: DomainSeparationTag,
-        _rand_epoch: ChainEpoch,
-        _entropy: &[u8],
+        personalization: DomainSeparationTag,
+        rand_epoch: ChainEpoch,
+        entropy: &[u8],
     ) -> Result<[u8; RANDOMNESS_LENGTH], ActorError> {
-        Ok(TEST_VM_RAND_ARRAY)
+        Ok(self.v.randomness.get_randomness(
+            RandomnessKind::Beacon,
+            personalization,
+            rand_epoch,
+            entropy,
+        ))
     }
 
     fn create<T: Serialize>(&mut self, obj: &T) -> Result<(), ActorError> {
+        if self.read_only {
+            return Err(actor_error!(read_only; "cannot create state in read-only mode"));
+        }
         let maybe_act = self.v.get_actor(self.to());
         match maybe_act {
             None => Err(ActorError::unchecked(
@@ -884,7 +1473,7 @@ impl<'invocation, 'bs> Runtime for InvocationCtx<'invocation, 'bs> {
                         "failed to construct state: already initialized".to_string(),
                     ))
                 } else {
-                    act.head = self.v.store.put_cbor(obj, Code::Blake2b256).unwrap();
+                    act.head = self.v.actor_store.put_cbor(obj, Code::Blake2b256).unwrap();
                     self.v.set_actor(self.to(), act);
                     Ok(())
                 }
@@ -893,7 +1482,8 @@ impl<'invocation, 'bs> Runtime for InvocationCtx<'invocation, 'bs> {
     }
 
     fn state<T: DeserializeOwned>(&self) -> Result<T, ActorError> {
-        Ok(self.v.get_state::<T>(self.to()).unwrap())
+        let act = self.v.get_actor(self.to()).unwrap();
+        Ok(self.v.actor_store.get_cbor::<T>(&act.head).unwrap().unwrap())
     }
 
     fn transaction<S, RT, F>(&mut self, f: F) -> Result<RT, ActorError>
@@ -901,13 +1491,16 @@ impl<'invocation, 'bs> Runtime for InvocationCtx<'invocation, 'bs> {
         S: Serialize + DeserializeOwned,
         F: FnOnce(&mut S, &mut Self) -> Result<RT, ActorError>,
     {
+        if self.read_only {
+            return Err(actor_error!(read_only; "cannot mutate state in read-only mode"));
+        }
         let mut st = self.state::<S>().unwrap();
         self.allow_side_effects = false;
         let result = f(&mut st, self);
         self.allow_side_effects = true;
         let ret = result?;
         let mut act = self.v.get_actor(self.to()).unwrap();
-        act.head = self.v.store.put_cbor(&st, Code::Blake2b256).unwrap();
+        act.head = self.v.actor_store.put_cbor(&st, Code::Blake2b256).unwrap();
         self.v.set_actor(self.to(), act);
         Ok(ret)
     }
@@ -922,43 +1515,68 @@ impl<'invocation, 'bs> Runtime for InvocationCtx<'invocation, 'bs> {
     }
 
     fn delete_actor(&mut self, _beneficiary: &Address) -> Result<(), ActorError> {
+        if self.read_only {
+            return Err(actor_error!(read_only; "cannot delete actor in read-only mode"));
+        }
         panic!("TODO implement me")
     }
 
     fn resolve_builtin_actor_type(&self, code_id: &Cid) -> Option<Type> {
-        ACTOR_TYPES.get(code_id).cloned()
+        self.v.type_for_code(code_id)
     }
 
     fn get_code_cid_for_type(&self, typ: Type) -> Cid {
-        ACTOR_CODES.get(&typ).cloned().unwrap()
+        self.v.code_for_type(typ)
     }
 
     fn total_fil_circ_supply(&self) -> TokenAmount {
         self.top.circ_supply.clone()
     }
 
-    fn charge_gas(&mut self, _name: &'static str, _compute: i64) {}
+    fn charge_gas(&mut self, _name: &'static str, compute: i64) {
+        self.charge_gas_with(|_| compute);
+    }
 
     fn base_fee(&self) -> TokenAmount {
-        TokenAmount::zero()
+        self.v.base_fee()
+    }
+
+    fn emit_event(&self, event: &ActorEvent) -> Result<(), ActorError> {
+        if self.read_only {
+            return Err(actor_error!(read_only; "cannot emit events in read-only mode"));
+        }
+        self.events.borrow_mut().push(event.clone());
+        Ok(())
+    }
+
+    fn read_only(&self) -> bool {
+        self.read_only
     }
 }
 
 impl Primitives for VM<'_> {
-    // A "valid" signature has its bytes equal to the plaintext.
+    // In mock mode, a "valid" signature has its bytes equal to the plaintext.
     // Anything else is considered invalid.
     fn verify_signature(
         &self,
         signature: &Signature,
-        _signer: &Address,
+        signer: &Address,
         plaintext: &[u8],
     ) -> Result<(), anyhow::Error> {
-        if signature.bytes != plaintext {
-            return Err(anyhow::format_err!(
-                "invalid signature (mock sig validation expects siggy bytes to be equal to plaintext)"
-            ));
+        match self.signature_mode {
+            SignatureMode::Mock => {
+                if signature.bytes != plaintext {
+                    return Err(anyhow::format_err!(
+                        "invalid signature (mock sig validation expects siggy bytes to be equal to plaintext)"
+                    ));
+                }
+                Ok(())
+            }
+            SignatureMode::Real => {
+                let signer = self.resolve_key_address(signer)?;
+                signatures::verify_signature(signature, &signer, plaintext)
+            }
         }
-        Ok(())
     }
 
     fn hash_blake2b(&self, data: &[u8]) -> [u8; 32] {
@@ -988,10 +1606,12 @@ impl Primitives for InvocationCtx<'_, '_> {
         signer: &Address,
         plaintext: &[u8],
     ) -> Result<(), anyhow::Error> {
+        self.charge_gas_with(|p| p.on_verify_signature(signature.sig_type));
         self.v.verify_signature(signature, signer, plaintext)
     }
 
     fn hash_blake2b(&self, data: &[u8]) -> [u8; 32] {
+        self.charge_gas_with(|p| p.hashing_base);
         self.v.hash_blake2b(data)
     }
 
@@ -1000,47 +1620,48 @@ impl Primitives for InvocationCtx<'_, '_> {
         proof_type: RegisteredSealProof,
         pieces: &[PieceInfo],
     ) -> Result<Cid, anyhow::Error> {
+        self.charge_gas_with(|p| p.compute_unsealed_sector_cid_base);
         self.v.compute_unsealed_sector_cid(proof_type, pieces)
     }
 }
 
 impl Verifier for InvocationCtx<'_, '_> {
-    fn verify_seal(&self, _vi: &SealVerifyInfo) -> Result<(), anyhow::Error> {
-        Ok(())
+    fn verify_seal(&self, vi: &SealVerifyInfo) -> Result<(), anyhow::Error> {
+        self.charge_gas_with(|p| p.verify_seal_base);
+        self.v.proof_faults.borrow().verify_seal(vi)
     }
 
     fn verify_post(&self, verify_info: &WindowPoStVerifyInfo) -> Result<(), anyhow::Error> {
-        for proof in &verify_info.proofs {
-            if proof.proof_bytes.eq(&TEST_VM_INVALID_POST.as_bytes().to_vec()) {
-                return Err(anyhow!("invalid proof"));
-            }
-        }
-
-        Ok(())
+        self.charge_gas_with(|p| p.on_verify_post(verify_info.challenged_sectors.len()));
+        self.v.proof_faults.borrow().verify_post(verify_info)
     }
 
     fn verify_consensus_fault(
         &self,
-        _h1: &[u8],
-        _h2: &[u8],
+        h1: &[u8],
+        h2: &[u8],
         _extra: &[u8],
     ) -> Result<Option<ConsensusFault>, anyhow::Error> {
-        Ok(None)
+        self.charge_gas_with(|p| p.verify_consensus_fault);
+        self.v.proof_faults.borrow().verify_consensus_fault(h1, h2)
     }
 
     fn batch_verify_seals(&self, batch: &[SealVerifyInfo]) -> anyhow::Result<Vec<bool>> {
-        Ok(vec![true; batch.len()]) // everyone wins
+        self.charge_gas_with(|p| p.verify_seal_base * batch.len() as i64);
+        Ok(self.v.proof_faults.borrow().batch_verify_seals(batch))
     }
 
     fn verify_aggregate_seals(
         &self,
-        _aggregate: &AggregateSealVerifyProofAndInfos,
+        aggregate: &AggregateSealVerifyProofAndInfos,
     ) -> Result<(), anyhow::Error> {
-        Ok(())
+        self.charge_gas_with(|p| p.verify_aggregate_seal_per_proof * aggregate.infos.len() as i64);
+        self.v.proof_faults.borrow().verify_aggregate_seals(aggregate)
     }
 
-    fn verify_replica_update(&self, _replica: &ReplicaUpdateInfo) -> Result<(), anyhow::Error> {
-        Ok(())
+    fn verify_replica_update(&self, replica: &ReplicaUpdateInfo) -> Result<(), anyhow::Error> {
+        self.charge_gas_with(|p| p.verify_replica_update);
+        self.v.proof_faults.borrow().verify_replica_update(replica)
     }
 }
 
@@ -1055,6 +1676,58 @@ pub struct MessageResult {
     pub code: ExitCode,
     pub message: String,
     pub ret: Option<IpldBlock>,
+    /// The error the message aborted with, including its context chain.
+    pub error: Option<ActorError>,
+    /// Blocks read and written by actors while executing the message.
+    pub store_stats: BlockstoreStats,
+    /// Gas used by the message, which is zero if the VM has no gas model.
+    pub gas_used: i64,
+}
+
+/// Gas limit of implicit messages, which is effectively unlimited.
+pub const IMPLICIT_MESSAGE_GAS_LIMIT: u64 = i64::MAX as u64;
+
+/// The result of a message applied as part of a tipset.
+pub struct TipsetReceipt {
+    pub result: MessageResult,
+    /// The invocation trace of the message, including the calls it made.
+    pub trace: InvocationTrace,
+}
+
+/// A message to be applied as part of a tipset.
+#[derive(Clone, Debug)]
+pub struct TipsetMessage {
+    pub from: Address,
+    pub to: Address,
+    pub value: TokenAmount,
+    pub method: MethodNum,
+    pub params: Option<IpldBlock>,
+    /// Gas limit of the message, or the gas model's default limit if none.
+    pub gas_limit: Option<u64>,
+}
+
+impl TipsetMessage {
+    pub fn new<S: ser::Serialize>(
+        from: Address,
+        to: Address,
+        value: TokenAmount,
+        method: MethodNum,
+        params: Option<S>,
+    ) -> Self {
+        Self { from, to, value, method, params: serialize_params(params), gas_limit: None }
+    }
+
+    /// Sets the gas limit of the message, overriding the gas model's default limit.
+    pub fn with_gas_limit(self, gas_limit: u64) -> Self {
+        Self { gas_limit: Some(gas_limit), ..self }
+    }
+}
+
+/// A miner that wins blocks in each tipset, and is awarded the block reward for them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlockWinner {
+    pub miner: Address,
+    pub win_count: i64,
 }
 
 #[derive(Serialize_tuple, Deserialize_tuple, Clone, PartialEq, Eq, Debug)]
@@ -1075,6 +1748,9 @@ pub struct InvocationTrace {
     pub code: ExitCode,
     pub ret: Option<IpldBlock>,
     pub subinvocations: Vec<InvocationTrace>,
+    pub events: Vec<ActorEvent>,
+    /// Blocks read and written by actors during this invocation, including its subinvocations.
+    pub store_stats: BlockstoreStats,
 }
 
 pub struct ExpectInvocation {
@@ -1088,6 +1764,8 @@ pub struct ExpectInvocation {
     pub params: Option<Option<IpldBlock>>,
     pub ret: Option<Option<IpldBlock>>,
     pub subinvocs: Option<Vec<ExpectInvocation>>,
+    pub events: Option<Vec<ActorEvent>>,
+    pub gas_limit: Option<Option<u64>>,
 }
 
 impl ExpectInvocation {
@@ -1130,6 +1808,20 @@ impl ExpectInvocation {
                 id, r, invoc.ret
             );
         }
+        if let Some(g) = self.gas_limit {
+            assert_eq!(
+                g, invoc.msg.gas_limit,
+                "{} unexpected gas limit: expected: {:?}, was: {:?}",
+                id, g, invoc.msg.gas_limit
+            );
+        }
+        if let Some(events) = &self.events {
+            assert_eq!(
+                events, &invoc.events,
+                "{} unexpected events: expected: {:?}, was: {:?}",
+                id, events, invoc.events
+            );
+        }
         if let Some(expect_subinvocs) = &self.subinvocs {
             let subinvocs = &invoc.subinvocations;
 
@@ -1191,6 +1883,8 @@ impl Default for ExpectInvocation {
             params: None,
             ret: None,
             subinvocs: None,
+            events: None,
+            gas_limit: None,
         }
     }
 }
@@ -1218,6 +1912,22 @@ impl From<fvm_ipld_hamt::Error> for TestVMError {
     }
 }
 
+/// Panics if the policy's parameters are inconsistent, since actors may misbehave under it.
+fn validated(policy: Policy) -> Policy {
+    if let Err(violations) = policy.validate() {
+        panic!("{}", PolicyError::Invalid(violations));
+    }
+    policy
+}
+
+fn out_of_gas() -> ActorError {
+    ActorError::unchecked(ExitCode::SYS_OUT_OF_GAS, "not enough gas".to_string())
+}
+
+fn serialize_params<S: ser::Serialize>(params: Option<S>) -> Option<IpldBlock> {
+    params.map(|p| IpldBlock::serialize_cbor(&p).unwrap().unwrap())
+}
+
 pub fn vm_err(msg: &str) -> TestVMError {
     TestVMError { msg: msg.to_string() }
 }
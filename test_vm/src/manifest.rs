@@ -0,0 +1,57 @@
+use anyhow::anyhow;
+use bimap::BiBTreeMap;
+use cid::Cid;
+use fil_actors_runtime::runtime::builtins::Type;
+use fil_actors_runtime::test_utils::{make_identity_cid, ACTOR_TYPES};
+use fil_builtin_actors_state::check::Tree;
+use fvm_ipld_blockstore::Blockstore;
+
+/// A mapping between actor code CIDs and the builtin actor types they implement.
+pub type Manifest = BiBTreeMap<Cid, Type>;
+
+lazy_static::lazy_static! {
+    /// The code CIDs used by the test VM unless others are registered for a network version.
+    pub static ref TEST_MANIFEST: Manifest =
+        ACTOR_TYPES.iter().map(|(code, typ)| (*code, *typ)).collect();
+}
+
+/// Returns a manifest of identity CIDs distinct from those of any other label, such as the
+/// name of an actors version.
+pub fn make_manifest(label: &str) -> Manifest {
+    Type::ALL
+        .iter()
+        .map(|typ| (make_identity_cid(format!("fil/{}/{}", label, typ.name()).as_bytes()), *typ))
+        .collect()
+}
+
+/// Whether actors of a type may be created after genesis.
+pub fn is_non_singleton(typ: Type) -> bool {
+    matches!(typ, Type::Account | Type::PaymentChannel | Type::Multisig | Type::Miner)
+}
+
+/// Replaces each actor's code in the tree with the code for the same type in a new manifest.
+pub fn migrate_actor_code<BS: Blockstore>(
+    tree: &mut Tree<BS>,
+    from: &Manifest,
+    to: &Manifest,
+) -> anyhow::Result<()> {
+    let mut updated = vec![];
+    tree.for_each(|addr, actor| {
+        let typ = from
+            .get_by_left(&actor.code)
+            .ok_or_else(|| anyhow!("actor {} has unknown code {}", addr, actor.code))?;
+        let code = to
+            .get_by_right(typ)
+            .ok_or_else(|| anyhow!("no code for {} actor {}", typ.name(), addr))?;
+        if *code != actor.code {
+            let mut actor = actor.clone();
+            actor.code = *code;
+            updated.push((*addr, actor));
+        }
+        Ok(())
+    })?;
+    for (addr, actor) in updated {
+        tree.map.set(addr.to_bytes().into(), actor)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use std::collections::BTreeMap;
+
+use fil_actors_runtime::runtime::DomainSeparationTag;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::randomness::RANDOMNESS_LENGTH;
+
+use crate::TEST_VM_RAND_ARRAY;
+
+/// The chain source that randomness is drawn from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RandomnessKind {
+    Tickets,
+    Beacon,
+}
+
+/// Provides the randomness returned to actors by the test VM.
+pub trait RandomnessSource {
+    fn get_randomness(
+        &self,
+        kind: RandomnessKind,
+        tag: DomainSeparationTag,
+        epoch: ChainEpoch,
+        entropy: &[u8],
+    ) -> [u8; RANDOMNESS_LENGTH];
+}
+
+/// Returns the same value for every draw.
+#[derive(Clone, Debug)]
+pub struct FixedRandomness(pub [u8; RANDOMNESS_LENGTH]);
+
+impl Default for FixedRandomness {
+    fn default() -> Self {
+        Self(TEST_VM_RAND_ARRAY)
+    }
+}
+
+impl RandomnessSource for FixedRandomness {
+    fn get_randomness(
+        &self,
+        _kind: RandomnessKind,
+        _tag: DomainSeparationTag,
+        _epoch: ChainEpoch,
+        _entropy: &[u8],
+    ) -> [u8; RANDOMNESS_LENGTH] {
+        self.0
+    }
+}
+
+/// Derives randomness by hashing a seed with the draw's source, domain tag, epoch and entropy,
+/// so that draws are deterministic but differ between epochs and domains.
+#[derive(Clone, Debug)]
+pub struct SeededRandomness {
+    pub seed: u64,
+}
+
+impl RandomnessSource for SeededRandomness {
+    fn get_randomness(
+        &self,
+        kind: RandomnessKind,
+        tag: DomainSeparationTag,
+        epoch: ChainEpoch,
+        entropy: &[u8],
+    ) -> [u8; RANDOMNESS_LENGTH] {
+        blake2b_simd::Params::new()
+            .hash_length(RANDOMNESS_LENGTH)
+            .to_state()
+            .update(&self.seed.to_be_bytes())
+            .update(&[kind as u8])
+            .update(&(tag as i64).to_be_bytes())
+            .update(&epoch.to_be_bytes())
+            .update(entropy)
+            .finalize()
+            .as_bytes()
+            .try_into()
+            .unwrap()
+    }
+}
+
+/// Returns values scripted for particular epochs, and draws from a fallback source otherwise.
+pub struct ScriptedRandomness {
+    script: BTreeMap<(RandomnessKind, ChainEpoch), [u8; RANDOMNESS_LENGTH]>,
+    fallback: Box<dyn RandomnessSource>,
+}
+
+impl ScriptedRandomness {
+    pub fn new(fallback: impl RandomnessSource + 'static) -> Self {
+        Self { script: BTreeMap::new(), fallback: Box::new(fallback) }
+    }
+
+    /// Scripts the value of every draw from `kind` at `epoch`.
+    pub fn with(
+        mut self,
+        kind: RandomnessKind,
+        epoch: ChainEpoch,
+        randomness: [u8; RANDOMNESS_LENGTH],
+    ) -> Self {
+        self.script.insert((kind, epoch), randomness);
+        self
+    }
+}
+
+impl RandomnessSource for ScriptedRandomness {
+    fn get_randomness(
+        &self,
+        kind: RandomnessKind,
+        tag: DomainSeparationTag,
+        epoch: ChainEpoch,
+        entropy: &[u8],
+    ) -> [u8; RANDOMNESS_LENGTH] {
+        match self.script.get(&(kind, epoch)) {
+            Some(randomness) => *randomness,
+            None => self.fallback.get_randomness(kind, tag, epoch, entropy),
+        }
+    }
+}
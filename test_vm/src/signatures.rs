@@ -0,0 +1,113 @@
+use anyhow::anyhow;
+use bls_signatures::Serialize as _;
+use fvm_shared::address::{Address, Protocol};
+use fvm_shared::crypto::signature::{Signature, SignatureType, SECP_SIG_LEN};
+use rand::{CryptoRng, RngCore};
+
+/// How the test VM verifies signatures.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SignatureMode {
+    /// A signature is valid if its bytes equal the plaintext.
+    #[default]
+    Mock,
+    /// Signatures are verified cryptographically against the signer's key address.
+    Real,
+}
+
+/// A locally-generated private key for signing test messages.
+pub enum TestKey {
+    Secp256k1(libsecp256k1::SecretKey),
+    Bls(bls_signatures::PrivateKey),
+}
+
+impl TestKey {
+    pub fn generate<R: RngCore + CryptoRng>(sig_type: SignatureType, rng: &mut R) -> Self {
+        match sig_type {
+            SignatureType::Secp256k1 => loop {
+                let mut bytes = [0u8; 32];
+                rng.fill_bytes(&mut bytes);
+                // Almost every 32 byte value is a valid key.
+                if let Ok(key) = libsecp256k1::SecretKey::parse(&bytes) {
+                    break TestKey::Secp256k1(key);
+                }
+            },
+            SignatureType::BLS => TestKey::Bls(bls_signatures::PrivateKey::generate(rng)),
+        }
+    }
+
+    /// The key's public key address.
+    pub fn address(&self) -> Address {
+        match self {
+            TestKey::Secp256k1(key) => {
+                let public = libsecp256k1::PublicKey::from_secret_key(key);
+                Address::new_secp256k1(&public.serialize()).unwrap()
+            }
+            TestKey::Bls(key) => Address::new_bls(&key.public_key().as_bytes()).unwrap(),
+        }
+    }
+
+    /// Signs a plaintext as the FVM expects: secp256k1 signatures are over the plaintext's
+    /// blake2b-256 digest, and BLS signatures over the plaintext itself.
+    pub fn sign(&self, plaintext: &[u8]) -> Signature {
+        match self {
+            TestKey::Secp256k1(key) => {
+                let message = libsecp256k1::Message::parse(&blake2b_256(plaintext));
+                let (sig, recovery_id) = libsecp256k1::sign(&message, key);
+                let mut bytes = sig.serialize().to_vec();
+                bytes.push(recovery_id.serialize());
+                Signature::new_secp256k1(bytes)
+            }
+            TestKey::Bls(key) => Signature::new_bls(key.sign(plaintext).as_bytes()),
+        }
+    }
+}
+
+/// Verifies a signature over a plaintext against the signer's public key address.
+pub fn verify_signature(
+    signature: &Signature,
+    signer: &Address,
+    plaintext: &[u8],
+) -> anyhow::Result<()> {
+    match (signature.sig_type, signer.protocol()) {
+        (SignatureType::Secp256k1, Protocol::Secp256k1) => {
+            let bytes: &[u8; SECP_SIG_LEN] = signature
+                .bytes
+                .as_slice()
+                .try_into()
+                .map_err(|_| anyhow!("invalid secp256k1 signature length"))?;
+            let message = libsecp256k1::Message::parse(&blake2b_256(plaintext));
+            let sig = libsecp256k1::Signature::parse_standard_slice(&bytes[..64])
+                .map_err(|e| anyhow!("invalid secp256k1 signature: {:?}", e))?;
+            let recovery_id = libsecp256k1::RecoveryId::parse(bytes[64])
+                .map_err(|e| anyhow!("invalid secp256k1 recovery id: {:?}", e))?;
+            let public = libsecp256k1::recover(&message, &sig, &recovery_id)
+                .map_err(|e| anyhow!("failed to recover secp256k1 key: {:?}", e))?;
+            if Address::new_secp256k1(&public.serialize())? != *signer {
+                return Err(anyhow!("secp256k1 signature is not from {}", signer));
+            }
+            Ok(())
+        }
+        (SignatureType::BLS, Protocol::BLS) => {
+            let public = bls_signatures::PublicKey::from_bytes(&signer.payload_bytes())?;
+            let sig = bls_signatures::Signature::from_bytes(&signature.bytes)?;
+            if !bls_signatures::verify_messages(&sig, &[plaintext], &[public]) {
+                return Err(anyhow!("bls signature is not from {}", signer));
+            }
+            Ok(())
+        }
+        (sig_type, protocol) => {
+            Err(anyhow!("cannot verify a {:?} signature against a {} address", sig_type, protocol))
+        }
+    }
+}
+
+fn blake2b_256(data: &[u8]) -> [u8; 32] {
+    blake2b_simd::Params::new()
+        .hash_length(32)
+        .to_state()
+        .update(data)
+        .finalize()
+        .as_bytes()
+        .try_into()
+        .unwrap()
+}
@@ -4,6 +4,7 @@ use frc46_token::receiver::{FRC46TokenReceived, FRC46_TOKEN_TYPE};
 use frc46_token::token::types::{BurnParams, TransferFromParams, TransferParams};
 use fvm_actor_utils::receiver::UniversalReceiverParams;
 use fvm_ipld_bitfield::BitField;
+use fvm_ipld_blockstore::MemoryBlockstore;
 use fvm_ipld_encoding::{BytesDe, RawBytes};
 use fvm_shared::address::{Address, BLS_PUB_LEN};
 use fvm_shared::crypto::signature::{Signature, SignatureType};
@@ -51,7 +52,9 @@ use fil_actors_runtime::cbor::deserialize;
 use fil_actors_runtime::runtime::policy_constants::{
     MARKET_DEFAULT_ALLOCATION_TERM_BUFFER, MAXIMUM_VERIFIED_ALLOCATION_EXPIRATION,
 };
+use fil_actors_runtime::EPOCHS_IN_DAY;
 
+use crate::signatures::TestKey;
 use crate::*;
 
 // Generate count addresses by seeding an rng
@@ -87,6 +90,26 @@ pub fn create_accounts_seeded(v: &VM, count: u64, balance: TokenAmount, seed: u6
     pk_addrs.iter().map(|&pk_addr| v.normalize_address(&pk_addr).unwrap()).collect()
 }
 
+/// Creates accounts for locally-generated keys, which can sign messages for verification
+/// with `SignatureMode::Real`.
+pub fn create_accounts_with_keys(
+    v: &VM,
+    count: u64,
+    balance: TokenAmount,
+    sig_type: SignatureType,
+    seed: u64,
+) -> Vec<(Address, TestKey)> {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    (0..count)
+        .map(|_| {
+            let key = TestKey::generate(sig_type, &mut rng);
+            let pk_addr = key.address();
+            apply_ok(v, TEST_FAUCET_ADDR, pk_addr, balance.clone(), METHOD_SEND, None::<RawBytes>);
+            (v.normalize_address(&pk_addr).unwrap(), key)
+        })
+        .collect()
+}
+
 pub fn apply_ok<S: Serialize>(
     v: &VM,
     from: Address,
@@ -156,6 +179,50 @@ pub fn create_miner(
     (res.id_address, res.robust_address)
 }
 
+/// Creates a VM with three funded accounts and a 32GiB miner owned and worked by the first,
+/// advanced to epoch 200. Returns the VM, the accounts and the miner's ID address.
+pub fn create_vm_with_miner(store: &MemoryBlockstore) -> (VM<'_>, Vec<Address>, Address) {
+    let mut v = VM::new_with_singletons(store);
+    let addrs = create_accounts(&v, 3, TokenAmount::from_whole(10_000));
+    let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
+    let (miner, _) = create_miner(
+        &mut v,
+        addrs[0],
+        addrs[0],
+        seal_proof.registered_window_post_proof().unwrap(),
+        TokenAmount::from_whole(1_000),
+    );
+    (v.with_epoch(200), addrs, miner)
+}
+
+pub fn create_msig(v: &VM, signers: Vec<Address>, threshold: u64) -> Address {
+    assert!(!signers.is_empty());
+    let msig_ctor_params = serialize(
+        &fil_actor_multisig::ConstructorParams {
+            signers: signers.clone(),
+            num_approvals_threshold: threshold,
+            unlock_duration: 0,
+            start_epoch: 0,
+        },
+        "multisig ctor params",
+    )
+    .unwrap();
+    let msig_ctor_ret: ExecReturn = apply_ok(
+        v,
+        signers[0],
+        INIT_ACTOR_ADDR,
+        TokenAmount::zero(),
+        fil_actor_init::Method::Exec as u64,
+        Some(fil_actor_init::ExecParams {
+            code_cid: *MULTISIG_ACTOR_CODE_ID,
+            constructor_params: msig_ctor_params,
+        }),
+    )
+    .deserialize()
+    .unwrap();
+    msig_ctor_ret.id_address
+}
+
 pub fn miner_precommit_sector(
     v: &VM,
     worker: Address,
@@ -1222,6 +1289,66 @@ pub fn market_publish_deal(
     ret
 }
 
+/// Registers a verifier and a verified client with datacap for one 32GiB sector, funds the client
+/// and the miner in the market, and publishes a verified deal for the whole sector.
+/// Returns the deal's ID and start epoch.
+pub fn publish_verified_deal(
+    v: &VM,
+    verifier: Address,
+    client: Address,
+    worker: Address,
+    miner_id: Address,
+) -> (DealID, ChainEpoch) {
+    let deal_size = 32u64 << 30;
+    verifreg_add_verifier(v, verifier, StoragePower::from(deal_size));
+    verifreg_add_client(v, verifier, client, StoragePower::from(deal_size));
+    market_add_balance(v, client, client, TokenAmount::from_whole(3));
+    market_add_balance(v, worker, miner_id, TokenAmount::from_whole(64));
+
+    let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
+    let deal_start =
+        v.get_epoch() + max_prove_commit_duration(&Policy::default(), seal_proof).unwrap();
+    let deal_ids = market_publish_deal(
+        v,
+        worker,
+        client,
+        miner_id,
+        "verified deal".to_string(),
+        PaddedPieceSize(deal_size),
+        true,
+        deal_start,
+        180 * EPOCHS_IN_DAY,
+    )
+    .ids;
+    (deal_ids[0], deal_start)
+}
+
+/// Pre-commits and proves a sector with a deal from `publish_verified_deal`, activating the deal
+/// and claiming its allocation.
+pub fn prove_verified_deal(
+    v: VM,
+    worker: Address,
+    miner_id: Address,
+    sector_number: SectorNumber,
+    deal_id: DealID,
+    deal_start: ChainEpoch,
+) -> VM {
+    let expiration = deal_start + 180 * EPOCHS_IN_DAY + MARKET_DEFAULT_ALLOCATION_TERM_BUFFER;
+    miner_precommit_sector(
+        &v,
+        worker,
+        miner_id,
+        RegisteredSealProof::StackedDRG32GiBV1P1,
+        sector_number,
+        vec![deal_id],
+        expiration,
+    );
+    let v = advance_by_deadline_to_epoch(v, miner_id, deal_start).0;
+    miner_prove_sector(&v, worker, miner_id, sector_number);
+    cron_tick(&v);
+    v
+}
+
 pub fn make_bitfield(bits: &[u64]) -> BitField {
     BitField::try_from_bits(bits.iter().copied()).unwrap()
 }
@@ -1230,16 +1357,6 @@ pub fn bf_all(bf: BitField) -> Vec<u64> {
     bf.bounded_iter(Policy::default().addressed_sectors_max).unwrap().collect()
 }
 
-pub mod invariant_failure_patterns {
-    use lazy_static::lazy_static;
-    use regex::Regex;
-
-    lazy_static! {
-        pub static ref REWARD_STATE_EPOCH_MISMATCH: Regex =
-            Regex::new("^reward state epoch \\d+ does not match prior_epoch\\+1 \\d+$").unwrap();
-    }
-}
-
 pub fn generate_deal_proposal(
     client: Address,
     provider: Address,
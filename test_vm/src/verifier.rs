@@ -0,0 +1,132 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::anyhow;
+use fvm_shared::consensus::ConsensusFault;
+use fvm_shared::sector::{
+    AggregateSealVerifyProofAndInfos, ReplicaUpdateInfo, SealVerifyInfo, SectorNumber,
+    WindowPoStVerifyInfo,
+};
+use fvm_shared::ActorID;
+
+use crate::TEST_VM_INVALID_POST;
+
+/// Programmable outcomes for the proofs verified by the test VM.
+///
+/// Every proof is valid unless a test marks it as failing, by the miner that submits it,
+/// the sector it proves, or its proof bytes. Replica update proofs identify neither miner
+/// nor sector, so only their proof bytes can fail. A PoSt with the bytes of
+/// `TEST_VM_INVALID_POST` always fails.
+#[derive(Clone, Debug, Default)]
+pub struct ProofFaults {
+    failing_miners: BTreeSet<ActorID>,
+    failing_sectors: BTreeSet<(ActorID, SectorNumber)>,
+    failing_proofs: BTreeSet<Vec<u8>>,
+    consensus_faults: BTreeMap<(Vec<u8>, Vec<u8>), ConsensusFault>,
+}
+
+impl ProofFaults {
+    /// Fails every proof submitted by a miner.
+    pub fn fail_miner(&mut self, miner: ActorID) -> &mut Self {
+        self.failing_miners.insert(miner);
+        self
+    }
+
+    /// Fails proofs of a miner's sector, including aggregates and PoSts covering it.
+    pub fn fail_sector(&mut self, miner: ActorID, sector: SectorNumber) -> &mut Self {
+        self.failing_sectors.insert((miner, sector));
+        self
+    }
+
+    /// Fails any proof with these bytes. For consensus faults, fails verification of any
+    /// block header with these bytes.
+    pub fn fail_proof(&mut self, proof: Vec<u8>) -> &mut Self {
+        self.failing_proofs.insert(proof);
+        self
+    }
+
+    /// Reports a consensus fault for a pair of block headers, in either order.
+    pub fn add_consensus_fault(
+        &mut self,
+        h1: Vec<u8>,
+        h2: Vec<u8>,
+        fault: ConsensusFault,
+    ) -> &mut Self {
+        self.consensus_faults.insert((h1, h2), fault);
+        self
+    }
+
+    /// Clears all faults, so that every proof is valid again.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn verify_seal(&self, info: &SealVerifyInfo) -> anyhow::Result<()> {
+        if self.sector_fails(info.sector_id.miner, info.sector_id.number)
+            || self.failing_proofs.contains(&info.proof)
+        {
+            return Err(anyhow!(
+                "invalid seal proof for sector {} of miner {}",
+                info.sector_id.number,
+                info.sector_id.miner
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn batch_verify_seals(&self, batch: &[SealVerifyInfo]) -> Vec<bool> {
+        batch.iter().map(|info| self.verify_seal(info).is_ok()).collect()
+    }
+
+    pub fn verify_aggregate_seals(
+        &self,
+        aggregate: &AggregateSealVerifyProofAndInfos,
+    ) -> anyhow::Result<()> {
+        let failing_sector = aggregate
+            .infos
+            .iter()
+            .any(|info| self.sector_fails(aggregate.miner, info.sector_number));
+        if failing_sector || self.failing_proofs.contains(&aggregate.proof) {
+            return Err(anyhow!("invalid aggregate seal proof from miner {}", aggregate.miner));
+        }
+        Ok(())
+    }
+
+    pub fn verify_post(&self, info: &WindowPoStVerifyInfo) -> anyhow::Result<()> {
+        let failing_sector =
+            info.challenged_sectors.iter().any(|s| self.sector_fails(info.prover, s.sector_number));
+        let failing_proof = info.proofs.iter().any(|p| {
+            p.proof_bytes == TEST_VM_INVALID_POST.as_bytes()
+                || self.failing_proofs.contains(&p.proof_bytes)
+        });
+        if failing_sector || failing_proof {
+            return Err(anyhow!("invalid proof"));
+        }
+        Ok(())
+    }
+
+    pub fn verify_replica_update(&self, replica: &ReplicaUpdateInfo) -> anyhow::Result<()> {
+        if self.failing_proofs.contains(&replica.proof) {
+            return Err(anyhow!("invalid replica update proof for {}", replica.new_sealed_cid));
+        }
+        Ok(())
+    }
+
+    pub fn verify_consensus_fault(
+        &self,
+        h1: &[u8],
+        h2: &[u8],
+    ) -> anyhow::Result<Option<ConsensusFault>> {
+        if self.failing_proofs.contains(h1) || self.failing_proofs.contains(h2) {
+            return Err(anyhow!("failed to verify consensus fault"));
+        }
+        let fault = self
+            .consensus_faults
+            .get(&(h1.to_vec(), h2.to_vec()))
+            .or_else(|| self.consensus_faults.get(&(h2.to_vec(), h1.to_vec())));
+        Ok(fault.cloned())
+    }
+
+    fn sector_fails(&self, miner: ActorID, sector: SectorNumber) -> bool {
+        self.failing_miners.contains(&miner) || self.failing_sectors.contains(&(miner, sector))
+    }
+}
@@ -14,8 +14,8 @@ use fvm_shared::econ::TokenAmount;
 use fvm_shared::sector::{RegisteredSealProof, SectorNumber};
 use test_case::test_case;
 use test_vm::util::{
-    advance_to_proving_deadline, apply_ok, create_accounts, create_miner,
-    invariant_failure_patterns, precommit_sectors_v2, prove_commit_sectors, submit_windowed_post,
+    advance_to_proving_deadline, apply_ok, create_accounts, create_miner, precommit_sectors_v2,
+    prove_commit_sectors, submit_windowed_post,
 };
 use test_vm::VM;
 
@@ -153,7 +153,5 @@ fn batch_onboarding(v2: bool) {
         None::<RawBytes>,
     );
 
-    v.expect_state_invariants(
-        &[invariant_failure_patterns::REWARD_STATE_EPOCH_MISMATCH.to_owned()],
-    );
+    v.expect_state_invariant_ids(&["reward.state_epoch"]);
 }
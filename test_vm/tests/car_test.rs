@@ -0,0 +1,87 @@
+use cid::multihash::Code;
+use fil_actors_runtime::test_utils::make_identity_cid;
+use fvm_ipld_blockstore::{Blockstore, MemoryBlockstore};
+use fvm_ipld_encoding::{CborStore, RawBytes};
+use fvm_shared::address::Address;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::sector::RegisteredSealProof;
+use fvm_shared::METHOD_SEND;
+use test_vm::car::{write_car, StateRoot};
+use test_vm::util::{apply_ok, create_accounts, create_miner};
+use test_vm::VM;
+
+fn setup(store: &MemoryBlockstore) -> (VM<'_>, Vec<Address>) {
+    let mut v = VM::new_with_singletons(store);
+    let addrs = create_accounts(&v, 2, TokenAmount::from_whole(10_000));
+    let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
+    create_miner(
+        &mut v,
+        addrs[0],
+        addrs[0],
+        seal_proof.registered_window_post_proof().unwrap(),
+        TokenAmount::from_whole(1_000),
+    );
+    (v, addrs)
+}
+
+#[test]
+fn export_and_import_round_trip() {
+    let store = MemoryBlockstore::new();
+    let (v, addrs) = setup(&store);
+    let mut car = vec![];
+    v.export_car(&mut car).unwrap();
+
+    let imported_store = MemoryBlockstore::new();
+    let imported = VM::import_car(&imported_store, car.as_slice()).unwrap();
+    assert_eq!(v.checkpoint(), imported.checkpoint());
+    imported.assert_state_invariants();
+
+    // Exporting the imported state writes the same blocks.
+    let mut reexported = vec![];
+    imported.export_car(&mut reexported).unwrap();
+    assert_eq!(car, reexported);
+
+    // The imported state is complete enough to execute messages against.
+    apply_ok(
+        &imported,
+        addrs[0],
+        addrs[1],
+        TokenAmount::from_whole(1),
+        METHOD_SEND,
+        None::<RawBytes>,
+    );
+    assert_eq!(TokenAmount::from_whole(10_001), imported.get_actor(addrs[1]).unwrap().balance);
+    imported.assert_state_invariants();
+}
+
+#[test]
+fn import_chain_state_root() {
+    let store = MemoryBlockstore::new();
+    let (v, _) = setup(&store);
+    let actors = v.checkpoint();
+    let state_root = StateRoot {
+        version: 4,
+        actors,
+        info: store.put_cbor(&make_identity_cid(b"info"), Code::Blake2b256).unwrap(),
+    };
+    let root = store.put_cbor(&state_root, Code::Blake2b256).unwrap();
+    let mut car = vec![];
+    write_car(&store, root, &mut car).unwrap();
+
+    let imported_store = MemoryBlockstore::new();
+    let imported = VM::import_car(&imported_store, car.as_slice()).unwrap();
+    assert_eq!(actors, imported.checkpoint());
+    imported.assert_state_invariants();
+}
+
+#[test]
+fn export_fails_on_missing_blocks() {
+    let store = MemoryBlockstore::new();
+    let (v, _) = setup(&store);
+    let root = v.checkpoint();
+
+    // A store holding only the root block can't export the state.
+    let partial = MemoryBlockstore::new();
+    partial.put_keyed(&root, &store.get(&root).unwrap().unwrap()).unwrap();
+    assert!(write_car(&partial, root, Vec::new()).is_err());
+}
@@ -7,7 +7,7 @@ use fil_actor_miner::{
 };
 use fil_actor_power::{Method as PowerMethod, State as PowerState};
 use fil_actor_reward::Method as RewardMethod;
-use fil_actors_runtime::runtime::Policy;
+use fil_actors_runtime::runtime::{DomainSeparationTag, Policy};
 use fil_actors_runtime::{
     BURNT_FUNDS_ACTOR_ADDR, CRON_ACTOR_ADDR, REWARD_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR,
     STORAGE_POWER_ACTOR_ADDR, SYSTEM_ACTOR_ADDR,
@@ -23,10 +23,11 @@ use fvm_shared::error::ExitCode;
 use fvm_shared::randomness::Randomness;
 use fvm_shared::sector::{PoStProof, RegisteredSealProof, SectorNumber, MAX_SECTOR_NUMBER};
 use fvm_shared::METHOD_SEND;
+use test_vm::randomness::{RandomnessKind, RandomnessSource, SeededRandomness};
 use test_vm::util::{
     advance_by_deadline_to_epoch, advance_to_proving_deadline, apply_code, apply_ok,
-    create_accounts, create_miner, invariant_failure_patterns, precommit_sectors,
-    submit_windowed_post,
+    check_sector_faulty, create_accounts, create_miner, cron_tick, deadline_state,
+    precommit_sectors, submit_windowed_post,
 };
 use test_vm::{ExpectInvocation, TEST_VM_RAND_ARRAY, VM};
 
@@ -191,6 +192,94 @@ fn submit_post_succeeds() {
     v.assert_state_invariants();
 }
 
+#[test]
+fn proven_and_missed_post_fork_from_shared_setup() {
+    let store = MemoryBlockstore::new();
+    let (proven, miner_info, sector_info) = setup(&store);
+    let missed = proven.fork();
+
+    // One future proves the sector.
+    let st = proven.get_state::<MinerState>(miner_info.miner_id).unwrap();
+    let sector = st.get_sector(proven.store, sector_info.number).unwrap().unwrap();
+    let sector_power = power_for_sector(miner_info.seal_proof.sector_size().unwrap(), &sector);
+    submit_windowed_post(
+        &proven,
+        miner_info.worker,
+        miner_info.miner_id,
+        sector_info.deadline_info,
+        sector_info.partition_index,
+        Some(sector_power.clone()),
+    );
+
+    // The other misses the deadline.
+    let missed = missed.with_epoch(sector_info.deadline_info.last());
+    cron_tick(&missed);
+    assert_eq!(1, missed.take_invocations().len());
+
+    assert_eq!(sector_power.raw, proven.get_network_stats().total_bytes_committed);
+    assert!(missed.get_network_stats().total_bytes_committed.is_zero());
+    assert!(proven.get_epoch() < missed.get_epoch());
+
+    // Only the missed PoSt marks the sector faulty, with all of its power.
+    let (dl_idx, p_idx) = (sector_info.deadline_info.index, sector_info.partition_index);
+    assert!(!check_sector_faulty(&proven, miner_info.miner_id, dl_idx, p_idx, sector_info.number));
+    assert!(check_sector_faulty(&missed, miner_info.miner_id, dl_idx, p_idx, sector_info.number));
+    assert!(deadline_state(&proven, miner_info.miner_id, dl_idx).faulty_power.is_zero());
+    assert_eq!(sector_power, deadline_state(&missed, miner_info.miner_id, dl_idx).faulty_power);
+
+    proven.assert_state_invariants();
+    missed.expect_state_invariant_ids(&["reward.state_epoch"]);
+}
+
+#[test]
+fn post_chain_commit_randomness_drawn_from_vm_source() {
+    let store = MemoryBlockstore::new();
+    let (v, miner_info, sector_info) = setup(&store);
+    let randomness = SeededRandomness { seed: 7 };
+    let chain_commit_rand = randomness.get_randomness(
+        RandomnessKind::Tickets,
+        DomainSeparationTag::PoStChainCommit,
+        sector_info.deadline_info.challenge,
+        &[],
+    );
+    assert_ne!(TEST_VM_RAND_ARRAY, chain_commit_rand);
+    let v = v.with_randomness(randomness);
+
+    let params = |rand: [u8; 32]| SubmitWindowedPoStParams {
+        deadline: sector_info.deadline_info.index,
+        partitions: vec![PoStPartition {
+            index: sector_info.partition_index,
+            skipped: BitField::new(),
+        }],
+        proofs: vec![PoStProof {
+            post_proof: miner_info.seal_proof.registered_window_post_proof().unwrap(),
+            proof_bytes: vec![],
+        }],
+        chain_commit_epoch: sector_info.deadline_info.challenge,
+        chain_commit_rand: Randomness(rand.into()),
+    };
+
+    // The fixed test randomness no longer matches the chain.
+    apply_code(
+        &v,
+        miner_info.worker,
+        miner_info.miner_id,
+        TokenAmount::zero(),
+        MinerMethod::SubmitWindowedPoSt as u64,
+        Some(params(TEST_VM_RAND_ARRAY)),
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+    );
+    apply_ok(
+        &v,
+        miner_info.worker,
+        miner_info.miner_id,
+        TokenAmount::zero(),
+        MinerMethod::SubmitWindowedPoSt as u64,
+        Some(params(chain_commit_rand)),
+    );
+    assert!(!v.get_network_stats().total_bytes_committed.is_zero());
+}
+
 #[test]
 fn skip_sector() {
     let store = MemoryBlockstore::new();
@@ -299,9 +388,7 @@ fn missed_first_post_deadline() {
     assert!(network_stats.total_bytes_committed.is_zero());
     assert!(network_stats.total_pledge_collateral.is_positive());
 
-    v.expect_state_invariants(
-        &[invariant_failure_patterns::REWARD_STATE_EPOCH_MISMATCH.to_owned()],
-    );
+    v.expect_state_invariant_ids(&["reward.state_epoch"]);
 }
 
 #[test]
@@ -414,9 +501,7 @@ fn overdue_precommit() {
     assert!(network_stats.total_raw_byte_power.is_zero());
     assert!(network_stats.total_quality_adj_power.is_zero());
 
-    v.expect_state_invariants(
-        &[invariant_failure_patterns::REWARD_STATE_EPOCH_MISMATCH.to_owned()],
-    );
+    v.expect_state_invariant_ids(&["reward.state_epoch"]);
 }
 
 #[test]
@@ -494,9 +579,7 @@ fn aggregate_bad_sector_number() {
         ..Default::default()
     }
     .matches(v.take_invocations().last().unwrap());
-    v.expect_state_invariants(
-        &[invariant_failure_patterns::REWARD_STATE_EPOCH_MISMATCH.to_owned()],
-    );
+    v.expect_state_invariant_ids(&["reward.state_epoch"]);
 }
 
 #[test]
@@ -628,9 +711,7 @@ fn aggregate_size_limits() {
         ..Default::default()
     }
     .matches(v.take_invocations().last().unwrap());
-    v.expect_state_invariants(
-        &[invariant_failure_patterns::REWARD_STATE_EPOCH_MISMATCH.to_owned()],
-    );
+    v.expect_state_invariant_ids(&["reward.state_epoch"]);
 }
 
 #[test]
@@ -704,9 +785,7 @@ fn aggregate_bad_sender() {
         ..Default::default()
     }
     .matches(v.take_invocations().last().unwrap());
-    v.expect_state_invariants(
-        &[invariant_failure_patterns::REWARD_STATE_EPOCH_MISMATCH.to_owned()],
-    );
+    v.expect_state_invariant_ids(&["reward.state_epoch"]);
 }
 
 #[test]
@@ -836,7 +915,5 @@ fn aggregate_one_precommit_expires() {
     assert!(balances.initial_pledge.is_positive());
     assert!(balances.pre_commit_deposit.is_positive());
 
-    v.expect_state_invariants(
-        &[invariant_failure_patterns::REWARD_STATE_EPOCH_MISMATCH.to_owned()],
-    );
+    v.expect_state_invariant_ids(&["reward.state_epoch"]);
 }
@@ -1,7 +1,7 @@
 use fil_actor_miner::{
     max_prove_commit_duration, ExpirationExtension, ExpirationExtension2,
     ExtendSectorExpiration2Params, ExtendSectorExpirationParams, Method as MinerMethod, PowerPair,
-    Sectors, State as MinerState,
+    SectorClaim, Sectors, State as MinerState,
 };
 use fil_actor_power::{Method as PowerMethod, UpdateClaimedPowerParams};
 use fil_actors_runtime::runtime::Policy;
@@ -13,14 +13,14 @@ use fvm_shared::address::Address;
 use fvm_shared::bigint::Zero;
 use fvm_shared::clock::ChainEpoch;
 use fvm_shared::econ::TokenAmount;
+use fvm_shared::error::ExitCode;
 use fvm_shared::piece::PaddedPieceSize;
 use fvm_shared::sector::{RegisteredSealProof, SectorNumber, StoragePower};
 use test_vm::util::{
     advance_by_deadline_to_epoch, advance_by_deadline_to_epoch_while_proving,
     advance_by_deadline_to_index, advance_to_proving_deadline, apply_ok, create_accounts,
-    create_miner, cron_tick, invariant_failure_patterns, market_add_balance, market_publish_deal,
-    miner_precommit_sector, miner_prove_sector, submit_windowed_post, verifreg_add_client,
-    verifreg_add_verifier,
+    create_miner, cron_tick, market_add_balance, market_publish_deal, miner_precommit_sector,
+    miner_prove_sector, submit_windowed_post, verifreg_add_client, verifreg_add_verifier,
 };
 use test_vm::{ExpectInvocation, VM};
 
@@ -298,7 +298,55 @@ fn extend_legacy_sector_with_deals_inner(do_extend2: bool) {
     assert_eq!(initial_verified_deal_weight / 4, sector_info.verified_deal_weight);
     // two halvings => 1/4 initial verified deal weight
 
-    v.expect_state_invariants(
-        &[invariant_failure_patterns::REWARD_STATE_EPOCH_MISMATCH.to_owned()],
-    );
+    v.expect_state_invariant_ids(&["reward.state_epoch"]);
+}
+
+#[test]
+fn extend2_with_missing_claim_reports_sector() {
+    let store = MemoryBlockstore::new();
+    let mut v = VM::new_with_singletons(&store);
+    let addrs = create_accounts(&v, 1, TokenAmount::from_whole(10_000));
+    let worker = addrs[0];
+    let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
+    let miner_id = create_miner(
+        &mut v,
+        worker,
+        worker,
+        seal_proof.registered_window_post_proof().unwrap(),
+        TokenAmount::from_whole(1_000),
+    )
+    .0;
+
+    let sector_number: SectorNumber = 100;
+    let params = ExtendSectorExpiration2Params {
+        extensions: vec![ExpirationExtension2 {
+            deadline: 0,
+            partition: 0,
+            sectors: BitField::new(),
+            new_expiration: 180 * EPOCHS_IN_DAY,
+            sectors_with_claims: vec![SectorClaim {
+                sector_number,
+                maintain_claims: vec![1],
+                drop_claims: vec![],
+            }],
+        }],
+    };
+    let res = v
+        .apply_message(
+            worker,
+            miner_id,
+            TokenAmount::zero(),
+            MinerMethod::ExtendSectorExpiration2 as u64,
+            Some(params),
+        )
+        .unwrap();
+    assert_eq!(ExitCode::USR_ILLEGAL_ARGUMENT, res.code);
+
+    // The message result keeps the context chain the miner added to the error.
+    let err = res.error.expect("no error for aborted message");
+    assert_eq!(res.message, err.msg());
+    assert_eq!(1, err.context().len());
+    let context = &err.context()[0];
+    assert_eq!(format!("failed to get claims for sector {}", sector_number), context.message);
+    assert_eq!(Some(sector_number.to_string().as_str()), context.get("sector"));
 }
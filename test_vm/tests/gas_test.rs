@@ -0,0 +1,351 @@
+use fil_actor_datacap::Method as DataCapMethod;
+use fil_actor_init::{ExecParams, ExecReturn, Method as InitMethod};
+use fil_actor_miner::{
+    aggregate_pre_commit_network_fee, max_prove_commit_duration, Method as MinerMethod,
+    PreCommitSectorBatchParams, PreCommitSectorParams,
+};
+use fil_actor_multisig::{
+    ConstructorParams as MsigConstructorParams, Method as MsigMethod, ProposeParams, ProposeReturn,
+};
+use fil_actor_paych::{
+    ConstructorParams as PaychConstructorParams, Method as PaychMethod, SignedVoucher,
+    State as PaychState, UpdateChannelStateParams,
+};
+use fil_actor_verifreg::{AddVerifiedClientParams, Method as VerifregMethod};
+use fil_actors_runtime::cbor::serialize;
+use fil_actors_runtime::runtime::Policy;
+use fil_actors_runtime::test_utils::{
+    make_sealed_cid, MULTISIG_ACTOR_CODE_ID, PAYCH_ACTOR_CODE_ID,
+};
+use fil_actors_runtime::{
+    BURNT_FUNDS_ACTOR_ADDR, DATACAP_TOKEN_ACTOR_ADDR, INIT_ACTOR_ADDR, USER_RECEIVER_GAS_LIMIT,
+    VERIFIED_REGISTRY_ACTOR_ADDR,
+};
+use fvm_ipld_blockstore::MemoryBlockstore;
+use fvm_ipld_encoding::RawBytes;
+use fvm_shared::bigint::Zero;
+use fvm_shared::crypto::signature::{Signature, SignatureType};
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::error::ExitCode;
+use fvm_shared::sector::{RegisteredSealProof, StoragePower};
+use fvm_shared::METHOD_SEND;
+use test_vm::gas::{GasModel, PriceList};
+use test_vm::util::{apply_ok, create_accounts, create_miner, verifreg_add_verifier};
+use test_vm::{ExpectInvocation, VM};
+
+fn send_gas_used(v: &VM) -> i64 {
+    let addrs = create_accounts(v, 2, TokenAmount::from_whole(100));
+    let res = v
+        .apply_message(
+            addrs[0],
+            addrs[1],
+            TokenAmount::from_whole(1),
+            METHOD_SEND,
+            None::<RawBytes>,
+        )
+        .unwrap();
+    assert_eq!(ExitCode::OK, res.code);
+    res.gas_used
+}
+
+#[test]
+fn no_gas_without_model() {
+    let store = MemoryBlockstore::new();
+    let v = VM::new_with_singletons(&store);
+    assert_eq!(0, send_gas_used(&v));
+    assert_eq!(TokenAmount::zero(), v.base_fee());
+}
+
+#[test]
+fn gas_is_deterministic() {
+    let store1 = MemoryBlockstore::new();
+    let v1 = VM::new_with_singletons(&store1).with_gas_model(GasModel::default());
+    let store2 = MemoryBlockstore::new();
+    let v2 = VM::new_with_singletons(&store2).with_gas_model(GasModel::default());
+
+    let gas_used = send_gas_used(&v1);
+    assert!(gas_used > 0);
+    assert_eq!(gas_used, send_gas_used(&v2));
+}
+
+#[test]
+fn out_of_gas_aborts_message() {
+    let store = MemoryBlockstore::new();
+    let v = VM::new_with_singletons(&store).with_gas_model(GasModel::default());
+    let addrs = create_accounts(&v, 2, TokenAmount::from_whole(100));
+
+    let res = v
+        .apply_message_with_gas_limit(
+            addrs[0],
+            addrs[1],
+            TokenAmount::from_whole(1),
+            METHOD_SEND,
+            None::<RawBytes>,
+            1000,
+        )
+        .unwrap();
+    assert_eq!(ExitCode::SYS_OUT_OF_GAS, res.code);
+    assert_eq!(TokenAmount::from_whole(100), v.get_actor(addrs[1]).unwrap().balance);
+
+    // The same message succeeds with the default limit.
+    apply_ok(&v, addrs[0], addrs[1], TokenAmount::from_whole(1), METHOD_SEND, None::<RawBytes>);
+    assert_eq!(TokenAmount::from_whole(101), v.get_actor(addrs[1]).unwrap().balance);
+}
+
+#[test]
+fn base_fee_schedule() {
+    let model = GasModel::default()
+        .with_base_fee(100, TokenAmount::from_nano(10))
+        .with_base_fee(200, TokenAmount::from_nano(20));
+    assert_eq!(TokenAmount::zero(), model.base_fee_at(99));
+    assert_eq!(TokenAmount::from_nano(10), model.base_fee_at(100));
+    assert_eq!(TokenAmount::from_nano(10), model.base_fee_at(199));
+    assert_eq!(TokenAmount::from_nano(20), model.base_fee_at(1000));
+
+    let store = MemoryBlockstore::new();
+    let v = VM::new_with_singletons(&store).with_gas_model(model).with_epoch(150);
+    assert_eq!(TokenAmount::from_nano(10), v.base_fee());
+}
+
+#[test]
+fn precommit_batch_burns_fee_at_base_fee() {
+    let base_fee = TokenAmount::from_nano(50);
+    let store = MemoryBlockstore::new();
+    let mut v = VM::new_with_singletons(&store)
+        .with_gas_model(GasModel::default().with_base_fee(0, base_fee.clone()));
+    let addrs = create_accounts(&v, 1, TokenAmount::from_whole(10_000));
+    let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
+    let (owner, worker) = (addrs[0], addrs[0]);
+    let (id_addr, _) = create_miner(
+        &mut v,
+        owner,
+        worker,
+        seal_proof.registered_window_post_proof().unwrap(),
+        TokenAmount::from_whole(1_000),
+    );
+    let v = v.with_epoch(200);
+
+    let count = 4;
+    let expiration = v.get_epoch()
+        + Policy::default().min_sector_expiration
+        + max_prove_commit_duration(&Policy::default(), seal_proof).unwrap();
+    let sectors = (0..count)
+        .map(|sector_number| PreCommitSectorParams {
+            seal_proof,
+            sector_number,
+            sealed_cid: make_sealed_cid(format!("sn: {}", sector_number).as_bytes()),
+            seal_rand_epoch: v.get_epoch() - 1,
+            deal_ids: vec![],
+            expiration,
+            ..Default::default()
+        })
+        .collect();
+
+    let burnt_before = v.get_actor(BURNT_FUNDS_ACTOR_ADDR).unwrap().balance;
+    let res = v
+        .apply_message(
+            worker,
+            id_addr,
+            TokenAmount::zero(),
+            MinerMethod::PreCommitSectorBatch as u64,
+            Some(PreCommitSectorBatchParams { sectors }),
+        )
+        .unwrap();
+    assert_eq!(ExitCode::OK, res.code);
+    assert!(res.gas_used > 0);
+
+    let burnt = v.get_actor(BURNT_FUNDS_ACTOR_ADDR).unwrap().balance - burnt_before;
+    let expected = aggregate_pre_commit_network_fee(count as i64, &base_fee);
+    assert!(expected > aggregate_pre_commit_network_fee(count as i64, &TokenAmount::zero()));
+    assert_eq!(expected, burnt);
+}
+
+// A gas model in which transferring funds alone exhausts the gas limit of a send to a receiver
+// chosen by a user.
+fn costly_transfers() -> GasModel {
+    let prices =
+        PriceList { send_transfer_funds: USER_RECEIVER_GAS_LIMIT as i64, ..Default::default() };
+    GasModel { prices, ..Default::default() }
+}
+
+#[test]
+fn capped_multisig_send_aborts_only_receiver() {
+    let store = MemoryBlockstore::new();
+    let v = VM::new_with_singletons(&store);
+    let addrs = create_accounts(&v, 2, TokenAmount::from_whole(10_000));
+    let (signer, recipient) = (addrs[0], addrs[1]);
+    let ctor_params = MsigConstructorParams {
+        signers: vec![signer],
+        num_approvals_threshold: 1,
+        unlock_duration: 0,
+        start_epoch: 0,
+    };
+    let ret: ExecReturn = apply_ok(
+        &v,
+        signer,
+        INIT_ACTOR_ADDR,
+        TokenAmount::zero(),
+        InitMethod::Exec as u64,
+        Some(ExecParams {
+            code_cid: *MULTISIG_ACTOR_CODE_ID,
+            constructor_params: serialize(&ctor_params, "multisig ctor params").unwrap(),
+        }),
+    )
+    .deserialize()
+    .unwrap();
+    let msig = ret.id_address;
+    let v = v.with_gas_model(costly_transfers());
+
+    // The approved transfer runs out of gas, but the proposal executing it succeeds.
+    let value = TokenAmount::from_whole(1);
+    let proposal = ProposeParams {
+        to: recipient,
+        value: value.clone(),
+        method: METHOD_SEND,
+        params: RawBytes::default(),
+    };
+    let ret: ProposeReturn =
+        apply_ok(&v, signer, msig, value.clone(), MsigMethod::Propose as u64, Some(proposal))
+            .deserialize()
+            .unwrap();
+    assert!(ret.applied);
+    assert_eq!(ExitCode::SYS_OUT_OF_GAS, ret.code);
+    ExpectInvocation {
+        to: msig,
+        method: MsigMethod::Propose as u64,
+        code: Some(ExitCode::OK),
+        subinvocs: Some(vec![ExpectInvocation {
+            to: recipient,
+            method: METHOD_SEND,
+            gas_limit: Some(Some(USER_RECEIVER_GAS_LIMIT)),
+            code: Some(ExitCode::SYS_OUT_OF_GAS),
+            ..Default::default()
+        }]),
+        ..Default::default()
+    }
+    .matches(v.take_invocations().last().unwrap());
+    assert_eq!(value, v.get_actor(msig).unwrap().balance);
+    assert_eq!(TokenAmount::from_whole(10_000), v.get_actor(recipient).unwrap().balance);
+}
+
+#[test]
+fn capped_paych_collect_aborts_only_receiver() {
+    let store = MemoryBlockstore::new();
+    let v = VM::new_with_singletons(&store);
+    let addrs = create_accounts(&v, 2, TokenAmount::from_whole(10_000));
+    let (from, to) = (addrs[0], addrs[1]);
+    let ctor_params = PaychConstructorParams { from, to };
+    let ret: ExecReturn = apply_ok(
+        &v,
+        from,
+        INIT_ACTOR_ADDR,
+        TokenAmount::from_whole(10),
+        InitMethod::Exec as u64,
+        Some(ExecParams {
+            code_cid: *PAYCH_ACTOR_CODE_ID,
+            constructor_params: serialize(&ctor_params, "paych ctor params").unwrap(),
+        }),
+    )
+    .deserialize()
+    .unwrap();
+    let paych = ret.id_address;
+
+    let mut voucher = SignedVoucher {
+        channel_addr: paych,
+        time_lock_min: 0,
+        time_lock_max: 0,
+        secret_pre_image: vec![],
+        extra: None,
+        lane: 0,
+        nonce: 1,
+        amount: TokenAmount::from_whole(1),
+        min_settle_height: 0,
+        merges: vec![],
+        signature: None,
+    };
+    // The test VM accepts a mock signature equal to the signed bytes.
+    voucher.signature = Some(Signature {
+        sig_type: SignatureType::Secp256k1,
+        bytes: voucher.signing_bytes().unwrap(),
+    });
+    apply_ok(
+        &v,
+        to,
+        paych,
+        TokenAmount::zero(),
+        PaychMethod::UpdateChannelState as u64,
+        Some(UpdateChannelStateParams::from(voucher)),
+    );
+    apply_ok(&v, to, paych, TokenAmount::zero(), PaychMethod::Settle as u64, None::<RawBytes>);
+    let settling_at = v.get_state::<PaychState>(paych).unwrap().settling_at;
+    let v = v.with_epoch(settling_at).with_gas_model(costly_transfers());
+
+    // The payment to the recipient runs out of gas, failing the collection with a code of its
+    // own rather than exhausting the collector's gas.
+    let res = v
+        .apply_message(
+            to,
+            paych,
+            TokenAmount::zero(),
+            PaychMethod::Collect as u64,
+            None::<RawBytes>,
+        )
+        .unwrap();
+    assert_eq!(ExitCode::USR_ASSERTION_FAILED, res.code);
+    assert!(res.gas_used < GasModel::default().default_gas_limit as i64);
+    ExpectInvocation {
+        to: paych,
+        method: PaychMethod::Collect as u64,
+        code: Some(ExitCode::USR_ASSERTION_FAILED),
+        subinvocs: Some(vec![ExpectInvocation {
+            to,
+            method: METHOD_SEND,
+            gas_limit: Some(Some(USER_RECEIVER_GAS_LIMIT)),
+            code: Some(ExitCode::SYS_OUT_OF_GAS),
+            ..Default::default()
+        }]),
+        ..Default::default()
+    }
+    .matches(v.take_invocations().last().unwrap());
+    assert_eq!(TokenAmount::from_whole(10), v.get_actor(paych).unwrap().balance);
+}
+
+#[test]
+fn capped_verifreg_mint_aborts_only_datacap() {
+    let store = MemoryBlockstore::new();
+    let v = VM::new_with_singletons(&store);
+    let addrs = create_accounts(&v, 2, TokenAmount::from_whole(10_000));
+    let (verifier, client) = (addrs[0], addrs[1]);
+    let allowance = StoragePower::from(1u64 << 40);
+    verifreg_add_verifier(&v, verifier, allowance.clone());
+
+    // Every block written exhausts the gas limit of a send to a receiver chosen by a user.
+    let prices =
+        PriceList { block_write_base: USER_RECEIVER_GAS_LIMIT as i64, ..Default::default() };
+    let v = v.with_gas_model(GasModel { prices, ..Default::default() });
+    let res = v
+        .apply_message(
+            verifier,
+            VERIFIED_REGISTRY_ACTOR_ADDR,
+            TokenAmount::zero(),
+            VerifregMethod::AddVerifiedClient as u64,
+            Some(AddVerifiedClientParams { address: client, allowance }),
+        )
+        .unwrap();
+    assert_eq!(ExitCode::USR_ASSERTION_FAILED, res.code);
+    assert!(res.gas_used < GasModel::default().default_gas_limit as i64);
+    ExpectInvocation {
+        to: VERIFIED_REGISTRY_ACTOR_ADDR,
+        method: VerifregMethod::AddVerifiedClient as u64,
+        code: Some(ExitCode::USR_ASSERTION_FAILED),
+        subinvocs: Some(vec![ExpectInvocation {
+            to: DATACAP_TOKEN_ACTOR_ADDR,
+            method: DataCapMethod::MintExported as u64,
+            gas_limit: Some(Some(USER_RECEIVER_GAS_LIMIT)),
+            code: Some(ExitCode::SYS_OUT_OF_GAS),
+            ..Default::default()
+        }]),
+        ..Default::default()
+    }
+    .matches(v.take_invocations().last().unwrap());
+}
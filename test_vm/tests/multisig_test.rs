@@ -1,11 +1,9 @@
-use fil_actor_init::ExecReturn;
 use fil_actor_multisig::{
     compute_proposal_hash, Method as MsigMethod, ProposeParams, RemoveSignerParams,
     State as MsigState, SwapSignerParams, Transaction, TxnID, TxnIDParams,
 };
 use fil_actors_runtime::cbor::serialize;
-use fil_actors_runtime::test_utils::*;
-use fil_actors_runtime::{make_map_with_root, INIT_ACTOR_ADDR, SYSTEM_ACTOR_ADDR};
+use fil_actors_runtime::{make_map_with_root, SYSTEM_ACTOR_ADDR};
 use fvm_ipld_blockstore::MemoryBlockstore;
 use fvm_ipld_encoding::RawBytes;
 use fvm_shared::address::Address;
@@ -16,7 +14,7 @@ use fvm_shared::METHOD_SEND;
 use integer_encoding::VarInt;
 use std::collections::HashSet;
 use std::iter::FromIterator;
-use test_vm::util::{apply_code, apply_ok, create_accounts};
+use test_vm::util::{apply_code, apply_ok, create_accounts, create_msig};
 use test_vm::{ExpectInvocation, VM};
 
 #[test]
@@ -273,34 +271,6 @@ fn swap_self_2_of_3() {
     v.assert_state_invariants();
 }
 
-fn create_msig(v: &VM, signers: Vec<Address>, threshold: u64) -> Address {
-    assert!(!signers.is_empty());
-    let msig_ctor_params = serialize(
-        &fil_actor_multisig::ConstructorParams {
-            signers: signers.clone(),
-            num_approvals_threshold: threshold,
-            unlock_duration: 0,
-            start_epoch: 0,
-        },
-        "multisig ctor params",
-    )
-    .unwrap();
-    let msig_ctor_ret: ExecReturn = apply_ok(
-        v,
-        signers[0],
-        INIT_ACTOR_ADDR,
-        TokenAmount::zero(),
-        fil_actor_init::Method::Exec as u64,
-        Some(fil_actor_init::ExecParams {
-            code_cid: *MULTISIG_ACTOR_CODE_ID,
-            constructor_params: msig_ctor_params,
-        }),
-    )
-    .deserialize()
-    .unwrap();
-    msig_ctor_ret.id_address
-}
-
 fn check_txs(v: &VM, msig_addr: Address, mut expect_txns: Vec<(TxnID, Transaction)>) {
     let st = v.get_state::<MsigState>(msig_addr).unwrap();
     let ptx = make_map_with_root::<_, Transaction>(&st.pending_txs, v.store).unwrap();
@@ -0,0 +1,130 @@
+use fil_actor_account::types::AuthenticateMessageParams;
+use fil_actor_account::Method::AuthenticateMessageExported;
+use fil_actor_init::{ExecParams, ExecReturn, Method as InitMethod};
+use fil_actor_paych::{
+    ConstructorParams as PaychConstructorParams, LaneState, Method as PaychMethod, SignedVoucher,
+    State as PaychState, UpdateChannelStateParams,
+};
+use fil_actors_runtime::cbor::serialize;
+use fil_actors_runtime::test_utils::PAYCH_ACTOR_CODE_ID;
+use fil_actors_runtime::INIT_ACTOR_ADDR;
+use fvm_ipld_blockstore::MemoryBlockstore;
+use fvm_shared::address::Address;
+use fvm_shared::bigint::Zero;
+use fvm_shared::crypto::signature::SignatureType;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::error::ExitCode;
+use test_case::test_case;
+use test_vm::signatures::{SignatureMode, TestKey};
+use test_vm::util::{apply_code, apply_ok, create_accounts_with_keys};
+use test_vm::VM;
+
+#[test_case(SignatureType::Secp256k1; "secp256k1")]
+#[test_case(SignatureType::BLS; "bls")]
+fn authenticate_message_with_real_signatures(sig_type: SignatureType) {
+    let store = MemoryBlockstore::new();
+    let v = VM::new_with_singletons(&store).with_signature_mode(SignatureMode::Real);
+    let accounts = create_accounts_with_keys(&v, 2, TokenAmount::from_whole(100), sig_type, 1);
+    let (addr, key) = &accounts[0];
+    let (_, other_key) = &accounts[1];
+    let message = b"a message to authenticate".to_vec();
+
+    let params = |signer: &TestKey| AuthenticateMessageParams {
+        signature: signer.sign(&message).bytes,
+        message: message.clone(),
+    };
+    apply_ok(
+        &v,
+        *addr,
+        *addr,
+        TokenAmount::zero(),
+        AuthenticateMessageExported as u64,
+        Some(params(key)),
+    );
+    apply_code(
+        &v,
+        *addr,
+        *addr,
+        TokenAmount::zero(),
+        AuthenticateMessageExported as u64,
+        Some(params(other_key)),
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+    );
+
+    // Mock signatures are no longer accepted.
+    apply_code(
+        &v,
+        *addr,
+        *addr,
+        TokenAmount::zero(),
+        AuthenticateMessageExported as u64,
+        Some(AuthenticateMessageParams { signature: message.clone(), message }),
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+    );
+}
+
+#[test]
+fn paych_voucher_with_real_signature() {
+    let store = MemoryBlockstore::new();
+    let v = VM::new_with_singletons(&store).with_signature_mode(SignatureMode::Real);
+    let accounts =
+        create_accounts_with_keys(&v, 2, TokenAmount::from_whole(100), SignatureType::Secp256k1, 2);
+    let ((from, from_key), (to, to_key)) = (&accounts[0], &accounts[1]);
+
+    let ctor_params =
+        serialize(&PaychConstructorParams { from: *from, to: *to }, "paych ctor params").unwrap();
+    let ret: ExecReturn = apply_ok(
+        &v,
+        *from,
+        INIT_ACTOR_ADDR,
+        TokenAmount::from_whole(10),
+        InitMethod::Exec as u64,
+        Some(ExecParams { code_cid: *PAYCH_ACTOR_CODE_ID, constructor_params: ctor_params }),
+    )
+    .deserialize()
+    .unwrap();
+    let paych = ret.id_address;
+
+    let voucher = |signer: &TestKey, channel: Address| {
+        let mut sv = SignedVoucher {
+            channel_addr: channel,
+            time_lock_min: 0,
+            time_lock_max: 0,
+            secret_pre_image: vec![],
+            extra: None,
+            lane: 0,
+            nonce: 1,
+            amount: TokenAmount::from_whole(1),
+            min_settle_height: 0,
+            merges: vec![],
+            signature: None,
+        };
+        sv.signature = Some(signer.sign(&sv.signing_bytes().unwrap()));
+        UpdateChannelStateParams::from(sv)
+    };
+
+    // A voucher signed by the recipient rather than the payer is rejected.
+    apply_code(
+        &v,
+        *to,
+        paych,
+        TokenAmount::zero(),
+        PaychMethod::UpdateChannelState as u64,
+        Some(voucher(to_key, paych)),
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+    );
+    apply_ok(
+        &v,
+        *to,
+        paych,
+        TokenAmount::zero(),
+        PaychMethod::UpdateChannelState as u64,
+        Some(voucher(from_key, paych)),
+    );
+
+    let st: PaychState = v.get_state(paych).unwrap();
+    let lanes =
+        fil_actors_runtime::Array::<LaneState, MemoryBlockstore>::load(&st.lane_states, &store)
+            .unwrap();
+    assert_eq!(TokenAmount::from_whole(1), lanes.get(0).unwrap().unwrap().redeemed);
+}
@@ -0,0 +1,187 @@
+use fil_actor_miner::{Method as MinerMethod, ProveCommitSectorParams};
+use fil_actors_runtime::runtime::builtins::Type;
+use fil_actors_runtime::runtime::Policy;
+use fil_actors_runtime::test_blockstores::SyncMemoryBlockstore;
+use fil_actors_runtime::{MessageAccumulator, Severity, REWARD_ACTOR_ADDR};
+use fil_builtin_actors_state::check::{
+    check_state_invariants, check_state_invariants_parallel, violations_to_json, Tree,
+};
+use fvm_ipld_blockstore::MemoryBlockstore;
+use fvm_shared::address::Address;
+use fvm_shared::bigint::Zero;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::sector::RegisteredSealProof;
+use test_vm::car::{actors_root, read_car};
+use test_vm::util::{
+    advance_by_deadline_to_epoch, apply_ok, create_accounts, create_miner, cron_tick,
+    precommit_sectors,
+};
+use test_vm::VM;
+
+fn setup(store: &'_ MemoryBlockstore) -> (VM<'_>, Address, Address) {
+    let mut v = VM::new_with_singletons(store);
+    let addrs = create_accounts(&v, 1, TokenAmount::from_whole(10_000));
+    let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
+    let (miner, _) = create_miner(
+        &mut v,
+        addrs[0],
+        addrs[0],
+        seal_proof.registered_window_post_proof().unwrap(),
+        TokenAmount::from_whole(10_000),
+    );
+    (v, addrs[0], miner)
+}
+
+// Checks the state both ways, returning the IDs of the broken invariants.
+fn check_both_ways(v: &VM) -> Vec<&'static str> {
+    let sorted = |acc: MessageAccumulator| {
+        let mut violations = acc.violations();
+        violations.sort_by(|a, b| a.message.cmp(&b.message));
+        violations
+    };
+    let full = sorted(v.check_state_invariants().unwrap());
+    let incremental = sorted(v.check_state_invariants_incremental().unwrap());
+    assert_eq!(full, incremental);
+    full.iter().map(|v| v.invariant).collect()
+}
+
+// Pre-commits and proves two sectors for the miner, checking the state after each step.
+fn prove_sectors<'bs>(
+    mut v: VM<'bs>,
+    worker: Address,
+    miner: Address,
+    check: impl Fn(&VM<'bs>),
+) -> VM<'bs> {
+    let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
+    precommit_sectors(&mut v, 2, 2, worker, miner, seal_proof, 100, true, None);
+    let prove_time = v.get_epoch() + Policy::default().pre_commit_challenge_delay + 1;
+    let v = advance_by_deadline_to_epoch(v, miner, prove_time).0;
+    check(&v);
+
+    for sector_number in [100, 101] {
+        apply_ok(
+            &v,
+            worker,
+            miner,
+            TokenAmount::zero(),
+            MinerMethod::ProveCommitSector as u64,
+            Some(ProveCommitSectorParams { sector_number, proof: vec![] }),
+        );
+    }
+    cron_tick(&v);
+    check(&v);
+    v
+}
+
+// Breaks the miner's invariants by emptying its balance.
+fn break_miner(v: &VM, miner: Address) {
+    let mut actor = v.get_actor(miner).unwrap();
+    actor.balance = TokenAmount::zero();
+    v.set_actor(miner, actor);
+}
+
+#[test]
+fn incremental_check_matches_full_check() {
+    let store = MemoryBlockstore::new();
+    let (v, worker, miner) = setup(&store);
+    assert!(check_both_ways(&v).is_empty());
+
+    // Advancing the epoch without running cron leaves the reward actor behind, which is only
+    // noticed if the reward actor is checked again at the new epoch.
+    let v = v.with_epoch(200);
+    assert_eq!(vec!["reward.state_epoch"], check_both_ways(&v));
+
+    let v = prove_sectors(v, worker, miner, |v| {
+        check_both_ways(v);
+    });
+
+    // Breaking a miner's invariants is reported, and is still reported when nothing changes.
+    break_miner(&v, miner);
+    let broken = check_both_ways(&v);
+    assert!(broken.contains(&"miner.balance.covers_locked_funds"));
+    assert!(broken.contains(&"state.total_balance"));
+    assert_eq!(broken, check_both_ways(&v));
+
+    let violations = v.check_state_invariants().unwrap().violations();
+    let violation =
+        violations.iter().find(|v| v.invariant == "miner.balance.covers_locked_funds").unwrap();
+    assert_eq!(Some(miner), violation.actor);
+    assert_eq!(Some(Type::Miner.name()), violation.actor_type);
+}
+
+#[test]
+fn incremental_check_reports_changed_expected_balance() {
+    let store = MemoryBlockstore::new();
+    let (v, _, _) = setup(&store);
+    v.check_state_invariants_incremental().unwrap().assert_empty();
+
+    // The state is unchanged, but the total it must add up to is not.
+    let total = v.get_total_actor_balance(&store).unwrap();
+    let v = v.with_total_fil(total + TokenAmount::from_atto(1));
+    v.check_state_invariants_incremental().unwrap().assert_invariants(&["state.total_balance"]);
+}
+
+#[test]
+fn violations_record_actor_and_invariant() {
+    let store = MemoryBlockstore::new();
+    let (v, _, _) = setup(&store);
+    let v = v.with_epoch(200);
+
+    let acc = v.check_state_invariants().unwrap();
+    acc.assert_invariants(&["reward.state_epoch"]);
+    let violations = acc.violations();
+    let violation = &violations[0];
+    assert_eq!(Severity::Error, violation.severity);
+    assert_eq!(Some(REWARD_ACTOR_ADDR), violation.actor);
+    assert_eq!(Some(Type::Reward.name()), violation.actor_type);
+    assert!(violation.related.is_empty());
+
+    let json = violations_to_json(&acc).unwrap();
+    let expected = format!(
+        r#"[{{"invariant":"reward.state_epoch","severity":"error","actor":"{REWARD_ACTOR_ADDR}","actor_type":"reward","related":{{}},"message":"{}"}}]"#,
+        violation.message
+    );
+    assert_eq!(expected, json);
+}
+
+#[test]
+fn parallel_check_matches_serial_check() {
+    let store = MemoryBlockstore::new();
+    let (v, worker, miner) = setup(&store);
+    create_accounts(&v, 20, TokenAmount::from_whole(1));
+    let v = prove_sectors(v.with_epoch(200), worker, miner, |_| {});
+    // Break the miner so that there are invariants to report.
+    break_miner(&v, miner);
+    let total = v.get_total_actor_balance(&store).unwrap();
+    let prior_epoch = v.get_epoch() - 1;
+
+    let mut car = vec![];
+    v.export_car(&mut car).unwrap();
+    let sync_store = SyncMemoryBlockstore::new();
+    let root = actors_root(&sync_store, &read_car(&sync_store, car.as_slice()).unwrap()).unwrap();
+
+    let policy = Policy::default();
+    let serial = check_state_invariants(
+        v.manifest(),
+        &policy,
+        Tree::load(&sync_store, &root).unwrap(),
+        &total,
+        prior_epoch,
+    )
+    .unwrap()
+    .violations();
+    assert!(serial.iter().any(|v| v.invariant == "miner.balance.covers_locked_funds"));
+    for threads in [1, 3, 64] {
+        let parallel = check_state_invariants_parallel(
+            v.manifest(),
+            &policy,
+            Tree::load(&sync_store, &root).unwrap(),
+            &total,
+            prior_epoch,
+            threads,
+        )
+        .unwrap()
+        .violations();
+        assert_eq!(serial, parallel);
+    }
+}
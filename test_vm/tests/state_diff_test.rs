@@ -0,0 +1,239 @@
+use fil_actor_multisig::{Method as MsigMethod, ProposeParams};
+use fil_actor_verifreg::State as VerifregState;
+use fil_actors_runtime::runtime::builtins::Type;
+use fil_actors_runtime::runtime::Policy;
+use fil_actors_runtime::{
+    DATACAP_TOKEN_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR,
+    VERIFIED_REGISTRY_ACTOR_ADDR,
+};
+use fil_builtin_actors_state::diff::{diff_state_trees, ActorDiff, Change, StateChange};
+use fvm_ipld_blockstore::MemoryBlockstore;
+use fvm_ipld_encoding::RawBytes;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::sector::RegisteredSealProof;
+use fvm_shared::METHOD_SEND;
+use test_vm::util::{
+    advance_by_deadline_to_epoch, advance_to_proving_deadline, apply_ok, create_accounts,
+    create_miner, create_msig, create_vm_with_miner, cron_tick, miner_prove_sector,
+    precommit_sectors, prove_verified_deal, publish_verified_deal, sector_deadline,
+};
+use test_vm::VM;
+
+fn changes(diff: &ActorDiff) -> &[StateChange] {
+    match diff {
+        ActorDiff::Changed { changes, .. } => changes,
+        _ => panic!("expected a changed actor, got {:?}", diff),
+    }
+}
+
+fn find<'a>(changes: &'a [StateChange], path: &str) -> Option<&'a Change> {
+    changes.iter().find(|c| c.path == path).map(|c| &c.change)
+}
+
+#[test]
+fn diff_miner_creation_and_precommits() {
+    let store = MemoryBlockstore::new();
+    let mut v = VM::new_with_singletons(&store);
+    let addrs = create_accounts(&v, 1, TokenAmount::from_whole(10_000));
+    let worker = addrs[0];
+    let before_miner = v.checkpoint();
+
+    let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
+    let (miner, _) = create_miner(
+        &mut v,
+        worker,
+        worker,
+        seal_proof.registered_window_post_proof().unwrap(),
+        TokenAmount::from_whole(1_000),
+    );
+    let before_precommits = v.checkpoint();
+
+    // Diffing a tree with itself finds nothing.
+    let diff =
+        diff_state_trees(v.manifest(), v.policy(), &store, &before_miner, &before_miner).unwrap();
+    assert!(diff.is_empty());
+
+    // Creating a miner adds the actor and its power claim, and debits the owner.
+    let diff =
+        diff_state_trees(v.manifest(), v.policy(), &store, &before_miner, &before_precommits)
+            .unwrap();
+    match &diff.actors[&miner.id().unwrap()] {
+        ActorDiff::Added { typ, actor } => {
+            assert_eq!(Some(Type::Miner), *typ);
+            assert_eq!(TokenAmount::from_whole(1_000), actor.balance);
+        }
+        d => panic!("expected the miner to be added, got {:?}", d),
+    }
+    let power = changes(&diff.actors[&STORAGE_POWER_ACTOR_ADDR.id().unwrap()]);
+    assert!(matches!(find(power, &format!("claims/{}", miner)), Some(Change::Added(_))));
+    assert!(matches!(find(power, "miner_count"), Some(Change::Changed { .. })));
+    assert!(find(changes(&diff.actors[&worker.id().unwrap()]), "balance").is_some());
+
+    // Pre-committing sectors is reported as typed changes to the miner's state.
+    precommit_sectors(&mut v, 2, 2, worker, miner, seal_proof, 100, true, None);
+    let after_precommits = v.checkpoint();
+    let diff =
+        diff_state_trees(v.manifest(), v.policy(), &store, &before_precommits, &after_precommits)
+            .unwrap();
+    let miner_changes = changes(&diff.actors[&miner.id().unwrap()]);
+    assert!(matches!(
+        diff.actors[&miner.id().unwrap()],
+        ActorDiff::Changed { typ: Some(Type::Miner), .. }
+    ));
+    for sector_number in [100, 101] {
+        let path = format!("pre_committed_sectors/{}", sector_number);
+        assert!(matches!(find(miner_changes, &path), Some(Change::Added(_))));
+    }
+    assert_eq!(
+        Some(&Change::Added("[100..=101]".to_string())),
+        find(miner_changes, "allocated_sectors")
+    );
+    assert!(matches!(find(miner_changes, "pre_commit_deposits"), Some(Change::Changed { .. })));
+    assert!(find(miner_changes, "head").is_none());
+
+    let printed = diff.to_string();
+    assert!(printed.contains(&format!("{} (storageminer):", miner)));
+    assert!(printed.contains("  allocated_sectors: added [100..=101]"));
+
+    // The reverse diff removes what was added.
+    let diff =
+        diff_state_trees(v.manifest(), v.policy(), &store, &after_precommits, &before_precommits)
+            .unwrap();
+    assert_eq!(
+        Some(&Change::Removed("[100..=101]".to_string())),
+        find(changes(&diff.actors[&miner.id().unwrap()]), "allocated_sectors")
+    );
+}
+
+#[test]
+fn diff_deadlines_and_partitions() {
+    let store = MemoryBlockstore::new();
+    let (mut v, addrs, miner) = create_vm_with_miner(&store);
+    let worker = addrs[0];
+    let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
+    precommit_sectors(&mut v, 2, 2, worker, miner, seal_proof, 100, true, None);
+    let prove_time = v.get_epoch() + Policy::default().pre_commit_challenge_delay + 1;
+    let v = advance_by_deadline_to_epoch(v, miner, prove_time).0;
+    let before_prove = v.checkpoint();
+
+    // Proving the sectors adds them, and a partition holding them, to a deadline.
+    for sector_number in [100, 101] {
+        miner_prove_sector(&v, worker, miner, sector_number);
+    }
+    cron_tick(&v);
+    let after_prove = v.checkpoint();
+    let (dl_idx, p_idx) = sector_deadline(&v, miner, 100);
+    let diff =
+        diff_state_trees(v.manifest(), v.policy(), &store, &before_prove, &after_prove).unwrap();
+    let miner_changes = changes(&diff.actors[&miner.id().unwrap()]);
+    for sector_number in [100, 101] {
+        let path = format!("sectors/{}", sector_number);
+        assert!(matches!(find(miner_changes, &path), Some(Change::Added(_))));
+    }
+    assert_eq!(
+        Some(&Change::Added("[100..=101]".to_string())),
+        find(miner_changes, &format!("deadlines/{}/partitions/{}", dl_idx, p_idx))
+    );
+    assert_eq!(
+        Some(&Change::Changed { old: "0".to_string(), new: "2".to_string() }),
+        find(miner_changes, &format!("deadlines/{}/live_sectors", dl_idx))
+    );
+
+    // Missing the deadline's PoSt marks the sectors of the existing partition faulty.
+    let (dline_info, _, v) = advance_to_proving_deadline(v, miner, 100);
+    let before_missed = v.checkpoint();
+    let v = v.with_epoch(dline_info.last());
+    cron_tick(&v);
+    let after_missed = v.checkpoint();
+    let diff =
+        diff_state_trees(v.manifest(), v.policy(), &store, &before_missed, &after_missed).unwrap();
+    let miner_changes = changes(&diff.actors[&miner.id().unwrap()]);
+    let faults = format!("deadlines/{}/partitions/{}/faults", dl_idx, p_idx);
+    assert_eq!(Some(&Change::Added("[100..=101]".to_string())), find(miner_changes, &faults));
+    let faulty_power = format!("deadlines/{}/faulty_power", dl_idx);
+    assert!(matches!(find(miner_changes, &faulty_power), Some(Change::Changed { .. })));
+    assert!(diff.to_string().contains(&format!("  {}: added [100..=101]", faults)));
+}
+
+#[test]
+fn diff_verified_deal() {
+    let store = MemoryBlockstore::new();
+    let (v, addrs, miner) = create_vm_with_miner(&store);
+    let (worker, verifier, client) = (addrs[0], addrs[1], addrs[2]);
+    let before_publish = v.checkpoint();
+
+    let (deal_id, deal_start) = publish_verified_deal(&v, verifier, client, worker, miner);
+    let verifreg_st: VerifregState = v.get_state(VERIFIED_REGISTRY_ACTOR_ADDR).unwrap();
+    let allocation_id = verifreg_st.next_allocation_id - 1;
+    let after_publish = v.checkpoint();
+
+    // Publishing escrows and locks funds in the market, and allocates the client's datacap.
+    let diff = diff_state_trees(v.manifest(), v.policy(), &store, &before_publish, &after_publish)
+        .unwrap();
+    let market = changes(&diff.actors[&STORAGE_MARKET_ACTOR_ADDR.id().unwrap()]);
+    for path in [
+        format!("escrow_table/{}", client),
+        format!("escrow_table/{}", miner),
+        format!("locked_table/{}", client),
+        format!("proposals/{}", deal_id),
+        format!("pending_deal_allocation_ids/{}", deal_id),
+    ] {
+        assert!(matches!(find(market, &path), Some(Change::Added(_))), "{}", path);
+    }
+    let verifreg = changes(&diff.actors[&VERIFIED_REGISTRY_ACTOR_ADDR.id().unwrap()]);
+    let verifier_path = format!("verifiers/{}", verifier);
+    assert!(matches!(find(verifreg, &verifier_path), Some(Change::Added(_))));
+    let allocation = format!("allocations/{}/{}", client.id().unwrap(), allocation_id);
+    assert!(matches!(find(verifreg, &allocation), Some(Change::Added(_))));
+    let datacap = changes(&diff.actors[&DATACAP_TOKEN_ACTOR_ADDR.id().unwrap()]);
+    assert!(matches!(find(datacap, "supply"), Some(Change::Changed { .. })));
+    let verifreg_balance = format!("balances/{}", VERIFIED_REGISTRY_ACTOR_ADDR.id().unwrap());
+    assert!(matches!(find(datacap, &verifreg_balance), Some(Change::Added(_))));
+
+    // Proving the sector activates the deal and claims the allocation.
+    let v = prove_verified_deal(v, worker, miner, 100, deal_id, deal_start);
+    let after_prove = v.checkpoint();
+    let diff =
+        diff_state_trees(v.manifest(), v.policy(), &store, &after_publish, &after_prove).unwrap();
+    let market = changes(&diff.actors[&STORAGE_MARKET_ACTOR_ADDR.id().unwrap()]);
+    assert!(matches!(find(market, &format!("states/{}", deal_id)), Some(Change::Added(_))));
+    let pending = format!("pending_deal_allocation_ids/{}", deal_id);
+    assert!(matches!(find(market, &pending), Some(Change::Removed(_))));
+    let verifreg = changes(&diff.actors[&VERIFIED_REGISTRY_ACTOR_ADDR.id().unwrap()]);
+    assert!(matches!(find(verifreg, &allocation), Some(Change::Removed(_))));
+    let claim = format!("claims/{}/{}", miner.id().unwrap(), allocation_id);
+    assert!(matches!(find(verifreg, &claim), Some(Change::Added(_))));
+}
+
+#[test]
+fn diff_multisig_proposal() {
+    let store = MemoryBlockstore::new();
+    let v = VM::new_with_singletons(&store);
+    let addrs = create_accounts(&v, 2, TokenAmount::from_whole(10_000));
+    let msig = create_msig(&v, addrs.clone(), 2);
+    let before_propose = v.checkpoint();
+
+    let value = TokenAmount::from_nano(3);
+    let params = ProposeParams {
+        to: addrs[1],
+        value: value.clone(),
+        method: METHOD_SEND,
+        params: RawBytes::default(),
+    };
+    apply_ok(&v, addrs[0], msig, value, MsigMethod::Propose as u64, Some(params));
+    let after_propose = v.checkpoint();
+
+    let diff = diff_state_trees(v.manifest(), v.policy(), &store, &before_propose, &after_propose)
+        .unwrap();
+    assert!(matches!(
+        diff.actors[&msig.id().unwrap()],
+        ActorDiff::Changed { typ: Some(Type::Multisig), .. }
+    ));
+    let msig_changes = changes(&diff.actors[&msig.id().unwrap()]);
+    assert_eq!(
+        Some(&Change::Changed { old: "0".to_string(), new: "1".to_string() }),
+        find(msig_changes, "next_tx_id")
+    );
+    assert!(matches!(find(msig_changes, "pending_txs/0"), Some(Change::Added(_))));
+    assert!(matches!(find(msig_changes, "balance"), Some(Change::Changed { .. })));
+}
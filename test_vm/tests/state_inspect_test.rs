@@ -0,0 +1,177 @@
+use fil_actor_account::State as AccountState;
+use fil_actor_verifreg::State as VerifregState;
+use fil_actors_runtime::{
+    DATACAP_TOKEN_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR,
+    VERIFIED_REGISTRY_ACTOR_ADDR,
+};
+use fil_builtin_actors_state::check::{Actor, Tree};
+use fil_builtin_actors_state::inspect::{inspect_actor, Node};
+use fvm_ipld_blockstore::MemoryBlockstore;
+use fvm_shared::address::Address;
+use fvm_shared::sector::RegisteredSealProof;
+use test_vm::util::{
+    create_vm_with_miner, precommit_sectors, prove_verified_deal, publish_verified_deal,
+};
+
+fn field<'a>(node: &'a Node, name: &str) -> &'a Node {
+    match node {
+        Node::Fields(fields) => &fields.iter().find(|(n, _)| n == name).unwrap().1,
+        _ => panic!("expected fields, got {:?}", node),
+    }
+}
+
+#[test]
+fn inspect_miner_state() {
+    let store = MemoryBlockstore::new();
+    let (mut v, addrs, miner) = create_vm_with_miner(&store);
+    let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
+    precommit_sectors(&mut v, 3, 3, addrs[0], miner, seal_proof, 100, true, None);
+
+    let dump = v.dump_actor(miner).unwrap();
+    assert!(dump.starts_with("type: storageminer\n"), "{}", dump);
+    assert!(dump.contains("\n  pre_committed_sectors (3 entries):\n"), "{}", dump);
+    assert!(dump.contains("\n  allocated_sectors: [100, 101, 102]\n"), "{}", dump);
+    assert!(dump.contains("\n  sectors (0 entries):\n"), "{}", dump);
+
+    let dump = v.dump_actor(STORAGE_POWER_ACTOR_ADDR).unwrap();
+    assert!(
+        dump.contains(&format!(
+            "\n  claims (1 entries):\n    {}:\n      window_post_proof_type: ",
+            miner
+        )),
+        "{}",
+        dump
+    );
+    assert!(v.dump_actor(Address::new_id(9999)).is_err());
+
+    // Collections are truncated at the limit.
+    let actor = v.get_actor(miner).unwrap();
+    let actor = Actor {
+        code: actor.code,
+        head: actor.head,
+        call_seq_num: actor.call_seq_num,
+        balance: actor.balance,
+    };
+    let node = inspect_actor(v.manifest(), v.policy(), &store, &actor, 0, 2).unwrap();
+    let state = field(&node, "state");
+    assert_eq!(
+        &Node::Bits { total: 3, offset: 0, bits: vec![100, 101] },
+        field(state, "allocated_sectors")
+    );
+    // The HAMT isn't walked past the page to count its entries.
+    match field(state, "pre_committed_sectors") {
+        Node::Entries { total, offset, entries } => {
+            assert_eq!(None, *total);
+            assert_eq!(0, *offset);
+            assert_eq!(2, entries.len());
+        }
+        n => panic!("expected entries, got {:?}", n),
+    }
+    let dump = node.to_string();
+    assert!(dump.contains("\n  allocated_sectors: [100, 101] and 1 more\n"), "{}", dump);
+    assert!(dump.contains("\n  pre_committed_sectors (over 2 entries):\n"), "{}", dump);
+    assert!(dump.contains("\n    ... more\n"), "{}", dump);
+
+    let json = node.to_json();
+    assert_eq!("storageminer", json["type"]);
+    assert_eq!(actor.call_seq_num, json["call_seq_num"]);
+    assert!(json["state"]["pre_committed_sectors"]["total"].is_null());
+    let entries = json["state"]["pre_committed_sectors"]["entries"].as_array().unwrap();
+    assert_eq!(2, entries.len());
+    assert_eq!(100, entries[0]["value"]["info"]["sector_number"]);
+    assert_eq!(3, json["state"]["allocated_sectors"]["total"]);
+    assert_eq!(101, json["state"]["allocated_sectors"]["bits"][1]);
+
+    // The next page holds the remaining entry, and the HAMT is counted once it's walked to the
+    // end.
+    let node = inspect_actor(v.manifest(), v.policy(), &store, &actor, 2, 2).unwrap();
+    let state = field(&node, "state");
+    assert_eq!(
+        &Node::Bits { total: 3, offset: 2, bits: vec![102] },
+        field(state, "allocated_sectors")
+    );
+    match field(state, "pre_committed_sectors") {
+        Node::Entries { total, offset, entries } => {
+            assert_eq!(Some(3), *total);
+            assert_eq!(2, *offset);
+            assert_eq!(1, entries.len());
+        }
+        n => panic!("expected entries, got {:?}", n),
+    }
+    let dump = node.to_string();
+    assert!(dump.contains("\n  allocated_sectors: 2 skipped, [102]\n"), "{}", dump);
+    assert!(
+        dump.contains("\n  pre_committed_sectors (3 entries):\n    ... 2 skipped\n"),
+        "{}",
+        dump
+    );
+
+    // The inspect-state tool accepts robust addresses, resolved by the init actor.
+    let tree = Tree::load(&store, &v.checkpoint()).unwrap();
+    let account: AccountState = v.get_state(addrs[0]).unwrap();
+    assert_eq!(Some(addrs[0]), tree.resolve_address(&account.address).unwrap());
+    let unknown = Address::new_secp256k1(&[1; 65]).unwrap();
+    assert_eq!(None, tree.resolve_address(&unknown).unwrap());
+}
+
+#[test]
+fn inspect_verified_deal_state() {
+    let store = MemoryBlockstore::new();
+    let (v, addrs, miner) = create_vm_with_miner(&store);
+    let (worker, verifier, client) = (addrs[0], addrs[1], addrs[2]);
+    let (deal_id, deal_start) = publish_verified_deal(&v, verifier, client, worker, miner);
+    let verifreg_st: VerifregState = v.get_state(VERIFIED_REGISTRY_ACTOR_ADDR).unwrap();
+    let allocation_id = verifreg_st.next_allocation_id - 1;
+    let client_id = client.id().unwrap();
+
+    // The published deal is pending its allocation, escrowed with both parties' funds.
+    let dump = v.dump_actor(STORAGE_MARKET_ACTOR_ADDR).unwrap();
+    assert!(dump.starts_with("type: storagemarket\n"), "{}", dump);
+    let proposal = format!("\n  proposals (1 entries):\n    {}:\n      piece_cid: ", deal_id);
+    assert!(dump.contains(&proposal), "{}", dump);
+    assert!(dump.contains("\n  states (0 entries):\n"), "{}", dump);
+    assert!(dump.contains("\n  escrow_table (2 entries):\n"), "{}", dump);
+    assert!(dump.contains(&format!("\n    {}: ", client)), "{}", dump);
+    let pending = format!(
+        "\n  pending_deal_allocation_ids (1 entries):\n    {}: {}\n",
+        deal_id, allocation_id
+    );
+    assert!(dump.contains(&pending), "{}", dump);
+    assert!(dump.contains("\n  pending_proposals (1 entries):\n    0: "), "{}", dump);
+    // The deal is scheduled for its first cron update, at an epoch after its start.
+    assert!(dump.contains("\n  deal_ops_by_epoch (1 entries):\n"), "{}", dump);
+    let scheduled = format!(" (1 entries):\n      0: {}\n  last_cron: ", deal_id);
+    assert!(dump.contains(&scheduled), "{}", dump);
+
+    // Allocations are nested under the client's ID.
+    let dump = v.dump_actor(VERIFIED_REGISTRY_ACTOR_ADDR).unwrap();
+    assert!(dump.starts_with("type: verifiedregistry\n"), "{}", dump);
+    assert!(dump.contains(&format!("\n  verifiers (1 entries):\n    {}: ", verifier)), "{}", dump);
+    let allocation = format!(
+        "\n  allocations (1 entries):\n    {} (1 entries):\n      {}:\n        client: {}\n",
+        client_id, allocation_id, client_id
+    );
+    assert!(dump.contains(&allocation), "{}", dump);
+    assert!(dump.contains("\n  claims (0 entries):\n"), "{}", dump);
+
+    // The allocated datacap is held by the registry, keyed by its actor ID.
+    let dump = v.dump_actor(DATACAP_TOKEN_ACTOR_ADDR).unwrap();
+    assert!(dump.starts_with("type: datacap\n"), "{}", dump);
+    let balance = format!("\n    {}: ", VERIFIED_REGISTRY_ACTOR_ADDR.id().unwrap());
+    assert!(dump.contains(&balance), "{}", dump);
+
+    // Proving the sector activates the deal and turns the allocation into the miner's claim.
+    let v = prove_verified_deal(v, worker, miner, 100, deal_id, deal_start);
+    let dump = v.dump_actor(STORAGE_MARKET_ACTOR_ADDR).unwrap();
+    let state = format!("\n  states (1 entries):\n    {}:\n      sector_start_epoch: ", deal_id);
+    assert!(dump.contains(&state), "{}", dump);
+    assert!(dump.contains("\n  pending_deal_allocation_ids (0 entries):\n"), "{}", dump);
+    let dump = v.dump_actor(VERIFIED_REGISTRY_ACTOR_ADDR).unwrap();
+    let claim = format!(
+        "\n  claims (1 entries):\n    {} (1 entries):\n      {}:\n        provider: {}\n",
+        miner.id().unwrap(),
+        allocation_id,
+        miner.id().unwrap()
+    );
+    assert!(dump.contains(&claim), "{}", dump);
+}
@@ -0,0 +1,104 @@
+use cid::multihash::Code;
+use fil_actor_miner::{
+    max_prove_commit_duration, Method as MinerMethod, PreCommitSectorBatchParams,
+    PreCommitSectorParams,
+};
+use fil_actors_runtime::runtime::policy_constants::PRE_COMMIT_SECTOR_BATCH_MAX_SIZE;
+use fil_actors_runtime::runtime::Policy;
+use fil_actors_runtime::test_blockstores::{BlockstoreStats, TrackingBlockstore};
+use fil_actors_runtime::test_utils::make_sealed_cid;
+use fvm_ipld_blockstore::MemoryBlockstore;
+use fvm_ipld_encoding::CborStore;
+use fvm_shared::bigint::Zero;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::error::ExitCode;
+use fvm_shared::sector::RegisteredSealProof;
+use test_vm::util::{create_accounts, create_miner};
+use test_vm::VM;
+
+#[test]
+fn tracking_blockstore_counts_blocks_and_bytes() {
+    let store = TrackingBlockstore::new(MemoryBlockstore::new());
+    let cid = store.put_cbor(&"block".to_string(), Code::Blake2b256).unwrap();
+    let written = store.stats();
+    assert_eq!(1, written.writes);
+    assert!(written.bytes_written > 0);
+
+    let _: String = store.get_cbor(&cid).unwrap().unwrap();
+    let stats = store.take_stats();
+    assert_eq!(
+        BlockstoreStats {
+            reads: 1,
+            writes: 1,
+            bytes_read: written.bytes_written,
+            bytes_written: written.bytes_written,
+        },
+        stats
+    );
+    assert_eq!(BlockstoreStats::default(), store.stats());
+}
+
+#[test]
+fn precommit_batch_block_writes() {
+    let store = MemoryBlockstore::new();
+    let mut v = VM::new_with_singletons(&store);
+    let addrs = create_accounts(&v, 1, TokenAmount::from_whole(10_000));
+    let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
+    let (owner, worker) = (addrs[0], addrs[0]);
+    let (id_addr, _) = create_miner(
+        &mut v,
+        owner,
+        worker,
+        seal_proof.registered_window_post_proof().unwrap(),
+        TokenAmount::from_whole(10_000),
+    );
+    let v = v.with_epoch(200);
+
+    let count = PRE_COMMIT_SECTOR_BATCH_MAX_SIZE as u64;
+    let expiration = v.get_epoch()
+        + Policy::default().min_sector_expiration
+        + max_prove_commit_duration(&Policy::default(), seal_proof).unwrap();
+    let sectors = (0..count)
+        .map(|sector_number| PreCommitSectorParams {
+            seal_proof,
+            sector_number,
+            sealed_cid: make_sealed_cid(format!("sn: {}", sector_number).as_bytes()),
+            seal_rand_epoch: v.get_epoch() - 1,
+            deal_ids: vec![],
+            expiration,
+            ..Default::default()
+        })
+        .collect();
+    v.take_invocations();
+
+    let res = v
+        .apply_message(
+            worker,
+            id_addr,
+            TokenAmount::zero(),
+            MinerMethod::PreCommitSectorBatch as u64,
+            Some(PreCommitSectorBatchParams { sectors }),
+        )
+        .unwrap();
+    assert_eq!(ExitCode::OK, res.code);
+
+    // Batching amortizes state writes: the whole batch writes fewer blocks than it has sectors.
+    let stats = res.store_stats;
+    assert!(stats.reads > 0);
+    assert!(
+        stats.writes < count as usize,
+        "pre-committing {} sectors wrote {} blocks",
+        count,
+        stats.writes
+    );
+
+    // The message's stats are those of its top-level invocation, which include subinvocations.
+    let trace = v.take_invocations().pop().unwrap();
+    assert_eq!(stats, trace.store_stats);
+    let sub_stats = trace
+        .subinvocations
+        .iter()
+        .fold(BlockstoreStats::default(), |acc, invoc| acc + invoc.store_stats);
+    assert!(sub_stats.reads <= stats.reads);
+    assert!(sub_stats.writes <= stats.writes);
+}
@@ -27,9 +27,8 @@ use fvm_shared::METHOD_SEND;
 use num_traits::cast::FromPrimitive;
 use test_vm::util::{
     advance_by_deadline_to_epoch, advance_by_deadline_to_epoch_while_proving,
-    advance_to_proving_deadline, apply_ok, create_accounts, create_miner,
-    invariant_failure_patterns, make_bitfield, market_publish_deal, submit_windowed_post,
-    verifreg_add_verifier,
+    advance_to_proving_deadline, apply_ok, create_accounts, create_miner, make_bitfield,
+    market_publish_deal, submit_windowed_post, verifreg_add_verifier,
 };
 use test_vm::{ExpectInvocation, VM};
 
@@ -366,7 +365,5 @@ fn terminate_sectors() {
     assert!(TokenAmount::from_whole(58) < value_withdrawn);
     assert!(TokenAmount::from_whole(59) > value_withdrawn);
 
-    v.expect_state_invariants(
-        &[invariant_failure_patterns::REWARD_STATE_EPOCH_MISMATCH.to_owned()],
-    );
+    v.expect_state_invariant_ids(&["reward.state_epoch"]);
 }
@@ -1,15 +1,18 @@
 use fil_actor_account::State as AccountState;
+use fil_actor_market::{GetBalanceReturn, Method as MarketMethod};
 use fil_actors_runtime::test_utils::{
     make_identity_cid, ACCOUNT_ACTOR_CODE_ID, PAYCH_ACTOR_CODE_ID,
 };
+use fil_actors_runtime::{EXIT_CODE_READ_ONLY, STORAGE_MARKET_ACTOR_ADDR};
 use fvm_ipld_blockstore::MemoryBlockstore;
 use fvm_ipld_encoding::RawBytes;
 use fvm_shared::address::Address;
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
+use fvm_shared::version::NetworkVersion;
 use fvm_shared::METHOD_SEND;
 use num_traits::Zero;
-use test_vm::util::pk_addrs_from;
+use test_vm::util::{apply_ok, create_accounts, pk_addrs_from};
 use test_vm::{actor, FIRST_TEST_USER_ADDR, TEST_FAUCET_ADDR, VM};
 
 #[test]
@@ -130,3 +133,92 @@ fn test_pk_gen() {
     let first_addr_seeded_six = addrs[0];
     assert_ne!(second_addr_seeded_five, first_addr_seeded_six);
 }
+
+#[test]
+fn read_only_invocation() {
+    let store = MemoryBlockstore::new();
+    let v = VM::new_with_singletons(&store);
+    let addrs = create_accounts(&v, 1, TokenAmount::from_whole(10_000));
+
+    // getters succeed in read-only mode
+    let ret = v
+        .apply_message_read_only(
+            addrs[0],
+            STORAGE_MARKET_ACTOR_ADDR,
+            MarketMethod::GetBalanceExported as u64,
+            Some(addrs[0]),
+        )
+        .unwrap();
+    assert_eq!(ExitCode::OK, ret.code);
+    let balance: GetBalanceReturn = ret.ret.unwrap().deserialize().unwrap();
+    assert!(balance.balance.is_zero());
+
+    // methods which modify state are rejected
+    let ret = v
+        .apply_message_read_only(
+            addrs[0],
+            STORAGE_MARKET_ACTOR_ADDR,
+            MarketMethod::AddBalanceExported as u64,
+            Some(addrs[0]),
+        )
+        .unwrap();
+    assert_eq!(EXIT_CODE_READ_ONLY, ret.code);
+
+    // implicitly creating an account is a side-effect
+    let addr = Address::new_bls(&[2; fvm_shared::address::BLS_PUB_LEN]).unwrap();
+    let ret = v.apply_message_read_only(addrs[0], addr, METHOD_SEND, None::<RawBytes>).unwrap();
+    assert_eq!(EXIT_CODE_READ_ONLY, ret.code);
+    assert_eq!(None, v.normalize_address(&addr));
+}
+
+#[test]
+fn forks_are_independent() {
+    let store = MemoryBlockstore::new();
+    let v = VM::new_with_singletons(&store);
+    let addrs = create_accounts(&v, 2, TokenAmount::from_whole(10));
+    v.take_invocations();
+
+    let fork = v.fork().with_epoch(100).with_network_version(NetworkVersion::V17);
+    apply_ok(&fork, addrs[0], addrs[1], TokenAmount::from_whole(1), METHOD_SEND, None::<RawBytes>);
+    apply_ok(&v, addrs[1], addrs[0], TokenAmount::from_whole(2), METHOD_SEND, None::<RawBytes>);
+
+    assert_eq!(TokenAmount::from_whole(9), fork.get_actor(addrs[0]).unwrap().balance);
+    assert_eq!(TokenAmount::from_whole(12), v.get_actor(addrs[0]).unwrap().balance);
+    assert_eq!(0, v.get_epoch());
+    assert_eq!(100, fork.get_epoch());
+    assert_eq!(NetworkVersion::V16, v.network_version());
+    assert_eq!(NetworkVersion::V17, fork.network_version());
+    assert_eq!(1, v.take_invocations().len());
+    assert_eq!(1, fork.take_invocations().len());
+}
+
+#[test]
+fn randomness_sources() {
+    use fil_actors_runtime::runtime::DomainSeparationTag::{PoStChainCommit, SealRandomness};
+    use test_vm::randomness::{
+        RandomnessKind::{Beacon, Tickets},
+        RandomnessSource, ScriptedRandomness, SeededRandomness,
+    };
+
+    let seeded = SeededRandomness { seed: 1 };
+    let draw = seeded.get_randomness(Tickets, SealRandomness, 10, b"entropy");
+    assert_eq!(
+        draw,
+        SeededRandomness { seed: 1 }.get_randomness(Tickets, SealRandomness, 10, b"entropy")
+    );
+    assert_ne!(
+        draw,
+        SeededRandomness { seed: 2 }.get_randomness(Tickets, SealRandomness, 10, b"entropy")
+    );
+    assert_ne!(draw, seeded.get_randomness(Tickets, SealRandomness, 11, b"entropy"));
+    assert_ne!(draw, seeded.get_randomness(Tickets, PoStChainCommit, 10, b"entropy"));
+    assert_ne!(draw, seeded.get_randomness(Beacon, SealRandomness, 10, b"entropy"));
+    assert_ne!(draw, seeded.get_randomness(Tickets, SealRandomness, 10, b""));
+
+    let scripted = ScriptedRandomness::new(seeded.clone()).with(Tickets, 10, [7; 32]);
+    assert_eq!([7; 32], scripted.get_randomness(Tickets, SealRandomness, 10, b"entropy"));
+    assert_eq!(
+        seeded.get_randomness(Beacon, SealRandomness, 10, b"entropy"),
+        scripted.get_randomness(Beacon, SealRandomness, 10, b"entropy")
+    );
+}
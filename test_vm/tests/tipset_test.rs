@@ -0,0 +1,104 @@
+use fil_actor_reward::State as RewardState;
+use fil_actors_runtime::REWARD_ACTOR_ADDR;
+use fvm_ipld_blockstore::MemoryBlockstore;
+use fvm_ipld_encoding::RawBytes;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::error::ExitCode;
+use fvm_shared::sector::RegisteredSealProof;
+use fvm_shared::METHOD_SEND;
+use num_traits::Zero;
+use test_vm::gas::GasModel;
+use test_vm::util::{create_accounts, create_miner};
+use test_vm::{BlockWinner, TipsetMessage, VM};
+
+#[test]
+fn tipset_messages_applied_in_order() {
+    let store = MemoryBlockstore::new();
+    let mut v = VM::new_with_singletons(&store);
+    let addrs = create_accounts(&v, 3, TokenAmount::from_whole(10));
+    let (a, b, c) = (addrs[0], addrs[1], addrs[2]);
+
+    let send = |from, to, whole| {
+        TipsetMessage::new(from, to, TokenAmount::from_whole(whole), METHOD_SEND, None::<RawBytes>)
+    };
+    // The second message is only funded by the first, and the third can't be funded at all.
+    let messages = vec![send(a, b, 5), send(b, c, 15), send(a, c, 100)];
+    let receipts = v.apply_tipset(messages, 10).unwrap();
+
+    assert_eq!(11, v.get_epoch());
+    let codes: Vec<ExitCode> = receipts.iter().map(|r| r.result.code).collect();
+    assert_eq!(vec![ExitCode::OK, ExitCode::OK, ExitCode::SYS_INSUFFICIENT_FUNDS], codes);
+    // Each receipt carries the trace of its own message.
+    for (receipt, to) in receipts.iter().zip([b, c, c]) {
+        assert_eq!(to, receipt.trace.msg.to());
+        assert_eq!(receipt.result.code, receipt.trace.code);
+    }
+    assert_eq!(TokenAmount::from_whole(5), v.get_actor(a).unwrap().balance);
+    assert_eq!(TokenAmount::zero(), v.get_actor(b).unwrap().balance);
+    assert_eq!(TokenAmount::from_whole(25), v.get_actor(c).unwrap().balance);
+
+    // The cron tick ran at the tipset's epoch, updating the reward actor.
+    let reward: RewardState = v.get_state(REWARD_ACTOR_ADDR).unwrap();
+    assert_eq!(11, reward.epoch);
+}
+
+#[test]
+fn tipset_message_gas_limits() {
+    let store = MemoryBlockstore::new();
+    let v = VM::new_with_singletons(&store);
+    let addrs = create_accounts(&v, 2, TokenAmount::from_whole(10));
+    let (a, b) = (addrs[0], addrs[1]);
+
+    // The default gas limit is too small for any message, but a message may set its own limit.
+    // The implicit messages are exempt, so the cron tick still runs.
+    let mut v = v.with_gas_model(GasModel { default_gas_limit: 1, ..Default::default() });
+    let send = TipsetMessage::new(a, b, TokenAmount::from_whole(1), METHOD_SEND, None::<RawBytes>);
+    let messages = vec![send.clone(), send.with_gas_limit(GasModel::default().default_gas_limit)];
+    let receipts = v.apply_tipset(messages, 10).unwrap();
+
+    let codes: Vec<ExitCode> = receipts.iter().map(|r| r.result.code).collect();
+    assert_eq!(vec![ExitCode::SYS_OUT_OF_GAS, ExitCode::OK], codes);
+    assert_eq!(TokenAmount::from_whole(11), v.get_actor(b).unwrap().balance);
+    let reward: RewardState = v.get_state(REWARD_ACTOR_ADDR).unwrap();
+    assert_eq!(11, reward.epoch);
+}
+
+#[test]
+fn block_winners_are_rewarded_each_tipset() {
+    let store = MemoryBlockstore::new();
+    let mut v = VM::new_with_singletons(&store);
+    let addrs = create_accounts(&v, 1, TokenAmount::from_whole(10_000));
+    let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
+    let (id_addr, _) = create_miner(
+        &mut v,
+        addrs[0],
+        addrs[0],
+        seal_proof.registered_window_post_proof().unwrap(),
+        TokenAmount::from_whole(1_000),
+    );
+
+    let mut v = v.with_block_winners(vec![BlockWinner { miner: id_addr, win_count: 1 }]);
+    let mut rewarded = v.get_miner_balance(id_addr).vesting_balance;
+    assert!(rewarded.is_zero());
+    for _ in 0..3 {
+        let epoch = v.get_epoch();
+        v.apply_tipset(vec![], epoch).unwrap();
+        let vesting = v.get_miner_balance(id_addr).vesting_balance;
+        assert!(vesting > rewarded);
+        rewarded = vesting;
+    }
+    assert_eq!(3, v.get_epoch());
+    // The miner has no sectors, so its deadline cron isn't running to vest the rewards.
+    v.expect_state_invariant_ids(&["miner.deadline_cron_active"]);
+}
+
+#[test]
+fn tipset_before_current_epoch_is_rejected() {
+    let store = MemoryBlockstore::new();
+    let mut v = VM::new_with_singletons(&store).with_epoch(100);
+    assert!(v.apply_tipset(vec![], 99).is_err());
+    // The VM is left as it was.
+    assert_eq!(100, v.get_epoch());
+    v.apply_tipset(vec![], 100).unwrap();
+    assert_eq!(101, v.get_epoch());
+}
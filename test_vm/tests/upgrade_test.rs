@@ -0,0 +1,83 @@
+use anyhow::anyhow;
+use fil_actors_runtime::runtime::builtins::Type;
+use fil_actors_runtime::test_utils::ACCOUNT_ACTOR_CODE_ID;
+use fil_actors_runtime::BURNT_FUNDS_ACTOR_ADDR;
+use fvm_ipld_blockstore::MemoryBlockstore;
+use fvm_ipld_encoding::RawBytes;
+use fvm_ipld_hamt::BytesKey;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::sector::RegisteredSealProof;
+use fvm_shared::version::NetworkVersion;
+use fvm_shared::METHOD_SEND;
+use num_traits::Zero;
+use test_vm::manifest::{make_manifest, TEST_MANIFEST};
+use test_vm::util::{apply_ok, create_accounts, create_miner};
+use test_vm::VM;
+
+#[test]
+fn upgrade_swaps_actor_code_and_migrates_state() {
+    let store = MemoryBlockstore::new();
+    let v17 = make_manifest("v17");
+    let mut v = VM::new_with_singletons(&store).with_manifest(NetworkVersion::V17, v17.clone());
+    let addrs = create_accounts(&v, 2, TokenAmount::from_whole(10_000));
+    let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
+    let (miner, _) = create_miner(
+        &mut v,
+        addrs[0],
+        addrs[0],
+        seal_proof.registered_window_post_proof().unwrap(),
+        TokenAmount::from_whole(1_000),
+    );
+    assert_eq!(&*TEST_MANIFEST, v.manifest());
+
+    // The migration moves burnt funds to an account, conserving the total balance.
+    let invariants = v
+        .upgrade(NetworkVersion::V17, |tree| {
+            let key: BytesKey = BURNT_FUNDS_ACTOR_ADDR.to_bytes().into();
+            let mut burnt =
+                tree.map.get(&key)?.cloned().ok_or_else(|| anyhow!("no burnt funds"))?;
+            let amount = std::mem::replace(&mut burnt.balance, TokenAmount::zero());
+            tree.map.set(key, burnt)?;
+            let key: BytesKey = addrs[1].to_bytes().into();
+            let mut account = tree.map.get(&key)?.cloned().ok_or_else(|| anyhow!("no account"))?;
+            account.balance += amount;
+            tree.map.set(key, account)?;
+            Ok(())
+        })
+        .unwrap();
+    invariants.assert_empty();
+
+    assert_eq!(NetworkVersion::V17, v.network_version());
+    assert_eq!(&v17, v.manifest());
+    assert_eq!(Some(Type::Miner), v17.get_by_left(&v.get_actor(miner).unwrap().code).copied());
+    assert_eq!(v.code_for_type(Type::Account), v.get_actor(addrs[0]).unwrap().code);
+    assert!(v.get_actor(BURNT_FUNDS_ACTOR_ADDR).unwrap().balance.is_zero());
+
+    // Actors run, and are created, with the new code.
+    let new_accounts = create_accounts(&v, 1, TokenAmount::from_whole(1));
+    assert_eq!(v.code_for_type(Type::Account), v.get_actor(new_accounts[0]).unwrap().code);
+    assert_ne!(*ACCOUNT_ACTOR_CODE_ID, v.get_actor(new_accounts[0]).unwrap().code);
+    apply_ok(
+        &v,
+        addrs[0],
+        new_accounts[0],
+        TokenAmount::from_whole(1),
+        METHOD_SEND,
+        None::<RawBytes>,
+    );
+    v.assert_state_invariants();
+}
+
+#[test]
+fn failed_migration_leaves_state_unchanged() {
+    let store = MemoryBlockstore::new();
+    let mut v =
+        VM::new_with_singletons(&store).with_manifest(NetworkVersion::V17, make_manifest("v17"));
+    let root = v.checkpoint();
+
+    let res = v.upgrade(NetworkVersion::V17, |_| Err(anyhow!("migration failed")));
+    assert!(res.is_err());
+    assert_eq!(NetworkVersion::V16, v.network_version());
+    assert_eq!(root, v.checkpoint());
+    assert!(v.upgrade(NetworkVersion::V16, |_| Ok(())).is_err());
+}
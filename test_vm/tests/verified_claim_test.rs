@@ -7,33 +7,31 @@ use std::ops::Neg;
 
 use fil_actor_datacap::State as DatacapState;
 use fil_actor_market::State as MarketState;
-use fil_actor_market::{deal_id_key, DealArray, DealMetaArray};
+use fil_actor_market::{DealArray, DealMetaArray};
 use fil_actor_miner::{max_prove_commit_duration, PowerPair, SectorClaim, State as MinerState};
 use fil_actor_power::State as PowerState;
 use fil_actor_verifreg::{
-    AllocationID, Claim, Method as VerifregMethod, RemoveExpiredClaimsParams,
-    RemoveExpiredClaimsReturn, State as VerifregState,
+    Claim, Method as VerifregMethod, RemoveExpiredClaimsParams, RemoveExpiredClaimsReturn,
+    State as VerifregState,
 };
 use fil_actors_runtime::cbor::deserialize;
 use fil_actors_runtime::runtime::policy_constants::{
     DEAL_UPDATES_INTERVAL, MARKET_DEFAULT_ALLOCATION_TERM_BUFFER,
 };
 use fil_actors_runtime::runtime::Policy;
-use fil_actors_runtime::shared::HAMT_BIT_WIDTH;
 use fil_actors_runtime::test_utils::make_piece_cid;
 use fil_actors_runtime::{
-    make_map_with_root_and_bitwidth, DealWeight, Map, DATACAP_TOKEN_ACTOR_ADDR, EPOCHS_IN_DAY,
-    STORAGE_MARKET_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR,
+    DealWeight, DATACAP_TOKEN_ACTOR_ADDR, EPOCHS_IN_DAY, STORAGE_MARKET_ACTOR_ADDR,
+    STORAGE_POWER_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR,
 };
 
 use test_vm::util::{
     advance_by_deadline_to_epoch, advance_by_deadline_to_epoch_while_proving,
     advance_by_deadline_to_index, advance_to_proving_deadline, apply_ok, create_accounts,
-    create_miner, cron_tick, datacap_extend_claim, datacap_get_balance, invariant_failure_patterns,
-    market_add_balance, market_publish_deal, miner_extend_sector_expiration2,
-    miner_precommit_sector, miner_prove_sector, sector_deadline, submit_windowed_post,
-    verifreg_add_client, verifreg_add_verifier, verifreg_extend_claim_terms,
-    verifreg_remove_expired_allocations,
+    create_miner, cron_tick, datacap_extend_claim, datacap_get_balance, market_add_balance,
+    market_publish_deal, miner_extend_sector_expiration2, miner_precommit_sector,
+    miner_prove_sector, sector_deadline, submit_windowed_post, verifreg_add_client,
+    verifreg_add_verifier, verifreg_extend_claim_terms, verifreg_remove_expired_allocations,
 };
 use test_vm::VM;
 
@@ -331,9 +329,7 @@ fn verified_claim_scenario() {
     assert_eq!(vec![claim_id], ret.considered);
     assert!(ret.results.all_ok(), "results had failures {}", ret.results);
 
-    v.expect_state_invariants(
-        &[invariant_failure_patterns::REWARD_STATE_EPOCH_MISMATCH.to_owned()],
-    );
+    v.expect_state_invariant_ids(&["reward.state_epoch"]);
 }
 
 #[test]
@@ -397,13 +393,8 @@ fn expired_allocations() {
     let proposals: DealArray<MemoryBlockstore> =
         DealArray::load(&market_state.proposals, v.store).unwrap();
     assert!(proposals.get(deal1).unwrap().is_none());
-    let pending_deal_allocs: Map<MemoryBlockstore, AllocationID> = make_map_with_root_and_bitwidth(
-        &market_state.pending_deal_allocation_ids,
-        v.store,
-        HAMT_BIT_WIDTH,
-    )
-    .unwrap();
-    assert!(pending_deal_allocs.get(&deal_id_key(deal1)).unwrap().is_none());
+    let pending_deal_allocs = market_state.load_pending_deal_allocation_ids(v.store).unwrap();
+    assert!(pending_deal_allocs.get(&deal1).unwrap().is_none());
 
     // Allocation still exists until explicit cleanup
     let alloc_id = 1;
@@ -421,7 +412,5 @@ fn expired_allocations() {
     // Client has original datacap balance
     assert_eq!(TokenAmount::from_whole(datacap), datacap_get_balance(&v, verified_client));
 
-    v.expect_state_invariants(
-        &[invariant_failure_patterns::REWARD_STATE_EPOCH_MISMATCH.to_owned()],
-    );
+    v.expect_state_invariant_ids(&["reward.state_epoch"]);
 }
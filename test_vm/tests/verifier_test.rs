@@ -0,0 +1,198 @@
+use fil_actor_miner::{
+    Method as MinerMethod, ProveCommitAggregateParams, ProveCommitSectorParams,
+    ReportConsensusFaultParams, State as MinerState,
+};
+use fil_actors_runtime::runtime::Policy;
+use fvm_ipld_blockstore::MemoryBlockstore;
+use fvm_shared::address::Address;
+use fvm_shared::bigint::Zero;
+use fvm_shared::consensus::{ConsensusFault, ConsensusFaultType};
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::error::ExitCode;
+use fvm_shared::sector::{RegisteredSealProof, SectorNumber};
+use test_vm::util::{
+    advance_by_deadline_to_epoch, apply_code, apply_ok, create_accounts, create_miner, cron_tick,
+    make_bitfield, precommit_sectors,
+};
+use test_vm::VM;
+
+fn setup(store: &'_ MemoryBlockstore) -> (VM<'_>, Address, Address) {
+    let mut v = VM::new_with_singletons(store);
+    let addrs = create_accounts(&v, 1, TokenAmount::from_whole(10_000));
+    let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
+    let (miner, _) = create_miner(
+        &mut v,
+        addrs[0],
+        addrs[0],
+        seal_proof.registered_window_post_proof().unwrap(),
+        TokenAmount::from_whole(10_000),
+    );
+    (v.with_epoch(200), addrs[0], miner)
+}
+
+fn precommit_and_advance(
+    mut v: VM<'_>,
+    worker: Address,
+    miner: Address,
+    count: u64,
+    first: SectorNumber,
+) -> VM<'_> {
+    let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
+    precommit_sectors(&mut v, count, count as i64, worker, miner, seal_proof, first, true, None);
+    let prove_time = v.get_epoch() + Policy::default().pre_commit_challenge_delay + 1;
+    advance_by_deadline_to_epoch(v, miner, prove_time).0
+}
+
+fn has_sector(v: &VM, miner: Address, sector: SectorNumber) -> bool {
+    let st = v.get_state::<MinerState>(miner).unwrap();
+    st.get_sector(v.store, sector).unwrap().is_some()
+}
+
+#[test]
+fn failed_bulk_verify_skips_sector() {
+    let store = MemoryBlockstore::new();
+    let (v, worker, miner) = setup(&store);
+    let v = precommit_and_advance(v, worker, miner, 2, 100);
+
+    v.proof_faults().fail_sector(miner.id().unwrap(), 101);
+    for sector_number in [100, 101] {
+        apply_ok(
+            &v,
+            worker,
+            miner,
+            TokenAmount::zero(),
+            MinerMethod::ProveCommitSector as u64,
+            Some(ProveCommitSectorParams { sector_number, proof: vec![] }),
+        );
+    }
+    cron_tick(&v);
+
+    // The sector that passed bulk verification is activated, and the other stays precommitted.
+    assert!(has_sector(&v, miner, 100));
+    assert!(!has_sector(&v, miner, 101));
+    let st = v.get_state::<MinerState>(miner).unwrap();
+    assert!(st.get_precommitted_sector(&store, 101).unwrap().is_some());
+
+    // Once the fault is cleared, the sector can be proven again, with the next cron tick
+    // at the following epoch.
+    let next_epoch = v.get_epoch() + 1;
+    let v = v.with_epoch(next_epoch);
+    v.proof_faults().clear();
+    apply_ok(
+        &v,
+        worker,
+        miner,
+        TokenAmount::zero(),
+        MinerMethod::ProveCommitSector as u64,
+        Some(ProveCommitSectorParams { sector_number: 101, proof: vec![] }),
+    );
+    cron_tick(&v);
+    assert!(has_sector(&v, miner, 101));
+    v.expect_state_invariant_ids(&["reward.state_epoch"]);
+}
+
+#[test]
+fn failed_aggregate_proof_is_rejected() {
+    let store = MemoryBlockstore::new();
+    let (v, worker, miner) = setup(&store);
+    let v = precommit_and_advance(v, worker, miner, 4, 100);
+    let bad_proof = b"bad aggregate".to_vec();
+    v.proof_faults().fail_proof(bad_proof.clone());
+
+    let params = |aggregate_proof: Vec<u8>| ProveCommitAggregateParams {
+        sector_numbers: make_bitfield(&[100, 101, 102, 103]),
+        aggregate_proof,
+    };
+    apply_code(
+        &v,
+        worker,
+        miner,
+        TokenAmount::zero(),
+        MinerMethod::ProveCommitAggregate as u64,
+        Some(params(bad_proof)),
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+    );
+    assert!(!has_sector(&v, miner, 100));
+
+    apply_ok(
+        &v,
+        worker,
+        miner,
+        TokenAmount::zero(),
+        MinerMethod::ProveCommitAggregate as u64,
+        Some(params(vec![])),
+    );
+    assert!(has_sector(&v, miner, 100));
+    assert!(has_sector(&v, miner, 103));
+}
+
+#[test]
+fn report_injected_consensus_fault() {
+    let store = MemoryBlockstore::new();
+    let (v, _, miner) = setup(&store);
+    let reporter = create_accounts(&v, 1, TokenAmount::from_whole(100))[0];
+    let (h1, h2) = (b"header one".to_vec(), b"header two".to_vec());
+    let params = |header1: &[u8], header2: &[u8]| ReportConsensusFaultParams {
+        header1: header1.to_vec(),
+        header2: header2.to_vec(),
+        header_extra: vec![],
+    };
+
+    // Headers with no registered fault, or that fail verification, are rejected.
+    apply_code(
+        &v,
+        reporter,
+        miner,
+        TokenAmount::zero(),
+        MinerMethod::ReportConsensusFault as u64,
+        Some(params(&h1, &h2)),
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+    );
+    v.proof_faults().fail_proof(b"bad header".to_vec());
+    apply_code(
+        &v,
+        reporter,
+        miner,
+        TokenAmount::zero(),
+        MinerMethod::ReportConsensusFault as u64,
+        Some(params(&h1, b"bad header")),
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+    );
+
+    let fault_epoch = v.get_epoch() - 1;
+    v.proof_faults().add_consensus_fault(
+        h1.clone(),
+        h2.clone(),
+        ConsensusFault {
+            target: miner,
+            epoch: fault_epoch,
+            fault_type: ConsensusFaultType::DoubleForkMining,
+        },
+    );
+    let miner_balance = v.get_actor(miner).unwrap().balance;
+    let reporter_balance = v.get_actor(reporter).unwrap().balance;
+    // The headers may be reported in either order.
+    apply_ok(
+        &v,
+        reporter,
+        miner,
+        TokenAmount::zero(),
+        MinerMethod::ReportConsensusFault as u64,
+        Some(params(&h2, &h1)),
+    );
+
+    assert!(v.get_actor(miner).unwrap().balance < miner_balance);
+    assert!(v.get_actor(reporter).unwrap().balance > reporter_balance);
+    assert!(v.get_miner_info(miner).consensus_fault_elapsed > v.get_epoch());
+
+    // A second report of the same fault is forbidden.
+    apply_code(
+        &v,
+        reporter,
+        miner,
+        TokenAmount::zero(),
+        MinerMethod::ReportConsensusFault as u64,
+        Some(params(&h1, &h2)),
+        ExitCode::USR_FORBIDDEN,
+    );
+}
@@ -10,7 +10,6 @@ use fvm_shared::crypto::signature::{Signature, SignatureType};
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
 use fvm_shared::sector::StoragePower;
-use fvm_shared::HAMT_BIT_WIDTH;
 use num_traits::ToPrimitive;
 
 use fil_actor_datacap::{
@@ -23,8 +22,7 @@ use fil_actor_verifreg::{
 use fil_actor_verifreg::{AddrPairKey, Method as VerifregMethod};
 use fil_actor_verifreg::{RemoveDataCapProposal, RemoveDataCapProposalID, State as VerifregState};
 use fil_actors_runtime::{
-    make_map_with_root_and_bitwidth, DATACAP_TOKEN_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR,
-    VERIFIED_REGISTRY_ACTOR_ADDR,
+    DATACAP_TOKEN_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR,
 };
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use test_vm::util::{apply_code, apply_ok, create_accounts, verifreg_add_verifier};
@@ -81,36 +79,27 @@ fn remove_datacap_simple_successful_path() {
 
     // state checks on the 2 verifiers and the client
     let mut v_st = v.get_state::<VerifregState>(VERIFIED_REGISTRY_ACTOR_ADDR).unwrap();
-    let verifiers =
-        make_map_with_root_and_bitwidth::<_, BigIntDe>(&v_st.verifiers, &store, HAMT_BIT_WIDTH)
-            .unwrap();
+    let verifiers = v_st.load_verifiers(&store).unwrap();
 
-    let BigIntDe(verifier1_data_cap) =
-        verifiers.get(&verifier1_id_addr.to_bytes()).unwrap().unwrap();
+    let BigIntDe(verifier1_data_cap) = verifiers.get(&verifier1_id_addr).unwrap().unwrap();
     assert_eq!(BigInt::zero(), *verifier1_data_cap);
 
-    let BigIntDe(verifier2_data_cap) =
-        verifiers.get(&verifier2_id_addr.to_bytes()).unwrap().unwrap();
+    let BigIntDe(verifier2_data_cap) = verifiers.get(&verifier2_id_addr).unwrap().unwrap();
     assert_eq!(verifier_allowance, *verifier2_data_cap);
 
     let token_st = v.get_state::<DataCapState>(DATACAP_TOKEN_ACTOR_ADDR).unwrap();
     let balance = token_st.balance(&store, verified_client_id_addr.id().unwrap()).unwrap();
     assert_eq!(balance, TokenAmount::from_whole(verifier_allowance.to_i64().unwrap()));
 
-    let mut proposal_ids = make_map_with_root_and_bitwidth::<_, RemoveDataCapProposalID>(
-        &v_st.remove_data_cap_proposal_ids,
-        &store,
-        HAMT_BIT_WIDTH,
-    )
-    .unwrap();
+    let mut proposal_ids = v_st.load_remove_data_cap_proposal_ids(&store).unwrap();
 
     assert!(proposal_ids
-        .get(&AddrPairKey::new(verifier1_id_addr, verified_client_id_addr).to_bytes())
+        .get(&AddrPairKey::new(verifier1_id_addr, verified_client_id_addr))
         .unwrap()
         .is_none());
 
     assert!(proposal_ids
-        .get(&AddrPairKey::new(verifier2_id_addr, verified_client_id_addr).to_bytes())
+        .get(&AddrPairKey::new(verifier2_id_addr, verified_client_id_addr))
         .unwrap()
         .is_none());
 
@@ -179,19 +168,17 @@ fn remove_datacap_simple_successful_path() {
 
     v_st = v.get_state::<VerifregState>(VERIFIED_REGISTRY_ACTOR_ADDR).unwrap();
     // confirm proposalIds has changed as expected
-    proposal_ids =
-        make_map_with_root_and_bitwidth(&v_st.remove_data_cap_proposal_ids, &store, HAMT_BIT_WIDTH)
-            .unwrap();
+    proposal_ids = v_st.load_remove_data_cap_proposal_ids(&store).unwrap();
 
     let verifier1_proposal_id: &RemoveDataCapProposalID = proposal_ids
-        .get(&AddrPairKey::new(verifier1_id_addr, verified_client_id_addr).to_bytes())
+        .get(&AddrPairKey::new(verifier1_id_addr, verified_client_id_addr))
         .unwrap()
         .unwrap();
 
     assert_eq!(1u64, verifier1_proposal_id.id);
 
     let verifier2_proposal_id: &RemoveDataCapProposalID = proposal_ids
-        .get(&AddrPairKey::new(verifier2_id_addr, verified_client_id_addr).to_bytes())
+        .get(&AddrPairKey::new(verifier2_id_addr, verified_client_id_addr))
         .unwrap()
         .unwrap();
 
@@ -260,19 +247,17 @@ fn remove_datacap_simple_successful_path() {
 
     // confirm proposalIds has changed as expected
     v_st = v.get_state::<VerifregState>(VERIFIED_REGISTRY_ACTOR_ADDR).unwrap();
-    proposal_ids =
-        make_map_with_root_and_bitwidth(&v_st.remove_data_cap_proposal_ids, &store, HAMT_BIT_WIDTH)
-            .unwrap();
+    proposal_ids = v_st.load_remove_data_cap_proposal_ids(&store).unwrap();
 
     let verifier1_proposal_id: &RemoveDataCapProposalID = proposal_ids
-        .get(&AddrPairKey::new(verifier1_id_addr, verified_client_id_addr).to_bytes())
+        .get(&AddrPairKey::new(verifier1_id_addr, verified_client_id_addr))
         .unwrap()
         .unwrap();
 
     assert_eq!(2u64, verifier1_proposal_id.id);
 
     let verifier2_proposal_id: &RemoveDataCapProposalID = proposal_ids
-        .get(&AddrPairKey::new(verifier2_id_addr, verified_client_id_addr).to_bytes())
+        .get(&AddrPairKey::new(verifier2_id_addr, verified_client_id_addr))
         .unwrap()
         .unwrap();
 
@@ -0,0 +1,116 @@
+use fil_actor_market::State as MarketState;
+use fil_actor_power::{
+    epoch_key, CronEvent, State as PowerState, CRON_QUEUE_AMT_BITWIDTH, CRON_QUEUE_HAMT_BITWIDTH,
+};
+use fil_actors_runtime::test_blockstores::TrackingBlockstore;
+use fil_actors_runtime::{MapMap, Multimap, SetMultimap, EPOCHS_IN_DAY};
+use fvm_ipld_blockstore::MemoryBlockstore;
+use fvm_ipld_encoding::RawBytes;
+use fvm_shared::address::Address;
+use fvm_shared::HAMT_BIT_WIDTH;
+
+// Measures the blocks written by the nested collections backing actor state when their inner
+// collections are modified repeatedly before a flush. Each baseline is the number of blocks the
+// same operations wrote before the inner collections were cached, when every modification wrote
+// its inner collection back to the store.
+
+const ENTRIES: u64 = 10;
+
+/// Blocks written scheduling ENTRIES deals at one epoch, when each put wrote the epoch's set.
+const UNCACHED_DEAL_OPS_WRITES: usize = 11;
+/// Blocks written enrolling ENTRIES cron events at one epoch, when each add wrote the epoch's
+/// array.
+const UNCACHED_CRON_QUEUE_WRITES: usize = 11;
+/// Blocks written modifying one inner map after reading all ENTRIES of them, when every inner
+/// map read was written back on flush.
+const UNCACHED_MAPMAP_WRITES: usize = 11;
+
+#[test]
+fn deal_ops_at_an_epoch_are_written_once() {
+    let store = TrackingBlockstore::new(MemoryBlockstore::new());
+    let mut st = MarketState::new(&store).unwrap();
+    store.take_stats();
+
+    let deals: Vec<_> = (0..ENTRIES).map(|id| (EPOCHS_IN_DAY, id)).collect();
+    st.put_deals_by_epoch(&store, &deals).unwrap();
+
+    // The epoch's set and the outer map are each written once.
+    let writes = store.take_stats().writes;
+    assert_eq!(2, writes);
+    assert!(writes < UNCACHED_DEAL_OPS_WRITES);
+
+    let deal_ops = SetMultimap::from_root(&store, &st.deal_ops_by_epoch).unwrap();
+    let mut scheduled = Vec::new();
+    deal_ops
+        .for_each(EPOCHS_IN_DAY, |id| {
+            scheduled.push(id);
+            Ok(())
+        })
+        .unwrap();
+    scheduled.sort_unstable();
+    assert_eq!((0..ENTRIES).collect::<Vec<_>>(), scheduled);
+}
+
+#[test]
+fn cron_events_at_an_epoch_are_written_once() {
+    let store = TrackingBlockstore::new(MemoryBlockstore::new());
+    let st = PowerState::new(&store).unwrap();
+    store.take_stats();
+
+    let mut queue = Multimap::from_root(
+        &store,
+        &st.cron_event_queue,
+        CRON_QUEUE_HAMT_BITWIDTH,
+        CRON_QUEUE_AMT_BITWIDTH,
+    )
+    .unwrap();
+    for id in 0..ENTRIES {
+        let event =
+            CronEvent { miner_addr: Address::new_id(id), callback_payload: RawBytes::default() };
+        queue.add(epoch_key(EPOCHS_IN_DAY), event).unwrap();
+    }
+    let root = queue.root().unwrap();
+
+    // The epoch's array and the outer map are each written once.
+    let writes = store.take_stats().writes;
+    assert_eq!(2, writes);
+    assert!(writes < UNCACHED_CRON_QUEUE_WRITES);
+
+    let queue: Multimap<_, CronEvent> =
+        Multimap::from_root(&store, &root, CRON_QUEUE_HAMT_BITWIDTH, CRON_QUEUE_AMT_BITWIDTH)
+            .unwrap();
+    let mut enrolled = 0;
+    queue
+        .for_each(&epoch_key(EPOCHS_IN_DAY), |_, _| {
+            enrolled += 1;
+            Ok(())
+        })
+        .unwrap();
+    assert_eq!(ENTRIES, enrolled);
+}
+
+#[test]
+fn only_modified_inner_maps_are_flushed() {
+    let store = TrackingBlockstore::new(MemoryBlockstore::new());
+    let mut mm = MapMap::<_, u64, u64, u64>::new(&store, HAMT_BIT_WIDTH, HAMT_BIT_WIDTH);
+    for outer in 0..ENTRIES {
+        mm.put(outer, 0, outer).unwrap();
+    }
+    let root = mm.flush().unwrap();
+    store.take_stats();
+
+    // Read every inner map, so all are cached, then modify one of them.
+    let mut mm =
+        MapMap::<_, u64, u64, u64>::from_root(&store, &root, HAMT_BIT_WIDTH, HAMT_BIT_WIDTH)
+            .unwrap();
+    for outer in 0..ENTRIES {
+        assert_eq!(Some(&outer), mm.get(outer, 0).unwrap());
+    }
+    mm.put(0, 1, 1).unwrap();
+    mm.flush().unwrap();
+
+    // One inner root and one outer root are written, regardless of how many maps were read.
+    let writes = store.take_stats().writes;
+    assert_eq!(2, writes);
+    assert!(writes < UNCACHED_MAPMAP_WRITES);
+}